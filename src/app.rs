@@ -20,9 +20,12 @@ use crate::{
     sync::lock_or_panic,
     update::UpdatableWidget,
     widgets::{
+        AlertsWidget,
+        DelegatorsWidget,
         DiskListWidget,
         NodeDetailWidget,
         NodeWidget,
+        NotificationLogWidget,
         PeerCountriesWidget,
         SystemSummaryWidget,
         TimeWidget,
@@ -88,6 +91,21 @@ impl App {
             should_redraw = true;
         }
 
+        if dirty.delegations {
+            self.widgets.delegators.update();
+            should_redraw = true;
+        }
+
+        if dirty.notifications {
+            self.widgets.notification_log.update();
+            should_redraw = self.widgets.show_notification_log || should_redraw;
+        }
+
+        if dirty.alerts {
+            self.widgets.alerts.update();
+            should_redraw = self.widgets.show_alerts || should_redraw;
+        }
+
         #[cfg(target_family = "unix")]
         if dirty.system {
             self.widgets.system_summary.update();
@@ -177,6 +195,18 @@ impl App {
         self.refresh_disk_list_widget();
         false
     }
+
+    /// 处理n键事件，在节点详情与通知投递记录之间切换
+    pub fn toggle_notification_log(&mut self) {
+        self.widgets.show_notification_log = !self.widgets.show_notification_log;
+        self.widgets.show_alerts = false;
+    }
+
+    /// 处理a键事件，在节点详情与告警规则状态之间切换
+    pub fn toggle_alerts(&mut self) {
+        self.widgets.show_alerts = !self.widgets.show_alerts;
+        self.widgets.show_notification_log = false;
+    }
 }
 
 pub struct Widgets {
@@ -191,6 +221,13 @@ pub struct Widgets {
     #[cfg(target_family = "unix")]
     pub disk_list: DiskListWidget,
     pub node_details: NodeDetailWidget,
+    pub delegators: DelegatorsWidget,
+    pub notification_log: NotificationLogWidget,
+    /// Whether the Notifications panel replaces Node Details.
+    pub show_notification_log: bool,
+    pub alerts: AlertsWidget,
+    /// Whether the Alerts panel replaces Node Details.
+    pub show_alerts: bool,
 }
 
 pub fn setup_app(opts: &Opts) -> App {
@@ -208,6 +245,9 @@ pub fn setup_app(opts: &Opts) -> App {
     let disk_list = DiskListWidget::new(data.clone());
 
     let node_details = NodeDetailWidget::new(data.clone());
+    let delegators = DelegatorsWidget::new(data.clone());
+    let notification_log = NotificationLogWidget::new(data.clone());
+    let alerts = AlertsWidget::new(data.clone());
 
     App {
         widgets: Widgets {
@@ -220,6 +260,11 @@ pub fn setup_app(opts: &Opts) -> App {
             #[cfg(target_family = "unix")]
             disk_list,
             node_details,
+            delegators,
+            notification_log,
+            show_notification_log: false,
+            alerts,
+            show_alerts: false,
         },
         data,
         geo_store,
@@ -362,6 +407,7 @@ mod tests {
                 node_name: "test-node".to_string(),
                 ranking: 1,
                 block_qty: 100,
+                expect_block_qty: 100,
                 block_rate: "50%".to_string(),
                 daily_block_rate: "10/day".to_string(),
                 reward_per: 10.0,
@@ -369,6 +415,15 @@ mod tests {
                 reward_address: "0x123".to_string(),
                 verifier_time: 3600,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }));
         }
 
@@ -612,6 +667,7 @@ mod tests {
                 node_name: "test-node".to_string(),
                 ranking: 1,
                 block_qty: 100,
+                expect_block_qty: 100,
                 block_rate: "50%".to_string(),
                 daily_block_rate: "10/day".to_string(),
                 reward_per: 10.0,
@@ -619,6 +675,15 @@ mod tests {
                 reward_address: "0x123".to_string(),
                 verifier_time: 3600,
                 last_updated_at: Some(Instant::now()),
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }));
         }
         app.widgets.node_details.update();
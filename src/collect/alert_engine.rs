@@ -0,0 +1,578 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    },
+    time::Instant,
+};
+
+use chrono::{
+    DateTime,
+    Local,
+};
+use tokio::time::{
+    self,
+    Duration,
+    MissedTickBehavior,
+};
+
+use super::{
+    alert_rules::{
+        AlertMetric,
+        AlertRule,
+        AlertSeverity,
+    },
+    data::{
+        Data,
+        SharedData,
+        StatusLevel,
+    },
+};
+use crate::{
+    error::Result,
+    notify::Notifier,
+    sync::lock_or_panic,
+};
+
+const ALERT_EVALUATION_INTERVAL: Duration = Duration::from_secs(5);
+const ALERT_STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Resolved alerts stay in the Alerts panel this long.
+const RESOLVED_ALERT_RETENTION: Duration = Duration::from_secs(3600);
+/// Series label of chain-wide metrics.
+const CHAIN_TARGET: &str = "chain";
+/// Series label of host metrics.
+const HOST_TARGET: &str = "host";
+/// Reported value of an alert resolved because its series stopped reporting.
+const NO_DATA_VALUE: &str = "无数据";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum AlertState {
+    Firing,
+    /// The condition holds but not yet for the rule's `for` duration.
+    Pending,
+    Resolved,
+}
+
+impl AlertState {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Firing => "firing",
+            Self::Pending => "pending",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+/// One rule series as shown in the Alerts panel.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AlertStatus {
+    pub rule: String,
+    pub target: String,
+    pub severity: AlertSeverity,
+    pub state: AlertState,
+    pub value: String,
+    pub condition: String,
+    /// When the series entered its current state.
+    pub since: DateTime<Local>,
+}
+
+/// A series that started firing or resolved, routed to the notifier.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AlertTransition {
+    pub rule: String,
+    pub metric: AlertMetric,
+    pub target: String,
+    pub severity: AlertSeverity,
+    /// `Firing` or `Resolved`.
+    pub state: AlertState,
+    pub value: String,
+    pub condition: String,
+    /// How long the condition held before firing, or how long the alert
+    /// fired before resolving.
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct AlertSeries {
+    state: AlertState,
+    value: f64,
+    entered_at: Instant,
+    /// When the series last reported a value.
+    seen_at: Instant,
+    since: DateTime<Local>,
+}
+
+impl AlertSeries {
+    fn new(
+        state: AlertState,
+        value: f64,
+        now: Instant,
+    ) -> Self {
+        Self {
+            state,
+            value,
+            entered_at: now,
+            seen_at: now,
+            since: Local::now(),
+        }
+    }
+}
+
+/// Current values of `metric`, one per node, mount point or the chain.
+fn sample_metric(
+    metric: AlertMetric,
+    data: &Data,
+) -> Vec<(String, f64)> {
+    match metric {
+        AlertMetric::BlockInterval => {
+            let interval_ms = data.cur_interval();
+            if interval_ms == 0 {
+                return Vec::new();
+            }
+            vec![(CHAIN_TARGET.to_string(), interval_ms as f64 / 1000.0)]
+        },
+        AlertMetric::NodeLag => {
+            let states = data.states();
+            let tip = states.iter().map(|state| state.current_number).max().unwrap_or(0);
+            states
+                .into_iter()
+                .filter(|state| state.current_number > 0)
+                .map(|state| (state.name, tip.saturating_sub(state.current_number) as f64))
+                .collect()
+        },
+        AlertMetric::PeerCount => data
+            .states()
+            .into_iter()
+            .filter_map(|state| state.peer_count.map(|count| (state.name, count as f64)))
+            .collect(),
+        AlertMetric::RpcLatency => data
+            .states()
+            .into_iter()
+            .filter(|state| state.current_number > 0)
+            .map(|state| (state.name, state.rpc_latency_ms as f64))
+            .collect(),
+        AlertMetric::Ranking => data
+            .node_details()
+            .into_iter()
+            .filter(|detail| detail.ranking > 0)
+            .map(|detail| {
+                let target = if detail.node_name.is_empty() {
+                    detail.node_id
+                } else {
+                    detail.node_name
+                };
+                (target, f64::from(detail.ranking))
+            })
+            .collect(),
+        #[cfg(target_family = "unix")]
+        AlertMetric::Cpu | AlertMetric::Memory => {
+            let stats = data.system_stats();
+            // Nothing has been sampled before the first refresh.
+            if stats.memory_total == 0 {
+                return Vec::new();
+            }
+            let usage = if metric == AlertMetric::Cpu {
+                stats.cpu_usage
+            } else {
+                stats.memory_usage_percent
+            };
+            vec![(HOST_TARGET.to_string(), f64::from(usage))]
+        },
+        #[cfg(target_family = "unix")]
+        AlertMetric::Disk => data
+            .system_stats()
+            .disk_details
+            .into_iter()
+            .map(|disk| (disk.mount_point, f64::from(disk.usage_percent)))
+            .collect(),
+        #[cfg(not(target_family = "unix"))]
+        AlertMetric::Cpu | AlertMetric::Memory | AlertMetric::Disk => Vec::new(),
+    }
+}
+
+/// Evaluates alert rules against collected samples and tracks each series
+/// through pending, firing and resolved.
+#[derive(Debug)]
+pub(crate) struct AlertEngine {
+    rules: Vec<AlertRule>,
+    /// Keyed by rule index and series target.
+    series: HashMap<(usize, String), AlertSeries>,
+}
+
+impl AlertEngine {
+    pub(crate) fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            series: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn evaluate(
+        &mut self,
+        data: &Data,
+        now: Instant,
+    ) -> Vec<AlertTransition> {
+        let samples: Vec<_> =
+            self.rules.iter().map(|rule| sample_metric(rule.metric, data)).collect();
+        let mut transitions = Vec::new();
+        for (rule_index, samples) in samples.into_iter().enumerate() {
+            transitions.extend(self.observe(rule_index, samples, now));
+        }
+        transitions
+    }
+
+    fn observe(
+        &mut self,
+        rule_index: usize,
+        samples: Vec<(String, f64)>,
+        now: Instant,
+    ) -> Vec<AlertTransition> {
+        let rule = &self.rules[rule_index];
+        let samples: HashMap<_, _> =
+            samples.into_iter().filter(|(target, _)| rule.matches_target(target)).collect();
+        let mut transitions = Vec::new();
+
+        // A firing series that stopped reporting resolves once it has been
+        // silent for the rule's `for` duration instead of firing forever.
+        for ((index, target), series) in &mut self.series {
+            if *index != rule_index
+                || series.state != AlertState::Firing
+                || samples.contains_key(target)
+                || now.saturating_duration_since(series.seen_at) < rule.for_duration
+            {
+                continue;
+            }
+            let fired_for = now.saturating_duration_since(series.entered_at);
+            *series = AlertSeries::new(AlertState::Resolved, series.value, now);
+            transitions.push(AlertTransition {
+                rule: rule.name.clone(),
+                metric: rule.metric,
+                target: target.clone(),
+                severity: rule.severity,
+                state: AlertState::Resolved,
+                value: NO_DATA_VALUE.to_string(),
+                condition: rule.condition(),
+                duration: fired_for,
+            });
+        }
+
+        // A pending series whose value disappeared starts over, firing ones
+        // keep their last value until the series reports again or goes
+        // silent for too long and resolved ones drop out of the panel after
+        // a while.
+        self.series.retain(|(index, target), series| {
+            *index != rule_index
+                || match series.state {
+                    AlertState::Pending => samples.contains_key(target),
+                    AlertState::Firing => true,
+                    AlertState::Resolved => {
+                        now.saturating_duration_since(series.entered_at) < RESOLVED_ALERT_RETENTION
+                    },
+                }
+        });
+
+        for (target, value) in samples {
+            let key = (rule_index, target);
+            let transition = match self.series.get_mut(&key) {
+                Some(series) if series.state == AlertState::Firing => {
+                    series.value = value;
+                    series.seen_at = now;
+                    if rule.clears(value) {
+                        let fired_for = now.saturating_duration_since(series.entered_at);
+                        *series = AlertSeries::new(AlertState::Resolved, value, now);
+                        Some((AlertState::Resolved, fired_for))
+                    } else {
+                        None
+                    }
+                },
+                Some(series) if series.state == AlertState::Pending => {
+                    series.value = value;
+                    series.seen_at = now;
+                    let pending_for = now.saturating_duration_since(series.entered_at);
+                    if !rule.breaches(value) {
+                        self.series.remove(&key);
+                        None
+                    } else if pending_for >= rule.for_duration {
+                        *series = AlertSeries::new(AlertState::Firing, value, now);
+                        Some((AlertState::Firing, pending_for))
+                    } else {
+                        None
+                    }
+                },
+                series => {
+                    if rule.breaches(value) {
+                        let state = if rule.for_duration.is_zero() {
+                            AlertState::Firing
+                        } else {
+                            AlertState::Pending
+                        };
+                        self.series.insert(key.clone(), AlertSeries::new(state, value, now));
+                        (state == AlertState::Firing).then_some((state, Duration::ZERO))
+                    } else {
+                        if let Some(series) = series {
+                            series.value = value;
+                            series.seen_at = now;
+                        }
+                        None
+                    }
+                },
+            };
+
+            if let Some((state, duration)) = transition {
+                transitions.push(AlertTransition {
+                    rule: rule.name.clone(),
+                    metric: rule.metric,
+                    target: key.1,
+                    severity: rule.severity,
+                    state,
+                    value: rule.metric.format_value(value),
+                    condition: rule.condition(),
+                    duration,
+                });
+            }
+        }
+
+        transitions
+    }
+
+    /// Every tracked series, firing first and the most severe first within
+    /// each state.
+    pub(crate) fn statuses(&self) -> Vec<AlertStatus> {
+        let mut statuses: Vec<_> = self
+            .series
+            .iter()
+            .map(|((rule_index, target), series)| {
+                let rule = &self.rules[*rule_index];
+                AlertStatus {
+                    rule: rule.name.clone(),
+                    target: target.clone(),
+                    severity: rule.severity,
+                    state: series.state,
+                    value: rule.metric.format_value(series.value),
+                    condition: rule.condition(),
+                    since: series.since,
+                }
+            })
+            .collect();
+        statuses.sort_by(|left, right| {
+            (left.state, Reverse(left.severity), &left.rule, &left.target).cmp(&(
+                right.state,
+                Reverse(right.severity),
+                &right.rule,
+                &right.target,
+            ))
+        });
+        statuses
+    }
+}
+
+/// Evaluate `--alert-rules` on a fixed cadence, publish rule states for the
+/// Alerts panel and route firing and resolved alerts to the notifier.
+pub(crate) async fn run_alert_rules(
+    rules: Vec<AlertRule>,
+    data: SharedData,
+    notifier: Option<Arc<Notifier>>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    let mut engine = AlertEngine::new(rules);
+    let mut evaluation_interval = time::interval(ALERT_EVALUATION_INTERVAL);
+    evaluation_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let stop_poll = time::sleep(ALERT_STOP_POLL_INTERVAL);
+        tokio::pin!(stop_poll);
+        tokio::select! {
+            biased;
+            _ = evaluation_interval.tick() => {},
+            _ = &mut stop_poll => continue,
+        }
+
+        let transitions = {
+            let mut data = lock_or_panic(&data);
+            let transitions = engine.evaluate(&data, Instant::now());
+            data.replace_alerts(engine.statuses());
+            for transition in transitions.iter().filter(|t| t.state == AlertState::Firing) {
+                let level = if transition.severity == AlertSeverity::Critical {
+                    StatusLevel::Error
+                } else {
+                    StatusLevel::Warn
+                };
+                data.set_status_message(
+                    level,
+                    format!(
+                        "Alert {} firing for {}: {} ({})",
+                        transition.rule, transition.target, transition.value, transition.condition
+                    ),
+                );
+            }
+            transitions
+        };
+
+        if let Some(notifier) = notifier.as_ref() {
+            for transition in &transitions {
+                notifier.notify_alert_transition(transition).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::{
+        alert_rules::AlertOperator,
+        ConsensusState,
+    };
+
+    fn lag_rule(for_duration: Duration) -> AlertRule {
+        AlertRule {
+            name: "lagging".to_string(),
+            metric: AlertMetric::NodeLag,
+            op: AlertOperator::Greater,
+            threshold: 5.0,
+            for_duration,
+            hysteresis: 2.0,
+            severity: AlertSeverity::Critical,
+            target: None,
+        }
+    }
+
+    fn data_with_heights(heights: &[(&str, u64)]) -> Data {
+        let mut data = Data::default();
+        for (name, current_number) in heights {
+            data.update_consensus_state(
+                name.to_string(),
+                ConsensusState {
+                    name: name.to_string(),
+                    current_number: *current_number,
+                    ..Default::default()
+                },
+            );
+        }
+        data
+    }
+
+    #[test]
+    fn test_node_lag_is_measured_against_the_highest_node() {
+        let data = data_with_heights(&[("a", 100), ("b", 90), ("c", 0)]);
+
+        let mut samples = sample_metric(AlertMetric::NodeLag, &data);
+        samples.sort_by(|left, right| left.0.cmp(&right.0));
+
+        assert_eq!(samples, vec![("a".to_string(), 0.0), ("b".to_string(), 10.0)]);
+    }
+
+    #[test]
+    fn test_rule_goes_pending_then_fires_after_for_duration() {
+        let mut engine = AlertEngine::new(vec![lag_rule(Duration::from_secs(60))]);
+        let start = Instant::now();
+        let lagging = data_with_heights(&[("a", 100), ("b", 90)]);
+
+        assert!(engine.evaluate(&lagging, start).is_empty());
+        assert_eq!(engine.statuses()[0].state, AlertState::Pending);
+
+        assert!(engine.evaluate(&lagging, start + Duration::from_secs(30)).is_empty());
+        let transitions = engine.evaluate(&lagging, start + Duration::from_secs(60));
+
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].state, AlertState::Firing);
+        assert_eq!(transitions[0].target, "b");
+        assert_eq!(transitions[0].value, "10");
+        assert_eq!(transitions[0].duration, Duration::from_secs(60));
+        assert_eq!(engine.statuses()[0].state, AlertState::Firing);
+    }
+
+    #[test]
+    fn test_pending_rule_resets_when_condition_clears() {
+        let mut engine = AlertEngine::new(vec![lag_rule(Duration::from_secs(60))]);
+        let start = Instant::now();
+
+        engine.evaluate(&data_with_heights(&[("a", 100), ("b", 90)]), start);
+        engine.evaluate(
+            &data_with_heights(&[("a", 100), ("b", 99)]),
+            start + Duration::from_secs(30),
+        );
+        let transitions = engine.evaluate(
+            &data_with_heights(&[("a", 100), ("b", 90)]),
+            start + Duration::from_secs(60),
+        );
+
+        assert!(transitions.is_empty());
+        assert_eq!(engine.statuses()[0].state, AlertState::Pending);
+    }
+
+    #[test]
+    fn test_firing_rule_resolves_only_past_hysteresis() {
+        let mut engine = AlertEngine::new(vec![lag_rule(Duration::ZERO)]);
+        let start = Instant::now();
+
+        let fired = engine.evaluate(&data_with_heights(&[("a", 100), ("b", 90)]), start);
+        assert_eq!(fired[0].state, AlertState::Firing);
+
+        // Back under the threshold but still inside the hysteresis band.
+        let held = engine.evaluate(&data_with_heights(&[("a", 100), ("b", 96)]), start);
+        assert!(held.is_empty());
+
+        let resolved = engine.evaluate(
+            &data_with_heights(&[("a", 100), ("b", 97)]),
+            start + Duration::from_secs(90),
+        );
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].state, AlertState::Resolved);
+        assert_eq!(resolved[0].duration, Duration::from_secs(90));
+        assert_eq!(engine.statuses()[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_firing_rule_resolves_after_for_duration_without_data() {
+        let mut engine = AlertEngine::new(vec![lag_rule(Duration::from_secs(60))]);
+        let start = Instant::now();
+        let lagging = data_with_heights(&[("a", 100), ("b", 90)]);
+        engine.evaluate(&lagging, start);
+        engine.evaluate(&lagging, start + Duration::from_secs(60));
+
+        // Node b stops reporting a height.
+        let silent = data_with_heights(&[("a", 100)]);
+        assert!(engine.evaluate(&silent, start + Duration::from_secs(119)).is_empty());
+        assert_eq!(engine.statuses()[0].state, AlertState::Firing);
+
+        let resolved = engine.evaluate(&silent, start + Duration::from_secs(120));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].state, AlertState::Resolved);
+        assert_eq!(resolved[0].target, "b");
+        assert_eq!(resolved[0].value, NO_DATA_VALUE);
+        assert_eq!(resolved[0].duration, Duration::from_secs(60));
+        assert_eq!(engine.statuses()[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_statuses_list_firing_before_pending_and_resolved() {
+        let mut quiet_rule = lag_rule(Duration::from_secs(60));
+        quiet_rule.name = "slow".to_string();
+        quiet_rule.severity = AlertSeverity::Info;
+        let mut engine = AlertEngine::new(vec![quiet_rule, lag_rule(Duration::ZERO)]);
+
+        engine.evaluate(&data_with_heights(&[("a", 100), ("b", 90)]), Instant::now());
+        let states: Vec<_> =
+            engine.statuses().into_iter().map(|status| (status.rule, status.state)).collect();
+
+        assert_eq!(
+            states,
+            vec![
+                ("lagging".to_string(), AlertState::Firing),
+                ("slow".to_string(), AlertState::Pending),
+            ]
+        );
+    }
+}
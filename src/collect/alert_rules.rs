@@ -0,0 +1,352 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::Path,
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::error::{
+    ChaindashError,
+    Result,
+};
+
+/// Collected value an alert rule is evaluated against. Per-node metrics
+/// produce one series per monitored node and disk usage one per mount point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AlertMetric {
+    /// Seconds between the two latest blocks.
+    BlockInterval,
+    /// Blocks behind the highest block reported by any monitored node.
+    NodeLag,
+    Cpu,
+    Memory,
+    Disk,
+    PeerCount,
+    Ranking,
+    /// Round trip of the latest node state poll, in milliseconds.
+    RpcLatency,
+}
+
+impl AlertMetric {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::BlockInterval => "block_interval",
+            Self::NodeLag => "node_lag",
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+            Self::PeerCount => "peer_count",
+            Self::Ranking => "ranking",
+            Self::RpcLatency => "rpc_latency",
+        }
+    }
+
+    pub(crate) fn format_value(
+        self,
+        value: f64,
+    ) -> String {
+        match self {
+            Self::BlockInterval => format!("{value:.1}s"),
+            Self::Cpu | Self::Memory | Self::Disk => format!("{value:.1}%"),
+            Self::RpcLatency => format!("{value:.0}ms"),
+            Self::NodeLag | Self::PeerCount | Self::Ranking => format!("{value:.0}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum AlertOperator {
+    #[serde(rename = ">")]
+    Greater,
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = "<")]
+    Less,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = "==")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+}
+
+impl AlertOperator {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+        }
+    }
+
+    fn compare(
+        self,
+        value: f64,
+        threshold: f64,
+    ) -> bool {
+        match self {
+            Self::Greater => value > threshold,
+            Self::GreaterOrEqual => value >= threshold,
+            Self::Less => value < threshold,
+            Self::LessOrEqual => value <= threshold,
+            Self::Equal => value == threshold,
+            Self::NotEqual => value != threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+/// A rule as written in the `--alert-rules` file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAlertRule {
+    name: String,
+    metric: AlertMetric,
+    op: AlertOperator,
+    threshold: f64,
+    #[serde(default, rename = "for")]
+    for_duration: Option<String>,
+    #[serde(default)]
+    hysteresis: f64,
+    #[serde(default)]
+    severity: AlertSeverity,
+    #[serde(default)]
+    target: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AlertRuleFile {
+    rules: Vec<RawAlertRule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AlertRule {
+    pub name: String,
+    pub metric: AlertMetric,
+    pub op: AlertOperator,
+    pub threshold: f64,
+    /// How long the condition must hold before the alert fires.
+    pub for_duration: Duration,
+    /// Distance past the threshold the value must move back before a firing
+    /// alert resolves.
+    pub hysteresis: f64,
+    pub severity: AlertSeverity,
+    /// Node name or mount point the rule is limited to; all series when unset.
+    pub target: Option<String>,
+}
+
+impl AlertRule {
+    pub(crate) fn matches_target(
+        &self,
+        target: &str,
+    ) -> bool {
+        self.target.as_deref().is_none_or(|expected| expected == target)
+    }
+
+    pub(crate) fn breaches(
+        &self,
+        value: f64,
+    ) -> bool {
+        self.op.compare(value, self.threshold)
+    }
+
+    /// Whether a firing alert may resolve, which for ordering operators
+    /// requires the value to clear the threshold by `hysteresis`.
+    pub(crate) fn clears(
+        &self,
+        value: f64,
+    ) -> bool {
+        let threshold = match self.op {
+            AlertOperator::Greater | AlertOperator::GreaterOrEqual => {
+                self.threshold - self.hysteresis
+            },
+            AlertOperator::Less | AlertOperator::LessOrEqual => self.threshold + self.hysteresis,
+            AlertOperator::Equal | AlertOperator::NotEqual => self.threshold,
+        };
+        !self.op.compare(value, threshold)
+    }
+
+    pub(crate) fn condition(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.metric.name(),
+            self.op.symbol(),
+            self.metric.format_value(self.threshold)
+        )
+    }
+
+    fn from_raw(raw: RawAlertRule) -> std::result::Result<Self, String> {
+        let name = raw.name.trim().to_string();
+        if name.is_empty() {
+            return Err("rule name must not be empty".to_string());
+        }
+        if !raw.threshold.is_finite() {
+            return Err(format!("rule {name}: threshold must be a finite number"));
+        }
+        if !(raw.hysteresis.is_finite() && raw.hysteresis >= 0.0) {
+            return Err(format!("rule {name}: hysteresis must be a non-negative number"));
+        }
+        if raw.hysteresis > 0.0 && matches!(raw.op, AlertOperator::Equal | AlertOperator::NotEqual)
+        {
+            return Err(format!("rule {name}: hysteresis requires one of >, >=, <, <="));
+        }
+        let for_duration = match raw.for_duration.as_deref() {
+            Some(value) => {
+                parse_rule_duration(value).map_err(|err| format!("rule {name}: {err}"))?
+            },
+            None => Duration::ZERO,
+        };
+        let target =
+            raw.target.map(|target| target.trim().to_string()).filter(|target| !target.is_empty());
+
+        Ok(Self {
+            name,
+            metric: raw.metric,
+            op: raw.op,
+            threshold: raw.threshold,
+            for_duration,
+            hysteresis: raw.hysteresis,
+            severity: raw.severity,
+            target,
+        })
+    }
+}
+
+/// Parse a `for` duration such as `30s`, `5m` or `1h`; a bare number is
+/// read as seconds.
+fn parse_rule_duration(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let (amount, unit_seconds) = match value.char_indices().last() {
+        Some((index, 's')) => (&value[..index], 1),
+        Some((index, 'm')) => (&value[..index], 60),
+        Some((index, 'h')) => (&value[..index], 3600),
+        _ => (value, 1),
+    };
+    let amount = amount
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("invalid duration: {value}. expected e.g. 30s, 5m or 1h"))?;
+
+    Ok(Duration::from_secs(amount.saturating_mul(unit_seconds)))
+}
+
+pub(crate) fn parse_alert_rules(content: &str) -> Result<Vec<AlertRule>> {
+    let file: AlertRuleFile = serde_json::from_str(content)?;
+    let mut names = HashSet::new();
+    let mut rules = Vec::with_capacity(file.rules.len());
+
+    for raw in file.rules {
+        let rule = AlertRule::from_raw(raw).map_err(ChaindashError::Other)?;
+        if !names.insert(rule.name.clone()) {
+            return Err(ChaindashError::Other(format!("duplicate alert rule name: {}", rule.name)));
+        }
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
+
+/// Load the `--alert-rules` file; no file means no rules.
+pub(crate) fn load_alert_rules(path: Option<&Path>) -> Result<Vec<AlertRule>> {
+    let Some(path) = path else {
+        return Ok(Vec::new());
+    };
+
+    let content = fs::read_to_string(path).map_err(|err| {
+        ChaindashError::Other(format!("failed to read alert rules {}: {err}", path.display()))
+    })?;
+    parse_alert_rules(&content).map_err(|err| {
+        ChaindashError::Other(format!("invalid alert rules {}: {err}", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_alert_rules_applies_defaults_and_durations() {
+        let rules = parse_alert_rules(
+            r#"{
+                "rules": [
+                    {"name": "cpu-high", "metric": "cpu", "op": ">", "threshold": 90,
+                     "for": "5m", "hysteresis": 5, "severity": "critical"},
+                    {"name": "lagging", "metric": "node_lag", "op": ">=", "threshold": 10,
+                     "target": " node-a "}
+                ]
+            }"#,
+        )
+        .expect("valid rules");
+
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].for_duration, Duration::from_secs(300));
+        assert_eq!(rules[0].severity, AlertSeverity::Critical);
+        assert_eq!(rules[0].condition(), "cpu > 90.0%");
+        assert_eq!(rules[1].for_duration, Duration::ZERO);
+        assert_eq!(rules[1].severity, AlertSeverity::Warning);
+        assert_eq!(rules[1].target.as_deref(), Some("node-a"));
+        assert!(rules[1].matches_target("node-a"));
+        assert!(!rules[1].matches_target("node-b"));
+    }
+
+    #[test]
+    fn test_parse_alert_rules_rejects_invalid_rules() {
+        let duplicate = r#"{"rules": [
+            {"name": "a", "metric": "cpu", "op": ">", "threshold": 1},
+            {"name": "a", "metric": "memory", "op": ">", "threshold": 1}
+        ]}"#;
+        let equality_hysteresis = r#"{"rules": [
+            {"name": "a", "metric": "peer_count", "op": "==", "threshold": 0, "hysteresis": 1}
+        ]}"#;
+        let bad_duration = r#"{"rules": [
+            {"name": "a", "metric": "cpu", "op": ">", "threshold": 1, "for": "5d"}
+        ]}"#;
+        let unknown_metric = r#"{"rules": [
+            {"name": "a", "metric": "gpu", "op": ">", "threshold": 1}
+        ]}"#;
+
+        assert!(parse_alert_rules(duplicate).is_err());
+        assert!(parse_alert_rules(equality_hysteresis).is_err());
+        assert!(parse_alert_rules(bad_duration).is_err());
+        assert!(parse_alert_rules(unknown_metric).is_err());
+    }
+
+    #[test]
+    fn test_hysteresis_delays_clearing_in_the_breach_direction() {
+        let rule = |op, hysteresis| AlertRule {
+            name: "rule".to_string(),
+            metric: AlertMetric::Disk,
+            op,
+            threshold: 90.0,
+            for_duration: Duration::ZERO,
+            hysteresis,
+            severity: AlertSeverity::Warning,
+            target: None,
+        };
+
+        let above = rule(AlertOperator::Greater, 5.0);
+        assert!(above.breaches(91.0));
+        assert!(!above.clears(88.0));
+        assert!(above.clears(85.0));
+
+        let below = rule(AlertOperator::Less, 5.0);
+        assert!(below.breaches(89.0));
+        assert!(!below.clears(92.0));
+        assert!(below.clears(95.0));
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    ops::Sub,
+};
+
+const BLOCK_PRODUCTION_EPOCH_HISTORY_LIMIT: usize = 8;
+const BLOCK_PRODUCTION_TREND_TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductionTrend {
+    Improving,
+    Steady,
+    Worsening,
+}
+
+/// Blocks produced and expected for one epoch, accumulated from consecutive
+/// Explorer samples of the cumulative `blockQty` / `expectBlockQty` counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EpochProduction {
+    pub epoch: u64,
+    pub produced: u64,
+    pub expected: u64,
+}
+
+impl EpochProduction {
+    pub fn missed(&self) -> u64 {
+        self.expected.saturating_sub(self.produced)
+    }
+
+    pub fn missed_ratio(&self) -> Option<f64> {
+        if self.expected == 0 {
+            return None;
+        }
+
+        Some(self.missed() as f64 / self.expected as f64)
+    }
+
+    pub fn production_ratio(&self) -> Option<f64> {
+        self.missed_ratio().map(|missed_ratio| 1.0 - missed_ratio)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockProductionSummary {
+    pub current: EpochProduction,
+    pub previous: Option<EpochProduction>,
+}
+
+impl BlockProductionSummary {
+    /// Compare the current epoch's missed-block ratio with the previous epoch.
+    pub fn trend(&self) -> Option<ProductionTrend> {
+        let current = self.current.missed_ratio()?;
+        let previous = self.previous.and_then(|previous| previous.missed_ratio())?;
+
+        Some(if current + BLOCK_PRODUCTION_TREND_TOLERANCE < previous {
+            ProductionTrend::Improving
+        } else if current > previous + BLOCK_PRODUCTION_TREND_TOLERANCE {
+            ProductionTrend::Worsening
+        } else {
+            ProductionTrend::Steady
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ProductionSample {
+    block_qty: u64,
+    expect_block_qty: u64,
+}
+
+#[derive(Debug, Default)]
+struct NodeProductionHistory {
+    last_sample: Option<ProductionSample>,
+    epochs: VecDeque<EpochProduction>,
+}
+
+impl NodeProductionHistory {
+    fn record(
+        &mut self,
+        epoch: u64,
+        sample: ProductionSample,
+    ) {
+        let (produced, expected) = match self.last_sample {
+            Some(previous) => (
+                counter_delta(previous.block_qty, sample.block_qty),
+                counter_delta(previous.expect_block_qty, sample.expect_block_qty),
+            ),
+            None => (0, 0),
+        };
+        self.last_sample = Some(sample);
+
+        match self.epochs.back_mut() {
+            Some(last) if last.epoch == epoch => {
+                last.produced = last.produced.saturating_add(produced);
+                last.expected = last.expected.saturating_add(expected);
+            },
+            _ => {
+                self.epochs.push_back(EpochProduction {
+                    epoch,
+                    produced,
+                    expected,
+                });
+                while self.epochs.len() > BLOCK_PRODUCTION_EPOCH_HISTORY_LIMIT {
+                    self.epochs.pop_front();
+                }
+            },
+        }
+    }
+
+    fn summary(&self) -> Option<BlockProductionSummary> {
+        let mut epochs = self.epochs.iter().rev();
+        let current = *epochs.next()?;
+        let previous = epochs.next().copied();
+
+        Some(BlockProductionSummary { current, previous })
+    }
+}
+
+/// Explorer counters are cumulative but can restart (e.g. after re-staking);
+/// a decrease is treated as a reset and the new value counts as the delta.
+pub(super) fn counter_delta<T>(
+    previous: T,
+    current: T,
+) -> T
+where
+    T: PartialOrd + Sub<Output = T> + Copy,
+{
+    if current >= previous {
+        current - previous
+    } else {
+        current
+    }
+}
+
+/// Per-node block production history keyed by validator node ID.
+///
+/// Samples arriving while the monitored nodes report the same epoch are
+/// accumulated into one bucket; the first sample for a node only establishes a
+/// baseline.
+#[derive(Debug, Default)]
+pub(super) struct BlockProductionTracker {
+    nodes: HashMap<String, NodeProductionHistory>,
+}
+
+impl BlockProductionTracker {
+    pub(super) fn record(
+        &mut self,
+        node_id: &str,
+        epoch: u64,
+        block_qty: u64,
+        expect_block_qty: u64,
+    ) -> Option<BlockProductionSummary> {
+        let history = self.nodes.entry(node_id.to_string()).or_default();
+        history.record(
+            epoch,
+            ProductionSample {
+                block_qty,
+                expect_block_qty,
+            },
+        );
+        history.summary()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_sample_only_sets_baseline() {
+        let mut tracker = BlockProductionTracker::default();
+
+        let summary = tracker.record("node-a", 7, 100, 120).expect("summary should exist");
+
+        assert_eq!(
+            summary.current,
+            EpochProduction {
+                epoch: 7,
+                produced: 0,
+                expected: 0,
+            }
+        );
+        assert_eq!(summary.current.missed_ratio(), None);
+    }
+
+    #[test]
+    fn test_consecutive_samples_accumulate_within_epoch() {
+        let mut tracker = BlockProductionTracker::default();
+        tracker.record("node-a", 7, 100, 100);
+        tracker.record("node-a", 7, 110, 110);
+
+        let summary = tracker.record("node-a", 7, 120, 130).expect("summary should exist");
+
+        assert_eq!(summary.current.produced, 20);
+        assert_eq!(summary.current.expected, 30);
+        assert_eq!(summary.current.missed(), 10);
+        assert_eq!(summary.previous, None);
+    }
+
+    #[test]
+    fn test_new_epoch_starts_new_bucket_and_reports_trend() {
+        let mut tracker = BlockProductionTracker::default();
+        tracker.record("node-a", 7, 100, 100);
+        tracker.record("node-a", 7, 110, 120);
+
+        let summary = tracker.record("node-a", 8, 130, 140).expect("summary should exist");
+
+        assert_eq!(summary.current.epoch, 8);
+        assert_eq!(summary.current.missed(), 0);
+        assert_eq!(summary.previous.map(|previous| previous.missed()), Some(10));
+        assert_eq!(summary.trend(), Some(ProductionTrend::Improving));
+    }
+
+    #[test]
+    fn test_counter_reset_counts_new_value_as_delta() {
+        assert_eq!(counter_delta(500, 20), 20);
+        assert_eq!(counter_delta(20, 30), 10);
+        assert_eq!(counter_delta(1_000.0, 5.0), 5.0);
+    }
+
+    #[test]
+    fn test_counter_reset_within_epoch_keeps_counting() {
+        let mut tracker = BlockProductionTracker::default();
+        tracker.record("node-a", 7, 500, 500);
+        tracker.record("node-a", 7, 510, 512);
+
+        let summary = tracker.record("node-a", 7, 4, 5).expect("summary should exist");
+
+        assert_eq!(summary.current.produced, 14);
+        assert_eq!(summary.current.expected, 17);
+    }
+
+    #[test]
+    fn test_epoch_history_is_bounded() {
+        let mut history = NodeProductionHistory::default();
+        for epoch in 0..20 {
+            history.record(
+                epoch,
+                ProductionSample {
+                    block_qty: epoch * 10,
+                    expect_block_qty: epoch * 10,
+                },
+            );
+        }
+
+        assert_eq!(history.epochs.len(), BLOCK_PRODUCTION_EPOCH_HISTORY_LIMIT);
+        assert_eq!(history.epochs.front().map(|epoch| epoch.epoch), Some(12));
+    }
+}
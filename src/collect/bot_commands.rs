@@ -0,0 +1,122 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        Ordering,
+    },
+    Arc,
+};
+
+use chrono::Local;
+use log::warn;
+use tokio::time::{
+    self,
+    Duration,
+};
+
+use super::{
+    daily_snapshot::{
+        summary_details_without_history,
+        DailyNodeSnapshotStore,
+        SummaryPeriod,
+    },
+    data::SharedData,
+};
+use crate::{
+    error::Result,
+    notify::{
+        parse_bot_command,
+        render_mute_reply,
+        render_nodes_reply,
+        render_status_reply,
+        render_unmute_reply,
+        BotCommand,
+        Notifier,
+        TelegramCommandBot,
+        BOT_HELP_TEXT,
+    },
+    sync::lock_or_panic,
+};
+
+const BOT_COMMAND_RETRY_DELAY: Duration = Duration::from_secs(5);
+const BOT_COMMAND_STOP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Answer Telegram bot commands from the shared data until the collector
+/// stops. A long poll in flight is abandoned on stop.
+pub(crate) async fn serve_bot_commands(
+    mut bot: TelegramCommandBot,
+    data: SharedData,
+    store: Option<Arc<DailyNodeSnapshotStore>>,
+    notifier: Arc<Notifier>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    while !stop_flag.load(Ordering::Relaxed) {
+        let commands = tokio::select! {
+            result = bot.poll() => result,
+            _ = wait_for_stop(&stop_flag) => break,
+        };
+        let commands = match commands {
+            Ok(commands) => commands,
+            Err(err) => {
+                warn!("Failed to poll telegram bot commands: {}", err);
+                time::sleep(BOT_COMMAND_RETRY_DELAY).await;
+                continue;
+            },
+        };
+
+        for command in commands {
+            let Some(parsed) = parse_bot_command(&command.text) else {
+                continue;
+            };
+            let reply = match parsed {
+                Ok(BotCommand::Daily) => render_daily_reply(&data, store.as_deref(), &notifier),
+                Ok(parsed) => answer_command(parsed, &data, &notifier),
+                Err(reply) => reply,
+            };
+            bot.reply(&command.chat_id, &reply).await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn wait_for_stop(stop_flag: &AtomicBool) {
+    while !stop_flag.load(Ordering::Relaxed) {
+        time::sleep(BOT_COMMAND_STOP_POLL_INTERVAL).await;
+    }
+}
+
+fn answer_command(
+    command: BotCommand,
+    data: &SharedData,
+    notifier: &Notifier,
+) -> String {
+    match command {
+        BotCommand::Status => {
+            let data = lock_or_panic(data);
+            render_status_reply(data.cur_block_number(), &data.states())
+        },
+        BotCommand::Nodes => render_nodes_reply(&lock_or_panic(data).node_details()),
+        BotCommand::Mute(duration) => render_mute_reply(notifier.mute_for(duration)),
+        BotCommand::Unmute => render_unmute_reply(notifier.unmute()),
+        BotCommand::Daily | BotCommand::Help => BOT_HELP_TEXT.to_string(),
+    }
+}
+
+/// Today's summary so far: live details against today's midnight snapshot.
+fn render_daily_reply(
+    data: &SharedData,
+    store: Option<&DailyNodeSnapshotStore>,
+    notifier: &Notifier,
+) -> String {
+    let today = Local::now().date_naive();
+    let (node_details, watched_validators) = {
+        let data = lock_or_panic(data);
+        (data.node_details(), data.watched_validators())
+    };
+    let summary_details = match store {
+        Some(store) => store.live_summary_details(today, &node_details),
+        None => summary_details_without_history(SummaryPeriod::Daily, today, &node_details),
+    };
+
+    notifier.render_on_demand_summary(&today.to_string(), &summary_details, &watched_validators)
+}
@@ -4,6 +4,7 @@ use std::sync::{
         Ordering,
     },
     Arc,
+    Mutex,
 };
 
 use log::{
@@ -18,13 +19,38 @@ use tokio::{
 #[cfg(target_family = "unix")]
 use super::system_stats::collect_system_stats;
 use super::{
+    alert_engine::run_alert_rules,
+    alert_rules::{
+        load_alert_rules,
+        AlertRule,
+    },
     block_subscription::{
         is_websocket_endpoint,
         run_block_subscription_loop,
     },
+    bot_commands::serve_bot_commands,
+    daily_snapshot::DailySnapshotSettings,
     data::SharedData,
-    node_detail::collect_node_details,
+    delegation::{
+        collect_delegations,
+        DelegationSettings,
+    },
+    explorer::{
+        ExplorerClient,
+        ExplorerSettings,
+    },
+    hourly_snapshot::{
+        collect_hourly_snapshots,
+        HourlySnapshotSettings,
+    },
+    node_detail::{
+        collect_daily_snapshots,
+        collect_node_details,
+        open_daily_snapshot_store,
+        NodeDetailAlerts,
+    },
     node_state::collect_node_state,
+    notification_upkeep::run_notification_upkeep,
     peer_geo::collect_peer_geo,
 };
 use crate::{
@@ -33,8 +59,12 @@ use crate::{
         Result,
     },
     geo::PeerGeoStore,
-    notify::TelegramNotifier,
+    notify::{
+        Notifier,
+        TelegramCommandBot,
+    },
     opts::Opts,
+    sync::lock_or_panic,
 };
 
 const COLLECTOR_RETRY_DELAY: Duration = Duration::from_secs(1);
@@ -48,12 +78,31 @@ pub struct Collector {
     disk_alert_threshold: f32,
     disk_refresh_interval: u64,
     node_ids: Vec<String>,
-    explorer_api_url: String,
-    notifier: Option<Arc<TelegramNotifier>>,
+    watch_node_ids: Vec<String>,
+    explorer: ExplorerSettings,
+    node_detail_alerts: NodeDetailAlerts,
+    delegation: DelegationSettings,
+    daily_snapshots: DailySnapshotSettings,
+    hourly_snapshots: HourlySnapshotSettings,
+    alert_rules: Vec<AlertRule>,
+    notifier: Option<Arc<Notifier>>,
+    /// Taken by the command task when the collector starts.
+    telegram_commands: Mutex<Option<TelegramCommandBot>>,
     stop_flag: Arc<AtomicBool>,
     geo_store: Arc<dyn PeerGeoStore>,
 }
 
+fn unique_node_ids(values: &[String]) -> Vec<String> {
+    let mut node_ids: Vec<String> = Vec::new();
+    for node_id in values {
+        let node_id = node_id.trim();
+        if !node_id.is_empty() && !node_ids.iter().any(|existing| existing == node_id) {
+            node_ids.push(node_id.to_string());
+        }
+    }
+    node_ids
+}
+
 pub async fn run(collector: Arc<Collector>) -> Result<()> {
     collector.run().await
 }
@@ -83,15 +132,16 @@ impl Collector {
         let disk_auto_discovery = opts.disk_auto_discovery;
         let disk_alert_threshold = opts.disk_alert_threshold;
         let disk_refresh_interval = opts.disk_refresh_interval;
-        let mut node_ids = Vec::new();
-        for node_id in &opts.node_id {
-            let node_id = node_id.trim();
-            if !node_id.is_empty() && !node_ids.iter().any(|existing| existing == node_id) {
-                node_ids.push(node_id.to_string());
-            }
-        }
-        let explorer_api_url = opts.explorer_api_url.clone();
-        let notifier = TelegramNotifier::from_opts(opts)?;
+        let node_ids = unique_node_ids(&opts.node_id);
+        let watch_node_ids = unique_node_ids(&opts.watch_node_id);
+        let explorer = ExplorerSettings::from_opts(opts);
+        let node_detail_alerts = NodeDetailAlerts::from_opts(opts);
+        let delegation = DelegationSettings::from_opts(opts);
+        let daily_snapshots = DailySnapshotSettings::from_opts(opts);
+        let hourly_snapshots = HourlySnapshotSettings::from_opts(opts);
+        let alert_rules = load_alert_rules(opts.alert_rules.as_deref())?;
+        let notifier = Notifier::from_opts(opts)?;
+        let telegram_commands = Mutex::new(TelegramCommandBot::from_opts(opts)?);
 
         Ok(Collector {
             data,
@@ -101,8 +151,15 @@ impl Collector {
             disk_alert_threshold,
             disk_refresh_interval,
             node_ids,
-            explorer_api_url,
+            watch_node_ids,
+            explorer,
+            node_detail_alerts,
+            delegation,
+            daily_snapshots,
+            hourly_snapshots,
+            alert_rules,
             notifier,
+            telegram_commands,
             stop_flag: Arc::new(AtomicBool::new(false)),
             geo_store,
         })
@@ -144,19 +201,116 @@ impl Collector {
             });
         }
 
-        if !self.node_ids.is_empty() {
+        // Endpoints may link themselves to validators via admin_nodeInfo, so details are
+        // collected even when no --node-id is configured.
+        let collects_details =
+            !self.node_ids.is_empty() || !self.watch_node_ids.is_empty() || !self.urls.is_empty();
+        let bot = lock_or_panic(&self.telegram_commands).take();
+        // The snapshot task and the /daily command share one store.
+        let daily_store = if collects_details || bot.is_some() {
+            open_daily_snapshot_store(&self.data, &self.daily_snapshots)
+        } else {
+            None
+        };
+
+        if collects_details {
             debug!("start collect node detail: {:?}", self.node_ids);
+            let explorer =
+                Arc::new(ExplorerClient::new(&self.explorer, Arc::clone(&self.stop_flag)));
+
             let node_ids = self.node_ids.clone();
-            let explorer_api_url = self.explorer_api_url.clone();
+            let watch_node_ids = self.watch_node_ids.clone();
+            let client = Arc::clone(&explorer);
+            let node_detail_alerts = self.node_detail_alerts.clone();
+            let data = self.data.clone();
+            let notifier = self.notifier.clone();
+            let stop_flag = self.stop_flag.clone();
+            background_tasks.spawn(async move {
+                if let Err(e) = collect_node_details(
+                    node_ids,
+                    watch_node_ids,
+                    data,
+                    client,
+                    node_detail_alerts,
+                    notifier,
+                    stop_flag,
+                )
+                .await
+                {
+                    warn!("collect_node_details failed: {}", e);
+                }
+            });
+
+            let daily_snapshots = self.daily_snapshots.clone();
+            let store = daily_store.clone();
             let data = self.data.clone();
             let notifier = self.notifier.clone();
             let stop_flag = self.stop_flag.clone();
             background_tasks.spawn(async move {
                 if let Err(e) =
-                    collect_node_details(node_ids, data, explorer_api_url, notifier, stop_flag)
+                    collect_daily_snapshots(data, daily_snapshots, store, notifier, stop_flag).await
+                {
+                    warn!("collect_daily_snapshots failed: {}", e);
+                }
+            });
+
+            let hourly_snapshots = self.hourly_snapshots.clone();
+            let data = self.data.clone();
+            let notifier = self.notifier.clone();
+            let stop_flag = self.stop_flag.clone();
+            background_tasks.spawn(async move {
+                if let Err(e) =
+                    collect_hourly_snapshots(data, hourly_snapshots, notifier, stop_flag).await
+                {
+                    warn!("collect_hourly_snapshots failed: {}", e);
+                }
+            });
+
+            let node_ids = self.node_ids.clone();
+            let delegation = self.delegation.clone();
+            let data = self.data.clone();
+            let notifier = self.notifier.clone();
+            let stop_flag = self.stop_flag.clone();
+            background_tasks.spawn(async move {
+                if let Err(e) =
+                    collect_delegations(node_ids, explorer, delegation, data, notifier, stop_flag)
                         .await
                 {
-                    warn!("collect_node_details failed: {}", e);
+                    warn!("collect_delegations failed: {}", e);
+                }
+            });
+        }
+
+        if let Some(notifier) = self.notifier.clone() {
+            let data = self.data.clone();
+            let stop_flag = self.stop_flag.clone();
+            background_tasks.spawn(async move {
+                if let Err(e) = run_notification_upkeep(notifier, data, stop_flag).await {
+                    warn!("run_notification_upkeep failed: {}", e);
+                }
+            });
+        }
+
+        if !self.alert_rules.is_empty() {
+            let rules = self.alert_rules.clone();
+            let data = self.data.clone();
+            let notifier = self.notifier.clone();
+            let stop_flag = self.stop_flag.clone();
+            background_tasks.spawn(async move {
+                if let Err(e) = run_alert_rules(rules, data, notifier, stop_flag).await {
+                    warn!("run_alert_rules failed: {}", e);
+                }
+            });
+        }
+
+        if let (Some(bot), Some(notifier)) = (bot, self.notifier.clone()) {
+            let data = self.data.clone();
+            let stop_flag = self.stop_flag.clone();
+            background_tasks.spawn(async move {
+                if let Err(e) =
+                    serve_bot_commands(bot, data, daily_store, notifier, stop_flag).await
+                {
+                    warn!("serve_bot_commands failed: {}", e);
                 }
             });
         }
@@ -276,6 +430,22 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_collector_new_rejects_unreadable_alert_rules() {
+        let opts = Opts::parse_from([
+            "test",
+            "--url",
+            "test@ws://127.0.0.1:6789",
+            "--alert-rules",
+            "/nonexistent/chaindash-alert-rules.json",
+        ]);
+        let data: SharedData = Arc::new(Mutex::new(Data::default()));
+
+        let result = Collector::new(&opts, data, test_geo_store());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("failed to read alert rules"));
+    }
+
     #[test]
     fn test_collector_new_rejects_non_websocket_url() {
         let opts = Opts::parse_from(["test", "--url", "test@http://127.0.0.1:6789"]);
@@ -331,6 +501,8 @@ mod tests {
             "123456,789012",
             "--telegram-notify-events",
             "connection-failed,ranking-changed",
+            "--db-path",
+            ":memory:",
         ]);
         let data: SharedData = Arc::new(Mutex::new(Data::default()));
 
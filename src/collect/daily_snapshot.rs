@@ -2,31 +2,86 @@ use std::{
     env,
     fs,
     io::ErrorKind,
-    path::PathBuf,
-    time::{
-        SystemTime,
-        UNIX_EPOCH,
+    path::{
+        Path,
+        PathBuf,
     },
+    sync::Mutex,
 };
 
 use chrono::{
     Datelike,
+    Local,
     NaiveDate,
 };
-use log::warn;
+use log::{
+    info,
+    warn,
+};
+use rusqlite::{
+    params,
+    types::Type,
+    Connection,
+};
 use serde::{
     Deserialize,
     Serialize,
 };
 
 use super::data::NodeDetail;
-use crate::error::Result;
+use crate::{
+    db::{
+        open_connection,
+        open_read_only_connection,
+        run_migrations,
+    },
+    error::Result,
+    opts::{
+        Opts,
+        ReportSchedule,
+    },
+    sync::lock_or_panic,
+};
 
 const DAILY_NODE_SNAPSHOT_FILE_NAME: &str = "daily-node-snapshots.json";
-const DAILY_NODE_SNAPSHOT_HISTORY_LIMIT: usize = 32;
+/// Suffix given to the JSON store once its snapshots have been imported.
+const DAILY_NODE_SNAPSHOT_IMPORTED_SUFFIX: &str = "imported";
+/// Daily deltas are averaged across at most this many days without snapshots.
+const DAILY_NODE_SNAPSHOT_GAP_LOOKBACK_DAYS: u64 = 31;
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
 const DEFAULT_STATE_DIR_NAME: &str = "chaindash";
 const DEFAULT_STATE_FALLBACK_DIR_NAME: &str = ".chaindash";
 
+/// Where daily node snapshots are kept and for how long.
+#[derive(Debug, Clone)]
+pub(crate) struct DailySnapshotSettings {
+    pub db_path: String,
+    /// Days of snapshots to keep; `None` keeps the full history.
+    pub retention_days: Option<u64>,
+    /// JSON store written by earlier releases, imported once on startup.
+    pub legacy_path: PathBuf,
+    /// Send the daily summary for a snapshot taken late after downtime.
+    pub late_summary: bool,
+    /// When summaries are sent and to whom; never empty.
+    pub report_schedules: Vec<ReportSchedule>,
+}
+
+impl DailySnapshotSettings {
+    pub(crate) fn from_opts(opts: &Opts) -> Self {
+        Self {
+            db_path: opts.db_path.clone(),
+            retention_days: opts.daily_snapshot_retention_days,
+            legacy_path: default_daily_node_snapshot_path(),
+            late_summary: opts.daily_summary_catch_up,
+            report_schedules: if opts.report_schedules.is_empty() {
+                vec![ReportSchedule::local_midnight()]
+            } else {
+                opts.report_schedules.clone()
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DailyNodeSummaryDetail {
     pub node_id: String,
@@ -36,196 +91,515 @@ pub(crate) struct DailyNodeSummaryDetail {
     pub reward_value: f64,
     pub daily_block_qty: Option<u64>,
     pub daily_reward_value: Option<f64>,
+    /// Days covered by the daily values. Above 1 when the previous day's
+    /// snapshot is missing and the values are averaged over the gap, or for
+    /// weekly and monthly summaries, whose values are period totals.
+    pub daily_span_days: u32,
     pub show_monthly_totals: bool,
     pub monthly_block_qty: Option<u64>,
     pub monthly_reward_value: Option<f64>,
+    pub stale: bool,
+    pub consecutive_failures: u32,
+    /// Daily reward extrapolated from the live reward history, used for the
+    /// yield estimates alongside the stake fields below.
+    pub estimated_daily_reward: Option<f64>,
+    pub reward_per: f64,
+    pub total_value: f64,
+    pub delegate_value: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The per-node counters a daily summary compares against.
+#[derive(Debug, Clone, Deserialize)]
 struct StoredDailyNodeSnapshot {
     date: String,
     node_details: Vec<StoredDailyNodeSnapshotDetail>,
+    /// Unix time the snapshot was taken; missing on imported snapshots.
+    #[serde(default)]
+    captured_at: Option<i64>,
+    /// Whether the snapshot was taken after startup instead of at midnight.
+    #[serde(default)]
+    late: bool,
 }
 
-impl StoredDailyNodeSnapshot {
-    fn from_node_details(
-        date: NaiveDate,
-        node_details: &[NodeDetail],
-    ) -> Self {
-        Self {
-            date: date.to_string(),
-            node_details: node_details
-                .iter()
-                .map(|detail| StoredDailyNodeSnapshotDetail {
-                    node_id: detail.node_id.clone(),
-                    node_name: detail.node_name.clone(),
-                    block_qty: detail.block_qty,
-                    reward_value: detail.reward_value,
-                })
-                .collect(),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct StoredDailyNodeSnapshotDetail {
     node_id: String,
     node_name: String,
     block_qty: u64,
     reward_value: f64,
+    /// Whether the snapshot was taken from a last-known detail rather than a
+    /// fresh Explorer response.
+    #[serde(default)]
+    stale: bool,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
-struct StoredDailyNodeSnapshotStore {
+/// Layout of the `daily-node-snapshots.json` file written by earlier releases.
+#[derive(Debug, Default, Deserialize)]
+struct LegacyDailyNodeSnapshotFile {
     snapshots: Vec<StoredDailyNodeSnapshot>,
 }
 
-impl StoredDailyNodeSnapshotStore {
-    fn snapshot_for(
-        &self,
-        date: NaiveDate,
-    ) -> Option<StoredDailyNodeSnapshot> {
-        let date = date.to_string();
-        self.snapshots.iter().find(|snapshot| snapshot.date == date).cloned()
-    }
+/// Length of the period a scheduled summary covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SummaryPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
 
-    fn upsert(
-        &mut self,
-        snapshot: StoredDailyNodeSnapshot,
-    ) {
-        self.snapshots.retain(|existing| existing.date != snapshot.date);
-        self.snapshots.push(snapshot);
-        self.snapshots.sort_by(|left, right| left.date.cmp(&right.date));
-    }
+/// One node's counters from a stored daily snapshot, as read by `report`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DailySnapshotRow {
+    pub date: NaiveDate,
+    pub node_id: String,
+    pub node_name: String,
+    /// Missing on rows imported from the JSON store.
+    pub ranking: Option<i32>,
+    pub block_qty: u64,
+    pub reward_value: f64,
+}
 
-    fn prune(&mut self) {
-        if self.snapshots.len() <= DAILY_NODE_SNAPSHOT_HISTORY_LIMIT {
-            return;
+/// Daily node snapshots persisted in the `--db-path` database.
+#[derive(Debug)]
+pub(crate) struct DailyNodeSnapshotStore {
+    conn: Mutex<Connection>,
+    retention_days: Option<u64>,
+}
+
+impl DailyNodeSnapshotStore {
+    /// Open the database and import the legacy JSON store if one is left. A
+    /// failed import is retried on the next start.
+    pub(crate) fn open(settings: &DailySnapshotSettings) -> Result<Self> {
+        let store = Self::open_database(&settings.db_path, settings.retention_days)?;
+        if let Err(err) = store.import_legacy_file(&settings.legacy_path) {
+            warn!(
+                "Failed to import daily node snapshots from {}: {}",
+                settings.legacy_path.display(),
+                err
+            );
         }
+        Ok(store)
+    }
 
-        let excess = self.snapshots.len() - DAILY_NODE_SNAPSHOT_HISTORY_LIMIT;
-        self.snapshots.drain(0..excess);
+    /// Open the database for reading only: nothing is imported, pruned or
+    /// migrated, and writes fail.
+    pub(crate) fn open_read_only(db_path: &str) -> Result<Self> {
+        Ok(Self {
+            conn: Mutex::new(open_read_only_connection(db_path)?),
+            retention_days: None,
+        })
     }
-}
 
-#[derive(Debug, Clone)]
-pub(super) struct DailyNodeSnapshotStore {
-    path: PathBuf,
-}
+    fn open_database(
+        db_path: &str,
+        retention_days: Option<u64>,
+    ) -> Result<Self> {
+        let mut conn = open_connection(db_path)?;
+        run_migrations(&mut conn)?;
 
-impl Default for DailyNodeSnapshotStore {
-    fn default() -> Self {
-        Self::new(default_daily_node_snapshot_path())
+        Ok(Self {
+            conn: Mutex::new(conn),
+            retention_days,
+        })
     }
-}
 
-impl DailyNodeSnapshotStore {
-    pub(super) fn new(path: PathBuf) -> Self {
-        Self { path }
+    /// Copy snapshots from the JSON store into the database, then rename the
+    /// file so the import runs only once. Rows already in the database win.
+    fn import_legacy_file(
+        &self,
+        path: &Path,
+    ) -> Result<()> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let legacy: LegacyDailyNodeSnapshotFile = if content.trim().is_empty() {
+            LegacyDailyNodeSnapshotFile::default()
+        } else {
+            serde_json::from_str(&content)?
+        };
+
+        let imported = insert_legacy_snapshots_tx(&mut lock_or_panic(&self.conn), &legacy)?;
+        self.prune(Local::now().date_naive())?;
+
+        let mut imported_path = path.as_os_str().to_owned();
+        imported_path.push(format!(".{DAILY_NODE_SNAPSHOT_IMPORTED_SUFFIX}"));
+        fs::rename(path, &imported_path)?;
+        info!(
+            "Imported {} daily node snapshot row(s) from {} into the database",
+            imported,
+            path.display()
+        );
+
+        Ok(())
     }
 
-    pub(super) fn daily_summary_details(
+    /// Summary of the period from the `start` snapshot to the `end` one. The
+    /// end counters come from the `end` snapshot when it exists and from the
+    /// live details otherwise; weekly and monthly periods report totals.
+    pub(super) fn summary_details(
         &self,
-        date: NaiveDate,
+        period: SummaryPeriod,
+        start: NaiveDate,
+        end: NaiveDate,
         node_details: &[NodeDetail],
     ) -> Vec<DailyNodeSummaryDetail> {
-        let store = self.read_store();
-        let previous_snapshot =
-            date.pred_opt().and_then(|previous_date| store.snapshot_for(previous_date));
-        let show_monthly_totals = date.day() == 1;
-        let monthly_snapshot = previous_month_start_snapshot_date(date)
-            .and_then(|monthly_date| store.snapshot_for(monthly_date));
-
-        build_daily_summary_details(
+        let end_snapshot = self.snapshot_or_warn(end);
+        let baseline_snapshot = self.baseline_snapshot_or_warn(start, end);
+        let show_monthly_totals = period == SummaryPeriod::Daily && end.day() == 1;
+        let monthly_snapshot = previous_month_start_snapshot_date(end)
+            .filter(|_| show_monthly_totals)
+            .and_then(|monthly_date| self.snapshot_or_warn(monthly_date));
+
+        build_summary_details(
+            period,
             node_details,
-            previous_snapshot.as_ref(),
+            end_snapshot.as_ref(),
+            baseline_snapshot.as_ref().map(|(snapshot, span_days)| (snapshot, *span_days)),
             monthly_snapshot.as_ref(),
             show_monthly_totals,
         )
     }
 
+    /// Progress since the `date` snapshot, taken from the live details, for
+    /// a summary requested before the day is over.
+    pub(super) fn live_summary_details(
+        &self,
+        date: NaiveDate,
+        node_details: &[NodeDetail],
+    ) -> Vec<DailyNodeSummaryDetail> {
+        let baseline_snapshot = self.baseline_snapshot_or_warn(date, date);
+
+        build_summary_details(
+            SummaryPeriod::Daily,
+            node_details,
+            None,
+            baseline_snapshot.as_ref().map(|(snapshot, span_days)| (snapshot, *span_days)),
+            None,
+            false,
+        )
+    }
+
+    /// Record every node detail for `date`, replacing an earlier snapshot of
+    /// the same day, and drop snapshots past the retention window. `late`
+    /// marks a snapshot taken after startup instead of at midnight.
     pub(super) fn save_snapshot(
         &self,
         date: NaiveDate,
         node_details: &[NodeDetail],
+        late: bool,
     ) -> Result<()> {
         if node_details.is_empty() {
             return Ok(());
         }
 
-        let mut store = self.read_store();
-        store.upsert(StoredDailyNodeSnapshot::from_node_details(date, node_details));
-        store.prune();
-        self.write_store(&store)
-    }
-
-    fn read_store(&self) -> StoredDailyNodeSnapshotStore {
-        match fs::read_to_string(&self.path) {
-            Ok(content) if content.trim().is_empty() => StoredDailyNodeSnapshotStore::default(),
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(store) => store,
-                Err(err) => {
-                    warn!(
-                        "Failed to parse daily node snapshot store at {}: {}",
-                        self.path.display(),
-                        err
-                    );
-                    StoredDailyNodeSnapshotStore::default()
-                },
-            },
-            Err(err) if err.kind() == ErrorKind::NotFound => {
-                StoredDailyNodeSnapshotStore::default()
+        insert_daily_snapshot_tx(&mut lock_or_panic(&self.conn), date, node_details, late)?;
+        self.prune(date)
+    }
+
+    pub(super) fn has_snapshot(
+        &self,
+        date: NaiveDate,
+    ) -> Result<bool> {
+        let exists = lock_or_panic(&self.conn).query_row(
+            "SELECT EXISTS(SELECT 1 FROM daily_node_snapshots WHERE snapshot_date = ?1)",
+            params![date.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// Date of the newest snapshot before `date`, if any.
+    pub(super) fn latest_snapshot_date_before(
+        &self,
+        date: NaiveDate,
+    ) -> Result<Option<NaiveDate>> {
+        latest_snapshot_date_between(&lock_or_panic(&self.conn), None, date)
+    }
+
+    /// Every stored row dated `from..=to`, oldest first.
+    pub(crate) fn snapshots_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailySnapshotRow>> {
+        daily_snapshots_between(&lock_or_panic(&self.conn), from, to)
+    }
+
+    /// The `start` snapshot, or the newest one inside the lookback window
+    /// before it, together with the number of days it lies behind `end`.
+    fn baseline_snapshot_or_warn(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Option<(StoredDailyNodeSnapshot, u32)> {
+        let oldest =
+            start.checked_sub_days(chrono::Days::new(DAILY_NODE_SNAPSHOT_GAP_LOOKBACK_DAYS - 1));
+        let baseline_date = match start.succ_opt().map(|after_start| {
+            latest_snapshot_date_between(&lock_or_panic(&self.conn), oldest, after_start)
+        }) {
+            Some(Ok(baseline_date)) => baseline_date?,
+            Some(Err(err)) => {
+                warn!("Failed to look up the daily node snapshot before {}: {}", end, err);
+                return None;
             },
+            None => return None,
+        };
+        let span_days = (end - baseline_date).num_days().max(1) as u32;
+
+        self.snapshot_or_warn(baseline_date).map(|snapshot| (snapshot, span_days))
+    }
+
+    fn snapshot_or_warn(
+        &self,
+        date: NaiveDate,
+    ) -> Option<StoredDailyNodeSnapshot> {
+        match daily_snapshot_for(&lock_or_panic(&self.conn), date) {
+            Ok(snapshot) => snapshot,
             Err(err) => {
-                warn!(
-                    "Failed to read daily node snapshot store at {}: {}",
-                    self.path.display(),
-                    err
-                );
-                StoredDailyNodeSnapshotStore::default()
+                warn!("Failed to read daily node snapshot for {}: {}", date, err);
+                None
             },
         }
     }
 
-    fn write_store(
+    fn prune(
         &self,
-        store: &StoredDailyNodeSnapshotStore,
+        today: NaiveDate,
     ) -> Result<()> {
-        let Some(parent) = self.path.parent() else {
-            return Err("daily node snapshot path has no parent directory".into());
+        let Some(retention_days) = self.retention_days else {
+            return Ok(());
+        };
+        let Some(oldest) = today.checked_sub_days(chrono::Days::new(retention_days)) else {
+            return Ok(());
         };
 
-        fs::create_dir_all(parent)?;
-
-        let temp_suffix = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.as_nanos())
-            .unwrap_or(0);
-        let temp_path =
-            parent.join(format!("{}.{}.tmp", DAILY_NODE_SNAPSHOT_FILE_NAME, temp_suffix));
-        let content = serde_json::to_vec_pretty(store)?;
+        lock_or_panic(&self.conn).execute(
+            "DELETE FROM daily_node_snapshots WHERE snapshot_date < ?1",
+            params![oldest.to_string()],
+        )?;
+        Ok(())
+    }
+}
 
-        fs::write(&temp_path, content)?;
+fn insert_daily_snapshot_tx(
+    conn: &mut Connection,
+    date: NaiveDate,
+    node_details: &[NodeDetail],
+    late: bool,
+) -> Result<()> {
+    let date = date.to_string();
+    let captured_at = Local::now().timestamp();
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM daily_node_snapshots WHERE snapshot_date = ?1", params![date])?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO daily_node_snapshots (
+                 snapshot_date, node_id, node_name, captured_at, ranking, block_qty,
+                 expect_block_qty, block_rate, daily_block_rate, reward_per, reward_value,
+                 reward_address, verifier_time, total_value, delegate_value, endpoint, stale,
+                 consecutive_failures, last_error, production_epoch, epoch_blocks_produced,
+                 epoch_blocks_expected, estimated_daily_reward, late
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                       ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+        )?;
+        for detail in node_details {
+            let production = detail.production.map(|production| production.current);
+            stmt.execute(params![
+                date,
+                detail.node_id,
+                detail.node_name,
+                captured_at,
+                detail.ranking,
+                detail.block_qty as i64,
+                detail.expect_block_qty as i64,
+                detail.block_rate,
+                detail.daily_block_rate,
+                detail.reward_per,
+                detail.reward_value,
+                detail.reward_address,
+                detail.verifier_time as i64,
+                detail.total_value,
+                detail.delegate_value,
+                detail.endpoint,
+                detail.is_stale(),
+                detail.consecutive_failures,
+                detail.last_error,
+                production.map(|production| production.epoch as i64),
+                production.map(|production| production.produced as i64),
+                production.map(|production| production.expected as i64),
+                detail.daily_reward(),
+                late,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
 
-        if self.path.exists() {
-            fs::remove_file(&self.path)?;
+/// Insert the JSON store's snapshots, keeping rows that already exist.
+/// Returns the number of rows written.
+fn insert_legacy_snapshots_tx(
+    conn: &mut Connection,
+    legacy: &LegacyDailyNodeSnapshotFile,
+) -> Result<usize> {
+    let tx = conn.transaction()?;
+    let mut imported = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR IGNORE INTO daily_node_snapshots
+                 (snapshot_date, node_id, node_name, block_qty, reward_value, stale)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for snapshot in &legacy.snapshots {
+            if snapshot.date.parse::<NaiveDate>().is_err() {
+                warn!("Skipping daily node snapshot with invalid date {:?}", snapshot.date);
+                continue;
+            }
+            for detail in &snapshot.node_details {
+                imported += stmt.execute(params![
+                    snapshot.date,
+                    detail.node_id,
+                    detail.node_name,
+                    detail.block_qty as i64,
+                    detail.reward_value,
+                    detail.stale,
+                ])?;
+            }
         }
-        fs::rename(&temp_path, &self.path)?;
+    }
+    tx.commit()?;
+    Ok(imported)
+}
 
-        Ok(())
+fn daily_snapshots_between(
+    conn: &Connection,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<DailySnapshotRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT snapshot_date, node_id, node_name, ranking, block_qty, reward_value
+         FROM daily_node_snapshots WHERE snapshot_date BETWEEN ?1 AND ?2
+         ORDER BY snapshot_date, node_name, node_id",
+    )?;
+    let rows = stmt.query_map(params![from.to_string(), to.to_string()], |row| {
+        let date = row.get::<_, String>(0)?.parse().map_err(|err| {
+            rusqlite::Error::FromSqlConversionFailure(0, Type::Text, Box::new(err))
+        })?;
+        Ok(DailySnapshotRow {
+            date,
+            node_id: row.get(1)?,
+            node_name: row.get(2)?,
+            ranking: row.get(3)?,
+            block_qty: row.get::<_, i64>(4)?.max(0) as u64,
+            reward_value: row.get(5)?,
+        })
+    })?;
+    let snapshots = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(snapshots)
+}
+
+/// Newest snapshot date before `to`, no older than `from` when given.
+fn latest_snapshot_date_between(
+    conn: &Connection,
+    from: Option<NaiveDate>,
+    to: NaiveDate,
+) -> Result<Option<NaiveDate>> {
+    let date: Option<String> = conn.query_row(
+        "SELECT MAX(snapshot_date) FROM daily_node_snapshots
+         WHERE (?1 IS NULL OR snapshot_date >= ?1) AND snapshot_date < ?2",
+        params![from.map(|from| from.to_string()), to.to_string()],
+        |row| row.get(0),
+    )?;
+
+    Ok(date.and_then(|date| date.parse().ok()))
+}
+
+fn daily_snapshot_for(
+    conn: &Connection,
+    date: NaiveDate,
+) -> Result<Option<StoredDailyNodeSnapshot>> {
+    let date = date.to_string();
+    let mut stmt = conn.prepare(
+        "SELECT node_id, node_name, block_qty, reward_value, stale, captured_at, late
+         FROM daily_node_snapshots WHERE snapshot_date = ?1
+         ORDER BY node_name, node_id",
+    )?;
+    let mut captured_at = None;
+    let mut late = false;
+    let rows = stmt.query_map(params![date], |row| {
+        Ok((
+            StoredDailyNodeSnapshotDetail {
+                node_id: row.get(0)?,
+                node_name: row.get(1)?,
+                block_qty: row.get::<_, i64>(2)?.max(0) as u64,
+                reward_value: row.get(3)?,
+                stale: row.get(4)?,
+            },
+            row.get::<_, Option<i64>>(5)?,
+            row.get::<_, bool>(6)?,
+        ))
+    })?;
+    let mut node_details = Vec::new();
+    for row in rows {
+        let (detail, row_captured_at, row_late) = row?;
+        // Rows of one snapshot are written together and share these.
+        captured_at = captured_at.or(row_captured_at);
+        late |= row_late;
+        node_details.push(detail);
     }
+
+    Ok((!node_details.is_empty()).then_some(StoredDailyNodeSnapshot {
+        date,
+        node_details,
+        captured_at,
+        late,
+    }))
+}
+
+/// Summary details when no snapshot history is available.
+pub(super) fn summary_details_without_history(
+    period: SummaryPeriod,
+    date: NaiveDate,
+    node_details: &[NodeDetail],
+) -> Vec<DailyNodeSummaryDetail> {
+    let show_monthly_totals = period == SummaryPeriod::Daily && date.day() == 1;
+
+    build_summary_details(period, node_details, None, None, None, show_monthly_totals)
 }
 
-fn build_daily_summary_details(
+fn build_summary_details(
+    period: SummaryPeriod,
     node_details: &[NodeDetail],
-    previous_snapshot: Option<&StoredDailyNodeSnapshot>,
+    end_snapshot: Option<&StoredDailyNodeSnapshot>,
+    baseline_snapshot: Option<(&StoredDailyNodeSnapshot, u32)>,
     monthly_snapshot: Option<&StoredDailyNodeSnapshot>,
     show_monthly_totals: bool,
 ) -> Vec<DailyNodeSummaryDetail> {
+    let span_days = baseline_snapshot.map_or(1, |(_, span_days)| span_days.max(1));
+    // Daily values are averaged across a gap, or across the time actually
+    // elapsed when a late snapshot is involved; longer periods are totals.
+    let divisor = match period {
+        SummaryPeriod::Daily => late_snapshot_elapsed_days(
+            baseline_snapshot.map(|(snapshot, _)| snapshot),
+            end_snapshot,
+        )
+        .unwrap_or(f64::from(span_days)),
+        SummaryPeriod::Weekly | SummaryPeriod::Monthly => 1.0,
+    };
+
     node_details
         .iter()
         .map(|detail| {
-            let previous = find_snapshot_detail(previous_snapshot, detail);
+            let (block_qty, reward_value) = find_snapshot_detail(end_snapshot, detail)
+                .map_or((detail.block_qty, detail.reward_value), |end| {
+                    (end.block_qty, end.reward_value)
+                });
+            let baseline =
+                find_snapshot_detail(baseline_snapshot.map(|(snapshot, _)| snapshot), detail);
             let monthly = find_snapshot_detail(monthly_snapshot, detail);
 
             DailyNodeSummaryDetail {
@@ -234,22 +608,46 @@ fn build_daily_summary_details(
                 ranking: detail.ranking,
                 block_qty: detail.block_qty,
                 reward_value: detail.reward_value,
-                daily_block_qty: previous
-                    .map(|snapshot| detail.block_qty.saturating_sub(snapshot.block_qty)),
-                daily_reward_value: previous.map(|snapshot| {
-                    non_negative_reward_delta(detail.reward_value, snapshot.reward_value)
+                daily_block_qty: baseline.map(|snapshot| {
+                    let blocks = block_qty.saturating_sub(snapshot.block_qty);
+                    (blocks as f64 / divisor).round() as u64
+                }),
+                daily_reward_value: baseline.map(|snapshot| {
+                    non_negative_reward_delta(reward_value, snapshot.reward_value) / divisor
                 }),
+                daily_span_days: span_days,
                 show_monthly_totals,
                 monthly_block_qty: monthly
-                    .map(|snapshot| detail.block_qty.saturating_sub(snapshot.block_qty)),
-                monthly_reward_value: monthly.map(|snapshot| {
-                    non_negative_reward_delta(detail.reward_value, snapshot.reward_value)
-                }),
+                    .map(|snapshot| block_qty.saturating_sub(snapshot.block_qty)),
+                monthly_reward_value: monthly
+                    .map(|snapshot| non_negative_reward_delta(reward_value, snapshot.reward_value)),
+                stale: detail.is_stale(),
+                consecutive_failures: detail.consecutive_failures,
+                estimated_daily_reward: detail.daily_reward(),
+                reward_per: detail.reward_per,
+                total_value: detail.total_value,
+                delegate_value: detail.delegate_value,
             }
         })
         .collect()
 }
 
+/// Days elapsed between two stored snapshots when either was taken late, so
+/// a catch-up snapshot neither inflates the delta that ends at it nor shrinks
+/// the one that starts from it.
+fn late_snapshot_elapsed_days(
+    baseline: Option<&StoredDailyNodeSnapshot>,
+    end: Option<&StoredDailyNodeSnapshot>,
+) -> Option<f64> {
+    let (baseline, end) = (baseline?, end?);
+    if !baseline.late && !end.late {
+        return None;
+    }
+
+    let elapsed = end.captured_at? - baseline.captured_at?;
+    (elapsed > 0).then(|| elapsed as f64 / SECONDS_PER_DAY)
+}
+
 fn previous_month_start_snapshot_date(date: NaiveDate) -> Option<NaiveDate> {
     if date.day() != 1 {
         return None;
@@ -299,14 +697,18 @@ fn non_negative_reward_delta(
 }
 
 fn default_daily_node_snapshot_path() -> PathBuf {
+    default_state_file_path(DAILY_NODE_SNAPSHOT_FILE_NAME)
+}
+
+/// Resolve a file inside chaindash's state directory: `CHAINDASH_STATE_DIR`,
+/// then `$XDG_STATE_HOME/chaindash`, then `~/.local/state/chaindash`.
+pub(super) fn default_state_file_path(file_name: &str) -> PathBuf {
     if let Some(path) = env::var_os("CHAINDASH_STATE_DIR") {
-        return PathBuf::from(path).join(DAILY_NODE_SNAPSHOT_FILE_NAME);
+        return PathBuf::from(path).join(file_name);
     }
 
     if let Some(path) = env::var_os("XDG_STATE_HOME") {
-        return PathBuf::from(path)
-            .join(DEFAULT_STATE_DIR_NAME)
-            .join(DAILY_NODE_SNAPSHOT_FILE_NAME);
+        return PathBuf::from(path).join(DEFAULT_STATE_DIR_NAME).join(file_name);
     }
 
     if let Some(path) = env::var_os("HOME") {
@@ -314,25 +716,19 @@ fn default_daily_node_snapshot_path() -> PathBuf {
             .join(".local")
             .join("state")
             .join(DEFAULT_STATE_DIR_NAME)
-            .join(DAILY_NODE_SNAPSHOT_FILE_NAME);
+            .join(file_name);
     }
 
-    PathBuf::from(DEFAULT_STATE_FALLBACK_DIR_NAME).join(DAILY_NODE_SNAPSHOT_FILE_NAME)
+    PathBuf::from(DEFAULT_STATE_FALLBACK_DIR_NAME).join(file_name)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{
-        fs,
-        path::PathBuf,
-        time::{
-            SystemTime,
-            UNIX_EPOCH,
-        },
+    use std::time::{
+        SystemTime,
+        UNIX_EPOCH,
     };
 
-    use chrono::NaiveDate;
-
     use super::*;
 
     fn sample_node_detail(
@@ -347,6 +743,7 @@ mod tests {
             node_name: node_name.to_string(),
             ranking,
             block_qty,
+            expect_block_qty: block_qty,
             block_rate: "75.00%".to_string(),
             daily_block_rate: "1/day".to_string(),
             reward_per: 10.0,
@@ -354,32 +751,68 @@ mod tests {
             reward_address: "addr".to_string(),
             verifier_time: 0,
             last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         }
     }
 
-    fn temp_snapshot_store_path() -> PathBuf {
+    fn daily_summary_details(
+        store: &DailyNodeSnapshotStore,
+        date: NaiveDate,
+        node_details: &[NodeDetail],
+    ) -> Vec<DailyNodeSummaryDetail> {
+        let start = date.pred_opt().expect("date should have a previous day");
+
+        store.summary_details(SummaryPeriod::Daily, start, date, node_details)
+    }
+
+    fn memory_store(retention_days: Option<u64>) -> DailyNodeSnapshotStore {
+        DailyNodeSnapshotStore::open_database(":memory:", retention_days)
+            .expect("in-memory store should open")
+    }
+
+    fn date(
+        year: i32,
+        month: u32,
+        day: u32,
+    ) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("date should be valid")
+    }
+
+    fn temp_legacy_path() -> PathBuf {
         let suffix = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|duration| duration.as_nanos())
             .unwrap_or(0);
 
-        std::env::temp_dir()
+        env::temp_dir()
             .join(format!("chaindash-daily-snapshot-test-{suffix}"))
             .join(DAILY_NODE_SNAPSHOT_FILE_NAME)
     }
 
     #[test]
     fn test_daily_summary_details_use_previous_snapshot_delta() {
-        let path = temp_snapshot_store_path();
-        let store = DailyNodeSnapshotStore::new(path.clone());
-        let previous_date = NaiveDate::from_ymd_opt(2026, 4, 14).expect("date should be valid");
+        let store = memory_store(None);
+        let previous_date = date(2026, 4, 14);
         let current_date = previous_date.succ_opt().expect("date should advance");
 
         store
-            .save_snapshot(previous_date, &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)])
+            .save_snapshot(
+                previous_date,
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
             .expect("snapshot should save");
 
-        let details = store.daily_summary_details(
+        let details = daily_summary_details(
+            &store,
             current_date,
             &[sample_node_detail("node-a", "node-a", 1, 135, 18.5)],
         );
@@ -390,19 +823,15 @@ mod tests {
         assert!(!details[0].show_monthly_totals);
         assert_eq!(details[0].monthly_block_qty, None);
         assert_eq!(details[0].monthly_reward_value, None);
-
-        if let Some(parent) = path.parent() {
-            let _ = fs::remove_dir_all(parent);
-        }
     }
 
     #[test]
     fn test_daily_summary_details_leave_daily_values_empty_without_previous_snapshot() {
-        let store = DailyNodeSnapshotStore::new(temp_snapshot_store_path());
-        let current_date = NaiveDate::from_ymd_opt(2026, 4, 15).expect("date should be valid");
+        let store = memory_store(None);
 
-        let details = store.daily_summary_details(
-            current_date,
+        let details = daily_summary_details(
+            &store,
+            date(2026, 4, 15),
             &[sample_node_detail("node-a", "node-a", 1, 135, 18.5)],
         );
 
@@ -415,48 +844,115 @@ mod tests {
     }
 
     #[test]
-    fn test_save_snapshot_round_trips_previous_day_data() {
-        let path = temp_snapshot_store_path();
-        let store = DailyNodeSnapshotStore::new(path.clone());
-        let snapshot_date = NaiveDate::from_ymd_opt(2026, 4, 14).expect("date should be valid");
+    fn test_save_snapshot_replaces_same_day_snapshot() {
+        let store = memory_store(None);
+        let snapshot_date = date(2026, 4, 14);
 
         store
-            .save_snapshot(snapshot_date, &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)])
+            .save_snapshot(
+                snapshot_date,
+                &[sample_node_detail("node-a", "node-a", 1, 90, 9.0)],
+                false,
+            )
+            .expect("snapshot should save");
+        store
+            .save_snapshot(
+                snapshot_date,
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
             .expect("snapshot should save");
 
-        let details = store.daily_summary_details(
+        let details = daily_summary_details(
+            &store,
             snapshot_date.succ_opt().expect("date should advance"),
             &[sample_node_detail("node-a", "node-a", 1, 120, 15.0)],
         );
 
         assert_eq!(details[0].daily_block_qty, Some(20));
         assert_eq!(details[0].daily_reward_value, Some(5.0));
-        assert!(!details[0].show_monthly_totals);
-        assert_eq!(details[0].monthly_block_qty, None);
-        assert_eq!(details[0].monthly_reward_value, None);
+    }
 
-        if let Some(parent) = path.parent() {
-            let _ = fs::remove_dir_all(parent);
-        }
+    #[test]
+    fn test_save_snapshot_records_every_node_detail_field() {
+        let store = memory_store(None);
+        let snapshot_date = date(2026, 4, 14);
+        let mut fresh = sample_node_detail("node-a", "node-a", 1, 100, 10.0);
+        fresh.last_updated_at = Some(std::time::Instant::now());
+        fresh.endpoint = Some("main".to_string());
+        fresh.total_value = 1_500_000.0;
+        fresh.delegate_value = 500_000.0;
+        let mut stale = sample_node_detail("node-b", "node-b", 2, 200, 20.0);
+        stale.consecutive_failures = 3;
+        stale.last_error = Some("timeout".to_string());
+
+        store
+            .save_snapshot(snapshot_date, &[fresh, stale.clone()], false)
+            .expect("snapshot should save");
+
+        let conn = lock_or_panic(&store.conn);
+        let row: (i32, String, Option<String>, f64, f64, bool) = conn
+            .query_row(
+                "SELECT ranking, block_rate, endpoint, total_value, delegate_value, stale
+                 FROM daily_node_snapshots WHERE node_id = 'node-a'",
+                [],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                },
+            )
+            .expect("row should exist");
+        assert_eq!(
+            row,
+            (1, "75.00%".to_string(), Some("main".to_string()), 1_500_000.0, 500_000.0, false)
+        );
+
+        let (stale_flag, failures, last_error): (bool, u32, Option<String>) = conn
+            .query_row(
+                "SELECT stale, consecutive_failures, last_error
+                 FROM daily_node_snapshots WHERE node_id = 'node-b'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .expect("row should exist");
+        assert!(stale_flag);
+        assert_eq!(failures, 3);
+        assert_eq!(last_error.as_deref(), Some("timeout"));
+        drop(conn);
+
+        let details = daily_summary_details(&store, snapshot_date, &[stale]);
+        assert!(details[0].stale);
+        assert_eq!(details[0].consecutive_failures, 3);
     }
 
     #[test]
     fn test_daily_summary_details_include_previous_month_totals_on_first_day() {
-        let path = temp_snapshot_store_path();
-        let store = DailyNodeSnapshotStore::new(path.clone());
-        let month_start = NaiveDate::from_ymd_opt(2026, 4, 1).expect("date should be valid");
-        let previous_day = NaiveDate::from_ymd_opt(2026, 4, 30).expect("date should be valid");
-        let report_date = NaiveDate::from_ymd_opt(2026, 5, 1).expect("date should be valid");
+        let store = memory_store(None);
 
         store
-            .save_snapshot(month_start, &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)])
+            .save_snapshot(
+                date(2026, 4, 1),
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
             .expect("snapshot should save");
         store
-            .save_snapshot(previous_day, &[sample_node_detail("node-a", "node-a", 1, 145, 16.5)])
+            .save_snapshot(
+                date(2026, 4, 30),
+                &[sample_node_detail("node-a", "node-a", 1, 145, 16.5)],
+                false,
+            )
             .expect("snapshot should save");
 
-        let details = store.daily_summary_details(
-            report_date,
+        let details = daily_summary_details(
+            &store,
+            date(2026, 5, 1),
             &[sample_node_detail("node-a", "node-a", 1, 150, 18.0)],
         );
 
@@ -466,9 +962,289 @@ mod tests {
         assert!(details[0].show_monthly_totals);
         assert_eq!(details[0].monthly_block_qty, Some(50));
         assert_eq!(details[0].monthly_reward_value, Some(8.0));
+    }
+
+    #[test]
+    fn test_daily_summary_details_average_deltas_across_missing_days() {
+        let store = memory_store(None);
+        store
+            .save_snapshot(
+                date(2026, 4, 11),
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
+            .expect("snapshot should save");
+
+        let details = daily_summary_details(
+            &store,
+            date(2026, 4, 14),
+            &[sample_node_detail("node-a", "node-a", 1, 160, 19.0)],
+        );
+
+        assert_eq!(details[0].daily_span_days, 3);
+        assert_eq!(details[0].daily_block_qty, Some(20));
+        assert_eq!(details[0].daily_reward_value, Some(3.0));
+    }
+
+    #[test]
+    fn test_weekly_summary_details_total_the_period_from_stored_snapshots() {
+        let store = memory_store(None);
+        for (day, block_qty, reward_value) in [(12, 100, 10.0), (13, 112, 11.5), (19, 184, 19.0)] {
+            store
+                .save_snapshot(
+                    date(2026, 4, day),
+                    &[sample_node_detail("node-a", "node-a", 1, block_qty, reward_value)],
+                    false,
+                )
+                .expect("snapshot should save");
+        }
+
+        // Sent mid-morning, so live details have moved past the end snapshot.
+        let details = store.summary_details(
+            SummaryPeriod::Weekly,
+            date(2026, 4, 13),
+            date(2026, 4, 19),
+            &[sample_node_detail("node-a", "node-a", 1, 190, 20.0)],
+        );
 
-        if let Some(parent) = path.parent() {
-            let _ = fs::remove_dir_all(parent);
+        assert_eq!(details[0].daily_span_days, 6);
+        assert_eq!(details[0].daily_block_qty, Some(72));
+        assert_eq!(details[0].daily_reward_value, Some(7.5));
+        assert_eq!(details[0].block_qty, 190);
+        assert!(!details[0].show_monthly_totals);
+    }
+
+    #[test]
+    fn test_daily_summary_details_ignore_snapshots_outside_gap_lookback() {
+        let store = memory_store(None);
+        store
+            .save_snapshot(
+                date(2026, 1, 1),
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
+            .expect("snapshot should save");
+
+        let details = daily_summary_details(
+            &store,
+            date(2026, 4, 14),
+            &[sample_node_detail("node-a", "node-a", 1, 160, 19.0)],
+        );
+
+        assert_eq!(details[0].daily_span_days, 1);
+        assert_eq!(details[0].daily_block_qty, None);
+    }
+
+    #[test]
+    fn test_late_snapshot_is_flagged_and_found() {
+        let store = memory_store(None);
+        store
+            .save_snapshot(
+                date(2026, 4, 10),
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
+            .expect("snapshot should save");
+        store
+            .save_snapshot(
+                date(2026, 4, 14),
+                &[sample_node_detail("node-a", "node-a", 1, 150, 15.0)],
+                true,
+            )
+            .expect("snapshot should save");
+
+        assert!(store.has_snapshot(date(2026, 4, 14)).expect("query should succeed"));
+        assert!(!store.has_snapshot(date(2026, 4, 13)).expect("query should succeed"));
+        assert_eq!(
+            store.latest_snapshot_date_before(date(2026, 4, 14)).expect("query should succeed"),
+            Some(date(2026, 4, 10))
+        );
+        let late: bool = lock_or_panic(&store.conn)
+            .query_row(
+                "SELECT late FROM daily_node_snapshots WHERE snapshot_date = '2026-04-14'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("row should exist");
+        assert!(late);
+    }
+
+    #[test]
+    fn test_daily_deltas_touching_a_late_snapshot_are_prorated() {
+        let store = memory_store(None);
+        for (day, block_qty, reward_value, late) in
+            [(13, 100, 10.0, false), (14, 175, 17.5, true), (15, 200, 20.0, false)]
+        {
+            store
+                .save_snapshot(
+                    date(2026, 4, day),
+                    &[sample_node_detail("node-a", "node-a", 1, block_qty, reward_value)],
+                    late,
+                )
+                .expect("snapshot should save");
+        }
+        // The 14th was taken at 06:00 instead of midnight.
+        lock_or_panic(&store.conn)
+            .execute_batch(
+                "UPDATE daily_node_snapshots SET captured_at = 1776038400
+                     WHERE snapshot_date = '2026-04-13';
+                 UPDATE daily_node_snapshots SET captured_at = 1776146400
+                     WHERE snapshot_date = '2026-04-14';
+                 UPDATE daily_node_snapshots SET captured_at = 1776211200
+                     WHERE snapshot_date = '2026-04-15';",
+            )
+            .expect("captured_at should update");
+        let live = [sample_node_detail("node-a", "node-a", 1, 210, 21.0)];
+
+        // 75 blocks over 30 hours, then 25 blocks over 18 hours.
+        let details = daily_summary_details(&store, date(2026, 4, 14), &live);
+        assert_eq!(details[0].daily_block_qty, Some(60));
+        assert_eq!(details[0].daily_reward_value, Some(6.0));
+        let details = daily_summary_details(&store, date(2026, 4, 15), &live);
+        assert_eq!(details[0].daily_block_qty, Some(33));
+        assert_eq!(details[0].daily_span_days, 1);
+    }
+
+    #[test]
+    fn test_read_only_store_reads_snapshots_without_writing() {
+        let path = temp_legacy_path().with_extension("sqlite");
+        fs::create_dir_all(path.parent().expect("path has a parent")).expect("dir is created");
+        let db_path = path.to_string_lossy().into_owned();
+        DailyNodeSnapshotStore::open_database(&db_path, None)
+            .expect("store should open")
+            .save_snapshot(
+                date(2026, 4, 14),
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
+            .expect("snapshot should save");
+
+        let store = DailyNodeSnapshotStore::open_read_only(&db_path).expect("store should open");
+
+        let rows =
+            store.snapshots_between(date(2026, 4, 1), date(2026, 4, 30)).expect("rows should load");
+        assert_eq!(rows.len(), 1);
+        assert!(store
+            .save_snapshot(
+                date(2026, 4, 15),
+                &[sample_node_detail("node-a", "node-a", 1, 110, 11.0)],
+                false,
+            )
+            .is_err());
+        drop(store);
+        fs::remove_dir_all(path.parent().expect("path has a parent")).expect("dir is removed");
+    }
+
+    #[test]
+    fn test_history_is_kept_beyond_a_month_without_retention() {
+        let store = memory_store(None);
+        let first = date(2026, 1, 1);
+
+        for offset in 0..60 {
+            store
+                .save_snapshot(
+                    first + chrono::Days::new(offset),
+                    &[sample_node_detail("node-a", "node-a", 1, 100 + offset, 10.0)],
+                    false,
+                )
+                .expect("snapshot should save");
+        }
+
+        assert!(store.snapshot_or_warn(first).is_some());
+    }
+
+    #[test]
+    fn test_retention_drops_snapshots_older_than_window() {
+        let store = memory_store(Some(7));
+
+        store
+            .save_snapshot(
+                date(2026, 4, 1),
+                &[sample_node_detail("node-a", "node-a", 1, 100, 10.0)],
+                false,
+            )
+            .expect("snapshot should save");
+        store
+            .save_snapshot(
+                date(2026, 4, 8),
+                &[sample_node_detail("node-a", "node-a", 1, 170, 17.0)],
+                false,
+            )
+            .expect("snapshot should save");
+        assert!(store.snapshot_or_warn(date(2026, 4, 1)).is_some());
+
+        store
+            .save_snapshot(
+                date(2026, 4, 9),
+                &[sample_node_detail("node-a", "node-a", 1, 180, 18.0)],
+                false,
+            )
+            .expect("snapshot should save");
+
+        assert!(store.snapshot_or_warn(date(2026, 4, 1)).is_none());
+        assert!(store.snapshot_or_warn(date(2026, 4, 8)).is_some());
+    }
+
+    #[test]
+    fn test_snapshots_between_returns_rows_in_range() {
+        let store = memory_store(None);
+        for (day, block_qty) in [(1, 100), (2, 110), (3, 125)] {
+            store
+                .save_snapshot(
+                    date(2026, 4, day),
+                    &[sample_node_detail("node-a", "node-a", 4, block_qty, 10.0)],
+                    false,
+                )
+                .expect("snapshot should save");
         }
+
+        let rows = store
+            .snapshots_between(date(2026, 4, 2), date(2026, 4, 3))
+            .expect("query should succeed");
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].date, date(2026, 4, 2));
+        assert_eq!(rows[0].ranking, Some(4));
+        assert_eq!(rows[1].block_qty, 125);
+    }
+
+    #[test]
+    fn test_legacy_json_store_is_imported_once() {
+        let path = temp_legacy_path();
+        let parent = path.parent().expect("path should have a parent").to_path_buf();
+        fs::create_dir_all(&parent).expect("temp dir should be created");
+        fs::write(
+            &path,
+            r#"{"snapshots":[
+                {"date":"2026-04-13","node_details":[
+                    {"node_id":"node-a","node_name":"node-a","block_qty":90,"reward_value":9.0}
+                ]},
+                {"date":"2026-04-14","node_details":[
+                    {"node_id":"node-a","node_name":"node-a","block_qty":100,"reward_value":10.0,"stale":true}
+                ]}
+            ]}"#,
+        )
+        .expect("legacy file should be written");
+        let store = memory_store(None);
+        store
+            .save_snapshot(
+                date(2026, 4, 14),
+                &[sample_node_detail("node-a", "node-a", 1, 110, 11.0)],
+                false,
+            )
+            .expect("snapshot should save");
+
+        store.import_legacy_file(&path).expect("import should succeed");
+
+        assert!(!path.exists());
+        assert!(parent.join("daily-node-snapshots.json.imported").exists());
+        let imported = store.snapshot_or_warn(date(2026, 4, 13)).expect("snapshot should exist");
+        assert_eq!(imported.node_details[0].block_qty, 90);
+        let existing = store.snapshot_or_warn(date(2026, 4, 14)).expect("snapshot should exist");
+        assert_eq!(existing.node_details[0].block_qty, 110);
+        assert_eq!(existing.node_details[0].reward_value, 11.0);
+
+        store.import_legacy_file(&path).expect("missing legacy file should be ignored");
+        let _ = fs::remove_dir_all(parent);
     }
 }
@@ -10,10 +10,38 @@ use std::{
     },
 };
 
+use chrono::Local;
 use crossbeam_channel::Sender;
 use log::warn;
 
-use crate::sync::lock_or_panic;
+use super::{
+    alert_engine::AlertStatus,
+    block_production::{
+        BlockProductionSummary,
+        BlockProductionTracker,
+    },
+    delegation::DelegationSnapshot,
+    detail_change::{
+        detect_commission_change,
+        detect_node_detail_changes,
+        NodeDetailChange,
+        NodeDetailChangeLog,
+    },
+    reward_history::{
+        annualized_yield,
+        RewardHistoryTracker,
+        RewardSummary,
+    },
+    stake_distance::StakeDistance,
+    watchlist::{
+        WatchedValidator,
+        WatchlistStore,
+    },
+};
+use crate::{
+    notify::DeliveryRecord,
+    sync::lock_or_panic,
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct ConsensusState {
@@ -26,6 +54,32 @@ pub struct ConsensusState {
     pub locked: u64,
     pub qc: u64,
     pub validator: bool,
+    /// Node ID reported by the endpoint's `admin_nodeInfo`, normalized with
+    /// [`normalize_node_id`]; empty when the admin API is unavailable.
+    pub node_id: String,
+    /// Round trip of the latest consensus status and block number poll.
+    pub rpc_latency_ms: u64,
+    /// Connected peers reported by `net_peerCount`, if the endpoint answers it.
+    pub peer_count: Option<u64>,
+}
+
+/// Strip the optional `0x` prefix and lowercase a validator node ID so IDs from
+/// `admin_nodeInfo`, `--node-id` and the Explorer compare equal.
+pub fn normalize_node_id(node_id: &str) -> String {
+    let node_id = node_id.trim();
+    node_id
+        .strip_prefix("0x")
+        .or_else(|| node_id.strip_prefix("0X"))
+        .unwrap_or(node_id)
+        .to_ascii_lowercase()
+}
+
+pub fn node_ids_match(
+    left: &str,
+    right: &str,
+) -> bool {
+    let left = normalize_node_id(left);
+    !left.is_empty() && left == normalize_node_id(right)
 }
 
 #[derive(Debug, Clone, Default)]
@@ -34,6 +88,7 @@ pub struct NodeDetail {
     pub node_name: String,
     pub ranking: i32,
     pub block_qty: u64,
+    pub expect_block_qty: u64,
     pub block_rate: String,
     pub daily_block_rate: String,
     pub reward_per: f64,
@@ -41,12 +96,65 @@ pub struct NodeDetail {
     pub reward_address: String,
     pub verifier_time: u64,
     pub last_updated_at: Option<Instant>,
+    pub production: Option<BlockProductionSummary>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    /// Name of the monitored `--url` endpoint running this validator, if linked.
+    pub endpoint: Option<String>,
+    /// Total stake including delegations, in LAT.
+    pub total_value: f64,
+    pub delegate_value: f64,
+    pub reward_summary: Option<RewardSummary>,
+    /// Stake gap to the `--stake-target-ranks` cutoffs and neighbouring validators.
+    pub stake_distance: Option<StakeDistance>,
+    /// Blocks produced in each of the most recent hours, oldest first; empty
+    /// until two hourly snapshots exist.
+    pub hourly_blocks: Vec<u64>,
 }
 
+/// Details older than this are still shown but flagged as stale.
+pub const NODE_DETAIL_STALE_AFTER: StdDuration = StdDuration::from_secs(30);
+
 impl NodeDetail {
     pub fn rewards(&self) -> f64 {
         self.reward_value * (1.0 - self.reward_per / 100.0)
     }
+
+    pub fn own_stake(&self) -> f64 {
+        (self.total_value - self.delegate_value).max(0.0)
+    }
+
+    /// Estimated system reward per day, before the delegation split.
+    pub fn daily_reward(&self) -> Option<f64> {
+        self.reward_summary.and_then(|summary| summary.daily_reward)
+    }
+
+    /// Estimated daily reward relative to the total stake.
+    pub fn daily_yield(&self) -> Option<f64> {
+        let daily_reward = self.daily_reward()?;
+        (self.total_value > 0.0).then(|| daily_reward / self.total_value)
+    }
+
+    /// Estimated annualised yield of the validator's own stake after sharing
+    /// `reward_per` percent of rewards with delegators.
+    pub fn validator_apy(&self) -> Option<f64> {
+        let daily_reward = self.daily_reward()? * (1.0 - self.reward_per / 100.0);
+        annualized_yield(daily_reward, self.own_stake())
+    }
+
+    /// Estimated annualised yield of the delegated stake.
+    pub fn delegator_apy(&self) -> Option<f64> {
+        let daily_reward = self.daily_reward()? * self.reward_per / 100.0;
+        annualized_yield(daily_reward, self.delegate_value)
+    }
+
+    pub fn age(&self) -> Option<StdDuration> {
+        self.last_updated_at.map(|updated_at| Instant::now().saturating_duration_since(updated_at))
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.age().is_none_or(|age| age > NODE_DETAIL_STALE_AFTER)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -205,6 +313,32 @@ impl NodeStateStore {
     ) {
         self.states.insert(name, state);
     }
+
+    fn current_epoch(&self) -> u64 {
+        self.states.values().map(|state| state.epoch).max().unwrap_or(0)
+    }
+
+    fn endpoint_for_node_id(
+        &self,
+        node_id: &str,
+    ) -> Option<String> {
+        self.sorted()
+            .into_iter()
+            .find(|state| node_ids_match(&state.node_id, node_id))
+            .map(|state| state.name)
+    }
+
+    fn linked_node_ids(&self) -> Vec<String> {
+        let mut node_ids: Vec<String> = self
+            .states
+            .values()
+            .filter(|state| !state.node_id.is_empty())
+            .map(|state| format!("0x{}", normalize_node_id(&state.node_id)))
+            .collect();
+        node_ids.sort();
+        node_ids.dedup();
+        node_ids
+    }
 }
 
 const DEFAULT_NODE_DETAIL_KEY: &str = "__default__";
@@ -295,6 +429,26 @@ impl NodeDetailStore {
         }
     }
 
+    fn merge_stake_distance_for(
+        &mut self,
+        node_id: &str,
+        distance: Option<StakeDistance>,
+    ) {
+        if let Some(detail) = self.details.get_mut(&Self::node_detail_key(node_id)) {
+            detail.stake_distance = distance;
+        }
+    }
+
+    fn merge_hourly_blocks_for(
+        &mut self,
+        node_id: &str,
+        hourly_blocks: Vec<u64>,
+    ) {
+        if let Some(detail) = self.details.get_mut(&Self::node_detail_key(node_id)) {
+            detail.hourly_blocks = hourly_blocks;
+        }
+    }
+
     fn merge_node_detail(
         &mut self,
         detail: Option<NodeDetail>,
@@ -331,6 +485,8 @@ impl NodeDetailStore {
         }
         if let Some(existing) = self.details.get(&key) {
             detail.ranking = existing.ranking;
+            detail.stake_distance = existing.stake_distance.clone();
+            detail.hourly_blocks = existing.hourly_blocks.clone();
         }
         if detail.node_name.is_empty() && !detail.node_id.is_empty() {
             detail.node_name = detail.node_id.clone();
@@ -340,11 +496,15 @@ impl NodeDetailStore {
         self.loaded = true;
     }
 
-    fn remove(
+    fn record_failure(
         &mut self,
         node_id: &str,
+        reason: String,
     ) {
-        self.details.remove(&Self::node_detail_key(node_id));
+        if let Some(detail) = self.details.get_mut(&Self::node_detail_key(node_id)) {
+            detail.last_error = Some(reason);
+            detail.consecutive_failures = detail.consecutive_failures.saturating_add(1);
+        }
         self.loaded = true;
     }
 
@@ -484,6 +644,9 @@ pub(crate) struct UiDirtyState {
     pub chain: bool,
     pub node_state: bool,
     pub node_details: bool,
+    pub delegations: bool,
+    pub notifications: bool,
+    pub alerts: bool,
     pub status: bool,
     #[cfg(target_family = "unix")]
     pub system: bool,
@@ -491,16 +654,23 @@ pub(crate) struct UiDirtyState {
 
 impl UiDirtyState {
     fn any(self) -> bool {
-        self.chain || self.node_state || self.node_details || self.status || {
-            #[cfg(target_family = "unix")]
-            {
-                self.system
-            }
-            #[cfg(not(target_family = "unix"))]
-            {
-                false
+        self.chain
+            || self.node_state
+            || self.node_details
+            || self.delegations
+            || self.notifications
+            || self.alerts
+            || self.status
+            || {
+                #[cfg(target_family = "unix")]
+                {
+                    self.system
+                }
+                #[cfg(not(target_family = "unix"))]
+                {
+                    false
+                }
             }
-        }
     }
 }
 
@@ -509,6 +679,13 @@ pub struct Data {
     chain: ChainStats,
     node_state: NodeStateStore,
     node_details: NodeDetailStore,
+    block_production: BlockProductionTracker,
+    reward_history: RewardHistoryTracker,
+    detail_changes: NodeDetailChangeLog,
+    watchlist: WatchlistStore,
+    delegations: HashMap<String, DelegationSnapshot>,
+    notification_deliveries: Vec<DeliveryRecord>,
+    alerts: Vec<AlertStatus>,
     status: UiStatusStore,
     ui_dirty: UiDirtyState,
     ui_waker: Option<Sender<()>>,
@@ -566,6 +743,21 @@ impl Data {
         self.notify_ui_if_needed();
     }
 
+    fn mark_delegations_dirty(&mut self) {
+        self.ui_dirty.delegations = true;
+        self.notify_ui_if_needed();
+    }
+
+    fn mark_notifications_dirty(&mut self) {
+        self.ui_dirty.notifications = true;
+        self.notify_ui_if_needed();
+    }
+
+    fn mark_alerts_dirty(&mut self) {
+        self.ui_dirty.alerts = true;
+        self.notify_ui_if_needed();
+    }
+
     fn mark_status_dirty(&mut self) {
         self.ui_dirty.status = true;
         self.notify_ui_if_needed();
@@ -633,14 +825,44 @@ impl Data {
     }
 
     pub fn node_detail(&self) -> Option<NodeDetail> {
-        self.node_details.first()
+        self.node_details.first().map(|detail| self.with_linked_endpoint(detail))
     }
 
     pub fn node_detail_for(
         &self,
         node_id: &str,
     ) -> Option<NodeDetail> {
-        self.node_details.get(node_id)
+        self.node_details.get(node_id).map(|detail| self.with_linked_endpoint(detail))
+    }
+
+    /// Validator node IDs derived from the monitored endpoints' `admin_nodeInfo`.
+    pub fn linked_node_ids(&self) -> Vec<String> {
+        self.node_state.linked_node_ids()
+    }
+
+    /// Explorer detail of the validator running on an endpoint, matched by the
+    /// endpoint's `admin_nodeInfo` node ID.
+    pub fn linked_node_detail(
+        &self,
+        state: &ConsensusState,
+    ) -> Option<NodeDetail> {
+        if state.node_id.is_empty() {
+            return None;
+        }
+
+        self.node_details
+            .details
+            .values()
+            .find(|detail| node_ids_match(&detail.node_id, &state.node_id))
+            .map(|detail| self.with_linked_endpoint(detail.clone()))
+    }
+
+    fn with_linked_endpoint(
+        &self,
+        mut detail: NodeDetail,
+    ) -> NodeDetail {
+        detail.endpoint = self.node_state.endpoint_for_node_id(&detail.node_id);
+        detail
     }
 
     pub fn node_details_loaded(&self) -> bool {
@@ -648,7 +870,11 @@ impl Data {
     }
 
     pub fn node_details(&self) -> Vec<NodeDetail> {
-        self.node_details.sorted()
+        self.node_details
+            .sorted()
+            .into_iter()
+            .map(|detail| self.with_linked_endpoint(detail))
+            .collect()
     }
 
     pub fn update_node_detail(
@@ -676,6 +902,24 @@ impl Data {
         self.mark_node_details_dirty();
     }
 
+    pub(crate) fn merge_stake_distance_for(
+        &mut self,
+        node_id: &str,
+        distance: Option<StakeDistance>,
+    ) {
+        self.node_details.merge_stake_distance_for(node_id, distance);
+        self.mark_node_details_dirty();
+    }
+
+    pub(crate) fn merge_hourly_blocks_for(
+        &mut self,
+        node_id: &str,
+        hourly_blocks: Vec<u64>,
+    ) {
+        self.node_details.merge_hourly_blocks_for(node_id, hourly_blocks);
+        self.mark_node_details_dirty();
+    }
+
     pub fn merge_node_detail(
         &mut self,
         detail: Option<NodeDetail>,
@@ -684,15 +928,158 @@ impl Data {
         self.mark_node_details_dirty();
     }
 
+    /// Merge a fresh Explorer detail and fold its block and reward counters
+    /// into the per-epoch histories for the current consensus epoch.
     pub fn merge_node_detail_for(
         &mut self,
         node_id: &str,
         detail: Option<NodeDetail>,
     ) {
+        let epoch = self.node_state.current_epoch();
+        let detail = detail.map(|mut detail| {
+            let key = if node_id.is_empty() {
+                detail.node_id.as_str()
+            } else {
+                node_id
+            };
+            detail.production =
+                self.block_production.record(key, epoch, detail.block_qty, detail.expect_block_qty);
+            detail.reward_summary = self.reward_history.record(
+                key,
+                epoch,
+                detail.last_updated_at.unwrap_or_else(Instant::now),
+                detail.reward_value,
+            );
+            detail
+        });
+        if let (Some(previous), Some(current)) = (self.node_details.get(node_id), detail.as_ref()) {
+            let changes = detect_node_detail_changes(&previous, current, Local::now());
+            for change in &changes {
+                let level = if change.kind.is_critical() {
+                    StatusLevel::Error
+                } else {
+                    StatusLevel::Warn
+                };
+                self.set_status_message(level, change.describe());
+            }
+            self.detail_changes.record(changes);
+        }
         self.node_details.merge_node_detail_for(node_id, detail);
         self.mark_node_details_dirty();
     }
 
+    /// `--watch-node-id` competitors, ranked validators first.
+    pub fn watched_validators(&self) -> Vec<WatchedValidator> {
+        self.watchlist.sorted()
+    }
+
+    /// Update a watched validator from its Explorer detail. Only commission
+    /// changes are tracked for competitors.
+    pub(crate) fn merge_watched_detail(
+        &mut self,
+        node_id: &str,
+        detail: &NodeDetail,
+    ) {
+        // A record created from the ranking list alone has no commission yet.
+        let previous =
+            self.watchlist.get(node_id).filter(|previous| previous.last_updated_at.is_some());
+        let change = previous.and_then(|previous| {
+            detect_commission_change(
+                node_id,
+                &detail.node_name,
+                previous.reward_per,
+                detail.reward_per,
+                Local::now(),
+            )
+        });
+        if let Some(change) = change {
+            self.set_status_message(StatusLevel::Warn, change.describe());
+            self.detail_changes.record(vec![change]);
+        }
+        self.watchlist.merge_detail(node_id, detail);
+        self.mark_node_details_dirty();
+    }
+
+    pub(crate) fn merge_watched_ranking(
+        &mut self,
+        node_id: &str,
+        ranking: Option<i32>,
+    ) {
+        self.watchlist.merge_ranking(node_id, ranking);
+        self.mark_node_details_dirty();
+    }
+
+    pub(crate) fn retain_watched_validators(
+        &mut self,
+        node_ids: &[String],
+    ) {
+        self.watchlist.retain(node_ids);
+    }
+
+    /// Latest delegator snapshot of each of our validators, ordered by node name.
+    pub fn delegation_snapshots(&self) -> Vec<DelegationSnapshot> {
+        let mut snapshots: Vec<_> = self.delegations.values().cloned().collect();
+        snapshots.sort_by(|left, right| {
+            left.node_name.cmp(&right.node_name).then_with(|| left.node_id.cmp(&right.node_id))
+        });
+        snapshots
+    }
+
+    pub(crate) fn delegation_snapshot_for(
+        &self,
+        node_id: &str,
+    ) -> Option<DelegationSnapshot> {
+        self.delegations.get(&normalize_node_id(node_id)).cloned()
+    }
+
+    pub(crate) fn merge_delegation_snapshot(
+        &mut self,
+        snapshot: DelegationSnapshot,
+    ) {
+        self.delegations.insert(normalize_node_id(&snapshot.node_id), snapshot);
+        self.mark_delegations_dirty();
+    }
+
+    /// Latest notification delivery outcomes, newest first.
+    pub(crate) fn notification_deliveries(&self) -> Vec<DeliveryRecord> {
+        self.notification_deliveries.clone()
+    }
+
+    pub(crate) fn replace_notification_deliveries(
+        &mut self,
+        deliveries: Vec<DeliveryRecord>,
+    ) {
+        if self.notification_deliveries != deliveries {
+            self.notification_deliveries = deliveries;
+            self.mark_notifications_dirty();
+        }
+    }
+
+    /// Alert rule series, firing first.
+    pub(crate) fn alerts(&self) -> Vec<AlertStatus> {
+        self.alerts.clone()
+    }
+
+    pub(crate) fn replace_alerts(
+        &mut self,
+        alerts: Vec<AlertStatus>,
+    ) {
+        if self.alerts != alerts {
+            self.alerts = alerts;
+            self.mark_alerts_dirty();
+        }
+    }
+
+    /// Commission and benefit-address changes seen so far, oldest first.
+    pub fn node_detail_changes(&self) -> Vec<NodeDetailChange> {
+        self.detail_changes.history()
+    }
+
+    /// Changes recorded since the last call, for the notifier.
+    pub(crate) fn take_pending_node_detail_changes(&mut self) -> Vec<NodeDetailChange> {
+        self.detail_changes.take_pending()
+    }
+
     pub(crate) fn record_block_sample(
         &mut self,
         block_number: u64,
@@ -759,11 +1146,14 @@ impl Data {
         }
     }
 
-    pub fn remove_node_detail(
+    /// Keep the last successful detail for `node_id` and record why the latest
+    /// refresh failed; nodes that never loaded stay absent.
+    pub fn record_node_detail_failure(
         &mut self,
         node_id: &str,
+        reason: impl Into<String>,
     ) {
-        self.node_details.remove(node_id);
+        self.node_details.record_failure(node_id, reason.into());
         self.mark_node_details_dirty();
     }
 
@@ -793,6 +1183,7 @@ mod tests {
     use crossbeam_channel::bounded;
 
     use super::*;
+    use crate::collect::NodeDetailChangeKind;
 
     #[test]
     fn test_merge_node_ranking_preserves_existing_detail_fields() {
@@ -802,6 +1193,7 @@ mod tests {
             node_name: "node-a".to_string(),
             ranking: 1,
             block_qty: 12,
+            expect_block_qty: 12,
             block_rate: "75.00%".to_string(),
             daily_block_rate: "1/day".to_string(),
             reward_per: 10.0,
@@ -809,6 +1201,15 @@ mod tests {
             reward_address: "addr".to_string(),
             verifier_time: 30,
             last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         }));
 
         data.merge_node_ranking(Some(9));
@@ -837,6 +1238,7 @@ mod tests {
             node_name: "old-node".to_string(),
             ranking: 7,
             block_qty: 12,
+            expect_block_qty: 12,
             block_rate: "75.00%".to_string(),
             daily_block_rate: "1/day".to_string(),
             reward_per: 10.0,
@@ -844,6 +1246,15 @@ mod tests {
             reward_address: "old-addr".to_string(),
             verifier_time: 30,
             last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         }));
 
         data.merge_node_detail(Some(NodeDetail {
@@ -851,6 +1262,7 @@ mod tests {
             node_name: "new-node".to_string(),
             ranking: 0,
             block_qty: 24,
+            expect_block_qty: 24,
             block_rate: "80.00%".to_string(),
             daily_block_rate: "2/day".to_string(),
             reward_per: 5.0,
@@ -858,6 +1270,15 @@ mod tests {
             reward_address: "new-addr".to_string(),
             verifier_time: 60,
             last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         }));
 
         let detail = data.node_detail().expect("node detail should exist");
@@ -866,6 +1287,45 @@ mod tests {
         assert_eq!(detail.block_qty, 24);
     }
 
+    #[test]
+    fn test_merge_node_detail_records_block_production_per_node() {
+        let mut data = Data::default();
+        let detail = |block_qty, expect_block_qty| NodeDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "node-a".to_string(),
+            ranking: 0,
+            block_qty,
+            expect_block_qty,
+            block_rate: "0.00%".to_string(),
+            daily_block_rate: "".to_string(),
+            reward_per: 0.0,
+            reward_value: 0.0,
+            reward_address: "addr".to_string(),
+            verifier_time: 0,
+            last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
+        };
+
+        data.merge_node_detail_for("node-a-id", Some(detail(100, 100)));
+        data.merge_node_detail_for("node-a-id", Some(detail(108, 110)));
+
+        let production = data
+            .node_detail_for("node-a-id")
+            .and_then(|detail| detail.production)
+            .expect("production should be recorded");
+        assert_eq!(production.current.produced, 8);
+        assert_eq!(production.current.expected, 10);
+        assert_eq!(production.current.missed(), 2);
+    }
+
     #[test]
     fn test_merge_node_ranking_none_preserves_existing_detail() {
         let mut data = Data::default();
@@ -874,6 +1334,7 @@ mod tests {
             node_name: "node-a".to_string(),
             ranking: 3,
             block_qty: 12,
+            expect_block_qty: 12,
             block_rate: "75.00%".to_string(),
             daily_block_rate: "1/day".to_string(),
             reward_per: 10.0,
@@ -881,6 +1342,15 @@ mod tests {
             reward_address: "addr".to_string(),
             verifier_time: 30,
             last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         };
         data.update_node_detail(Some(existing.clone()));
 
@@ -901,6 +1371,7 @@ mod tests {
             node_name: "node-a".to_string(),
             ranking: 3,
             block_qty: 12,
+            expect_block_qty: 12,
             block_rate: "75.00%".to_string(),
             daily_block_rate: "1/day".to_string(),
             reward_per: 10.0,
@@ -908,6 +1379,15 @@ mod tests {
             reward_address: "addr".to_string(),
             verifier_time: 30,
             last_updated_at: None,
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         };
         data.update_node_detail(Some(existing.clone()));
 
@@ -921,7 +1401,7 @@ mod tests {
     }
 
     #[test]
-    fn test_remove_node_detail_removes_only_target() {
+    fn test_record_node_detail_failure_keeps_last_known_detail() {
         let mut data = Data::default();
         data.merge_node_detail_for(
             "node-a-id",
@@ -930,6 +1410,7 @@ mod tests {
                 node_name: "node-a".to_string(),
                 ranking: 1,
                 block_qty: 12,
+                expect_block_qty: 12,
                 block_rate: "75.00%".to_string(),
                 daily_block_rate: "1/day".to_string(),
                 reward_per: 10.0,
@@ -937,6 +1418,15 @@ mod tests {
                 reward_address: "addr-a".to_string(),
                 verifier_time: 30,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
         data.merge_node_detail_for(
@@ -946,6 +1436,7 @@ mod tests {
                 node_name: "node-b".to_string(),
                 ranking: 2,
                 block_qty: 24,
+                expect_block_qty: 24,
                 block_rate: "80.00%".to_string(),
                 daily_block_rate: "2/day".to_string(),
                 reward_per: 5.0,
@@ -953,14 +1444,30 @@ mod tests {
                 reward_address: "addr-b".to_string(),
                 verifier_time: 60,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
 
-        data.remove_node_detail("node-a-id");
+        data.record_node_detail_failure("node-a-id", "timeout");
+        data.record_node_detail_failure("node-a-id", "HTTP 502");
+        data.record_node_detail_failure("node-c-id", "timeout");
 
         let details = data.node_details();
-        assert_eq!(details.len(), 1);
-        assert_eq!(details[0].node_id, "node-b-id");
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].node_id, "node-a-id");
+        assert_eq!(details[0].block_qty, 12);
+        assert_eq!(details[0].last_error.as_deref(), Some("HTTP 502"));
+        assert_eq!(details[0].consecutive_failures, 2);
+        assert_eq!(details[1].last_error, None);
+        assert_eq!(details[1].consecutive_failures, 0);
     }
 
     #[test]
@@ -973,6 +1480,7 @@ mod tests {
                 node_name: "node-a".to_string(),
                 ranking: 1,
                 block_qty: 12,
+                expect_block_qty: 12,
                 block_rate: "75.00%".to_string(),
                 daily_block_rate: "1/day".to_string(),
                 reward_per: 10.0,
@@ -980,6 +1488,15 @@ mod tests {
                 reward_address: "addr-a".to_string(),
                 verifier_time: 30,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
         data.merge_node_detail_for(
@@ -989,6 +1506,7 @@ mod tests {
                 node_name: "node-b".to_string(),
                 ranking: 2,
                 block_qty: 24,
+                expect_block_qty: 24,
                 block_rate: "80.00%".to_string(),
                 daily_block_rate: "2/day".to_string(),
                 reward_per: 5.0,
@@ -996,6 +1514,15 @@ mod tests {
                 reward_address: "addr-b".to_string(),
                 verifier_time: 60,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
 
@@ -1032,6 +1559,157 @@ mod tests {
         assert_eq!(names, vec!["node-a".to_string(), "node-b".to_string()]);
     }
 
+    #[test]
+    fn test_reward_yields_split_daily_reward_by_commission() {
+        let detail = NodeDetail {
+            reward_per: 40.0,
+            total_value: 3_000_000.0,
+            delegate_value: 2_000_000.0,
+            reward_summary: Some(RewardSummary {
+                daily_reward: Some(1_000.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(detail.own_stake(), 1_000_000.0);
+        assert_eq!(detail.daily_yield(), Some(1_000.0 / 3_000_000.0));
+        assert_eq!(detail.validator_apy(), Some(600.0 * 365.0 / 1_000_000.0));
+        assert_eq!(detail.delegator_apy(), Some(400.0 * 365.0 / 2_000_000.0));
+        assert_eq!(NodeDetail::default().validator_apy(), None);
+    }
+
+    #[test]
+    fn test_merge_node_detail_records_reward_history_per_epoch() {
+        let mut data = Data::default();
+        let detail = |reward_value| NodeDetail {
+            node_id: "node-a-id".to_string(),
+            reward_value,
+            last_updated_at: Some(Instant::now()),
+            ..Default::default()
+        };
+
+        data.merge_node_detail_for("node-a-id", Some(detail(100.0)));
+        data.merge_node_detail_for("node-a-id", Some(detail(112.5)));
+
+        let summary = data
+            .node_detail_for("node-a-id")
+            .and_then(|detail| detail.reward_summary)
+            .expect("reward summary should be recorded");
+        assert_eq!(summary.current.reward, 12.5);
+    }
+
+    #[test]
+    fn test_watched_validators_stay_out_of_node_details() {
+        let mut data = Data::default();
+        let detail = |reward_per| NodeDetail {
+            node_name: "rival".to_string(),
+            reward_per,
+            last_updated_at: Some(Instant::now()),
+            ..Default::default()
+        };
+
+        data.merge_watched_ranking("rival-id", Some(7));
+        data.merge_watched_detail("rival-id", &detail(20.0));
+        data.merge_watched_detail("rival-id", &detail(10.0));
+
+        assert!(data.node_details().is_empty());
+        assert_eq!(data.watched_validators()[0].ranking, 7);
+        let changes = data.take_pending_node_detail_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, NodeDetailChangeKind::Commission);
+    }
+
+    #[test]
+    fn test_merge_node_detail_records_commission_and_address_changes() {
+        let mut data = Data::default();
+        let detail = |reward_per, reward_address: &str| NodeDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "node-a".to_string(),
+            reward_per,
+            reward_address: reward_address.to_string(),
+            ..Default::default()
+        };
+
+        data.merge_node_detail_for("node-a-id", Some(detail(10.0, "lat1old")));
+        assert!(data.take_pending_node_detail_changes().is_empty());
+
+        data.merge_node_detail_for("node-a-id", Some(detail(10.0, "lat1new")));
+        let changes = data.take_pending_node_detail_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous, "lat1old");
+        assert_eq!(data.node_detail_changes().len(), 1);
+        assert_eq!(data.status_message().map(|status| status.level), Some(StatusLevel::Error));
+    }
+
+    #[test]
+    fn test_stake_distance_survives_detail_refresh() {
+        let mut data = Data::default();
+        let detail = || NodeDetail {
+            node_id: "node-a-id".to_string(),
+            ..Default::default()
+        };
+        let distance = StakeDistance {
+            ranking: 3,
+            total_value: 1_000.0,
+            above: None,
+            below: None,
+            targets: Vec::new(),
+        };
+
+        data.merge_node_detail_for("node-a-id", Some(detail()));
+        data.merge_stake_distance_for("node-a-id", Some(distance.clone()));
+        data.merge_node_detail_for("node-a-id", Some(detail()));
+
+        assert_eq!(
+            data.node_detail_for("node-a-id").and_then(|detail| detail.stake_distance),
+            Some(distance)
+        );
+    }
+
+    #[test]
+    fn test_node_ids_match_ignores_prefix_and_case() {
+        assert!(node_ids_match("0xABCDEF", "abcdef"));
+        assert!(!node_ids_match("", ""));
+        assert!(!node_ids_match("0xabc", "0xabd"));
+    }
+
+    #[test]
+    fn test_endpoint_node_ids_link_states_and_details() {
+        let mut data = Data::default();
+        data.update_consensus_state(
+            "main".to_string(),
+            ConsensusState {
+                name: "main".to_string(),
+                node_id: "abcdef".to_string(),
+                ..Default::default()
+            },
+        );
+        data.update_consensus_state(
+            "observer".to_string(),
+            ConsensusState {
+                name: "observer".to_string(),
+                ..Default::default()
+            },
+        );
+        data.merge_node_detail_for(
+            "0xABCDEF",
+            Some(NodeDetail {
+                node_id: "0xABCDEF".to_string(),
+                node_name: "validator-a".to_string(),
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(data.linked_node_ids(), vec!["0xabcdef".to_string()]);
+        assert_eq!(data.node_details()[0].endpoint.as_deref(), Some("main"));
+
+        let states = data.states();
+        let linked = data.linked_node_detail(&states[0]).expect("main should be linked");
+        assert_eq!(linked.node_name, "validator-a");
+        assert!(data.linked_node_detail(&states[1]).is_none());
+    }
+
     #[test]
     fn test_node_details_return_results_sorted_by_node_name() {
         let mut data = Data::default();
@@ -1042,6 +1720,7 @@ mod tests {
                 node_name: "node-b".to_string(),
                 ranking: 2,
                 block_qty: 24,
+                expect_block_qty: 24,
                 block_rate: "80.00%".to_string(),
                 daily_block_rate: "2/day".to_string(),
                 reward_per: 5.0,
@@ -1049,6 +1728,15 @@ mod tests {
                 reward_address: "addr-b".to_string(),
                 verifier_time: 60,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
         data.merge_node_detail_for(
@@ -1058,6 +1746,7 @@ mod tests {
                 node_name: "node-a".to_string(),
                 ranking: 7,
                 block_qty: 12,
+                expect_block_qty: 12,
                 block_rate: "75.00%".to_string(),
                 daily_block_rate: "1/day".to_string(),
                 reward_per: 10.0,
@@ -1065,6 +1754,15 @@ mod tests {
                 reward_address: "addr-a".to_string(),
                 verifier_time: 30,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
         data.merge_node_detail_for(
@@ -1074,6 +1772,7 @@ mod tests {
                 node_name: "node-c".to_string(),
                 ranking: 0,
                 block_qty: 36,
+                expect_block_qty: 36,
                 block_rate: "85.00%".to_string(),
                 daily_block_rate: "3/day".to_string(),
                 reward_per: 3.0,
@@ -1081,6 +1780,15 @@ mod tests {
                 reward_address: "addr-c".to_string(),
                 verifier_time: 90,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }),
         );
 
@@ -0,0 +1,478 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    },
+};
+
+use chrono::{
+    DateTime,
+    Local,
+};
+use log::{
+    debug,
+    warn,
+};
+use tokio::time::{
+    self,
+    Duration,
+    MissedTickBehavior,
+};
+
+use super::{
+    data::{
+        warn_with_status,
+        SharedData,
+        StatusLevel,
+    },
+    delegation_store::DelegationStore,
+    explorer::ExplorerClient,
+    node_detail::tracked_node_ids,
+    types,
+};
+use crate::{
+    error::Result,
+    notify::Notifier,
+    opts::Opts,
+    sync::lock_or_panic,
+};
+
+/// Amounts are LAT with up to 18 decimals; smaller differences are float
+/// noise from the Explorer's string encoding.
+const DELEGATION_AMOUNT_EPSILON: f64 = 1e-6;
+const DELEGATION_STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delegation refresh cadence, alert threshold and snapshot database.
+#[derive(Debug, Clone)]
+pub(crate) struct DelegationSettings {
+    pub refresh_interval: Duration,
+    pub alert_threshold: Option<f64>,
+    pub db_path: String,
+}
+
+impl DelegationSettings {
+    pub(crate) fn from_opts(opts: &Opts) -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(opts.delegation_refresh_interval),
+            alert_threshold: opts.delegator_alert_threshold,
+            db_path: opts.db_path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delegator {
+    pub address: String,
+    /// Delegated stake in LAT.
+    pub amount: f64,
+}
+
+/// Everyone delegating to one of our validators at `captured_at`, largest
+/// delegator first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegationSnapshot {
+    pub node_id: String,
+    pub node_name: String,
+    pub delegators: Vec<Delegator>,
+    pub total: f64,
+    pub captured_at: DateTime<Local>,
+}
+
+impl DelegationSnapshot {
+    pub(super) fn new(
+        node_id: &str,
+        node_name: &str,
+        mut delegators: Vec<Delegator>,
+        captured_at: DateTime<Local>,
+    ) -> Self {
+        delegators.sort_by(|left, right| {
+            right.amount.total_cmp(&left.amount).then_with(|| left.address.cmp(&right.address))
+        });
+        let total = delegators.iter().map(|delegator| delegator.amount).sum();
+
+        Self {
+            node_id: node_id.to_string(),
+            node_name: node_name.to_string(),
+            delegators,
+            total,
+            captured_at,
+        }
+    }
+
+    /// Build a snapshot from Explorer rows, merging repeated addresses and
+    /// dropping rows without an address or stake.
+    pub(super) fn from_rows(
+        node_id: &str,
+        node_name: &str,
+        rows: &[types::DelegationInfo],
+        captured_at: DateTime<Local>,
+    ) -> Self {
+        let mut amounts: HashMap<String, f64> = HashMap::new();
+        for row in rows {
+            let address = row.delegate_addr.trim();
+            let amount = row.delegate_value.parse::<f64>().ok().unwrap_or(0.0);
+            if address.is_empty() || amount <= 0.0 {
+                continue;
+            }
+            *amounts.entry(address.to_string()).or_default() += amount;
+        }
+
+        let delegators =
+            amounts.into_iter().map(|(address, amount)| Delegator { address, amount }).collect();
+        Self::new(node_id, node_name, delegators, captured_at)
+    }
+
+    /// Share of the node's delegated stake held by `delegator`, in percent.
+    pub fn share(
+        &self,
+        delegator: &Delegator,
+    ) -> f64 {
+        if self.total > 0.0 {
+            delegator.amount / self.total * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    fn amounts(&self) -> HashMap<String, f64> {
+        self.delegators
+            .iter()
+            .map(|delegator| (delegator.address.to_ascii_lowercase(), delegator.amount))
+            .collect()
+    }
+
+    /// Whether both snapshots hold the same delegators with the same stakes.
+    pub(super) fn same_delegations(
+        &self,
+        other: &DelegationSnapshot,
+    ) -> bool {
+        let amounts = other.amounts();
+        self.delegators.len() == other.delegators.len()
+            && self.delegators.iter().all(|delegator| {
+                amounts.get(&delegator.address.to_ascii_lowercase()).is_some_and(|amount| {
+                    (amount - delegator.amount).abs() < DELEGATION_AMOUNT_EPSILON
+                })
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegatorChangeKind {
+    Joined,
+    Added,
+    Reduced,
+    Left,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegatorChange {
+    pub node_id: String,
+    pub node_name: String,
+    pub address: String,
+    pub previous: f64,
+    pub current: f64,
+    pub changed_at: DateTime<Local>,
+}
+
+impl DelegatorChange {
+    pub fn kind(&self) -> DelegatorChangeKind {
+        if self.previous <= 0.0 {
+            DelegatorChangeKind::Joined
+        } else if self.current <= 0.0 {
+            DelegatorChangeKind::Left
+        } else if self.current > self.previous {
+            DelegatorChangeKind::Added
+        } else {
+            DelegatorChangeKind::Reduced
+        }
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.current - self.previous
+    }
+
+    pub fn describe(&self) -> String {
+        let action = match self.kind() {
+            DelegatorChangeKind::Joined => "started delegating",
+            DelegatorChangeKind::Added => "added",
+            DelegatorChangeKind::Reduced => "withdrew",
+            DelegatorChangeKind::Left => "withdrew all",
+        };
+        format!(
+            "Delegator {} {action} {:.2} LAT on {}",
+            self.address,
+            self.delta().abs(),
+            self.node_name
+        )
+    }
+}
+
+/// Stake changes of delegators holding at least `threshold` LAT before or
+/// after the change, largest change first.
+pub(super) fn detect_delegator_changes(
+    previous: &DelegationSnapshot,
+    current: &DelegationSnapshot,
+    threshold: f64,
+) -> Vec<DelegatorChange> {
+    let mut previous_amounts = previous.amounts();
+    let mut addresses: Vec<(String, f64)> = current
+        .delegators
+        .iter()
+        .map(|delegator| {
+            let key = delegator.address.to_ascii_lowercase();
+            let before = previous_amounts.remove(&key).unwrap_or(0.0);
+            (delegator.address.clone(), before)
+        })
+        .collect();
+    let current_amounts = current.amounts();
+
+    // Whoever is left in `previous_amounts` has withdrawn everything.
+    addresses.extend(
+        previous
+            .delegators
+            .iter()
+            .filter(|delegator| {
+                previous_amounts.contains_key(&delegator.address.to_ascii_lowercase())
+            })
+            .map(|delegator| (delegator.address.clone(), delegator.amount)),
+    );
+
+    let mut changes: Vec<_> = addresses
+        .into_iter()
+        .filter_map(|(address, before)| {
+            let after = current_amounts.get(&address.to_ascii_lowercase()).copied().unwrap_or(0.0);
+            let large = before.max(after) >= threshold;
+            ((after - before).abs() >= DELEGATION_AMOUNT_EPSILON && large).then(|| {
+                DelegatorChange {
+                    node_id: current.node_id.clone(),
+                    node_name: current.node_name.clone(),
+                    address,
+                    previous: before,
+                    current: after,
+                    changed_at: current.captured_at,
+                }
+            })
+        })
+        .collect();
+    changes.sort_by(|left, right| right.delta().abs().total_cmp(&left.delta().abs()));
+    changes
+}
+
+/// Refresh delegator lists for our own validators every
+/// `--delegation-refresh-interval` seconds.
+///
+/// A snapshot is written to the database only when it differs from the
+/// previous one, and the last stored snapshot is the baseline after a restart
+/// so withdrawals during downtime still raise alerts.
+pub(crate) async fn collect_delegations(
+    node_ids: Vec<String>,
+    client: Arc<ExplorerClient>,
+    settings: DelegationSettings,
+    data: SharedData,
+    notifier: Option<Arc<Notifier>>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    let store = match DelegationStore::open(&settings.db_path) {
+        Ok(store) => Some(store),
+        Err(err) => {
+            warn!("failed to open delegation database {}: {err}", settings.db_path);
+            None
+        },
+    };
+    let mut refresh_interval = time::interval(settings.refresh_interval);
+    refresh_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let stop_poll = time::sleep(DELEGATION_STOP_POLL_INTERVAL);
+        tokio::pin!(stop_poll);
+        tokio::select! {
+            biased;
+            _ = refresh_interval.tick() => {},
+            _ = &mut stop_poll => continue,
+        }
+
+        let tracked_node_ids = {
+            let data = lock_or_panic(&data);
+            tracked_node_ids(&node_ids, &data.linked_node_ids())
+        };
+
+        for node_id in &tracked_node_ids {
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            refresh_delegations(
+                &client,
+                store.as_ref(),
+                node_id,
+                &data,
+                settings.alert_threshold,
+                notifier.as_ref(),
+            )
+            .await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn refresh_delegations(
+    client: &ExplorerClient,
+    store: Option<&DelegationStore>,
+    node_id: &str,
+    data: &SharedData,
+    alert_threshold: Option<f64>,
+    notifier: Option<&Arc<Notifier>>,
+) {
+    let rows = match client.delegation_list(node_id).await {
+        Ok(rows) => rows,
+        Err(message) => {
+            warn_with_status(data, message);
+            return;
+        },
+    };
+
+    let (previous, node_name) = {
+        let data = lock_or_panic(data);
+        let node_name =
+            data.node_detail_for(node_id).map(|detail| detail.node_name).unwrap_or_default();
+        (data.delegation_snapshot_for(node_id), node_name)
+    };
+    let previous = previous.or_else(|| {
+        store.and_then(|store| {
+            store.latest(node_id).unwrap_or_else(|err| {
+                warn!("failed to load delegation snapshot for {node_id}: {err}");
+                None
+            })
+        })
+    });
+
+    let snapshot = DelegationSnapshot::from_rows(node_id, &node_name, &rows, Local::now());
+    debug!("{} delegators for {}", snapshot.delegators.len(), node_id);
+
+    let unchanged = previous.as_ref().is_some_and(|previous| previous.same_delegations(&snapshot));
+    if let Some(store) = store.filter(|_| !unchanged) {
+        if let Err(err) = store.save(&snapshot) {
+            warn!("failed to save delegation snapshot for {node_id}: {err}");
+        }
+    }
+
+    let changes = match (previous.as_ref(), alert_threshold) {
+        (Some(previous), Some(threshold)) => {
+            detect_delegator_changes(previous, &snapshot, threshold)
+        },
+        _ => Vec::new(),
+    };
+
+    {
+        let mut data = lock_or_panic(data);
+        if let Some(change) = changes.first() {
+            data.set_status_message(StatusLevel::Warn, change.describe());
+        }
+        data.merge_delegation_snapshot(snapshot);
+    }
+
+    let Some(notifier) = notifier else {
+        return;
+    };
+    for change in &changes {
+        notifier.notify_delegator_change(change).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(delegators: &[(&str, f64)]) -> DelegationSnapshot {
+        DelegationSnapshot::new(
+            "node-a-id",
+            "node-a",
+            delegators
+                .iter()
+                .map(|(address, amount)| Delegator {
+                    address: address.to_string(),
+                    amount: *amount,
+                })
+                .collect(),
+            Local::now(),
+        )
+    }
+
+    fn row(
+        address: &str,
+        amount: &str,
+    ) -> types::DelegationInfo {
+        types::DelegationInfo {
+            delegate_addr: address.to_string(),
+            delegate_value: amount.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_from_rows_merges_addresses_and_sorts_by_amount() {
+        let snapshot = DelegationSnapshot::from_rows(
+            "node-a-id",
+            "node-a",
+            &[row("lat1a", "100"), row("lat1b", "300"), row("lat1a", "50"), row("", "9")],
+            Local::now(),
+        );
+
+        let addresses: Vec<_> =
+            snapshot.delegators.iter().map(|delegator| delegator.address.as_str()).collect();
+        assert_eq!(addresses, vec!["lat1b", "lat1a"]);
+        assert_eq!(snapshot.total, 450.0);
+        assert!((snapshot.share(&snapshot.delegators[1]) - 33.333).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_small_delegators_do_not_raise_changes() {
+        let changes = detect_delegator_changes(
+            &snapshot(&[("lat1small", 10.0)]),
+            &snapshot(&[("lat1small", 500.0)]),
+            1_000.0,
+        );
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_large_delegator_changes_are_classified() {
+        let changes = detect_delegator_changes(
+            &snapshot(&[("lat1whale", 50_000.0), ("lat1big", 20_000.0), ("lat1keep", 5_000.0)]),
+            &snapshot(&[("lat1big", 30_000.0), ("lat1keep", 5_000.0), ("LAT1NEW", 8_000.0)]),
+            5_000.0,
+        );
+
+        let kinds: Vec<_> = changes.iter().map(DelegatorChange::kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DelegatorChangeKind::Left,
+                DelegatorChangeKind::Added,
+                DelegatorChangeKind::Joined
+            ]
+        );
+        assert_eq!(changes[0].delta(), -50_000.0);
+        assert_eq!(
+            changes[0].describe(),
+            "Delegator lat1whale withdrew all 50000.00 LAT on node-a"
+        );
+    }
+
+    #[test]
+    fn test_same_delegations_ignores_address_case_and_order() {
+        let previous = snapshot(&[("lat1a", 10.0), ("lat1b", 20.0)]);
+
+        assert!(previous.same_delegations(&snapshot(&[("LAT1B", 20.0), ("lat1a", 10.0)])));
+        assert!(!previous.same_delegations(&snapshot(&[("lat1a", 10.0), ("lat1b", 21.0)])));
+        assert!(!previous.same_delegations(&snapshot(&[("lat1a", 10.0)])));
+    }
+}
@@ -0,0 +1,196 @@
+use std::sync::Mutex;
+
+use chrono::{
+    Local,
+    TimeZone,
+};
+use rusqlite::{
+    params,
+    Connection,
+    OptionalExtension,
+};
+
+use super::{
+    data::normalize_node_id,
+    delegation::{
+        DelegationSnapshot,
+        Delegator,
+    },
+};
+use crate::{
+    db::{
+        open_connection,
+        run_migrations,
+    },
+    error::Result,
+    sync::lock_or_panic,
+};
+
+/// Delegator snapshots persisted in the `--db-path` database.
+#[derive(Debug)]
+pub(super) struct DelegationStore {
+    conn: Mutex<Connection>,
+}
+
+impl DelegationStore {
+    pub(super) fn open(db_path: &str) -> Result<Self> {
+        let mut conn = open_connection(db_path)?;
+        run_migrations(&mut conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub(super) fn save(
+        &self,
+        snapshot: &DelegationSnapshot,
+    ) -> Result<()> {
+        insert_delegation_snapshot_tx(&mut lock_or_panic(&self.conn), snapshot)
+    }
+
+    pub(super) fn latest(
+        &self,
+        node_id: &str,
+    ) -> Result<Option<DelegationSnapshot>> {
+        latest_delegation_snapshot(&lock_or_panic(&self.conn), node_id)
+    }
+}
+
+/// Write a snapshot header and its delegator rows in one transaction.
+pub(super) fn insert_delegation_snapshot_tx(
+    conn: &mut Connection,
+    snapshot: &DelegationSnapshot,
+) -> Result<()> {
+    let node_id = normalize_node_id(&snapshot.node_id);
+    let captured_at = snapshot.captured_at.timestamp();
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO delegation_snapshots
+             (node_id, captured_at, total_amount, delegator_count)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![node_id, captured_at, snapshot.total, snapshot.delegators.len() as i64],
+    )?;
+    tx.execute(
+        "DELETE FROM delegator_snapshots WHERE node_id = ?1 AND captured_at = ?2",
+        params![node_id, captured_at],
+    )?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO delegator_snapshots (node_id, captured_at, address, amount)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for delegator in &snapshot.delegators {
+            stmt.execute(params![node_id, captured_at, delegator.address, delegator.amount])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Most recent stored snapshot for `node_id`. The node name is not stored and
+/// comes back empty.
+pub(super) fn latest_delegation_snapshot(
+    conn: &Connection,
+    node_id: &str,
+) -> Result<Option<DelegationSnapshot>> {
+    let node_id = normalize_node_id(node_id);
+    let Some(captured_at) = conn
+        .query_row(
+            "SELECT MAX(captured_at) FROM delegation_snapshots WHERE node_id = ?1",
+            params![node_id],
+            |row| row.get::<_, Option<i64>>(0),
+        )
+        .optional()?
+        .flatten()
+    else {
+        return Ok(None);
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT address, amount FROM delegator_snapshots
+         WHERE node_id = ?1 AND captured_at = ?2",
+    )?;
+    let rows = stmt.query_map(params![node_id, captured_at], |row| {
+        Ok(Delegator {
+            address: row.get(0)?,
+            amount: row.get(1)?,
+        })
+    })?;
+    let delegators = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+    let captured_at = Local.timestamp_opt(captured_at, 0).single().unwrap_or_else(Local::now);
+
+    Ok(Some(DelegationSnapshot::new(&node_id, "", delegators, captured_at)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrated_conn() -> Connection {
+        let mut conn = open_connection(":memory:").expect("in-memory connection should open");
+        run_migrations(&mut conn).expect("migration should succeed");
+        conn
+    }
+
+    fn snapshot(
+        timestamp: i64,
+        delegators: &[(&str, f64)],
+    ) -> DelegationSnapshot {
+        DelegationSnapshot::new(
+            "0xNODE",
+            "node-a",
+            delegators
+                .iter()
+                .map(|(address, amount)| Delegator {
+                    address: address.to_string(),
+                    amount: *amount,
+                })
+                .collect(),
+            Local.timestamp_opt(timestamp, 0).single().expect("timestamp should be valid"),
+        )
+    }
+
+    #[test]
+    fn test_latest_snapshot_returns_newest_delegators() {
+        let mut conn = migrated_conn();
+        insert_delegation_snapshot_tx(&mut conn, &snapshot(100, &[("lat1a", 10.0)]))
+            .expect("first insert should succeed");
+        insert_delegation_snapshot_tx(
+            &mut conn,
+            &snapshot(200, &[("lat1a", 5.0), ("lat1b", 20.0)]),
+        )
+        .expect("second insert should succeed");
+
+        let latest = latest_delegation_snapshot(&conn, "node")
+            .expect("query should succeed")
+            .expect("snapshot should exist");
+
+        assert_eq!(latest.captured_at.timestamp(), 200);
+        assert_eq!(latest.total, 25.0);
+        assert_eq!(latest.delegators[0].address, "lat1b");
+    }
+
+    #[test]
+    fn test_empty_snapshot_is_kept_after_everyone_leaves() {
+        let mut conn = migrated_conn();
+        insert_delegation_snapshot_tx(&mut conn, &snapshot(100, &[("lat1a", 10.0)]))
+            .expect("first insert should succeed");
+        insert_delegation_snapshot_tx(&mut conn, &snapshot(200, &[]))
+            .expect("empty insert should succeed");
+
+        let latest = latest_delegation_snapshot(&conn, "0xnode")
+            .expect("query should succeed")
+            .expect("snapshot should exist");
+
+        assert!(latest.delegators.is_empty());
+        assert_eq!(latest.captured_at.timestamp(), 200);
+    }
+
+    #[test]
+    fn test_latest_snapshot_is_none_for_unknown_node() {
+        let conn = migrated_conn();
+
+        assert!(latest_delegation_snapshot(&conn, "other").expect("query should work").is_none());
+    }
+}
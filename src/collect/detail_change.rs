@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+
+use chrono::{
+    DateTime,
+    Local,
+};
+
+use super::data::NodeDetail;
+
+const NODE_DETAIL_CHANGE_HISTORY_LIMIT: usize = 64;
+/// `rewardPer` is a percentage with two decimals; smaller differences are
+/// float noise from the Explorer's string encoding.
+const COMMISSION_CHANGE_EPSILON: f64 = 0.005;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeDetailChangeKind {
+    Commission,
+    BenefitAddress,
+}
+
+impl NodeDetailChangeKind {
+    /// A new benefit address may mean the validator's key was compromised.
+    pub fn is_critical(self) -> bool {
+        matches!(self, Self::BenefitAddress)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeDetailChange {
+    pub node_id: String,
+    pub node_name: String,
+    pub kind: NodeDetailChangeKind,
+    pub previous: String,
+    pub current: String,
+    pub changed_at: DateTime<Local>,
+}
+
+impl NodeDetailChange {
+    pub fn describe(&self) -> String {
+        let label = match self.kind {
+            NodeDetailChangeKind::Commission => "Commission",
+            NodeDetailChangeKind::BenefitAddress => "Benefit address",
+        };
+        format!("{label} of {} changed: {} → {}", self.node_name, self.previous, self.current)
+    }
+}
+
+fn format_commission(reward_per: f64) -> String {
+    format!("{reward_per:.2}%")
+}
+
+/// Commission change between two `rewardPer` samples of the same validator.
+pub(super) fn detect_commission_change(
+    node_id: &str,
+    node_name: &str,
+    previous: f64,
+    current: f64,
+    changed_at: DateTime<Local>,
+) -> Option<NodeDetailChange> {
+    ((previous - current).abs() >= COMMISSION_CHANGE_EPSILON).then(|| NodeDetailChange {
+        node_id: node_id.to_string(),
+        node_name: node_name.to_string(),
+        kind: NodeDetailChangeKind::Commission,
+        previous: format_commission(previous),
+        current: format_commission(current),
+        changed_at,
+    })
+}
+
+/// Compare a refreshed Explorer detail with the one it replaces. Empty
+/// addresses are treated as missing data rather than as a change.
+pub(super) fn detect_node_detail_changes(
+    previous: &NodeDetail,
+    current: &NodeDetail,
+    changed_at: DateTime<Local>,
+) -> Vec<NodeDetailChange> {
+    let mut changes: Vec<_> = detect_commission_change(
+        &current.node_id,
+        &current.node_name,
+        previous.reward_per,
+        current.reward_per,
+        changed_at,
+    )
+    .into_iter()
+    .collect();
+
+    let previous_address = previous.reward_address.trim();
+    let current_address = current.reward_address.trim();
+    if !previous_address.is_empty()
+        && !current_address.is_empty()
+        && !previous_address.eq_ignore_ascii_case(current_address)
+    {
+        changes.push(NodeDetailChange {
+            node_id: current.node_id.clone(),
+            node_name: current.node_name.clone(),
+            kind: NodeDetailChangeKind::BenefitAddress,
+            previous: previous_address.to_string(),
+            current: current_address.to_string(),
+            changed_at,
+        });
+    }
+
+    changes
+}
+
+/// Recent commission and benefit-address changes, plus the ones not yet
+/// handed to the notifier.
+#[derive(Debug, Default)]
+pub(super) struct NodeDetailChangeLog {
+    history: VecDeque<NodeDetailChange>,
+    pending: Vec<NodeDetailChange>,
+}
+
+impl NodeDetailChangeLog {
+    pub(super) fn record(
+        &mut self,
+        changes: Vec<NodeDetailChange>,
+    ) {
+        for change in changes {
+            self.history.push_back(change.clone());
+            self.pending.push(change);
+        }
+        while self.history.len() > NODE_DETAIL_CHANGE_HISTORY_LIMIT {
+            self.history.pop_front();
+        }
+    }
+
+    pub(super) fn history(&self) -> Vec<NodeDetailChange> {
+        self.history.iter().cloned().collect()
+    }
+
+    pub(super) fn take_pending(&mut self) -> Vec<NodeDetailChange> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detail(
+        reward_per: f64,
+        reward_address: &str,
+    ) -> NodeDetail {
+        NodeDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "node-a".to_string(),
+            reward_per,
+            reward_address: reward_address.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unchanged_detail_reports_nothing() {
+        let changes = detect_node_detail_changes(
+            &detail(10.0, "lat1a"),
+            &detail(10.0, "LAT1A"),
+            Local::now(),
+        );
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_commission_and_address_changes_are_detected() {
+        let changes = detect_node_detail_changes(
+            &detail(10.0, "lat1old"),
+            &detail(8.5, "lat1new"),
+            Local::now(),
+        );
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, NodeDetailChangeKind::Commission);
+        assert_eq!(
+            (changes[0].previous.as_str(), changes[0].current.as_str()),
+            ("10.00%", "8.50%")
+        );
+        assert_eq!(changes[1].kind, NodeDetailChangeKind::BenefitAddress);
+        assert!(changes[1].kind.is_critical());
+        assert_eq!(changes[1].describe(), "Benefit address of node-a changed: lat1old → lat1new");
+    }
+
+    #[test]
+    fn test_missing_address_is_not_a_change() {
+        let changes =
+            detect_node_detail_changes(&detail(10.0, "lat1a"), &detail(10.0, ""), Local::now());
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_change_log_keeps_history_after_pending_is_taken() {
+        let mut log = NodeDetailChangeLog::default();
+        log.record(detect_node_detail_changes(
+            &detail(10.0, "lat1a"),
+            &detail(5.0, "lat1a"),
+            Local::now(),
+        ));
+
+        assert_eq!(log.take_pending().len(), 1);
+        assert!(log.take_pending().is_empty());
+        assert_eq!(log.history().len(), 1);
+    }
+}
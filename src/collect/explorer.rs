@@ -0,0 +1,707 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::PathBuf,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use log::{
+    debug,
+    warn,
+};
+use reqwest::{
+    header::RETRY_AFTER,
+    StatusCode,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+use tokio::{
+    sync::Semaphore,
+    time,
+};
+
+use super::{
+    daily_snapshot::default_state_file_path,
+    types::{
+        self,
+        NodeInfo,
+    },
+};
+use crate::{
+    error::Result,
+    opts::Opts,
+    sync::lock_or_panic,
+};
+
+const EXPLORER_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const EXPLORER_BACKOFF_BASE: Duration = Duration::from_millis(500);
+const EXPLORER_BACKOFF_MAX: Duration = Duration::from_secs(30);
+const EXPLORER_STOP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const EXPLORER_CACHE_FILE_NAME: &str = "explorer-cache.json";
+/// Cached responses older than this are not used to seed the detail panel.
+const EXPLORER_CACHE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const NODE_LIST_PAGE_SIZE: u32 = 300;
+const DELEGATION_LIST_PAGE_SIZE: u32 = 100;
+/// Stop paging a delegation list that never reaches its `totalCount`.
+const DELEGATION_LIST_MAX_PAGES: u32 = 200;
+
+/// Explorer refresh cadence and request limits, resolved from the CLI options.
+#[derive(Debug, Clone)]
+pub(crate) struct ExplorerSettings {
+    pub api_url: String,
+    pub detail_interval: Duration,
+    pub ranking_interval: Duration,
+    pub max_concurrency: usize,
+    pub max_retries: u32,
+    pub cache_path: PathBuf,
+}
+
+impl ExplorerSettings {
+    pub(crate) fn from_opts(opts: &Opts) -> Self {
+        Self {
+            api_url: opts.explorer_api_url.trim_end_matches('/').to_string(),
+            detail_interval: Duration::from_secs(opts.explorer_detail_interval),
+            ranking_interval: Duration::from_secs(opts.explorer_ranking_interval),
+            max_concurrency: usize::try_from(opts.explorer_max_concurrency).unwrap_or(usize::MAX),
+            max_retries: opts.explorer_max_retries,
+            cache_path: opts
+                .explorer_cache_file
+                .clone()
+                .unwrap_or_else(|| default_state_file_path(EXPLORER_CACHE_FILE_NAME)),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ExplorerRequestError {
+    Status(StatusCode),
+    Transport(reqwest::Error),
+    Closed,
+}
+
+impl std::fmt::Display for ExplorerRequestError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            ExplorerRequestError::Status(status) => write!(f, "HTTP {status}"),
+            ExplorerRequestError::Transport(err) if err.is_timeout() => {
+                write!(f, "Request timed out after {:?}", EXPLORER_REQUEST_TIMEOUT)
+            },
+            ExplorerRequestError::Transport(err) => write!(f, "{err}"),
+            ExplorerRequestError::Closed => write!(f, "explorer client is shutting down"),
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Parse a `Retry-After` header given either as delay seconds or an HTTP date.
+fn parse_retry_after(
+    value: &str,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let retry_at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some(retry_at.signed_duration_since(now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// The server's `Retry-After` when present, otherwise exponential backoff
+/// from `EXPLORER_BACKOFF_BASE`; either way capped at `EXPLORER_BACKOFF_MAX`.
+fn retry_delay(
+    attempt: u32,
+    retry_after: Option<Duration>,
+) -> Duration {
+    retry_after
+        .unwrap_or_else(|| EXPLORER_BACKOFF_BASE.saturating_mul(2u32.saturating_pow(attempt)))
+        .min(EXPLORER_BACKOFF_MAX)
+}
+
+async fn wait_for_stop(stop_flag: &AtomicBool) {
+    while !stop_flag.load(Ordering::Relaxed) {
+        time::sleep(EXPLORER_STOP_POLL_INTERVAL).await;
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedNodeDetail {
+    fetched_at: u64,
+    detail: types::NodeDetail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedNodeList {
+    fetched_at: u64,
+    nodes: Vec<NodeInfo>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredExplorerCache {
+    #[serde(default)]
+    details: HashMap<String, CachedNodeDetail>,
+    #[serde(default)]
+    node_list: Option<CachedNodeList>,
+}
+
+/// On-disk copy of the last successful Explorer responses, so a restart can
+/// show the previous details immediately instead of a cold `Loading...`.
+#[derive(Debug)]
+struct ExplorerResponseCache {
+    path: PathBuf,
+    state: Mutex<StoredExplorerCache>,
+    dirty: AtomicBool,
+}
+
+impl ExplorerResponseCache {
+    fn load(path: PathBuf) -> Self {
+        let state = match fs::read_to_string(&path) {
+            Ok(content) if content.trim().is_empty() => StoredExplorerCache::default(),
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                warn!("Failed to parse explorer cache at {}: {}", path.display(), err);
+                StoredExplorerCache::default()
+            }),
+            Err(err) if err.kind() == ErrorKind::NotFound => StoredExplorerCache::default(),
+            Err(err) => {
+                warn!("Failed to read explorer cache at {}: {}", path.display(), err);
+                StoredExplorerCache::default()
+            },
+        };
+
+        Self {
+            path,
+            state: Mutex::new(state),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    fn store_detail(
+        &self,
+        node_id: &str,
+        detail: &types::NodeDetail,
+    ) {
+        lock_or_panic(&self.state).details.insert(
+            node_id.to_string(),
+            CachedNodeDetail {
+                fetched_at: unix_now(),
+                detail: detail.clone(),
+            },
+        );
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn store_node_list(
+        &self,
+        nodes: &[NodeInfo],
+    ) {
+        lock_or_panic(&self.state).node_list = Some(CachedNodeList {
+            fetched_at: unix_now(),
+            nodes: nodes.to_vec(),
+        });
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn detail(
+        &self,
+        node_id: &str,
+        now: u64,
+    ) -> Option<(types::NodeDetail, Duration)> {
+        let state = lock_or_panic(&self.state);
+        let cached = state.details.get(node_id)?;
+        let age = Duration::from_secs(now.saturating_sub(cached.fetched_at));
+
+        (age <= EXPLORER_CACHE_MAX_AGE).then(|| (cached.detail.clone(), age))
+    }
+
+    fn node_list(
+        &self,
+        now: u64,
+    ) -> Option<Vec<NodeInfo>> {
+        let state = lock_or_panic(&self.state);
+        let cached = state.node_list.as_ref()?;
+        let age = Duration::from_secs(now.saturating_sub(cached.fetched_at));
+
+        (age <= EXPLORER_CACHE_MAX_AGE).then(|| cached.nodes.clone())
+    }
+
+    fn persist(&self) -> Result<()> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let Some(parent) = self.path.parent() else {
+            return Err("explorer cache path has no parent directory".into());
+        };
+        fs::create_dir_all(parent)?;
+
+        let content = serde_json::to_vec(&*lock_or_panic(&self.state))?;
+        let temp_path = parent.join(format!("{}.{}.tmp", EXPLORER_CACHE_FILE_NAME, unix_now()));
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &self.path)?;
+
+        Ok(())
+    }
+}
+
+/// Explorer API client shared by the detail, ranking and delegation refresh loops.
+///
+/// Every request holds a permit from a semaphore sized by
+/// `--explorer-max-concurrency`. HTTP 429, 5xx and transport failures are
+/// retried up to `--explorer-max-retries` times; permits are released while
+/// waiting so a throttled request doesn't block the others, and the wait is
+/// abandoned when the collector stops.
+#[derive(Debug)]
+pub(super) struct ExplorerClient {
+    http: reqwest::Client,
+    detail_url: String,
+    ranking_url: String,
+    delegation_url: String,
+    permits: Semaphore,
+    max_retries: u32,
+    cache: ExplorerResponseCache,
+    settings: ExplorerSettings,
+    stop_flag: Arc<AtomicBool>,
+}
+
+impl ExplorerClient {
+    pub(super) fn new(
+        settings: &ExplorerSettings,
+        stop_flag: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(EXPLORER_REQUEST_TIMEOUT)
+                .build()
+                .expect("http client should build"),
+            detail_url: format!("{}/staking/stakingDetails", settings.api_url),
+            ranking_url: format!("{}/staking/aliveStakingList", settings.api_url),
+            delegation_url: format!("{}/staking/delegationListByStaking", settings.api_url),
+            permits: Semaphore::new(settings.max_concurrency.max(1)),
+            max_retries: settings.max_retries,
+            cache: ExplorerResponseCache::load(settings.cache_path.clone()),
+            settings: settings.clone(),
+            stop_flag,
+        }
+    }
+
+    pub(super) fn settings(&self) -> &ExplorerSettings {
+        &self.settings
+    }
+
+    async fn post_json(
+        &self,
+        url: &str,
+        body: &Value,
+    ) -> std::result::Result<Vec<u8>, ExplorerRequestError> {
+        let mut attempt = 0;
+
+        loop {
+            let (error, retry_after) = {
+                let _permit =
+                    self.permits.acquire().await.map_err(|_| ExplorerRequestError::Closed)?;
+
+                match self.http.post(url).json(body).send().await {
+                    Ok(resp) if resp.status().is_success() => {
+                        return resp
+                            .bytes()
+                            .await
+                            .map(|bytes| bytes.to_vec())
+                            .map_err(ExplorerRequestError::Transport);
+                    },
+                    Ok(resp) if is_retryable_status(resp.status()) => {
+                        let retry_after = resp
+                            .headers()
+                            .get(RETRY_AFTER)
+                            .and_then(|value| value.to_str().ok())
+                            .and_then(|value| parse_retry_after(value, Utc::now()));
+                        (ExplorerRequestError::Status(resp.status()), retry_after)
+                    },
+                    Ok(resp) => return Err(ExplorerRequestError::Status(resp.status())),
+                    Err(err) => (ExplorerRequestError::Transport(err), None),
+                }
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error);
+            }
+
+            let delay = retry_delay(attempt, retry_after);
+            debug!("Explorer request to {url} failed ({error}); retrying in {delay:?}");
+            tokio::select! {
+                _ = time::sleep(delay) => {},
+                _ = wait_for_stop(&self.stop_flag) => return Err(ExplorerRequestError::Closed),
+            }
+            attempt += 1;
+        }
+    }
+
+    pub(super) async fn node_detail(
+        &self,
+        node_id: &str,
+    ) -> std::result::Result<types::NodeDetail, String> {
+        let body = serde_json::json!({
+            "nodeId": node_id
+        });
+
+        debug!("fetch node detail: {}", self.detail_url);
+
+        let body_bytes =
+            self.post_json(&self.detail_url, &body).await.map_err(|err| match err {
+                ExplorerRequestError::Status(status) => {
+                    format!("Node detail API returned error status for {}: {}", node_id, status)
+                },
+                err => format!("Failed to fetch node detail for {}: {}", node_id, err),
+            })?;
+        let node_detail_resp: types::NodeDetailResponse = serde_json::from_slice(&body_bytes)
+            .map_err(|e| format!("Failed to parse node detail JSON for {}: {}", node_id, e))?;
+        debug!("Node detail response: {:?}", node_detail_resp);
+
+        if node_detail_resp.code != 0 {
+            return Err(format!(
+                "Node detail API returned error code for {}: {}, err_msg: {}",
+                node_id, node_detail_resp.code, node_detail_resp.err_msg
+            ));
+        }
+
+        let detail = node_detail_resp
+            .data
+            .ok_or_else(|| format!("Node detail response missing data field for {}", node_id))?;
+        self.cache.store_detail(node_id, &detail);
+
+        Ok(detail)
+    }
+
+    pub(super) async fn node_list(&self) -> std::result::Result<Vec<NodeInfo>, String> {
+        let body = serde_json::json!({
+            "pageNo": 1,
+            "pageSize": NODE_LIST_PAGE_SIZE,
+            "key": "",
+            "queryStatus": "all",
+        });
+
+        debug!("fetch node ranking: {}", self.ranking_url);
+
+        let body_bytes =
+            self.post_json(&self.ranking_url, &body).await.map_err(|err| match err {
+                ExplorerRequestError::Status(status) => {
+                    format!("Node ranking API returned error status: {}", status)
+                },
+                err => format!("Failed to fetch node ranking: {}", err),
+            })?;
+        let node_list_resp: types::NodeListResponse = serde_json::from_slice(&body_bytes)
+            .map_err(|e| format!("Failed to parse response JSON: {}", e))?;
+        debug!("Node list response: {:?}", node_list_resp);
+
+        if node_list_resp.code != 0 {
+            return Err(format!(
+                "Node ranking API returned error code: {}, err_msg: {}",
+                node_list_resp.code, node_list_resp.err_msg
+            ));
+        }
+
+        let nodes = node_list_resp
+            .data
+            .ok_or_else(|| "Node ranking response missing data field".to_string())?;
+        self.cache.store_node_list(&nodes);
+
+        Ok(nodes)
+    }
+
+    async fn delegation_page(
+        &self,
+        node_id: &str,
+        page_no: u32,
+    ) -> std::result::Result<types::DelegationListResponse, String> {
+        let body = serde_json::json!({
+            "pageNo": page_no,
+            "pageSize": DELEGATION_LIST_PAGE_SIZE,
+            "nodeId": node_id,
+        });
+
+        let body_bytes =
+            self.post_json(&self.delegation_url, &body).await.map_err(|err| match err {
+                ExplorerRequestError::Status(status) => {
+                    format!("Delegation list API returned error status for {}: {}", node_id, status)
+                },
+                err => format!("Failed to fetch delegation list for {}: {}", node_id, err),
+            })?;
+        let page: types::DelegationListResponse = serde_json::from_slice(&body_bytes)
+            .map_err(|e| format!("Failed to parse delegation list JSON for {}: {}", node_id, e))?;
+
+        if page.code != 0 {
+            return Err(format!(
+                "Delegation list API returned error code for {}: {}, err_msg: {}",
+                node_id, page.code, page.err_msg
+            ));
+        }
+
+        Ok(page)
+    }
+
+    /// Every delegation to `node_id`, fetched page by page. Any failed page
+    /// fails the whole list: a partial list would read as mass withdrawals.
+    pub(super) async fn delegation_list(
+        &self,
+        node_id: &str,
+    ) -> std::result::Result<Vec<types::DelegationInfo>, String> {
+        debug!("fetch delegation list: {} for {}", self.delegation_url, node_id);
+
+        let mut delegations = Vec::new();
+        for page_no in 1..=DELEGATION_LIST_MAX_PAGES {
+            let page = self.delegation_page(node_id, page_no).await?;
+            let rows = page.data.unwrap_or_default();
+            let total_count = usize::try_from(page.total_count).unwrap_or(0);
+            let last_page = rows.len() < DELEGATION_LIST_PAGE_SIZE as usize;
+            delegations.extend(rows);
+
+            if last_page || delegations.len() >= total_count {
+                return Ok(delegations);
+            }
+        }
+
+        Err(format!("Delegation list for {} exceeded {} pages", node_id, DELEGATION_LIST_MAX_PAGES))
+    }
+
+    /// Last cached detail for a node and how long ago it was fetched.
+    pub(super) fn cached_node_detail(
+        &self,
+        node_id: &str,
+    ) -> Option<(types::NodeDetail, Duration)> {
+        self.cache.detail(node_id, unix_now())
+    }
+
+    pub(super) fn cached_node_list(&self) -> Option<Vec<NodeInfo>> {
+        self.cache.node_list(unix_now())
+    }
+
+    /// Write the response cache to disk if anything changed since the last call.
+    pub(super) fn persist_cache(&self) -> Result<()> {
+        self.cache.persist()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt,
+        },
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    fn temp_cache_path() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "chaindash-explorer-cache-{}-{}.json",
+            std::process::id(),
+            unix_now()
+        ))
+    }
+
+    fn test_settings(api_url: String) -> ExplorerSettings {
+        ExplorerSettings {
+            api_url,
+            detail_interval: Duration::from_secs(10),
+            ranking_interval: Duration::from_secs(10),
+            max_concurrency: 2,
+            max_retries: 2,
+            cache_path: temp_cache_path(),
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after_accepts_seconds_and_http_dates() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .expect("date should parse")
+            .with_timezone(&Utc);
+
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_and_is_capped() {
+        assert_eq!(retry_delay(0, None), Duration::from_millis(500));
+        assert_eq!(retry_delay(2, None), Duration::from_secs(2));
+        assert_eq!(retry_delay(10, None), EXPLORER_BACKOFF_MAX);
+        assert_eq!(retry_delay(0, Some(Duration::from_secs(3))), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_retry_delay_caps_retry_after_at_the_backoff_max() {
+        let retry_after = EXPLORER_BACKOFF_MAX + Duration::from_secs(90);
+
+        assert_eq!(retry_delay(0, Some(retry_after)), EXPLORER_BACKOFF_MAX);
+        assert_eq!(retry_delay(10, Some(retry_after)), EXPLORER_BACKOFF_MAX);
+    }
+
+    #[test]
+    fn test_only_throttling_and_server_errors_are_retried() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_cache_round_trips_through_disk_and_expires_old_entries() {
+        let path = temp_cache_path();
+        let cache = ExplorerResponseCache::load(path.clone());
+        let detail: types::NodeDetail =
+            serde_json::from_str(r#"{"nodeName":"validator-a","blockQty":42}"#)
+                .expect("detail should parse");
+        cache.store_detail("0xabc", &detail);
+        cache.persist().expect("cache should persist");
+
+        let reloaded = ExplorerResponseCache::load(path.clone());
+        let now = unix_now();
+        let (cached, age) = reloaded.detail("0xabc", now).expect("detail should be cached");
+        assert_eq!(cached.node_name, "validator-a");
+        assert_eq!(cached.block_qty, 42);
+        assert!(age < Duration::from_secs(5));
+        assert!(reloaded.detail("0xabc", now + EXPLORER_CACHE_MAX_AGE.as_secs() + 1).is_none());
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_node_detail_retries_after_throttling() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind should work");
+        let addr = listener.local_addr().expect("local addr should exist");
+        let server = tokio::spawn(async move {
+            let body = r#"{"errMsg":"","code":0,"data":{"nodeName":"validator-a"}}"#;
+            let responses = [
+                "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\ncontent-length: \
+                 0\r\nconnection: close\r\n\r\n"
+                    .to_string(),
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: \
+                     {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                ),
+            ];
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.expect("accept should work");
+                let mut buffer = [0u8; 4096];
+                let _ = socket.read(&mut buffer).await.expect("read should work");
+                socket.write_all(response.as_bytes()).await.expect("write should work");
+            }
+        });
+
+        let settings = test_settings(format!("http://{addr}"));
+        let client = ExplorerClient::new(&settings, Arc::new(AtomicBool::new(false)));
+        let detail = client.node_detail("0xabc").await.expect("retry should succeed");
+
+        assert_eq!(detail.node_name, "validator-a");
+        assert!(client.cached_node_detail("0xabc").is_some());
+        server.await.expect("server should finish");
+    }
+
+    #[tokio::test]
+    async fn test_delegation_list_follows_pages_until_total_count() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind should work");
+        let addr = listener.local_addr().expect("local addr should exist");
+        let server = tokio::spawn(async move {
+            let total = DELEGATION_LIST_PAGE_SIZE as usize + 1;
+            let pages = [(0..DELEGATION_LIST_PAGE_SIZE as usize).collect::<Vec<_>>(), vec![total]];
+            for page in pages {
+                let rows: Vec<_> = page
+                    .iter()
+                    .map(|index| format!(r#"{{"delegateAddr":"lat1{index}","delegateValue":"1"}}"#))
+                    .collect();
+                let body = format!(
+                    r#"{{"errMsg":"","code":0,"totalCount":{total},"data":[{}]}}"#,
+                    rows.join(",")
+                );
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: \
+                     {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let (mut socket, _) = listener.accept().await.expect("accept should work");
+                let mut buffer = [0u8; 4096];
+                let _ = socket.read(&mut buffer).await.expect("read should work");
+                socket.write_all(response.as_bytes()).await.expect("write should work");
+            }
+        });
+
+        let settings = test_settings(format!("http://{addr}"));
+        let client = ExplorerClient::new(&settings, Arc::new(AtomicBool::new(false)));
+        let delegations = client.delegation_list("0xabc").await.expect("paging should succeed");
+
+        assert_eq!(delegations.len(), DELEGATION_LIST_PAGE_SIZE as usize + 1);
+        assert_eq!(delegations.last().map(|row| row.delegate_addr.as_str()), Some("lat1101"));
+        server.await.expect("server should finish");
+    }
+
+    #[tokio::test]
+    async fn test_retry_wait_is_abandoned_when_the_collector_stops() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind should work");
+        let addr = listener.local_addr().expect("local addr should exist");
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept should work");
+            let mut buffer = [0u8; 4096];
+            let _ = socket.read(&mut buffer).await.expect("read should work");
+            socket
+                .write_all(
+                    b"HTTP/1.1 503 Service Unavailable\r\nretry-after: 3600\r\ncontent-length: \
+                      0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .expect("write should work");
+        });
+
+        let settings = test_settings(format!("http://{addr}"));
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let client = ExplorerClient::new(&settings, Arc::clone(&stop_flag));
+        let request = client.node_detail("0xabc");
+        let stop = async {
+            server.await.expect("server should finish");
+            stop_flag.store(true, Ordering::Relaxed);
+        };
+        let (result, ()) =
+            time::timeout(Duration::from_secs(5), async { tokio::join!(request, stop) })
+                .await
+                .expect("the retry wait should end on stop");
+
+        assert!(result.is_err());
+    }
+}
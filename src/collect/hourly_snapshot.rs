@@ -0,0 +1,424 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        Ordering,
+    },
+    Arc,
+    Mutex,
+};
+
+use chrono::{
+    DateTime,
+    Duration as ChronoDuration,
+    Local,
+    TimeZone,
+    Timelike,
+};
+use log::warn;
+use rusqlite::{
+    params,
+    Connection,
+};
+use tokio::time::{
+    self,
+    Duration,
+};
+
+use super::data::{
+    normalize_node_id,
+    warn_with_status,
+    NodeDetail,
+    SharedData,
+};
+use crate::{
+    db::{
+        open_connection,
+        run_migrations,
+    },
+    error::Result,
+    notify::Notifier,
+    opts::Opts,
+    sync::lock_or_panic,
+};
+
+const HOURLY_SNAPSHOT_STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SECONDS_PER_HOUR: i64 = 3600;
+/// Hours of production kept on each node detail for the sparkline.
+const HOURLY_BLOCKS_HISTORY_HOURS: usize = 24;
+/// A trailing average below one block an hour is too thin to compare against.
+const PRODUCTION_DROP_MIN_TRAILING_AVERAGE: f64 = 1.0;
+
+/// Hourly snapshot database, retention and production-drop thresholds.
+#[derive(Debug, Clone)]
+pub(crate) struct HourlySnapshotSettings {
+    pub db_path: String,
+    pub retention_days: u64,
+    pub drop_alert_ratio: f64,
+    pub trailing_hours: usize,
+    pub validator_set_size: u32,
+}
+
+impl HourlySnapshotSettings {
+    pub(crate) fn from_opts(opts: &Opts) -> Self {
+        Self {
+            db_path: opts.db_path.clone(),
+            retention_days: opts.hourly_snapshot_retention_days,
+            drop_alert_ratio: opts.production_drop_alert_ratio,
+            trailing_hours: opts.production_drop_trailing_hours as usize,
+            validator_set_size: opts.validator_set_size,
+        }
+    }
+}
+
+/// One node's block counter at the top of an hour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HourlyBlockSample {
+    /// Unix timestamp of the local hour start.
+    hour: i64,
+    block_qty: u64,
+}
+
+/// An hour in which a validator produced well below its trailing average.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProductionDrop {
+    pub node_id: String,
+    pub node_name: String,
+    /// Start of the hour the blocks were produced in.
+    pub hour_start: DateTime<Local>,
+    pub blocks: u64,
+    pub trailing_average: f64,
+    pub trailing_hours: usize,
+    pub ratio_threshold: f64,
+}
+
+/// Hourly node counters persisted in the `--db-path` database.
+#[derive(Debug)]
+struct HourlyNodeSnapshotStore {
+    conn: Mutex<Connection>,
+    retention_days: u64,
+}
+
+impl HourlyNodeSnapshotStore {
+    fn open(
+        db_path: &str,
+        retention_days: u64,
+    ) -> Result<Self> {
+        let mut conn = open_connection(db_path)?;
+        run_migrations(&mut conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            retention_days,
+        })
+    }
+
+    /// Record the counters of every fresh node detail for `hour` and drop
+    /// snapshots past the retention window. Stale details are skipped; the
+    /// gap is averaged over once fresh data returns.
+    fn save_snapshot(
+        &self,
+        hour: i64,
+        node_details: &[NodeDetail],
+    ) -> Result<()> {
+        let mut conn = lock_or_panic(&self.conn);
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO hourly_node_snapshots
+                     (captured_hour, node_id, node_name, ranking, block_qty, reward_value)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for detail in node_details.iter().filter(|detail| !detail.is_stale()) {
+                stmt.execute(params![
+                    hour,
+                    normalize_node_id(&detail.node_id),
+                    detail.node_name,
+                    detail.ranking,
+                    detail.block_qty as i64,
+                    detail.reward_value,
+                ])?;
+            }
+        }
+        let oldest = hour - self.retention_days as i64 * 24 * SECONDS_PER_HOUR;
+        tx.execute("DELETE FROM hourly_node_snapshots WHERE captured_hour < ?1", params![oldest])?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Block counters for `node_id` from `since` onwards, oldest first.
+    fn block_samples_since(
+        &self,
+        node_id: &str,
+        since: i64,
+    ) -> Result<Vec<HourlyBlockSample>> {
+        let conn = lock_or_panic(&self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT captured_hour, block_qty FROM hourly_node_snapshots
+             WHERE node_id = ?1 AND captured_hour >= ?2
+             ORDER BY captured_hour",
+        )?;
+        let rows = stmt.query_map(params![normalize_node_id(node_id), since], |row| {
+            Ok(HourlyBlockSample {
+                hour: row.get(0)?,
+                block_qty: row.get::<_, i64>(1)?.max(0) as u64,
+            })
+        })?;
+        let samples = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(samples)
+    }
+}
+
+/// Blocks produced in each hour between consecutive samples, oldest first.
+/// Hours without a snapshot share the delta across the gap evenly.
+fn hourly_block_series(samples: &[HourlyBlockSample]) -> Vec<u64> {
+    samples
+        .windows(2)
+        .flat_map(|pair| {
+            let span_hours = ((pair[1].hour - pair[0].hour) / SECONDS_PER_HOUR).max(1) as usize;
+            let blocks = pair[1].block_qty.saturating_sub(pair[0].block_qty);
+            let per_hour = (blocks as f64 / span_hours as f64).round() as u64;
+            std::iter::repeat_n(per_hour, span_hours)
+        })
+        .collect()
+}
+
+/// The latest hour's blocks and the trailing average they fell short of, if
+/// they are below `ratio` of the average over the preceding `trailing_hours`.
+fn detect_production_drop(
+    series: &[u64],
+    trailing_hours: usize,
+    ratio: f64,
+) -> Option<(u64, f64)> {
+    let (&latest, earlier) = series.split_last()?;
+    if trailing_hours == 0 || earlier.len() < trailing_hours {
+        return None;
+    }
+
+    let trailing = &earlier[earlier.len() - trailing_hours..];
+    let average = trailing.iter().sum::<u64>() as f64 / trailing_hours as f64;
+    if average < PRODUCTION_DROP_MIN_TRAILING_AVERAGE {
+        return None;
+    }
+
+    ((latest as f64) < average * ratio).then_some((latest, average))
+}
+
+fn in_validator_set(
+    detail: &NodeDetail,
+    validator_set_size: u32,
+) -> bool {
+    u32::try_from(detail.ranking).is_ok_and(|ranking| ranking > 0 && ranking <= validator_set_size)
+}
+
+fn local_hour_start(now: DateTime<Local>) -> DateTime<Local> {
+    now.with_minute(0)
+        .and_then(|hour| hour.with_second(0))
+        .and_then(|hour| hour.with_nanosecond(0))
+        .unwrap_or(now)
+}
+
+fn next_hour_deadline(now: DateTime<Local>) -> DateTime<Local> {
+    local_hour_start(now) + ChronoDuration::hours(1)
+}
+
+/// Snapshot block and reward counters of our own validators at the top of
+/// every local hour, publish the hourly production series for the detail
+/// panel and alert when an hour falls well below the trailing average.
+pub(crate) async fn collect_hourly_snapshots(
+    data: SharedData,
+    settings: HourlySnapshotSettings,
+    notifier: Option<Arc<Notifier>>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    let store = match HourlyNodeSnapshotStore::open(&settings.db_path, settings.retention_days) {
+        Ok(store) => store,
+        Err(err) => {
+            warn_with_status(
+                &data,
+                format!(
+                    "Hourly node snapshots disabled, failed to open {}: {}",
+                    settings.db_path, err
+                ),
+            );
+            return Ok(());
+        },
+    };
+    let mut next_snapshot_at = next_hour_deadline(Local::now());
+    let mut history_published = false;
+
+    loop {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+        time::sleep(HOURLY_SNAPSHOT_STOP_POLL_INTERVAL).await;
+
+        let now = Local::now();
+        if now >= next_snapshot_at {
+            let hour = local_hour_start(now).timestamp();
+            let node_details = lock_or_panic(&data).node_details();
+            if let Err(err) = store.save_snapshot(hour, &node_details) {
+                warn_with_status(&data, format!("Failed to persist hourly node snapshot: {err}"));
+            }
+            let checks = publish_hourly_blocks(&data, &store, &settings, hour, true);
+            if let Some(notifier) = notifier.as_ref() {
+                for (node_id, drop) in &checks {
+                    notifier.notify_production_drop(node_id, drop.as_ref()).await;
+                }
+            }
+            next_snapshot_at = next_hour_deadline(now);
+            history_published = true;
+        } else if !history_published && lock_or_panic(&data).node_details_loaded() {
+            // Show the stored series right after startup instead of an hour later.
+            publish_hourly_blocks(
+                &data,
+                &store,
+                &settings,
+                local_hour_start(now).timestamp(),
+                false,
+            );
+            history_published = true;
+        }
+    }
+
+    Ok(())
+}
+
+/// Load each node's recent hourly series into its detail and, when
+/// `check_drops` is set, return every validator checked for a production drop
+/// together with the drop found, if any.
+fn publish_hourly_blocks(
+    data: &SharedData,
+    store: &HourlyNodeSnapshotStore,
+    settings: &HourlySnapshotSettings,
+    hour: i64,
+    check_drops: bool,
+) -> Vec<(String, Option<ProductionDrop>)> {
+    let history_hours = HOURLY_BLOCKS_HISTORY_HOURS.max(settings.trailing_hours + 1);
+    let since = hour - history_hours as i64 * SECONDS_PER_HOUR;
+    let node_details = lock_or_panic(data).node_details();
+    let mut checks = Vec::new();
+
+    for detail in &node_details {
+        let samples = match store.block_samples_since(&detail.node_id, since) {
+            Ok(samples) => samples,
+            Err(err) => {
+                warn!("Failed to read hourly node snapshots for {}: {}", detail.node_id, err);
+                continue;
+            },
+        };
+        let series = hourly_block_series(&samples);
+        let latest_is_current = samples.last().is_some_and(|sample| sample.hour == hour);
+
+        if check_drops && latest_is_current && in_validator_set(detail, settings.validator_set_size)
+        {
+            let drop =
+                detect_production_drop(&series, settings.trailing_hours, settings.drop_alert_ratio)
+                    .map(|(blocks, trailing_average)| ProductionDrop {
+                        node_id: detail.node_id.clone(),
+                        node_name: detail.node_name.clone(),
+                        hour_start: Local
+                            .timestamp_opt(hour - SECONDS_PER_HOUR, 0)
+                            .single()
+                            .unwrap_or_else(Local::now),
+                        blocks,
+                        trailing_average,
+                        trailing_hours: settings.trailing_hours,
+                        ratio_threshold: settings.drop_alert_ratio,
+                    });
+            checks.push((detail.node_id.clone(), drop));
+        }
+
+        let shown = series.len().saturating_sub(HOURLY_BLOCKS_HISTORY_HOURS);
+        lock_or_panic(data).merge_hourly_blocks_for(&detail.node_id, series[shown..].to_vec());
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(
+        hour: i64,
+        block_qty: u64,
+    ) -> HourlyBlockSample {
+        HourlyBlockSample {
+            hour: hour * SECONDS_PER_HOUR,
+            block_qty,
+        }
+    }
+
+    fn fresh_detail(
+        node_id: &str,
+        ranking: i32,
+        block_qty: u64,
+    ) -> NodeDetail {
+        NodeDetail {
+            node_id: node_id.to_string(),
+            node_name: node_id.to_string(),
+            ranking,
+            block_qty,
+            last_updated_at: Some(std::time::Instant::now()),
+            ..NodeDetail::default()
+        }
+    }
+
+    #[test]
+    fn test_hourly_block_series_averages_across_missing_hours() {
+        let samples = [sample(0, 100), sample(1, 110), sample(4, 140), sample(5, 141)];
+
+        assert_eq!(hourly_block_series(&samples), vec![10, 10, 10, 10, 1]);
+        assert!(hourly_block_series(&samples[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_production_drop_compares_latest_hour_with_trailing_average() {
+        assert_eq!(detect_production_drop(&[10, 12, 8, 10, 3], 4, 0.5), Some((3, 10.0)));
+        assert_eq!(detect_production_drop(&[10, 12, 8, 10, 6], 4, 0.5), None);
+        // Not enough history yet.
+        assert_eq!(detect_production_drop(&[10, 12, 0], 4, 0.5), None);
+        // A node that barely produces anyway doesn't alert.
+        assert_eq!(detect_production_drop(&[0, 1, 0, 1, 0], 4, 0.5), None);
+    }
+
+    #[test]
+    fn test_validator_set_membership_uses_ranking() {
+        assert!(in_validator_set(&fresh_detail("node-a", 25, 0), 201));
+        assert!(!in_validator_set(&fresh_detail("node-a", 202, 0), 201));
+        assert!(!in_validator_set(&fresh_detail("node-a", 0, 0), 201));
+    }
+
+    #[test]
+    fn test_store_skips_stale_details_and_prunes_old_hours() {
+        let store =
+            HourlyNodeSnapshotStore::open(":memory:", 1).expect("in-memory store should open");
+        let stale = NodeDetail {
+            last_updated_at: None,
+            ..fresh_detail("node-b", 2, 50)
+        };
+
+        store
+            .save_snapshot(0, &[fresh_detail("0xNODE-A", 1, 100), stale])
+            .expect("snapshot should save");
+        store
+            .save_snapshot(24 * SECONDS_PER_HOUR, &[fresh_detail("node-a", 1, 340)])
+            .expect("snapshot should save");
+
+        assert_eq!(
+            store.block_samples_since("node-a", 0).expect("query should succeed"),
+            vec![sample(0, 100), sample(24, 340)]
+        );
+        assert!(store.block_samples_since("node-b", 0).expect("query should succeed").is_empty());
+
+        store
+            .save_snapshot(25 * SECONDS_PER_HOUR, &[fresh_detail("node-a", 1, 350)])
+            .expect("snapshot should save");
+        assert_eq!(
+            store.block_samples_since("node-a", 0).expect("query should succeed"),
+            vec![sample(24, 340), sample(25, 350)]
+        );
+    }
+}
@@ -1,21 +1,61 @@
+mod alert_engine;
+mod alert_rules;
+mod block_production;
 mod block_subscription;
+mod bot_commands;
 mod collector;
 mod daily_snapshot;
 mod data;
+mod delegation;
+mod delegation_store;
+mod detail_change;
+mod explorer;
+mod hourly_snapshot;
 mod node_detail;
 mod node_state;
+mod notification_upkeep;
 mod peer_geo;
+mod report_schedule;
+mod reward_history;
+mod stake_distance;
 #[cfg(target_family = "unix")]
 mod system_stats;
 mod types;
+mod watchlist;
 
 // Exported for tests (Unix only)
-pub(crate) use self::daily_snapshot::DailyNodeSummaryDetail;
+#[cfg(test)]
+pub use self::block_production::BlockProductionSummary;
 #[cfg(all(target_family = "unix", test))]
 pub use self::data::DiskDetail;
 #[cfg(target_family = "unix")]
 pub use self::data::SystemStats;
+#[cfg(test)]
+pub use self::reward_history::RewardSummary;
+pub(crate) use self::{
+    alert_engine::{
+        AlertState,
+        AlertStatus,
+        AlertTransition,
+    },
+    alert_rules::{
+        AlertMetric,
+        AlertSeverity,
+    },
+    daily_snapshot::{
+        DailyNodeSnapshotStore,
+        DailyNodeSummaryDetail,
+        DailySnapshotRow,
+        SummaryPeriod,
+    },
+    data::node_ids_match,
+    hourly_snapshot::ProductionDrop,
+};
 pub use self::{
+    block_production::{
+        EpochProduction,
+        ProductionTrend,
+    },
     collector::{
         run,
         Collector,
@@ -27,5 +67,23 @@ pub use self::{
         SharedData,
         StatusLevel,
         StatusMessage,
+        NODE_DETAIL_STALE_AFTER,
+    },
+    delegation::{
+        DelegationSnapshot,
+        Delegator,
+        DelegatorChange,
+        DelegatorChangeKind,
+    },
+    detail_change::{
+        NodeDetailChange,
+        NodeDetailChangeKind,
+    },
+    reward_history::annualized_yield,
+    stake_distance::{
+        RankTargetGap,
+        StakeDistance,
+        StakeNeighbor,
     },
+    watchlist::WatchedValidator,
 };
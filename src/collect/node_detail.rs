@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     convert::TryFrom,
     sync::{
         atomic::{
@@ -11,10 +12,12 @@ use std::{
 };
 
 use chrono::{
+    DateTime,
     Local,
     LocalResult,
     NaiveDate,
     TimeZone,
+    Utc,
 };
 use log::{
     debug,
@@ -24,17 +27,33 @@ use tokio::time::{
     self,
     Duration,
     Instant as TokioInstant,
+    MissedTickBehavior,
 };
 
 use super::{
-    daily_snapshot::DailyNodeSnapshotStore,
+    block_production::EpochProduction,
+    daily_snapshot::{
+        summary_details_without_history,
+        DailyNodeSnapshotStore,
+        DailySnapshotSettings,
+    },
     data::{
+        node_ids_match,
+        normalize_node_id,
         record_status_message,
         warn_with_status,
         NodeDetail,
         SharedData,
         StatusLevel,
     },
+    explorer::ExplorerClient,
+    report_schedule::{
+        next_report_after,
+        ran_earlier_today,
+        report_period_start,
+        summary_period,
+    },
+    stake_distance::compute_stake_distance,
     types::{
         self,
         NodeInfo,
@@ -42,15 +61,47 @@ use super::{
 };
 use crate::{
     error::Result,
-    notify::TelegramNotifier,
+    notify::{
+        Notifier,
+        SummaryReport,
+    },
+    opts::{
+        Opts,
+        ReportCadence,
+        ReportSchedule,
+    },
     sync::lock_or_panic,
 };
 
-const NODE_DETAIL_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
-const NODE_RANKING_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
-const NODE_DETAIL_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
 const NODE_DETAIL_STATUS_PREVIEW_COUNT: usize = 3;
 const DAILY_SUMMARY_STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How long a late daily snapshot waits for the first node details.
+const DAILY_SNAPSHOT_CATCH_UP_TIMEOUT: Duration = Duration::from_secs(300);
+/// Early in an epoch a single late block swings the ratio wildly, so alerts wait
+/// until enough blocks are expected for the ratio to mean something.
+const BLOCK_PRODUCTION_ALERT_MIN_EXPECTED: u64 = 10;
+
+/// Alert thresholds and stake targets applied to Explorer node details.
+#[derive(Debug, Clone)]
+pub(crate) struct NodeDetailAlerts {
+    pub block_production_ratio: f64,
+    pub stake_target_ranks: Vec<u32>,
+    pub stake_margin_threshold: Option<f64>,
+}
+
+impl NodeDetailAlerts {
+    pub(crate) fn from_opts(opts: &Opts) -> Self {
+        let mut stake_target_ranks = opts.stake_target_ranks.clone();
+        stake_target_ranks.sort_unstable();
+        stake_target_ranks.dedup();
+
+        Self {
+            block_production_ratio: opts.block_production_alert_ratio,
+            stake_target_ranks,
+            stake_margin_threshold: opts.stake_margin_alert_threshold,
+        }
+    }
+}
 
 struct DailySummarySchedule {
     date: NaiveDate,
@@ -125,83 +176,302 @@ fn summarize_node_detail_failures(node_ids: &[String]) -> String {
     }
 }
 
+/// Our own `--node-id` validators and the `--watch-node-id` competitors.
+struct ConfiguredNodeIds {
+    own: Vec<String>,
+    watched: Vec<String>,
+}
+
 pub(crate) async fn collect_node_details(
     node_ids: Vec<String>,
+    watch_node_ids: Vec<String>,
     data: SharedData,
-    explorer_api_url: String,
-    notifier: Option<Arc<TelegramNotifier>>,
+    client: Arc<ExplorerClient>,
+    alerts: NodeDetailAlerts,
+    notifier: Option<Arc<Notifier>>,
     stop_flag: Arc<AtomicBool>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
-    let detail_url = format!("{explorer_api_url}/staking/stakingDetails");
-    let ranking_url = format!("{explorer_api_url}/staking/aliveStakingList");
+    let node_ids = ConfiguredNodeIds {
+        own: node_ids,
+        watched: watch_node_ids,
+    };
 
-    if let Some(daily_summary_notifier) = notifier.clone() {
-        tokio::join!(
-            run_node_detail_refresh_loop(
-                client,
-                detail_url,
-                ranking_url,
-                node_ids,
-                data.clone(),
-                notifier,
-                stop_flag.clone(),
-            ),
-            run_daily_summary_loop(data, daily_summary_notifier, stop_flag),
-        );
-    } else {
-        run_node_detail_refresh_loop(
-            client,
-            detail_url,
-            ranking_url,
-            node_ids,
-            data,
-            notifier,
-            stop_flag,
-        )
-        .await;
-    }
+    run_node_detail_refresh_loop(&client, node_ids, data, &alerts, notifier, stop_flag).await;
 
     Ok(())
 }
 
+/// Refresh details and rankings on their own `--explorer-*-interval` cadences.
+///
+/// Nodes without a detail yet are first seeded from the on-disk response
+/// cache, so the panel shows the last known values while the first request
+/// is still in flight.
 async fn run_node_detail_refresh_loop(
-    client: reqwest::Client,
-    detail_url: String,
-    ranking_url: String,
-    node_ids: Vec<String>,
+    client: &ExplorerClient,
+    node_ids: ConfiguredNodeIds,
     data: SharedData,
-    notifier: Option<Arc<TelegramNotifier>>,
+    alerts: &NodeDetailAlerts,
+    notifier: Option<Arc<Notifier>>,
     stop_flag: Arc<AtomicBool>,
 ) {
-    let mut interval = time::interval(NODE_DETAIL_REFRESH_INTERVAL);
-
-    fetch_all_node_details(&client, &detail_url, &node_ids, data.clone()).await;
-    fetch_node_rankings(&client, &ranking_url, &node_ids, data.clone(), notifier.clone()).await;
-    interval.tick().await;
+    let settings = client.settings();
+    let mut detail_interval = time::interval(settings.detail_interval);
+    let mut ranking_interval = time::interval(settings.ranking_interval);
+    detail_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    ranking_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
     loop {
         if stop_flag.load(Ordering::Relaxed) {
             break;
         }
 
-        interval.tick().await;
+        let refresh_rankings = tokio::select! {
+            biased;
+            _ = detail_interval.tick() => false,
+            _ = ranking_interval.tick() => true,
+        };
 
         if stop_flag.load(Ordering::Relaxed) {
             break;
         }
 
-        fetch_all_node_details(&client, &detail_url, &node_ids, data.clone()).await;
-        fetch_node_rankings(&client, &ranking_url, &node_ids, data.clone(), notifier.clone()).await;
+        let (tracked_node_ids, watched_node_ids) = {
+            let mut data = lock_or_panic(&data);
+            let tracked_node_ids = tracked_node_ids(&node_ids.own, &data.linked_node_ids());
+            let watched_node_ids = watched_node_ids(&node_ids.watched, &tracked_node_ids);
+            data.retain_watched_validators(&watched_node_ids);
+            (tracked_node_ids, watched_node_ids)
+        };
+        if tracked_node_ids.is_empty() && watched_node_ids.is_empty() {
+            continue;
+        }
+
+        if refresh_rankings {
+            fetch_node_rankings(
+                client,
+                &tracked_node_ids,
+                &watched_node_ids,
+                data.clone(),
+                alerts,
+                notifier.clone(),
+            )
+            .await;
+        } else {
+            seed_node_details_from_cache(
+                client,
+                &tracked_node_ids,
+                &alerts.stake_target_ranks,
+                &data,
+            );
+            fetch_all_node_details(client, &tracked_node_ids, data.clone()).await;
+            fetch_watched_node_details(client, &watched_node_ids, &data).await;
+            notify_node_detail_changes(&data, notifier.as_ref()).await;
+            notify_low_block_production(&data, notifier.as_ref(), alerts.block_production_ratio)
+                .await;
+        }
+
+        if let Err(err) = client.persist_cache() {
+            warn!("Failed to persist explorer response cache: {}", err);
+        }
     }
 }
 
+fn seed_node_details_from_cache(
+    client: &ExplorerClient,
+    node_ids: &[String],
+    stake_target_ranks: &[u32],
+    data: &SharedData,
+) {
+    let cached_node_list = client.cached_node_list();
+    let mut data = lock_or_panic(data);
+
+    for node_id in node_ids {
+        if data.node_detail_for(node_id).is_some() {
+            continue;
+        }
+        let Some((detail, age)) = client.cached_node_detail(node_id) else {
+            continue;
+        };
+
+        debug!("seed node detail for {} from cache ({:?} old)", node_id, age);
+        let mut node_detail = parse_node_detail(node_id, &detail);
+        node_detail.last_updated_at = Instant::now().checked_sub(age);
+        data.merge_node_detail_for(node_id, Some(node_detail));
+        if let Some(nodes) = cached_node_list.as_deref() {
+            data.merge_node_ranking_for(node_id, parse_node_ranking(nodes, node_id));
+            data.merge_stake_distance_for(
+                node_id,
+                compute_stake_distance(nodes, node_id, stake_target_ranks),
+            );
+        }
+    }
+}
+
+/// Configured `--node-id` values followed by node IDs linked from monitored
+/// endpoints via `admin_nodeInfo`, deduplicated by normalized ID.
+pub(super) fn tracked_node_ids(
+    configured: &[String],
+    linked: &[String],
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    configured
+        .iter()
+        .chain(linked)
+        .filter(|node_id| seen.insert(normalize_node_id(node_id)))
+        .cloned()
+        .collect()
+}
+
+/// Watched validators that aren't already tracked as our own nodes.
+fn watched_node_ids(
+    watched: &[String],
+    tracked: &[String],
+) -> Vec<String> {
+    let mut seen: HashSet<_> = tracked.iter().map(|node_id| normalize_node_id(node_id)).collect();
+    watched.iter().filter(|node_id| seen.insert(normalize_node_id(node_id))).cloned().collect()
+}
+
+/// Open the daily node snapshot store once for every task that reads it, so
+/// the legacy import and pruning don't run twice.
+pub(crate) fn open_daily_snapshot_store(
+    data: &SharedData,
+    settings: &DailySnapshotSettings,
+) -> Option<Arc<DailyNodeSnapshotStore>> {
+    match DailyNodeSnapshotStore::open(settings) {
+        Ok(store) => Some(Arc::new(store)),
+        Err(err) => {
+            warn_with_status(
+                data,
+                format!(
+                    "Daily node snapshots disabled, failed to open {}: {}",
+                    settings.db_path, err
+                ),
+            );
+            None
+        },
+    }
+}
+
+/// Snapshot every node detail at local midnight and send the scheduled
+/// summaries when Telegram is configured. Without a usable database the
+/// summaries are still sent, just without the deltas.
+pub(crate) async fn collect_daily_snapshots(
+    data: SharedData,
+    settings: DailySnapshotSettings,
+    store: Option<Arc<DailyNodeSnapshotStore>>,
+    notifier: Option<Arc<Notifier>>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    let catch_up = async {
+        if let Some(store) = store.as_deref() {
+            catch_up_missed_snapshot(&data, store, notifier.as_ref(), &settings, &stop_flag).await;
+        }
+    };
+    // The catch-up may still be waiting for node details at midnight.
+    tokio::join!(
+        catch_up,
+        run_daily_summary_loop(
+            data.clone(),
+            store.as_deref(),
+            notifier.as_ref(),
+            &settings.report_schedules,
+            stop_flag.clone(),
+        ),
+    );
+
+    Ok(())
+}
+
+/// Take today's snapshot late when the process was down at midnight, so the
+/// next summary still has a baseline. Earlier missed days can't be recovered;
+/// their deltas are averaged across the gap instead.
+async fn catch_up_missed_snapshot(
+    data: &SharedData,
+    store: &DailyNodeSnapshotStore,
+    notifier: Option<&Arc<Notifier>>,
+    settings: &DailySnapshotSettings,
+    stop_flag: &AtomicBool,
+) {
+    let today = Local::now().date_naive();
+    match store.has_snapshot(today) {
+        Ok(false) => {},
+        Ok(true) => return,
+        Err(err) => {
+            warn!("Failed to check the daily node snapshot for {}: {}", today, err);
+            return;
+        },
+    }
+
+    if !wait_for_node_details(data, stop_flag).await {
+        return;
+    }
+
+    let previous = store.latest_snapshot_date_before(today).unwrap_or_else(|err| {
+        warn!("Failed to look up the last daily node snapshot: {}", err);
+        None
+    });
+    save_daily_snapshot(data, Some(store), today, true);
+
+    // Only daily reports that were due earlier today are sent late; the rest
+    // run at their next scheduled time.
+    if settings.late_summary && previous.is_some() {
+        let now = Utc::now();
+        for (index, schedule) in settings.report_schedules.iter().enumerate() {
+            if schedule.cadence == ReportCadence::Daily && ran_earlier_today(schedule, now) {
+                send_summary_report(data, Some(store), notifier, index, schedule, today, true)
+                    .await;
+            }
+        }
+    }
+
+    let missed_days = previous.map_or(0, |previous| (today - previous).num_days() - 1);
+    let message = if missed_days > 0 {
+        format!(
+            "Took late daily snapshot for {}; {} earlier day(s) have no snapshot",
+            today, missed_days
+        )
+    } else {
+        format!("Took late daily snapshot for {}", today)
+    };
+    record_status_message(data, StatusLevel::Info, message);
+}
+
+/// Wait for the first node details after startup. Returns false when none
+/// arrive before the timeout or the collector stops.
+async fn wait_for_node_details(
+    data: &SharedData,
+    stop_flag: &AtomicBool,
+) -> bool {
+    let deadline = TokioInstant::now() + DAILY_SNAPSHOT_CATCH_UP_TIMEOUT;
+
+    while TokioInstant::now() < deadline {
+        if stop_flag.load(Ordering::Relaxed) {
+            return false;
+        }
+        {
+            let data = lock_or_panic(data);
+            if data.node_details_loaded() && !data.node_details().is_empty() {
+                return true;
+            }
+        }
+        time::sleep(DAILY_SUMMARY_STOP_POLL_INTERVAL).await;
+    }
+
+    warn!("No node details to take a late daily snapshot from");
+    false
+}
+
 async fn run_daily_summary_loop(
     data: SharedData,
-    notifier: Arc<TelegramNotifier>,
+    store: Option<&DailyNodeSnapshotStore>,
+    notifier: Option<&Arc<Notifier>>,
+    report_schedules: &[ReportSchedule],
     stop_flag: Arc<AtomicBool>,
 ) {
-    let mut schedule = next_daily_summary_schedule();
+    let mut snapshot = next_daily_summary_schedule();
+    let started_at = Utc::now();
+    let mut next_reports: Vec<DateTime<Utc>> =
+        report_schedules.iter().map(|schedule| next_report_after(schedule, started_at)).collect();
 
     loop {
         if stop_flag.load(Ordering::Relaxed) {
@@ -209,72 +479,121 @@ async fn run_daily_summary_loop(
         }
 
         let stop_poll = time::sleep(DAILY_SUMMARY_STOP_POLL_INTERVAL);
-        let daily_summary_sleep = time::sleep_until(schedule.deadline);
+        let snapshot_sleep = time::sleep_until(snapshot.deadline);
         tokio::pin!(stop_poll);
-        tokio::pin!(daily_summary_sleep);
+        tokio::pin!(snapshot_sleep);
 
         tokio::select! {
             biased;
-            _ = &mut daily_summary_sleep => {
-                send_daily_summary(&data, &notifier, schedule.date).await;
-                schedule = next_daily_summary_schedule();
+            _ = &mut snapshot_sleep => {
+                save_daily_snapshot(&data, store, snapshot.date, false);
+                snapshot = next_daily_summary_schedule();
             }
             _ = &mut stop_poll => {},
         }
+
+        let now = Utc::now();
+        for (index, (schedule, next_report)) in
+            report_schedules.iter().zip(next_reports.iter_mut()).enumerate()
+        {
+            if now < *next_report {
+                continue;
+            }
+
+            let end_date = next_report.with_timezone(&Local).date_naive();
+            // The wall clock can pass midnight just before the snapshot timer
+            // fires; the report needs that snapshot as its end point.
+            if snapshot.date <= end_date {
+                save_daily_snapshot(&data, store, snapshot.date, false);
+                snapshot = next_daily_summary_schedule();
+            }
+            send_summary_report(&data, store, notifier, index, schedule, end_date, false).await;
+            *next_report = next_report_after(schedule, now);
+        }
     }
 }
 
-async fn send_daily_summary(
+fn save_daily_snapshot(
     data: &SharedData,
-    notifier: &Arc<TelegramNotifier>,
-    scheduled_date: NaiveDate,
+    store: Option<&DailyNodeSnapshotStore>,
+    date: NaiveDate,
+    late: bool,
 ) {
-    let node_details = {
-        let data = lock_or_panic(data);
-        data.node_details()
+    let Some(store) = store else {
+        return;
     };
-    let snapshot_store = DailyNodeSnapshotStore::default();
-    let daily_summary_details = snapshot_store.daily_summary_details(scheduled_date, &node_details);
+    let node_details = lock_or_panic(data).node_details();
 
-    if let Err(err) = snapshot_store.save_snapshot(scheduled_date, &node_details) {
+    if let Err(err) = store.save_snapshot(date, &node_details, late) {
         warn_with_status(
             data,
-            format!("Failed to persist daily node snapshot for {}: {}", scheduled_date, err),
+            format!("Failed to persist daily node snapshot for {}: {}", date, err),
         );
     }
+}
 
-    notifier.notify_daily_node_snapshot(&scheduled_date.to_string(), &daily_summary_details).await;
+/// Send the report of `schedule` covering the period that ends with the
+/// `end_date` snapshot.
+async fn send_summary_report(
+    data: &SharedData,
+    store: Option<&DailyNodeSnapshotStore>,
+    notifier: Option<&Arc<Notifier>>,
+    schedule_index: usize,
+    schedule: &ReportSchedule,
+    end_date: NaiveDate,
+    late: bool,
+) {
+    let Some(notifier) = notifier else {
+        return;
+    };
+    let (node_details, watched_validators) = {
+        let data = lock_or_panic(data);
+        (data.node_details(), data.watched_validators())
+    };
+    let period = summary_period(schedule.cadence);
+    let summary_details = match store {
+        Some(store) => store.summary_details(
+            period,
+            report_period_start(schedule.cadence, end_date),
+            end_date,
+            &node_details,
+        ),
+        None => summary_details_without_history(period, end_date, &node_details),
+    };
+    let date = end_date.to_string();
+
+    notifier
+        .notify_summary_report(&SummaryReport {
+            schedule_index,
+            period,
+            date: &date,
+            node_details: &summary_details,
+            watched_validators: &watched_validators,
+            late,
+            chat_ids: &schedule.chat_ids,
+        })
+        .await;
 }
 
 async fn fetch_all_node_details(
-    client: &reqwest::Client,
-    url: &str,
+    client: &ExplorerClient,
     node_ids: &[String],
     data: SharedData,
 ) {
     let requests = node_ids.iter().map(|node_id| {
         let data = data.clone();
         async move {
-            match tokio::time::timeout(
-                NODE_DETAIL_REQUEST_TIMEOUT,
-                fetch_node_detail(client, url, node_id, data.clone()),
-            )
-            .await
-            {
-                Ok(Ok(())) => None,
-                Ok(Err(message)) => {
-                    warn!("{message}");
+            match client.node_detail(node_id).await {
+                Ok(detail) => {
+                    let node_detail = parse_node_detail(node_id, &detail);
                     let mut data = lock_or_panic(&data);
-                    data.remove_node_detail(node_id);
-                    Some(node_id.clone())
+                    data.merge_node_detail_for(node_id, Some(node_detail));
+                    None
                 },
-                Err(err) => {
-                    warn!(
-                        "Node detail request timed out after {:?} for {}: {}",
-                        NODE_DETAIL_REQUEST_TIMEOUT, node_id, err
-                    );
+                Err(message) => {
+                    warn!("{message}");
                     let mut data = lock_or_panic(&data);
-                    data.remove_node_detail(node_id);
+                    data.record_node_detail_failure(node_id, message);
                     Some(node_id.clone())
                 },
             }
@@ -294,190 +613,146 @@ async fn fetch_all_node_details(
     }
 }
 
-async fn fetch_node_rankings(
-    client: &reqwest::Client,
-    url: &str,
+async fn fetch_watched_node_details(
+    client: &ExplorerClient,
     node_ids: &[String],
-    data: SharedData,
-    notifier: Option<Arc<TelegramNotifier>>,
+    data: &SharedData,
 ) {
-    match tokio::time::timeout(
-        NODE_RANKING_REQUEST_TIMEOUT,
-        fetch_node_rankings_once(client, url, node_ids, data.clone(), notifier.clone()),
-    )
-    .await
-    {
-        Ok(()) => {},
-        Err(err) => {
-            warn_with_status(
-                &data,
-                format!(
-                    "Node ranking request timed out after {:?}: {}",
-                    NODE_RANKING_REQUEST_TIMEOUT, err
-                ),
-            );
-        },
+    let requests = node_ids.iter().map(|node_id| async move {
+        match client.node_detail(node_id).await {
+            Ok(detail) => {
+                let node_detail = parse_node_detail(node_id, &detail);
+                lock_or_panic(data).merge_watched_detail(node_id, &node_detail);
+            },
+            Err(message) => warn!("watched validator: {message}"),
+        }
+    });
+
+    futures::future::join_all(requests).await;
+}
+
+fn low_block_production_alerts(
+    node_details: &[NodeDetail],
+    threshold: f64,
+) -> Vec<(String, String, EpochProduction)> {
+    node_details
+        .iter()
+        .filter_map(|detail| {
+            let current = detail.production?.current;
+            if current.expected < BLOCK_PRODUCTION_ALERT_MIN_EXPECTED {
+                return None;
+            }
+
+            current
+                .production_ratio()
+                .filter(|ratio| *ratio < threshold)
+                .map(|_| (detail.node_id.clone(), detail.node_name.clone(), current))
+        })
+        .collect()
+}
+
+async fn notify_low_block_production(
+    data: &SharedData,
+    notifier: Option<&Arc<Notifier>>,
+    threshold: f64,
+) {
+    let Some(notifier) = notifier else {
+        return;
+    };
+
+    let alerts = {
+        let data = lock_or_panic(data);
+        low_block_production_alerts(&data.node_details(), threshold)
+    };
+
+    for (node_id, node_name, production) in alerts {
+        notifier.notify_block_production_low(&node_id, &node_name, production, threshold).await;
     }
 }
 
-async fn fetch_node_rankings_once(
-    client: &reqwest::Client,
-    url: &str,
-    node_ids: &[String],
-    data: SharedData,
-    notifier: Option<Arc<TelegramNotifier>>,
+async fn notify_node_detail_changes(
+    data: &SharedData,
+    notifier: Option<&Arc<Notifier>>,
 ) {
-    let body = serde_json::json!({
-        "pageNo": 1,
-        "pageSize": 300,
-        "key": "",
-        "queryStatus": "all",
-    });
+    // Drain even without a notifier so changes don't pile up.
+    let changes = lock_or_panic(data).take_pending_node_detail_changes();
+    let Some(notifier) = notifier else {
+        return;
+    };
 
-    debug!("fetch node ranking: {}", url);
-
-    match client.post(url).header("content-type", "application/json").json(&body).send().await {
-        Ok(resp) => {
-            debug!("Reponse: {}", resp.status());
-            if !resp.status().is_success() {
-                warn_with_status(
-                    &data,
-                    format!("Node ranking API returned error status: {}", resp.status()),
-                );
-                return;
-            }
-            let body_bytes = match resp.bytes().await {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    warn_with_status(&data, format!("Failed to read response body: {}", e));
-                    return;
-                },
-            };
-            let node_list_resp: types::NodeListResponse = match serde_json::from_slice(&body_bytes)
-            {
-                Ok(node_list_resp) => node_list_resp,
-                Err(e) => {
-                    warn_with_status(&data, format!("Failed to parse response JSON: {}", e));
-                    return;
-                },
-            };
-            debug!("Node list response: {:?}", node_list_resp);
-
-            if node_list_resp.code == 0 {
-                if let Some(data_obj) = node_list_resp.data {
-                    let ranking_observations = {
-                        let mut data = lock_or_panic(&data);
-                        let mut ranking_observations = Vec::new();
-
-                        for node_id in node_ids {
-                            let ranking = parse_node_ranking(&data_obj, node_id);
-                            data.merge_node_ranking_for(node_id, ranking);
-
-                            let Some(ranking) = ranking.filter(|ranking| *ranking > 0) else {
-                                continue;
-                            };
-
-                            let node_name = data
-                                .node_detail_for(node_id)
-                                .map(|detail| detail.node_name)
-                                .unwrap_or_default();
-
-                            ranking_observations.push((node_id.clone(), node_name, ranking));
-                        }
-
-                        ranking_observations
-                    };
-
-                    if let Some(notifier) = notifier.as_ref() {
-                        for (node_id, node_name, ranking) in ranking_observations {
-                            notifier
-                                .notify_node_ranking_change(&node_id, &node_name, ranking)
-                                .await;
-                        }
-                    }
-                } else {
-                    warn_with_status(&data, "Node ranking response missing data field");
-                }
-            } else {
-                warn_with_status(
-                    &data,
-                    format!(
-                        "Node ranking API returned error code: {}, err_msg: {}",
-                        node_list_resp.code, node_list_resp.err_msg
-                    ),
-                );
-            }
-        },
-        Err(e) => {
-            warn_with_status(&data, format!("Failed to fetch node ranking: {}", e));
-        },
+    for change in &changes {
+        notifier.notify_node_detail_change(change).await;
     }
 }
 
-async fn fetch_node_detail(
-    client: &reqwest::Client,
-    url: &str,
-    node_id: &str,
+async fn fetch_node_rankings(
+    client: &ExplorerClient,
+    node_ids: &[String],
+    watched_node_ids: &[String],
     data: SharedData,
-) -> std::result::Result<(), String> {
-    let body = serde_json::json!({
-        "nodeId": node_id
-    });
+    alerts: &NodeDetailAlerts,
+    notifier: Option<Arc<Notifier>>,
+) {
+    let nodes = match client.node_list().await {
+        Ok(nodes) => nodes,
+        Err(message) => {
+            warn_with_status(&data, message);
+            return;
+        },
+    };
 
-    debug!("fetch node detail: {}", url);
-
-    match client.post(url).header("content-type", "application/json").json(&body).send().await {
-        Ok(resp) => {
-            debug!("Reponse: {}", resp.status());
-            if !resp.status().is_success() {
-                return Err(format!(
-                    "Node detail API returned error status for {}: {}",
-                    node_id,
-                    resp.status()
-                ));
-            }
-            let body_bytes = match resp.bytes().await {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to read node detail response body for {}: {}",
-                        node_id, e
-                    ));
-                },
+    let (ranking_observations, stake_distances) = {
+        let mut data = lock_or_panic(&data);
+        let mut ranking_observations = Vec::new();
+        let mut stake_distances = Vec::new();
+
+        for node_id in watched_node_ids {
+            data.merge_watched_ranking(node_id, parse_node_ranking(&nodes, node_id));
+        }
+
+        for node_id in node_ids {
+            let ranking = parse_node_ranking(&nodes, node_id);
+            data.merge_node_ranking_for(node_id, ranking);
+            let distance = compute_stake_distance(&nodes, node_id, &alerts.stake_target_ranks);
+            data.merge_stake_distance_for(node_id, distance.clone());
+
+            let Some(ranking) = ranking.filter(|ranking| *ranking > 0) else {
+                continue;
             };
-            let node_detail_resp: types::NodeDetailResponse =
-                match serde_json::from_slice(&body_bytes) {
-                    Ok(node_detail_resp) => node_detail_resp,
-                    Err(e) => {
-                        return Err(format!(
-                            "Failed to parse node detail JSON for {}: {}",
-                            node_id, e
-                        ));
-                    },
-                };
-            debug!("Node detail response: {:?}", node_detail_resp);
-
-            if node_detail_resp.code == 0 {
-                if let Some(detail) = node_detail_resp.data {
-                    let node_detail = parse_node_detail(node_id, &detail);
-                    let mut data = lock_or_panic(&data);
-                    data.merge_node_detail_for(node_id, Some(node_detail));
-                } else {
-                    return Err(format!("Node detail response missing data field for {}", node_id));
+
+            let node_name =
+                data.node_detail_for(node_id).map(|detail| detail.node_name).unwrap_or_default();
+
+            if let Some(distance) = distance {
+                stake_distances.push((node_id.clone(), node_name.clone(), distance));
+            }
+            ranking_observations.push((node_id.clone(), node_name, ranking));
+        }
+
+        (ranking_observations, stake_distances)
+    };
+
+    if let Some(notifier) = notifier.as_ref() {
+        for (node_id, node_name, ranking) in ranking_observations {
+            notifier.notify_node_ranking_change(&node_id, &node_name, ranking).await;
+        }
+
+        if let Some(threshold) = alerts.stake_margin_threshold {
+            for (node_id, node_name, distance) in stake_distances {
+                for gap in &distance.targets {
+                    notifier
+                        .notify_stake_margin_low(
+                            &node_id,
+                            &node_name,
+                            distance.ranking,
+                            *gap,
+                            threshold,
+                        )
+                        .await;
                 }
-            } else {
-                return Err(format!(
-                    "Node detail API returned error code for {}: {}, err_msg: {}",
-                    node_id, node_detail_resp.code, node_detail_resp.err_msg
-                ));
             }
-        },
-        Err(e) => {
-            return Err(format!("Failed to fetch node detail for {}: {}", node_id, e));
-        },
+        }
     }
-
-    Ok(())
 }
 
 fn parse_node_detail(
@@ -497,6 +772,8 @@ fn parse_node_detail(
     let reward_per = node_detail.reward_per.parse::<f64>().ok().unwrap_or(0.0);
     let reward_value = node_detail.reward_value.parse::<f64>().ok().unwrap_or(0.0);
     let reward_address = node_detail.benefit_addr.clone();
+    let total_value = node_detail.total_value.parse::<f64>().ok().unwrap_or(0.0);
+    let delegate_value = node_detail.delegate_value.parse::<f64>().ok().unwrap_or(0.0);
     let verifier_time = u64::try_from(node_detail.verifier_time).unwrap_or(0);
 
     NodeDetail {
@@ -504,6 +781,7 @@ fn parse_node_detail(
         node_name,
         ranking: 0,
         block_qty,
+        expect_block_qty,
         block_rate,
         daily_block_rate,
         reward_per,
@@ -511,6 +789,15 @@ fn parse_node_detail(
         reward_address,
         verifier_time,
         last_updated_at: Some(Instant::now()),
+        production: None,
+        last_error: None,
+        consecutive_failures: 0,
+        endpoint: None,
+        total_value,
+        delegate_value,
+        reward_summary: None,
+        stake_distance: None,
+        hourly_blocks: Vec::new(),
     }
 }
 
@@ -519,13 +806,14 @@ fn parse_node_ranking(
     node_id: &str,
 ) -> Option<i32> {
     data.iter()
-        .find(|node| node.node_id == node_id)
+        .find(|node| node_ids_match(&node.node_id, node_id))
         .and_then(|node| i32::try_from(node.ranking).ok())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::collect::BlockProductionSummary;
 
     #[test]
     fn test_parse_node_ranking_missing_returns_none() {
@@ -534,6 +822,38 @@ mod tests {
         assert_eq!(ranking, None);
     }
 
+    #[test]
+    fn test_parse_node_ranking_matches_ids_without_prefix() {
+        let nodes = vec![NodeInfo {
+            node_id: "ABCDEF".to_string(),
+            node_name: "node-a".to_string(),
+            ranking: 3,
+            total_value: "0".to_string(),
+        }];
+
+        assert_eq!(parse_node_ranking(&nodes, "0xabcdef"), Some(3));
+    }
+
+    #[test]
+    fn test_tracked_node_ids_merge_configured_and_linked_ids() {
+        let configured = vec!["0xABC".to_string(), "def".to_string()];
+        let linked = vec!["0xabc".to_string(), "0x123".to_string()];
+
+        assert_eq!(
+            tracked_node_ids(&configured, &linked),
+            vec!["0xABC".to_string(), "def".to_string(), "0x123".to_string()]
+        );
+        assert!(tracked_node_ids(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_watched_node_ids_skip_our_own_nodes() {
+        let watched = vec!["0xABC".to_string(), "rival".to_string(), "RIVAL".to_string()];
+        let tracked = vec!["abc".to_string()];
+
+        assert_eq!(watched_node_ids(&watched, &tracked), vec!["rival".to_string()]);
+    }
+
     #[test]
     fn test_summarize_node_detail_failures_for_single_node() {
         let summary = summarize_node_detail_failures(&["node-a".to_string()]);
@@ -567,6 +887,53 @@ mod tests {
         );
     }
 
+    fn node_detail_with_production(
+        node_id: &str,
+        produced: u64,
+        expected: u64,
+    ) -> NodeDetail {
+        let mut detail = parse_node_detail(
+            node_id,
+            &types::NodeDetail {
+                node_name: node_id.to_string(),
+                total_value: "0".to_string(),
+                delegate_value: "0".to_string(),
+                delegate_qty: 0,
+                block_qty: 0,
+                expect_block_qty: 0,
+                gen_blocks_rate: "".to_string(),
+                reward_per: "0".to_string(),
+                reward_value: "0".to_string(),
+                benefit_addr: "addr".to_string(),
+                verifier_time: 0,
+            },
+        );
+        detail.production = Some(BlockProductionSummary {
+            current: EpochProduction {
+                epoch: 3,
+                produced,
+                expected,
+            },
+            previous: None,
+        });
+        detail
+    }
+
+    #[test]
+    fn test_low_block_production_alerts_respect_threshold_and_minimum_expected() {
+        let details = vec![
+            node_detail_with_production("below", 70, 100),
+            node_detail_with_production("above", 90, 100),
+            node_detail_with_production("too-early", 0, 5),
+        ];
+
+        let alerts = low_block_production_alerts(&details, 0.8);
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].0, "below");
+        assert_eq!(alerts[0].2.missed(), 30);
+    }
+
     #[test]
     fn test_parse_node_detail_clamps_negative_values() {
         let parsed = parse_node_detail(
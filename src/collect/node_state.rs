@@ -12,31 +12,62 @@ use alloy::providers::{
     ProviderBuilder,
     WsConnect,
 };
+use log::debug;
+use serde_json::Value;
 use tokio::time::{
     self,
     Duration,
 };
 
 use super::data::{
+    normalize_node_id,
     warn_with_status,
     ConsensusState,
     SharedData,
 };
 use crate::{
     error::Result,
-    notify::TelegramNotifier,
+    notify::Notifier,
     sync::lock_or_panic,
 };
 
+const NODE_INFO_TIMEOUT: Duration = Duration::from_secs(5);
+const PEER_COUNT_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
 fn websocket_host(url: &str) -> String {
     url.trim_start_matches("ws://").trim_start_matches("wss://").to_string()
 }
 
+/// Extract the node ID from an `admin_nodeInfo` response, preferring the
+/// public key embedded in the enode URL and falling back to the `id` field.
+fn parse_node_info_id(node_info: &Value) -> Option<String> {
+    let from_enode = node_info
+        .get("enode")
+        .and_then(Value::as_str)
+        .and_then(|enode| enode.strip_prefix("enode://"))
+        .and_then(|rest| rest.split('@').next());
+    let node_id = from_enode.or_else(|| node_info.get("id").and_then(Value::as_str))?;
+    let node_id = normalize_node_id(node_id);
+    (!node_id.is_empty() && node_id.chars().all(|c| c.is_ascii_hexdigit())).then_some(node_id)
+}
+
+/// Parse a `net_peerCount` response, a hex quantity such as `"0x19"`.
+fn parse_peer_count(peer_count: &Value) -> Option<u64> {
+    match peer_count {
+        Value::String(quantity) => {
+            let digits = quantity.strip_prefix("0x").or_else(|| quantity.strip_prefix("0X"))?;
+            u64::from_str_radix(digits, 16).ok()
+        },
+        Value::Number(number) => number.as_u64(),
+        _ => None,
+    }
+}
+
 pub(crate) async fn collect_node_state(
     name: String,
     url: String,
     data: SharedData,
-    notifier: Option<Arc<TelegramNotifier>>,
+    notifier: Option<Arc<Notifier>>,
     stop_flag: Arc<AtomicBool>,
     retry_delay: Duration,
 ) -> Result<()> {
@@ -67,7 +98,26 @@ pub(crate) async fn collect_node_state(
                 continue;
             },
         };
+        // The admin namespace is optional; without it the endpoint simply stays unlinked.
+        let node_id = match time::timeout(
+            NODE_INFO_TIMEOUT,
+            provider.client().request_noparams::<Value>("admin_nodeInfo"),
+        )
+        .await
+        {
+            Ok(Ok(node_info)) => parse_node_info_id(&node_info).unwrap_or_default(),
+            Ok(Err(err)) => {
+                debug!("admin_nodeInfo unavailable for {}: {}", name, err);
+                String::new()
+            },
+            Err(_) => {
+                debug!("admin_nodeInfo timed out for {}", name);
+                String::new()
+            },
+        };
         let mut interval = time::interval(Duration::from_secs(1));
+        let mut peer_count = None;
+        let mut peer_count_refreshed_at: Option<time::Instant> = None;
 
         loop {
             if stop_flag.load(Ordering::Relaxed) {
@@ -76,6 +126,7 @@ pub(crate) async fn collect_node_state(
 
             interval.tick().await;
 
+            let poll_started_at = time::Instant::now();
             let status = match provider.debug_consensus_status().await {
                 Ok(status) => status,
                 Err(err) => {
@@ -117,6 +168,30 @@ pub(crate) async fn collect_node_state(
                     break;
                 },
             };
+            let rpc_latency_ms =
+                u64::try_from(poll_started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+            // Optional like admin_nodeInfo; refreshed less often than the consensus status.
+            if peer_count_refreshed_at
+                .is_none_or(|refreshed_at| refreshed_at.elapsed() >= PEER_COUNT_REFRESH_INTERVAL)
+            {
+                peer_count = match time::timeout(
+                    NODE_INFO_TIMEOUT,
+                    provider.client().request_noparams::<Value>("net_peerCount"),
+                )
+                .await
+                {
+                    Ok(Ok(peer_count)) => parse_peer_count(&peer_count),
+                    Ok(Err(err)) => {
+                        debug!("net_peerCount unavailable for {}: {}", name, err);
+                        None
+                    },
+                    Err(_) => {
+                        debug!("net_peerCount timed out for {}", name);
+                        None
+                    },
+                };
+                peer_count_refreshed_at = Some(time::Instant::now());
+            }
             let epoch = status.state.view.as_ref().map(|v| v.epoch).unwrap_or(0);
             let view = status.state.view.as_ref().and_then(|v| v.view_number).unwrap_or(0);
             let committed =
@@ -135,6 +210,9 @@ pub(crate) async fn collect_node_state(
                 locked,
                 qc,
                 validator,
+                node_id: node_id.clone(),
+                rpc_latency_ms,
+                peer_count,
             };
 
             {
@@ -152,3 +230,35 @@ pub(crate) async fn collect_node_state(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_node_info_id_prefers_enode_public_key() {
+        let node_info = json!({
+            "id": "ffff",
+            "enode": "enode://ABCDEF0123@127.0.0.1:16789?discport=16789",
+        });
+
+        assert_eq!(parse_node_info_id(&node_info).as_deref(), Some("abcdef0123"));
+    }
+
+    #[test]
+    fn test_parse_node_info_id_falls_back_to_id_field() {
+        assert_eq!(parse_node_info_id(&json!({ "id": "0xAbC1" })).as_deref(), Some("abc1"));
+        assert_eq!(parse_node_info_id(&json!({ "id": "not-hex" })), None);
+        assert_eq!(parse_node_info_id(&json!({})), None);
+    }
+
+    #[test]
+    fn test_parse_peer_count_reads_hex_quantity() {
+        assert_eq!(parse_peer_count(&json!("0x19")), Some(25));
+        assert_eq!(parse_peer_count(&json!(7)), Some(7));
+        assert_eq!(parse_peer_count(&json!("25")), None);
+        assert_eq!(parse_peer_count(&json!(null)), None);
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        Ordering,
+    },
+    Arc,
+};
+
+use tokio::time::{
+    self,
+    Duration,
+    MissedTickBehavior,
+};
+
+use super::data::SharedData;
+use crate::{
+    error::Result,
+    notify::Notifier,
+    sync::lock_or_panic,
+};
+
+const NOTIFICATION_UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+const NOTIFICATION_UPKEEP_STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Audit rows shown in the Notifications panel.
+const NOTIFICATION_LOG_LIMIT: usize = 50;
+
+/// Follow up on unresolved connection failures, retry queued notifications
+/// as they come due and publish the delivery audit log for the TUI.
+pub(crate) async fn run_notification_upkeep(
+    notifier: Arc<Notifier>,
+    data: SharedData,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<()> {
+    let mut upkeep_interval = time::interval(NOTIFICATION_UPKEEP_INTERVAL);
+    upkeep_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let stop_poll = time::sleep(NOTIFICATION_UPKEEP_STOP_POLL_INTERVAL);
+        tokio::pin!(stop_poll);
+        tokio::select! {
+            biased;
+            _ = upkeep_interval.tick() => {},
+            _ = &mut stop_poll => continue,
+        }
+
+        notifier.follow_up_unresolved_incidents().await;
+        notifier.flush_outboxes().await;
+        let deliveries = notifier.recent_deliveries(NOTIFICATION_LOG_LIMIT);
+        lock_or_panic(&data).replace_notification_deliveries(deliveries);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use chrono::{
+    DateTime,
+    Datelike,
+    Days,
+    Duration,
+    Local,
+    LocalResult,
+    Months,
+    NaiveDate,
+    NaiveDateTime,
+    TimeZone,
+    Utc,
+};
+
+use super::daily_snapshot::SummaryPeriod;
+use crate::opts::{
+    ReportCadence,
+    ReportSchedule,
+};
+
+/// Long enough to reach the next run of any cadence, including a monthly
+/// schedule on the 31st.
+const REPORT_SCHEDULE_SEARCH_DAYS: u64 = 400;
+/// A time skipped by a DST change runs at the first valid minute after it.
+const REPORT_SCHEDULE_DST_GAP_MINUTES: i64 = 180;
+
+/// The first run of `schedule` strictly after `after`.
+pub(super) fn next_report_after(
+    schedule: &ReportSchedule,
+    after: DateTime<Utc>,
+) -> DateTime<Utc> {
+    match schedule.timezone {
+        Some(timezone) => next_report_in(&timezone, schedule, after),
+        None => next_report_in(&Local, schedule, after),
+    }
+}
+
+fn next_report_in<Tz: TimeZone>(
+    timezone: &Tz,
+    schedule: &ReportSchedule,
+    after: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let first_date = after.with_timezone(timezone).date_naive();
+
+    (0..REPORT_SCHEDULE_SEARCH_DAYS)
+        .filter_map(|offset| first_date.checked_add_days(Days::new(offset)))
+        .filter(|date| runs_on(schedule.cadence, *date))
+        .filter_map(|date| resolve_local(timezone, date.and_time(schedule.time)))
+        .find(|run_at| *run_at > after)
+        .unwrap_or_else(|| after + Duration::days(1))
+}
+
+fn resolve_local<Tz: TimeZone>(
+    timezone: &Tz,
+    mut candidate: NaiveDateTime,
+) -> Option<DateTime<Utc>> {
+    for _ in 0..=REPORT_SCHEDULE_DST_GAP_MINUTES {
+        match timezone.from_local_datetime(&candidate) {
+            LocalResult::Single(run_at) | LocalResult::Ambiguous(run_at, _) => {
+                return Some(run_at.with_timezone(&Utc));
+            },
+            LocalResult::None => candidate += Duration::minutes(1),
+        }
+    }
+
+    None
+}
+
+fn runs_on(
+    cadence: ReportCadence,
+    date: NaiveDate,
+) -> bool {
+    match cadence {
+        ReportCadence::Daily => true,
+        ReportCadence::Weekly(weekday) => date.weekday() == weekday,
+        ReportCadence::Monthly(day) => date.day() == day.min(last_day_of_month(date)),
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    date.with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+        .and_then(|next_first| next_first.pred_opt())
+        .map_or(31, |last| last.day())
+}
+
+/// Whether the run of `schedule` due today, by the date in the schedule's
+/// timezone, already passed at `now`, i.e. it was missed while the process
+/// was down.
+pub(super) fn ran_earlier_today(
+    schedule: &ReportSchedule,
+    now: DateTime<Utc>,
+) -> bool {
+    let previous = next_report_after(schedule, now - Duration::days(1));
+
+    previous <= now
+        && match schedule.timezone {
+            Some(timezone) => same_date_in(&timezone, previous, now),
+            None => same_date_in(&Local, previous, now),
+        }
+}
+
+fn same_date_in<Tz: TimeZone>(
+    timezone: &Tz,
+    left: DateTime<Utc>,
+    right: DateTime<Utc>,
+) -> bool {
+    left.with_timezone(timezone).date_naive() == right.with_timezone(timezone).date_naive()
+}
+
+pub(super) fn summary_period(cadence: ReportCadence) -> SummaryPeriod {
+    match cadence {
+        ReportCadence::Daily => SummaryPeriod::Daily,
+        ReportCadence::Weekly(_) => SummaryPeriod::Weekly,
+        ReportCadence::Monthly(_) => SummaryPeriod::Monthly,
+    }
+}
+
+/// First snapshot date of the period a report ending at the `end` snapshot
+/// covers.
+pub(super) fn report_period_start(
+    cadence: ReportCadence,
+    end: NaiveDate,
+) -> NaiveDate {
+    let start = match cadence {
+        ReportCadence::Daily => end.checked_sub_days(Days::new(1)),
+        ReportCadence::Weekly(_) => end.checked_sub_days(Days::new(7)),
+        ReportCadence::Monthly(_) => end.checked_sub_months(Months::new(1)),
+    };
+
+    start.unwrap_or(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{
+        NaiveTime,
+        Weekday,
+    };
+    use chrono_tz::{
+        America::New_York,
+        Asia::Shanghai,
+        Pacific::Kiritimati,
+    };
+
+    use super::*;
+
+    fn schedule(
+        cadence: ReportCadence,
+        hour: u32,
+        minute: u32,
+        timezone: chrono_tz::Tz,
+    ) -> ReportSchedule {
+        ReportSchedule {
+            cadence,
+            time: NaiveTime::from_hms_opt(hour, minute, 0).expect("time should be valid"),
+            timezone: Some(timezone),
+            chat_ids: Vec::new(),
+            template: None,
+        }
+    }
+
+    fn utc(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+    ) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).single().expect("time is valid")
+    }
+
+    fn date(
+        year: i32,
+        month: u32,
+        day: u32,
+    ) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("date should be valid")
+    }
+
+    #[test]
+    fn test_daily_schedule_runs_at_time_in_its_timezone() {
+        let daily = schedule(ReportCadence::Daily, 9, 0, Shanghai);
+
+        // 09:00 in Shanghai is 01:00 UTC.
+        assert_eq!(next_report_after(&daily, utc(2026, 4, 14, 0, 30)), utc(2026, 4, 14, 1, 0));
+        assert_eq!(next_report_after(&daily, utc(2026, 4, 14, 1, 0)), utc(2026, 4, 15, 1, 0));
+    }
+
+    #[test]
+    fn test_weekly_schedule_waits_for_weekday() {
+        let weekly = schedule(ReportCadence::Weekly(Weekday::Mon), 8, 0, Shanghai);
+
+        // 2026-04-15 is a Wednesday; the next Monday is 2026-04-20.
+        assert_eq!(next_report_after(&weekly, utc(2026, 4, 15, 0, 0)), utc(2026, 4, 20, 0, 0));
+    }
+
+    #[test]
+    fn test_monthly_schedule_uses_last_day_of_short_months() {
+        let monthly = schedule(ReportCadence::Monthly(31), 0, 0, Shanghai);
+
+        // Midnight on 2026-04-30 in Shanghai is 16:00 UTC the day before.
+        assert_eq!(next_report_after(&monthly, utc(2026, 4, 1, 0, 0)), utc(2026, 4, 29, 16, 0));
+        assert_eq!(next_report_after(&monthly, utc(2026, 4, 29, 16, 0)), utc(2026, 5, 30, 16, 0));
+    }
+
+    #[test]
+    fn test_schedule_in_dst_gap_runs_after_the_gap() {
+        // Clocks in New York jump from 02:00 to 03:00 on 2026-03-08.
+        let daily = schedule(ReportCadence::Daily, 2, 30, New_York);
+
+        assert_eq!(next_report_after(&daily, utc(2026, 3, 8, 5, 0)), utc(2026, 3, 8, 7, 0));
+    }
+
+    #[test]
+    fn test_missed_run_is_judged_by_the_date_in_the_schedule_timezone() {
+        // Kiritimati is UTC+14, a day ahead of UTC for most of the UTC day.
+        let morning = schedule(ReportCadence::Daily, 9, 0, Kiritimati);
+        let late_evening = schedule(ReportCadence::Daily, 23, 0, Kiritimati);
+
+        // 15:00 on 2026-04-15 there; today's 09:00 run was 19:00 UTC the day
+        // before.
+        assert!(ran_earlier_today(&morning, utc(2026, 4, 15, 1, 0)));
+        // 00:30 on 2026-04-16 there; the 23:00 run at 09:00 UTC was yesterday.
+        assert!(!ran_earlier_today(&late_evening, utc(2026, 4, 15, 10, 30)));
+    }
+
+    #[test]
+    fn test_report_period_start_matches_cadence() {
+        assert_eq!(report_period_start(ReportCadence::Daily, date(2026, 3, 1)), date(2026, 2, 28));
+        assert_eq!(
+            report_period_start(ReportCadence::Weekly(Weekday::Mon), date(2026, 4, 20)),
+            date(2026, 4, 13)
+        );
+        assert_eq!(
+            report_period_start(ReportCadence::Monthly(31), date(2026, 3, 31)),
+            date(2026, 2, 28)
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use super::block_production::counter_delta;
+
+const REWARD_EPOCH_HISTORY_LIMIT: usize = 8;
+const REWARD_SAMPLE_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+const REWARD_MIN_SAMPLE_SPAN: Duration = Duration::from_secs(10 * 60);
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// System reward earned during one epoch, accumulated from consecutive
+/// Explorer samples of the cumulative `rewardValue` counter.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EpochReward {
+    pub epoch: u64,
+    pub reward: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RewardSummary {
+    pub current: EpochReward,
+    pub previous: Option<EpochReward>,
+    /// System reward per day, extrapolated from the samples of the last 24
+    /// hours. `None` until the samples span at least ten minutes.
+    pub daily_reward: Option<f64>,
+}
+
+/// Annualise a daily reward against the stake that earned it.
+pub fn annualized_yield(
+    daily_reward: f64,
+    stake: f64,
+) -> Option<f64> {
+    (stake > 0.0).then(|| daily_reward * DAYS_PER_YEAR / stake)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RewardSample {
+    at: Instant,
+    reward_value: f64,
+}
+
+#[derive(Debug, Default)]
+struct NodeRewardHistory {
+    samples: VecDeque<RewardSample>,
+    epochs: VecDeque<EpochReward>,
+}
+
+impl NodeRewardHistory {
+    fn record(
+        &mut self,
+        epoch: u64,
+        sample: RewardSample,
+    ) {
+        let (reward, reset) = match self.samples.back() {
+            Some(previous) => (
+                counter_delta(previous.reward_value, sample.reward_value),
+                sample.reward_value < previous.reward_value,
+            ),
+            None => (0.0, false),
+        };
+        // Cumulative rewards only shrink when the validator re-stakes; restart
+        // the window so the daily extrapolation doesn't span the reset.
+        if reset {
+            self.samples.clear();
+        }
+        self.samples.push_back(sample);
+        while self.samples.front().is_some_and(|oldest| {
+            sample.at.saturating_duration_since(oldest.at) > REWARD_SAMPLE_WINDOW
+        }) {
+            self.samples.pop_front();
+        }
+
+        match self.epochs.back_mut() {
+            Some(last) if last.epoch == epoch => last.reward += reward,
+            _ => {
+                self.epochs.push_back(EpochReward { epoch, reward });
+                while self.epochs.len() > REWARD_EPOCH_HISTORY_LIMIT {
+                    self.epochs.pop_front();
+                }
+            },
+        }
+    }
+
+    fn daily_reward(&self) -> Option<f64> {
+        let oldest = self.samples.front()?;
+        let latest = self.samples.back()?;
+        let span = latest.at.saturating_duration_since(oldest.at);
+        if span < REWARD_MIN_SAMPLE_SPAN {
+            return None;
+        }
+
+        Some((latest.reward_value - oldest.reward_value) * SECONDS_PER_DAY / span.as_secs_f64())
+    }
+
+    fn summary(&self) -> Option<RewardSummary> {
+        let mut epochs = self.epochs.iter().rev();
+        let current = *epochs.next()?;
+        let previous = epochs.next().copied();
+
+        Some(RewardSummary {
+            current,
+            previous,
+            daily_reward: self.daily_reward(),
+        })
+    }
+}
+
+/// Per-node reward history keyed by validator node ID.
+#[derive(Debug, Default)]
+pub(super) struct RewardHistoryTracker {
+    nodes: HashMap<String, NodeRewardHistory>,
+}
+
+impl RewardHistoryTracker {
+    pub(super) fn record(
+        &mut self,
+        node_id: &str,
+        epoch: u64,
+        at: Instant,
+        reward_value: f64,
+    ) -> Option<RewardSummary> {
+        let history = self.nodes.entry(node_id.to_string()).or_default();
+        history.record(epoch, RewardSample { at, reward_value });
+        history.summary()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_sample_only_sets_baseline() {
+        let mut tracker = RewardHistoryTracker::default();
+
+        let summary =
+            tracker.record("node-a", 7, Instant::now(), 1_000.0).expect("summary should exist");
+
+        assert_eq!(
+            summary.current,
+            EpochReward {
+                epoch: 7,
+                reward: 0.0
+            }
+        );
+        assert_eq!(summary.daily_reward, None);
+    }
+
+    #[test]
+    fn test_rewards_accumulate_per_epoch() {
+        let mut tracker = RewardHistoryTracker::default();
+        let start = Instant::now();
+        tracker.record("node-a", 7, start, 1_000.0);
+        tracker.record("node-a", 7, start + Duration::from_secs(60), 1_010.0);
+
+        let summary = tracker
+            .record("node-a", 8, start + Duration::from_secs(120), 1_025.0)
+            .expect("summary should exist");
+
+        assert_eq!(
+            summary.current,
+            EpochReward {
+                epoch: 8,
+                reward: 15.0
+            }
+        );
+        assert_eq!(
+            summary.previous,
+            Some(EpochReward {
+                epoch: 7,
+                reward: 10.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_daily_reward_is_extrapolated_once_span_is_long_enough() {
+        let mut tracker = RewardHistoryTracker::default();
+        let start = Instant::now();
+        tracker.record("node-a", 7, start, 1_000.0);
+
+        let early = tracker
+            .record("node-a", 7, start + Duration::from_secs(60), 1_001.0)
+            .expect("summary should exist");
+        let hourly = tracker
+            .record("node-a", 7, start + Duration::from_secs(60 * 60), 1_010.0)
+            .expect("summary should exist");
+
+        assert_eq!(early.daily_reward, None);
+        assert_eq!(hourly.daily_reward, Some(240.0));
+    }
+
+    #[test]
+    fn test_reward_reset_counts_new_value_and_restarts_sample_window() {
+        let mut history = NodeRewardHistory::default();
+        let start = Instant::now();
+        history.record(
+            7,
+            RewardSample {
+                at: start,
+                reward_value: 1_000.0,
+            },
+        );
+        history.record(
+            7,
+            RewardSample {
+                at: start + Duration::from_secs(60 * 60),
+                reward_value: 5.0,
+            },
+        );
+
+        assert_eq!(history.samples.len(), 1);
+        assert_eq!(history.daily_reward(), None);
+        assert_eq!(history.epochs.back().map(|epoch| epoch.reward), Some(5.0));
+    }
+
+    #[test]
+    fn test_annualized_yield_requires_stake() {
+        assert_eq!(annualized_yield(10.0, 36_500.0), Some(0.1));
+        assert_eq!(annualized_yield(10.0, 0.0), None);
+    }
+}
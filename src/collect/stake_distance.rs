@@ -0,0 +1,173 @@
+use super::{
+    data::node_ids_match,
+    types::NodeInfo,
+};
+
+/// Stake position of a validator relative to one `--stake-target-ranks` cutoff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankTargetGap {
+    pub target_rank: u32,
+    pub within: bool,
+    /// Signed distance in LAT. Inside the cutoff this is the lead over the
+    /// first validator outside it; outside it is the (negative) shortfall to
+    /// the validator holding the cutoff rank.
+    pub margin: f64,
+}
+
+/// Validator directly above or below us in the staking list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakeNeighbor {
+    pub node_name: String,
+    pub ranking: i64,
+    /// Absolute stake difference to this neighbour, in LAT.
+    pub gap: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakeDistance {
+    pub ranking: i64,
+    pub total_value: f64,
+    pub above: Option<StakeNeighbor>,
+    pub below: Option<StakeNeighbor>,
+    pub targets: Vec<RankTargetGap>,
+}
+
+/// Work out how far a validator sits from each target rank using the
+/// `totalValue` of every entry in `aliveStakingList`.
+pub(super) fn compute_stake_distance(
+    nodes: &[NodeInfo],
+    node_id: &str,
+    target_ranks: &[u32],
+) -> Option<StakeDistance> {
+    let mut ranked: Vec<(&NodeInfo, f64)> = nodes
+        .iter()
+        .filter(|node| node.ranking > 0)
+        .map(|node| (node, node.total_value.parse::<f64>().unwrap_or(0.0)))
+        .collect();
+    ranked.sort_by_key(|(node, _)| node.ranking);
+
+    let index = ranked.iter().position(|(node, _)| node_ids_match(&node.node_id, node_id))?;
+    let (own, total_value) = ranked[index];
+    let neighbor = |index: usize| {
+        ranked.get(index).map(|(node, stake)| StakeNeighbor {
+            node_name: node.node_name.clone(),
+            ranking: node.ranking,
+            gap: (stake - total_value).abs(),
+        })
+    };
+    let stake_at_rank =
+        |rank: i64| ranked.iter().find(|(node, _)| node.ranking == rank).map(|(_, stake)| *stake);
+
+    let targets = target_ranks
+        .iter()
+        .map(|&target_rank| {
+            let cutoff = i64::from(target_rank);
+            let within = own.ranking <= cutoff;
+            let margin = if within {
+                // Nobody outside the cutoff yet: the whole stake is the margin.
+                total_value - stake_at_rank(cutoff + 1).unwrap_or(0.0)
+            } else {
+                total_value - stake_at_rank(cutoff).unwrap_or(total_value)
+            };
+
+            RankTargetGap {
+                target_rank,
+                within,
+                margin,
+            }
+        })
+        .collect();
+
+    Some(StakeDistance {
+        ranking: own.ranking,
+        total_value,
+        above: index.checked_sub(1).and_then(neighbor),
+        below: neighbor(index + 1),
+        targets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(
+        node_id: &str,
+        ranking: i64,
+        total_value: &str,
+    ) -> NodeInfo {
+        NodeInfo {
+            node_id: node_id.to_string(),
+            node_name: format!("name-{node_id}"),
+            ranking,
+            total_value: total_value.to_string(),
+        }
+    }
+
+    fn staking_list() -> Vec<NodeInfo> {
+        vec![node("d", 4, "700"), node("a", 1, "1000"), node("c", 3, "800"), node("b", 2, "950")]
+    }
+
+    #[test]
+    fn test_margin_inside_cutoff_is_lead_over_first_outsider() {
+        let distance =
+            compute_stake_distance(&staking_list(), "0xB", &[2, 3]).expect("node should be found");
+
+        assert_eq!(distance.ranking, 2);
+        assert_eq!(
+            distance.targets,
+            vec![
+                RankTargetGap {
+                    target_rank: 2,
+                    within: true,
+                    margin: 150.0,
+                },
+                RankTargetGap {
+                    target_rank: 3,
+                    within: true,
+                    margin: 250.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_margin_outside_cutoff_is_negative_shortfall() {
+        let distance =
+            compute_stake_distance(&staking_list(), "d", &[2]).expect("node should be found");
+
+        assert_eq!(
+            distance.targets,
+            vec![RankTargetGap {
+                target_rank: 2,
+                within: false,
+                margin: -250.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_neighbors_are_taken_from_sorted_ranking() {
+        let distance =
+            compute_stake_distance(&staking_list(), "c", &[]).expect("node should be found");
+
+        let above = distance.above.expect("node above should exist");
+        let below = distance.below.expect("node below should exist");
+        assert_eq!((above.ranking, above.gap), (2, 150.0));
+        assert_eq!((below.node_name.as_str(), below.gap), ("name-d", 100.0));
+    }
+
+    #[test]
+    fn test_last_validator_keeps_whole_stake_as_margin() {
+        let distance =
+            compute_stake_distance(&staking_list(), "d", &[10]).expect("node should be found");
+
+        assert_eq!(distance.below, None);
+        assert_eq!(distance.targets[0].margin, 700.0);
+    }
+
+    #[test]
+    fn test_unknown_node_has_no_distance() {
+        assert_eq!(compute_stake_distance(&staking_list(), "missing", &[25]), None);
+    }
+}
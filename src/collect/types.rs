@@ -147,8 +147,50 @@ pub(crate) struct NodeInfo {
         deserialize_with = "deserialize_string_or_default"
     )]
     pub node_id: String,
+    #[serde(
+        rename = "nodeName",
+        default,
+        deserialize_with = "deserialize_string_or_default"
+    )]
+    pub node_name: String,
     #[serde(default, deserialize_with = "deserialize_i64_or_default")]
     pub ranking: i64,
+    #[serde(
+        rename = "totalValue",
+        default,
+        deserialize_with = "deserialize_string_or_default"
+    )]
+    pub total_value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct DelegationListResponse {
+    #[serde(rename = "errMsg")]
+    pub err_msg: String,
+    pub code: i32,
+    #[serde(
+        rename = "totalCount",
+        default,
+        deserialize_with = "deserialize_i64_or_default"
+    )]
+    pub total_count: i64,
+    pub data: Option<Vec<DelegationInfo>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct DelegationInfo {
+    #[serde(
+        rename = "delegateAddr",
+        default,
+        deserialize_with = "deserialize_string_or_default"
+    )]
+    pub delegate_addr: String,
+    #[serde(
+        rename = "delegateValue",
+        default,
+        deserialize_with = "deserialize_string_or_default"
+    )]
+    pub delegate_value: String,
 }
 
 #[cfg(test)]
@@ -214,4 +256,29 @@ mod tests {
         assert_eq!(data[1].node_id, "node-a");
         assert_eq!(data[1].ranking, 7);
     }
+
+    #[test]
+    fn test_delegation_list_response_allows_nullable_fields() {
+        let body = r#"
+        {
+            "errMsg": "",
+            "code": 0,
+            "totalCount": "2",
+            "data": [
+                { "delegateAddr": "lat1a", "delegateValue": "1500.5" },
+                { "delegateAddr": null, "delegateValue": 20 }
+            ]
+        }
+        "#;
+
+        let parsed: DelegationListResponse =
+            serde_json::from_str(body).expect("response should parse");
+        let data = parsed.data.expect("list should exist");
+
+        assert_eq!(parsed.total_count, 2);
+        assert_eq!(data[0].delegate_addr, "lat1a");
+        assert_eq!(data[0].delegate_value, "1500.5");
+        assert_eq!(data[1].delegate_addr, "");
+        assert_eq!(data[1].delegate_value, "20");
+    }
 }
@@ -0,0 +1,152 @@
+use std::{
+    collections::HashMap,
+    time::Instant,
+};
+
+use super::data::{
+    normalize_node_id,
+    NodeDetail,
+};
+
+/// Lightweight Explorer record of a `--watch-node-id` competitor. Watched
+/// validators never feed connection, production or stake alerts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchedValidator {
+    pub node_id: String,
+    pub node_name: String,
+    pub ranking: i32,
+    /// Total stake including delegations, in LAT.
+    pub total_value: f64,
+    pub reward_per: f64,
+    pub block_rate: String,
+    pub last_updated_at: Option<Instant>,
+}
+
+impl WatchedValidator {
+    pub fn display_name(&self) -> &str {
+        if self.node_name.is_empty() {
+            &self.node_id
+        } else {
+            &self.node_name
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct WatchlistStore {
+    validators: HashMap<String, WatchedValidator>,
+}
+
+impl WatchlistStore {
+    pub(super) fn get(
+        &self,
+        node_id: &str,
+    ) -> Option<&WatchedValidator> {
+        self.validators.get(&normalize_node_id(node_id))
+    }
+
+    /// Ranked validators first, by rank; unranked ones after, by name.
+    pub(super) fn sorted(&self) -> Vec<WatchedValidator> {
+        let mut validators: Vec<_> = self.validators.values().cloned().collect();
+        validators.sort_by(|left, right| {
+            (left.ranking <= 0)
+                .cmp(&(right.ranking <= 0))
+                .then_with(|| left.ranking.cmp(&right.ranking))
+                .then_with(|| left.display_name().cmp(right.display_name()))
+        });
+        validators
+    }
+
+    pub(super) fn merge_detail(
+        &mut self,
+        node_id: &str,
+        detail: &NodeDetail,
+    ) {
+        let validator = self.validators.entry(normalize_node_id(node_id)).or_default();
+        validator.node_id = node_id.to_string();
+        validator.node_name = detail.node_name.clone();
+        validator.total_value = detail.total_value;
+        validator.reward_per = detail.reward_per;
+        validator.block_rate = detail.block_rate.clone();
+        validator.last_updated_at = detail.last_updated_at;
+    }
+
+    pub(super) fn merge_ranking(
+        &mut self,
+        node_id: &str,
+        ranking: Option<i32>,
+    ) {
+        let Some(ranking) = ranking else {
+            return;
+        };
+
+        let validator = self.validators.entry(normalize_node_id(node_id)).or_default();
+        if validator.node_id.is_empty() {
+            validator.node_id = node_id.to_string();
+        }
+        validator.ranking = ranking;
+    }
+
+    /// Forget validators no longer on the watchlist, e.g. because they turned
+    /// out to be one of our own nodes.
+    pub(super) fn retain(
+        &mut self,
+        node_ids: &[String],
+    ) {
+        self.validators
+            .retain(|key, _| node_ids.iter().any(|node_id| normalize_node_id(node_id) == *key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detail(
+        node_name: &str,
+        reward_per: f64,
+    ) -> NodeDetail {
+        NodeDetail {
+            node_name: node_name.to_string(),
+            total_value: 1_000.0,
+            reward_per,
+            block_rate: "98.00%".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ranking_and_detail_merge_into_one_record() {
+        let mut store = WatchlistStore::default();
+        store.merge_ranking("0xABC", Some(12));
+        store.merge_detail("abc", &detail("rival", 20.0));
+
+        let validator = store.get("0xabc").expect("validator should be tracked");
+        assert_eq!(validator.ranking, 12);
+        assert_eq!(validator.display_name(), "rival");
+        assert_eq!(validator.reward_per, 20.0);
+    }
+
+    #[test]
+    fn test_sorted_puts_unranked_validators_last() {
+        let mut store = WatchlistStore::default();
+        store.merge_detail("a", &detail("unranked", 10.0));
+        store.merge_ranking("b", Some(30));
+        store.merge_ranking("c", Some(4));
+
+        let ranks: Vec<_> = store.sorted().into_iter().map(|validator| validator.ranking).collect();
+        assert_eq!(ranks, vec![4, 30, 0]);
+    }
+
+    #[test]
+    fn test_retain_drops_validators_removed_from_watchlist() {
+        let mut store = WatchlistStore::default();
+        store.merge_ranking("a", Some(1));
+        store.merge_ranking("b", Some(2));
+
+        store.retain(&["0xA".to_string()]);
+
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    path::Path,
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use rusqlite::{
+    params,
+    Connection,
+    OpenFlags,
+    TransactionBehavior,
+};
+
+use crate::error::Result;
+
+/// How long a connection waits for another one sharing the database file to
+/// release its lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Versioned, idempotent migrations for the `--db-path` database, shared by
+/// every store that opens it.
+const MIGRATIONS: &[&str] = &[
+    // v1: current Peer Snapshot and per-IP Location Cache.
+    "
+    CREATE TABLE IF NOT EXISTS current_peers (
+        ip TEXT PRIMARY KEY,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS location_cache (
+        ip TEXT PRIMARY KEY,
+        country TEXT NOT NULL DEFAULT '',
+        loc TEXT NOT NULL DEFAULT '',
+        refreshed_at INTEGER NOT NULL DEFAULT 0,
+        last_error TEXT NOT NULL DEFAULT ''
+    );
+    ",
+    // v2: per-node delegator snapshots. The header row records snapshots in
+    // which every delegator has left.
+    "
+    CREATE TABLE IF NOT EXISTS delegation_snapshots (
+        node_id TEXT NOT NULL,
+        captured_at INTEGER NOT NULL,
+        total_amount REAL NOT NULL,
+        delegator_count INTEGER NOT NULL,
+        PRIMARY KEY (node_id, captured_at)
+    );
+    CREATE TABLE IF NOT EXISTS delegator_snapshots (
+        node_id TEXT NOT NULL,
+        captured_at INTEGER NOT NULL,
+        address TEXT NOT NULL,
+        amount REAL NOT NULL,
+        PRIMARY KEY (node_id, captured_at, address)
+    );
+    ",
+    // v3: daily node snapshots taken at local midnight, one row per node.
+    // Rows imported from the old JSON store only carry block count and reward,
+    // so the remaining detail columns are nullable.
+    "
+    CREATE TABLE IF NOT EXISTS daily_node_snapshots (
+        snapshot_date TEXT NOT NULL,
+        node_id TEXT NOT NULL,
+        node_name TEXT NOT NULL,
+        captured_at INTEGER,
+        ranking INTEGER,
+        block_qty INTEGER NOT NULL,
+        expect_block_qty INTEGER,
+        block_rate TEXT,
+        daily_block_rate TEXT,
+        reward_per REAL,
+        reward_value REAL NOT NULL,
+        reward_address TEXT,
+        verifier_time INTEGER,
+        total_value REAL,
+        delegate_value REAL,
+        endpoint TEXT,
+        stale INTEGER NOT NULL DEFAULT 0,
+        consecutive_failures INTEGER NOT NULL DEFAULT 0,
+        last_error TEXT,
+        production_epoch INTEGER,
+        epoch_blocks_produced INTEGER,
+        epoch_blocks_expected INTEGER,
+        estimated_daily_reward REAL,
+        PRIMARY KEY (snapshot_date, node_id, node_name)
+    );
+    ",
+    // v4: daily snapshots taken after startup because the process was down at
+    // midnight.
+    "
+    ALTER TABLE daily_node_snapshots ADD COLUMN late INTEGER NOT NULL DEFAULT 0;
+    ",
+    // v5: hourly node counters taken at the top of each local hour, keyed by
+    // the hour's Unix timestamp and the normalized node ID.
+    "
+    CREATE TABLE IF NOT EXISTS hourly_node_snapshots (
+        captured_hour INTEGER NOT NULL,
+        node_id TEXT NOT NULL,
+        node_name TEXT NOT NULL,
+        ranking INTEGER NOT NULL,
+        block_qty INTEGER NOT NULL,
+        reward_value REAL NOT NULL,
+        PRIMARY KEY (captured_hour, node_id)
+    );
+    ",
+    // v6: notifier state restored after a restart. Quiet-hours events are
+    // kept one row each and replayed into the buffer.
+    "
+    CREATE TABLE IF NOT EXISTS notification_connection_states (
+        connection_key TEXT PRIMARY KEY,
+        healthy INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS notification_rankings (
+        node_id TEXT PRIMARY KEY,
+        ranking INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS notification_rate_limits (
+        rate_limit_key TEXT PRIMARY KEY,
+        sent_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS notification_quiet_events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        event TEXT NOT NULL,
+        subject TEXT NOT NULL,
+        recorded_at INTEGER NOT NULL
+    );
+    ",
+    // v7: notification outbox with one row per recipient, retried until
+    // delivered or dead-lettered, and the audit log of every attempt outcome.
+    "
+    CREATE TABLE IF NOT EXISTS notification_outbox (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        channel TEXT NOT NULL,
+        recipient TEXT NOT NULL,
+        event TEXT NOT NULL,
+        text TEXT NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        next_attempt_at INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        last_error TEXT,
+        dead_at INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS notification_outbox_due
+        ON notification_outbox (channel, dead_at, next_attempt_at);
+    CREATE TABLE IF NOT EXISTS notification_audit_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        channel TEXT NOT NULL,
+        recipient TEXT NOT NULL,
+        event TEXT NOT NULL,
+        title TEXT NOT NULL,
+        status TEXT NOT NULL,
+        attempts INTEGER NOT NULL,
+        error TEXT,
+        recorded_at INTEGER NOT NULL
+    );
+    ",
+    // v8: open connection incidents, so reminders and escalation continue
+    // across a restart.
+    "
+    CREATE TABLE IF NOT EXISTS notification_incidents (
+        connection_key TEXT PRIMARY KEY,
+        reason TEXT NOT NULL,
+        failed_at INTEGER NOT NULL,
+        reminders_sent INTEGER NOT NULL,
+        escalated INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    ",
+    // v9: node name and URL of each incident, which the `NAME@URL` key can't
+    // be split back into when either contains `@`.
+    "
+    ALTER TABLE notification_incidents ADD COLUMN node_name TEXT NOT NULL DEFAULT '';
+    ALTER TABLE notification_incidents ADD COLUMN node_url TEXT NOT NULL DEFAULT '';
+    ",
+    // v10: stake-margin alerts already sent, so a margin that is still thin
+    // after a restart is not reported again.
+    "
+    CREATE TABLE IF NOT EXISTS notification_stake_margin_alerts (
+        alert_key TEXT PRIMARY KEY,
+        updated_at INTEGER NOT NULL
+    );
+    ",
+    // v11: nodes whose hourly production drop was already reported.
+    "
+    CREATE TABLE IF NOT EXISTS notification_production_drop_alerts (
+        node_id TEXT PRIMARY KEY,
+        updated_at INTEGER NOT NULL
+    );
+    ",
+    // v12: the last epoch each node's low block production was reported for.
+    "
+    CREATE TABLE IF NOT EXISTS notification_production_alert_epochs (
+        node_id TEXT PRIMARY KEY,
+        epoch INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    ",
+];
+
+/// Open (or create) the database at `db_path`; `:memory:` opens a private
+/// in-memory database.
+pub(crate) fn open_connection(db_path: &str) -> Result<Connection> {
+    let conn = if db_path == ":memory:" {
+        Connection::open_in_memory()
+    } else {
+        Connection::open(Path::new(db_path))
+    }?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+
+    Ok(conn)
+}
+
+/// Open the existing database at `db_path` without write access and without
+/// migrating it, for commands that only read.
+pub(crate) fn open_read_only_connection(db_path: &str) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        Path::new(db_path),
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+
+    Ok(conn)
+}
+
+/// Open the database at `db_path` and bring its schema up to date, before
+/// the stores sharing it open their own connections.
+pub(crate) fn migrate_database(db_path: &str) -> Result<()> {
+    let mut conn = open_connection(db_path)?;
+    run_migrations(&mut conn)
+}
+
+/// Apply versioned migrations idempotently. Each version is checked and
+/// applied under a write lock, so connections migrating the same file at
+/// once apply it only once.
+pub(crate) fn run_migrations(conn: &mut Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at INTEGER NOT NULL
+        );",
+    )?;
+
+    for (index, migration_sql) in MIGRATIONS.iter().enumerate() {
+        let version = index as i64 + 1;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let already_applied: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
+            params![version],
+            |row| row.get(0),
+        )?;
+        if already_applied {
+            continue;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+        tx.execute_batch(migration_sql)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, ?2)",
+            params![version, now],
+        )?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_names(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+            .expect("prepare should work");
+        let names = stmt.query_map([], |row| row.get::<_, String>(0)).expect("query should work");
+        names.collect::<std::result::Result<Vec<_>, _>>().expect("rows should read")
+    }
+
+    #[test]
+    fn test_run_migrations_creates_tables() {
+        let mut conn = open_connection(":memory:").expect("in-memory connection should open");
+
+        run_migrations(&mut conn).expect("migration should succeed");
+
+        let names = table_names(&conn);
+        assert!(names.contains(&"current_peers".to_string()));
+        assert!(names.contains(&"location_cache".to_string()));
+        assert!(names.contains(&"delegation_snapshots".to_string()));
+        assert!(names.contains(&"delegator_snapshots".to_string()));
+        assert!(names.contains(&"daily_node_snapshots".to_string()));
+        assert!(names.contains(&"hourly_node_snapshots".to_string()));
+        assert!(names.contains(&"notification_connection_states".to_string()));
+        assert!(names.contains(&"notification_quiet_events".to_string()));
+        assert!(names.contains(&"notification_outbox".to_string()));
+        assert!(names.contains(&"notification_audit_log".to_string()));
+        assert!(names.contains(&"notification_incidents".to_string()));
+        assert!(names.contains(&"schema_migrations".to_string()));
+    }
+
+    #[test]
+    fn test_run_migrations_is_idempotent() {
+        let mut conn = open_connection(":memory:").expect("in-memory connection should open");
+
+        run_migrations(&mut conn).expect("first migration should succeed");
+        run_migrations(&mut conn).expect("second migration should succeed");
+
+        let version: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
+            .expect("query should work");
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn test_concurrent_connections_migrate_a_file_once() {
+        let path = std::env::temp_dir()
+            .join(format!("chaindash-migrations-{}.sqlite", std::process::id()));
+        let db_path = path.to_string_lossy().into_owned();
+
+        let workers: Vec<_> = (0..6)
+            .map(|_| {
+                let db_path = db_path.clone();
+                std::thread::spawn(move || {
+                    let mut conn = open_connection(&db_path).expect("connection should open");
+                    run_migrations(&mut conn).map_err(|err| err.to_string())
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().expect("worker should not panic").expect("migration should succeed");
+        }
+
+        let conn = open_connection(&db_path).expect("connection should open");
+        let version: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
+            .expect("query should work");
+        assert_eq!(version, MIGRATIONS.len() as i64);
+        drop(conn);
+        std::fs::remove_file(&path).expect("database should be removed");
+    }
+}
@@ -35,6 +35,7 @@ use crate::{
 
 const AUXILIARY_ROW_HEIGHT: u16 = 6;
 const MIN_PANEL_HEIGHT: u16 = 3;
+const MIN_DELEGATORS_COLUMN_HEIGHT: u16 = 10;
 
 pub fn draw<B: Backend>(
     terminal: &mut Terminal<B>,
@@ -245,7 +246,25 @@ pub fn draw_top_row(
     frame.render_widget(&widgets.txs, columns[1]);
 }
 
-/// Draw Node and Node Details in their unchanged equal-width bottom layout.
+/// Share the Node column with the Delegators panel once delegators are known,
+/// as long as both keep a usable height.
+fn split_node_column(
+    area: Rect,
+    show_delegators: bool,
+) -> (Rect, Option<Rect>) {
+    if !show_delegators || area.height < MIN_DELEGATORS_COLUMN_HEIGHT {
+        return (area, None);
+    }
+
+    let node_height = area.height.div_ceil(2);
+    let node = Rect::new(area.x, area.y, area.width, node_height);
+    let delegators = Rect::new(area.x, area.y + node_height, area.width, area.height - node_height);
+
+    (node, Some(delegators))
+}
+
+/// Draw Node and Node Details side by side, with Delegators below Node.
+/// Notifications takes the place of Node Details while toggled.
 pub fn draw_bottom_section(
     frame: &mut Frame,
     widgets: &mut Widgets,
@@ -256,9 +275,20 @@ pub fn draw_bottom_section(
     }
 
     let horizontal_chunks = split_equal_columns(area);
+    let (node_area, delegators_area) =
+        split_node_column(horizontal_chunks[0], widgets.delegators.has_delegations());
 
-    frame.render_widget(&widgets.node, horizontal_chunks[0]);
-    frame.render_widget(&widgets.node_details, horizontal_chunks[1]);
+    frame.render_widget(&widgets.node, node_area);
+    if let Some(delegators_area) = delegators_area {
+        frame.render_widget(&widgets.delegators, delegators_area);
+    }
+    if widgets.show_alerts {
+        frame.render_widget(&widgets.alerts, horizontal_chunks[1]);
+    } else if widgets.show_notification_log {
+        frame.render_widget(&widgets.notification_log, horizontal_chunks[1]);
+    } else {
+        frame.render_widget(&widgets.node_details, horizontal_chunks[1]);
+    }
 }
 
 #[cfg(test)]
@@ -379,6 +409,18 @@ mod tests {
         assert_eq!(content_row_heights(16, 6), (5, 5));
     }
 
+    #[test]
+    fn test_node_column_makes_room_for_delegators_only_when_tall_enough() {
+        let area = Rect::new(0, 27, 60, 13);
+
+        assert_eq!(split_node_column(area, false), (area, None));
+        assert_eq!(
+            split_node_column(area, true),
+            (Rect::new(0, 27, 60, 7), Some(Rect::new(0, 34, 60, 6)))
+        );
+        assert_eq!(split_node_column(Rect::new(0, 0, 60, 9), true).1, None);
+    }
+
     #[test]
     fn test_dashboard_rows_keep_auxiliary_strip_above_block_and_node_sections() {
         let rows = split_dashboard_rows(Rect::new(4, 7, 100, 30), AUXILIARY_ROW_HEIGHT);
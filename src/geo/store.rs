@@ -1,5 +1,4 @@
 use std::{
-    path::Path,
     sync::{
         Arc,
         Mutex,
@@ -27,6 +26,10 @@ use super::snapshot::{
     GeoViewSnapshot,
 };
 use crate::{
+    db::{
+        open_connection,
+        run_migrations,
+    },
     error::{
         ChaindashError,
         Result,
@@ -38,24 +41,6 @@ pub type Clock = Arc<dyn Fn() -> i64 + Send + Sync>;
 
 const LOCATION_CACHE_TTL_SECONDS: i64 = 24 * 60 * 60;
 
-/// Versioned, idempotent migrations applied when the worker starts.
-const MIGRATIONS: &[&str] = &[
-    // v1: current Peer Snapshot and per-IP Location Cache.
-    "
-    CREATE TABLE IF NOT EXISTS current_peers (
-        ip TEXT PRIMARY KEY,
-        updated_at INTEGER NOT NULL
-    );
-    CREATE TABLE IF NOT EXISTS location_cache (
-        ip TEXT PRIMARY KEY,
-        country TEXT NOT NULL DEFAULT '',
-        loc TEXT NOT NULL DEFAULT '',
-        refreshed_at INTEGER NOT NULL DEFAULT 0,
-        last_error TEXT NOT NULL DEFAULT ''
-    );
-    ",
-];
-
 /// Wall-clock source in unix seconds. Injected so cache expiry can be tested
 /// deterministically without sleeping.
 pub(crate) fn default_clock() -> Clock {
@@ -188,11 +173,7 @@ impl SqlitePeerGeoStore {
             .name("peer-geo-store".to_string())
             .spawn(move || {
                 let init = (|| -> Result<Connection> {
-                    let mut conn = if db_path == ":memory:" {
-                        Connection::open_in_memory()
-                    } else {
-                        Connection::open(Path::new(&db_path))
-                    }?;
+                    let mut conn = open_connection(&db_path)?;
                     run_migrations(&mut conn)?;
                     Ok(conn)
                 })();
@@ -352,39 +333,6 @@ impl PeerGeoStore for NullPeerGeoStore {
 // Database operations (also exercised directly by tests)
 // ============================================================================
 
-/// Apply versioned migrations idempotently.
-pub(crate) fn run_migrations(conn: &mut Connection) -> Result<()> {
-    conn.execute_batch(
-        "CREATE TABLE IF NOT EXISTS schema_migrations (
-            version INTEGER PRIMARY KEY,
-            applied_at INTEGER NOT NULL
-        );",
-    )?;
-
-    for (index, migration_sql) in MIGRATIONS.iter().enumerate() {
-        let version = index as i64 + 1;
-        let already_applied: bool = conn.query_row(
-            "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
-            params![version],
-            |row| row.get(0),
-        )?;
-        if already_applied {
-            continue;
-        }
-
-        let now = default_clock()();
-        let tx = conn.transaction()?;
-        tx.execute_batch(migration_sql)?;
-        tx.execute(
-            "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, ?2)",
-            params![version, now],
-        )?;
-        tx.commit()?;
-    }
-
-    Ok(())
-}
-
 /// Replace the current Peer Snapshot in one transaction.
 ///
 /// Returns the IPs needing enrichment: not in the Location Cache yet, or with
@@ -501,39 +449,6 @@ mod tests {
         Connection::open_in_memory().expect("in-memory connection should open")
     }
 
-    fn table_names(conn: &Connection) -> Vec<String> {
-        let mut stmt = conn
-            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
-            .expect("prepare should work");
-        let names = stmt.query_map([], |row| row.get::<_, String>(0)).expect("query should work");
-        names.collect::<std::result::Result<Vec<_>, _>>().expect("rows should read")
-    }
-
-    #[test]
-    fn test_run_migrations_creates_tables() {
-        let mut conn = in_memory_conn();
-
-        run_migrations(&mut conn).expect("migration should succeed");
-
-        let names = table_names(&conn);
-        assert!(names.contains(&"current_peers".to_string()));
-        assert!(names.contains(&"location_cache".to_string()));
-        assert!(names.contains(&"schema_migrations".to_string()));
-    }
-
-    #[test]
-    fn test_run_migrations_is_idempotent() {
-        let mut conn = in_memory_conn();
-
-        run_migrations(&mut conn).expect("first migration should succeed");
-        run_migrations(&mut conn).expect("second migration should succeed");
-
-        let version: i64 = conn
-            .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
-            .expect("query should work");
-        assert_eq!(version, MIGRATIONS.len() as i64);
-    }
-
     #[test]
     fn test_replace_snapshot_replaces_peers_transactionally() {
         let mut conn = in_memory_conn();
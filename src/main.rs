@@ -1,10 +1,12 @@
 mod app;
 mod collect;
+mod db;
 mod draw;
 mod error;
 mod geo;
 mod notify;
 mod opts;
+mod report;
 mod sync;
 mod update;
 mod widgets;
@@ -53,7 +55,10 @@ use draw::draw;
 use error::ChaindashError;
 use log::error;
 use num_rational::Ratio;
-use opts::Opts;
+use opts::{
+    Command,
+    Opts,
+};
 use ratatui::{
     backend::{
         Backend,
@@ -271,6 +276,14 @@ fn is_shift_tab(key_event: &KeyEvent) -> bool {
         || (key_event.code == KeyCode::Tab && key_event.modifiers == KeyModifiers::SHIFT)
 }
 
+fn is_notification_log_key(key_event: &KeyEvent) -> bool {
+    key_event.code == KeyCode::Char('n') && key_event.modifiers.is_empty()
+}
+
+fn is_alerts_key(key_event: &KeyEvent) -> bool {
+    key_event.code == KeyCode::Char('a') && key_event.modifiers.is_empty()
+}
+
 fn handle_ui_event(
     app: &mut App,
     event: Event,
@@ -292,6 +305,14 @@ fn handle_ui_event(
             }
         },
         Event::Key(key_event) if is_ctrl_c(&key_event) => UiAction::Exit,
+        Event::Key(key_event) if is_notification_log_key(&key_event) => {
+            app.toggle_notification_log();
+            UiAction::Redraw
+        },
+        Event::Key(key_event) if is_alerts_key(&key_event) => {
+            app.toggle_alerts();
+            UiAction::Redraw
+        },
         Event::Resize(_, _) => UiAction::Redraw,
         _ => UiAction::None,
     }
@@ -303,6 +324,18 @@ async fn main() -> Result<(), ChaindashError> {
 
     let opts: Opts = Opts::parse();
 
+    match &opts.command {
+        Some(Command::Report(args)) => return report::run(&opts, args),
+        Some(Command::Template(args)) => return notify::run_template_command(&opts, args),
+        None => {},
+    }
+
+    // Bring the shared database up to date once, before the stores that use
+    // it open their own connections.
+    if let Err(err) = db::migrate_database(&opts.db_path) {
+        eprintln!("Failed to migrate database {}: {}", opts.db_path, err);
+    }
+
     let mut app = setup_app(&opts);
 
     if let Err(e) = setup_logfile(Path::new("./errors.log"), opts.debug) {
@@ -631,6 +664,31 @@ mod tests {
         assert!(matches!(handle_ui_event(&mut app, event), UiAction::None));
     }
 
+    #[test]
+    fn test_handle_ui_event_toggles_notification_log() {
+        let mut app = create_test_app();
+        let event = Event::Key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert!(matches!(handle_ui_event(&mut app, event.clone()), UiAction::Redraw));
+        assert!(app.widgets.show_notification_log);
+        assert!(matches!(handle_ui_event(&mut app, event), UiAction::Redraw));
+        assert!(!app.widgets.show_notification_log);
+    }
+
+    #[test]
+    fn test_handle_ui_event_toggles_alerts_in_place_of_notification_log() {
+        let mut app = create_test_app();
+        let notification_log = Event::Key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        let alerts = Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+
+        handle_ui_event(&mut app, notification_log);
+        assert!(matches!(handle_ui_event(&mut app, alerts.clone()), UiAction::Redraw));
+        assert!(app.widgets.show_alerts);
+        assert!(!app.widgets.show_notification_log);
+        assert!(matches!(handle_ui_event(&mut app, alerts), UiAction::Redraw));
+        assert!(!app.widgets.show_alerts);
+    }
+
     #[test]
     fn test_handle_ui_event_returns_exit_for_quit_key() {
         let mut app = create_test_app();
@@ -0,0 +1,269 @@
+use std::time::Duration;
+
+use chrono::{
+    DateTime,
+    Local,
+};
+
+use super::{
+    display_node_name,
+    format_reward_value,
+};
+use crate::collect::{
+    ConsensusState,
+    NodeDetail,
+};
+
+/// Longest `/mute` accepted, so a typo can't silence alerts for good.
+const MAX_MUTE_DURATION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MUTE_USAGE: &str = "用法：/mute <时长>，例如 /mute 30m、/mute 2h、/mute 1d";
+
+pub(crate) const BOT_HELP_TEXT: &str =
+    "🤖 可用命令\n/status - 各端点共识状态与最新区块\n/nodes - 验证节点详情与排名\n/daily - \
+     立即生成当天节点快照\n/mute <时长> - 暂停非严重通知，例如 /mute 2h\n/unmute - 恢复通知推送";
+
+/// Commands the Telegram bot answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BotCommand {
+    Status,
+    Nodes,
+    Daily,
+    Mute(Duration),
+    Unmute,
+    Help,
+}
+
+/// `None` when `text` is not a command. Unknown commands map to
+/// [`BotCommand::Help`]; a malformed argument is returned as the reply text.
+pub(crate) fn parse_bot_command(text: &str) -> Option<Result<BotCommand, String>> {
+    let mut parts = text.split_whitespace();
+    let command = parts.next()?.strip_prefix('/')?;
+    // Commands picked from the menu in a group chat carry the bot name.
+    let command = command.split_once('@').map_or(command, |(command, _)| command);
+
+    let command = match command.to_ascii_lowercase().as_str() {
+        "status" => BotCommand::Status,
+        "nodes" => BotCommand::Nodes,
+        "daily" => BotCommand::Daily,
+        "mute" => {
+            return Some(
+                parts
+                    .next()
+                    .ok_or_else(|| MUTE_USAGE.to_string())
+                    .and_then(parse_mute_duration)
+                    .map(BotCommand::Mute),
+            );
+        },
+        "unmute" => BotCommand::Unmute,
+        _ => BotCommand::Help,
+    };
+
+    Some(Ok(command))
+}
+
+/// Parses durations such as `30m`, `2h` or `1h30m`.
+fn parse_mute_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("无法识别的时长：{value}\n{MUTE_USAGE}");
+    let mut seconds = 0u64;
+    let mut digits = String::new();
+
+    for ch in value.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+
+        let amount = digits.parse::<u64>().map_err(|_| invalid())?;
+        digits.clear();
+        let unit = match ch.to_ascii_lowercase() {
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        seconds = seconds.saturating_add(amount.saturating_mul(unit));
+    }
+
+    if !digits.is_empty() || seconds == 0 {
+        return Err(invalid());
+    }
+
+    let duration = Duration::from_secs(seconds);
+    if duration > MAX_MUTE_DURATION {
+        return Err("静音时长最长为 7 天".to_string());
+    }
+
+    Ok(duration)
+}
+
+pub(crate) fn render_status_reply(
+    block_number: u64,
+    states: &[ConsensusState],
+) -> String {
+    let block_number = if block_number > 0 {
+        block_number.to_string()
+    } else {
+        "-".to_string()
+    };
+    let mut lines = vec!["📡 节点状态".to_string(), format!("⛓️ 最新区块：{block_number}")];
+
+    if states.is_empty() {
+        lines.push("📭 暂无端点状态".to_string());
+    }
+    for state in states {
+        lines.push(format!("🔹 {}（{}）", state.name, state.host));
+        lines.push(format!(
+            "  🧭 角色：{}",
+            if state.validator {
+                "验证人"
+            } else {
+                "观察者"
+            }
+        ));
+        lines.push(format!(
+            "  🧱 区块 {}｜Epoch {}｜View {}",
+            state.current_number, state.epoch, state.view
+        ));
+        lines.push(format!(
+            "  🔒 QC {}｜Locked {}｜Committed {}",
+            state.qc, state.locked, state.committed
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub(crate) fn render_nodes_reply(node_details: &[NodeDetail]) -> String {
+    if node_details.is_empty() {
+        return "🏅 验证节点\n📭 暂无可用节点详情".to_string();
+    }
+
+    let mut sorted_details = node_details.iter().collect::<Vec<_>>();
+    sorted_details.sort_by(|left, right| {
+        (left.ranking <= 0)
+            .cmp(&(right.ranking <= 0))
+            .then_with(|| left.ranking.cmp(&right.ranking))
+            .then_with(|| left.node_name.cmp(&right.node_name))
+    });
+
+    let mut sections = vec![format!("🏅 验证节点（{}）", node_details.len())];
+    for detail in sorted_details {
+        let ranking = if detail.ranking > 0 {
+            detail.ranking.to_string()
+        } else {
+            "-".to_string()
+        };
+        let mut lines = vec![
+            format!("🔹 {}", display_node_name(&detail.node_name)),
+            format!("  📍 排名：{ranking}"),
+            format!(
+                "  🧱 出块：{} / 预期 {}（{}）",
+                detail.block_qty, detail.expect_block_qty, detail.block_rate
+            ),
+            format!("  💰 累计系统奖励：{}", format_reward_value(detail.reward_value)),
+            format!(
+                "  🪙 质押：{} LAT（委托 {} LAT）｜佣金 {}%",
+                format_reward_value(detail.total_value),
+                format_reward_value(detail.delegate_value),
+                format_reward_value(detail.reward_per)
+            ),
+        ];
+        if detail.is_stale() {
+            if detail.consecutive_failures > 0 {
+                lines.push(format!(
+                    "  ⚠️ 数据状态：缓存数据（连续失败 {} 次）",
+                    detail.consecutive_failures
+                ));
+            } else {
+                lines.push("  ⚠️ 数据状态：缓存数据".to_string());
+            }
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    sections.join("\n\n")
+}
+
+pub(crate) fn render_mute_reply(until: DateTime<Local>) -> String {
+    format!(
+        "🔕 通知已静音至 {}\n严重告警与定时报告照常推送，其余通知将在静音结束后汇总为静默期摘要",
+        until.format("%Y-%m-%d %H:%M")
+    )
+}
+
+pub(crate) fn render_unmute_reply(was_muted: bool) -> String {
+    if was_muted {
+        "🔔 已恢复通知推送".to_string()
+    } else {
+        "🔔 通知未处于静音状态".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bot_command_handles_bot_suffix_and_arguments() {
+        assert_eq!(parse_bot_command("/status"), Some(Ok(BotCommand::Status)));
+        assert_eq!(parse_bot_command("/nodes@chaindash_bot"), Some(Ok(BotCommand::Nodes)));
+        assert_eq!(
+            parse_bot_command("/mute 1h30m"),
+            Some(Ok(BotCommand::Mute(Duration::from_secs(90 * 60))))
+        );
+        assert_eq!(parse_bot_command("/start"), Some(Ok(BotCommand::Help)));
+        assert_eq!(parse_bot_command("hello"), None);
+        assert!(matches!(parse_bot_command("/mute"), Some(Err(_))));
+        assert!(matches!(parse_bot_command("/mute 2x"), Some(Err(_))));
+        assert!(matches!(parse_bot_command("/mute 8d"), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_status_reply_lists_each_endpoint() {
+        let states = vec![ConsensusState {
+            name: "main".to_string(),
+            host: "127.0.0.1:6789".to_string(),
+            current_number: 120,
+            epoch: 3,
+            view: 7,
+            committed: 118,
+            locked: 119,
+            qc: 120,
+            validator: true,
+            node_id: String::new(),
+            rpc_latency_ms: 0,
+            peer_count: None,
+        }];
+
+        assert_eq!(
+            render_status_reply(120, &states),
+            "📡 节点状态\n⛓️ 最新区块：120\n🔹 main（127.0.0.1:6789）\n  🧭 角色：验证人\n  🧱 \
+             区块 120｜Epoch 3｜View 7\n  🔒 QC 120｜Locked 119｜Committed 118"
+        );
+    }
+
+    #[test]
+    fn test_nodes_reply_orders_by_ranking_with_unranked_last() {
+        let node = |name: &str, ranking| NodeDetail {
+            node_name: name.to_string(),
+            ranking,
+            block_qty: 10,
+            expect_block_qty: 12,
+            block_rate: "83.33%".to_string(),
+            reward_value: 1.5,
+            total_value: 1000.0,
+            delegate_value: 400.0,
+            reward_per: 20.0,
+            consecutive_failures: 2,
+            ..NodeDetail::default()
+        };
+
+        let reply = render_nodes_reply(&[node("unranked", 0), node("b", 9), node("a", 3)]);
+
+        assert!(reply.starts_with("🏅 验证节点（3）\n\n🔹 a\n  📍 排名：3"));
+        assert!(reply.find("🔹 b") < reply.find("🔹 unranked"));
+        assert!(reply.contains("  🧱 出块：10 / 预期 12（83.33%）"));
+        assert!(reply.contains("  🪙 质押：1000 LAT（委托 400 LAT）｜佣金 20%"));
+        assert!(reply.contains("  ⚠️ 数据状态：缓存数据（连续失败 2 次）"));
+    }
+}
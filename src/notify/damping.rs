@@ -0,0 +1,267 @@
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use crate::opts::Opts;
+
+/// When a failing connection is reported, and when its up/down cycles are
+/// collapsed into a single flapping notification.
+#[derive(Debug, Clone)]
+pub(super) struct DampingPolicy {
+    /// How long a connection must keep failing before it is reported.
+    grace: Duration,
+    /// Consecutive failed polls before a connection is reported.
+    failure_threshold: u32,
+    /// Failure streaks within `flap_window` that mark a connection as
+    /// flapping; `None` disables flap detection.
+    flap_threshold: Option<usize>,
+    flap_window: Duration,
+}
+
+impl DampingPolicy {
+    pub(super) fn from_opts(opts: &Opts) -> Self {
+        Self {
+            grace: Duration::from_secs(opts.notify_failure_grace_seconds),
+            failure_threshold: opts.notify_failure_threshold,
+            flap_threshold: (opts.notify_flap_threshold > 0)
+                .then_some(opts.notify_flap_threshold as usize),
+            flap_window: Duration::from_secs(opts.notify_flap_window_minutes * 60),
+        }
+    }
+
+    pub(super) fn flap_window(&self) -> Duration {
+        self.flap_window
+    }
+}
+
+impl Default for DampingPolicy {
+    /// Report every failure at once, without flap detection.
+    fn default() -> Self {
+        Self {
+            grace: Duration::ZERO,
+            failure_threshold: 1,
+            flap_threshold: None,
+            flap_window: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+/// What to do with a failed poll of a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FailureDecision {
+    /// Within the grace period or below the failure threshold.
+    Pending,
+    /// Report the failure, which started at `since`.
+    Confirmed { since: Instant },
+    /// Report that the connection started flapping after `cycles` failure
+    /// streaks within the flap window.
+    FlapStarted { since: Instant, cycles: usize },
+    /// Already reported as flapping.
+    Flapping,
+}
+
+/// Consecutive failed polls of a connection.
+#[derive(Debug, Clone, Copy)]
+struct FailureStreak {
+    failures: u32,
+    since: Instant,
+}
+
+#[derive(Debug, Default)]
+struct ConnectionHistory {
+    streak: Option<FailureStreak>,
+    /// Start of each failure streak within the flap window, oldest first.
+    streak_starts: VecDeque<Instant>,
+    flapping: bool,
+}
+
+impl ConnectionHistory {
+    fn prune(
+        &mut self,
+        policy: &DampingPolicy,
+        now: Instant,
+    ) {
+        while self
+            .streak_starts
+            .front()
+            .is_some_and(|started| now.saturating_duration_since(*started) >= policy.flap_window)
+        {
+            self.streak_starts.pop_front();
+        }
+        // A flapping connection settles once a whole window passes without a
+        // new failure streak.
+        if self.streak_starts.is_empty() {
+            self.flapping = false;
+        }
+    }
+}
+
+/// Failure streaks and recent up/down cycles of every connection. Kept in
+/// memory only; a restart starts every connection afresh.
+#[derive(Debug, Default)]
+pub(super) struct ConnectionDamping {
+    connections: HashMap<String, ConnectionHistory>,
+}
+
+impl ConnectionDamping {
+    pub(super) fn record_failure(
+        &mut self,
+        key: &str,
+        policy: &DampingPolicy,
+        now: Instant,
+    ) -> FailureDecision {
+        let history = self.connections.entry(key.to_string()).or_default();
+        history.prune(policy, now);
+        let streak = history.streak.get_or_insert_with(|| {
+            history.streak_starts.push_back(now);
+            FailureStreak {
+                failures: 0,
+                since: now,
+            }
+        });
+        streak.failures = streak.failures.saturating_add(1);
+        let streak = *streak;
+
+        if history.flapping {
+            return FailureDecision::Flapping;
+        }
+        if policy.flap_threshold.is_some_and(|threshold| history.streak_starts.len() >= threshold) {
+            history.flapping = true;
+            return FailureDecision::FlapStarted {
+                since: history.streak_starts.front().copied().unwrap_or(streak.since),
+                cycles: history.streak_starts.len(),
+            };
+        }
+        if streak.failures >= policy.failure_threshold
+            && now.saturating_duration_since(streak.since) >= policy.grace
+        {
+            FailureDecision::Confirmed {
+                since: streak.since,
+            }
+        } else {
+            FailureDecision::Pending
+        }
+    }
+
+    /// Ends the failure streak of `key`. Returns whether the connection is
+    /// still flapping, in which case its recovery is not reported yet.
+    pub(super) fn record_success(
+        &mut self,
+        key: &str,
+        policy: &DampingPolicy,
+        now: Instant,
+    ) -> bool {
+        let Some(history) = self.connections.get_mut(key) else {
+            return false;
+        };
+        history.prune(policy, now);
+        history.streak = None;
+
+        let flapping = history.flapping;
+        if history.streak_starts.is_empty() {
+            self.connections.remove(key);
+        }
+        flapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(
+        grace_seconds: u64,
+        failure_threshold: u32,
+        flap_threshold: Option<usize>,
+    ) -> DampingPolicy {
+        DampingPolicy {
+            grace: Duration::from_secs(grace_seconds),
+            failure_threshold,
+            flap_threshold,
+            flap_window: Duration::from_secs(600),
+        }
+    }
+
+    #[test]
+    fn test_failure_is_confirmed_after_grace_and_threshold() {
+        let policy = policy(30, 3, None);
+        let mut damping = ConnectionDamping::default();
+        let start = Instant::now();
+
+        assert_eq!(damping.record_failure("main", &policy, start), FailureDecision::Pending);
+        assert_eq!(
+            damping.record_failure("main", &policy, start + Duration::from_secs(10)),
+            FailureDecision::Pending
+        );
+        // Third failure, but still within the grace period.
+        assert_eq!(
+            damping.record_failure("main", &policy, start + Duration::from_secs(20)),
+            FailureDecision::Pending
+        );
+        assert_eq!(
+            damping.record_failure("main", &policy, start + Duration::from_secs(30)),
+            FailureDecision::Confirmed { since: start }
+        );
+
+        // A successful poll resets the streak.
+        assert!(!damping.record_success("main", &policy, start + Duration::from_secs(31)));
+        assert_eq!(
+            damping.record_failure("main", &policy, start + Duration::from_secs(40)),
+            FailureDecision::Pending
+        );
+    }
+
+    #[test]
+    fn test_default_policy_confirms_the_first_failure() {
+        let mut damping = ConnectionDamping::default();
+        let now = Instant::now();
+
+        assert_eq!(
+            damping.record_failure("main", &DampingPolicy::default(), now),
+            FailureDecision::Confirmed { since: now }
+        );
+    }
+
+    #[test]
+    fn test_rapid_cycles_flap_once_until_a_quiet_window() {
+        let policy = policy(0, 1, Some(3));
+        let mut damping = ConnectionDamping::default();
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+
+        assert_eq!(
+            damping.record_failure("main", &policy, at(0)),
+            FailureDecision::Confirmed { since: at(0) }
+        );
+        assert!(!damping.record_success("main", &policy, at(5)));
+        assert_eq!(
+            damping.record_failure("main", &policy, at(60)),
+            FailureDecision::Confirmed { since: at(60) }
+        );
+        assert!(!damping.record_success("main", &policy, at(65)));
+        assert_eq!(
+            damping.record_failure("main", &policy, at(120)),
+            FailureDecision::FlapStarted {
+                since: at(0),
+                cycles: 3,
+            }
+        );
+        assert!(damping.record_success("main", &policy, at(125)));
+        assert_eq!(damping.record_failure("main", &policy, at(180)), FailureDecision::Flapping);
+        assert!(damping.record_success("main", &policy, at(185)));
+
+        // Ten minutes after the last failure streak started it settles.
+        assert!(!damping.record_success("main", &policy, at(780)));
+        assert_eq!(
+            damping.record_failure("main", &policy, at(900)),
+            FailureDecision::Confirmed { since: at(900) }
+        );
+    }
+}
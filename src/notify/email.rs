@@ -0,0 +1,512 @@
+use futures::future::BoxFuture;
+use lettre::{
+    message::{
+        header::ContentType,
+        Mailbox,
+        MultiPart,
+    },
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport,
+    AsyncTransport,
+    Message,
+    Tokio1Executor,
+};
+use log::warn;
+
+use super::{
+    daily_span_suffix,
+    display_node_name,
+    format_optional_daily_count,
+    format_optional_reward_value,
+    format_reward_value,
+    split_title,
+    trimmed_option,
+    trimmed_values,
+    Notification,
+    NotificationData,
+    NotificationEventKind,
+    NotificationFilter,
+    NotifierBackend,
+    MESSAGE_PREFIX,
+};
+use crate::{
+    collect::{
+        DailyNodeSummaryDetail,
+        SummaryPeriod,
+    },
+    error::{
+        ChaindashError,
+        Result,
+    },
+    opts::{
+        Opts,
+        SmtpSecurity,
+    },
+};
+
+/// Recipients of the notifications an `--email-route` selects.
+#[derive(Debug)]
+struct EmailRoute {
+    events: NotificationFilter,
+    recipients: Vec<Mailbox>,
+}
+
+/// Sends notifications as email over SMTP: alerts as plain text, scheduled
+/// summaries as an HTML table with a plain-text alternative.
+#[derive(Debug)]
+pub(super) struct EmailBackend {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    default_recipients: Vec<Mailbox>,
+    routes: Vec<EmailRoute>,
+}
+
+impl EmailBackend {
+    pub(super) fn from_opts(opts: &Opts) -> Result<Option<Self>> {
+        let Some(host) = trimmed_option(opts.smtp_host.as_deref()) else {
+            if !opts.email_to.is_empty() || !opts.email_routes.is_empty() {
+                return Err(ChaindashError::Other(
+                    "smtp host is required when email recipients are set".to_string(),
+                ));
+            }
+            return Ok(None);
+        };
+
+        let from = trimmed_option(opts.smtp_from.as_deref()).ok_or_else(|| {
+            ChaindashError::Other("smtp from address is required when smtp host is set".to_string())
+        })?;
+        let from = parse_mailbox(from)?;
+        let default_recipients = parse_mailboxes(&trimmed_values(&opts.email_to))?;
+        let routes = opts
+            .email_routes
+            .iter()
+            .map(|route| {
+                Ok(EmailRoute {
+                    events: NotificationFilter::from_opts(&[route.event]),
+                    recipients: parse_mailboxes(&route.recipients)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if default_recipients.is_empty() && routes.is_empty() {
+            return Err(ChaindashError::Other(
+                "at least one email recipient is required when smtp host is set".to_string(),
+            ));
+        }
+
+        let mut builder = match opts.smtp_security {
+            SmtpSecurity::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)),
+        }
+        .map_err(|err| ChaindashError::Other(format!("invalid smtp host {host}: {err}")))?;
+        if let Some(port) = opts.smtp_port {
+            builder = builder.port(port);
+        }
+        match (
+            trimmed_option(opts.smtp_username.as_deref()),
+            opts.smtp_password.as_deref().filter(|password| !password.is_empty()),
+        ) {
+            (Some(username), Some(password)) => {
+                builder = builder
+                    .credentials(Credentials::new(username.to_string(), password.to_string()));
+            },
+            (None, None) => {},
+            _ => {
+                return Err(ChaindashError::Other(
+                    "smtp username and password must be set together".to_string(),
+                ));
+            },
+        }
+
+        Ok(Some(Self {
+            transport: builder.build(),
+            from,
+            default_recipients,
+            routes,
+        }))
+    }
+
+    /// Routed recipients of `kind`, or the default recipients when no route
+    /// selects it. Quiet-hours digests always go to the defaults.
+    fn recipients(
+        &self,
+        kind: NotificationEventKind,
+    ) -> Vec<Mailbox> {
+        let mut recipients = Vec::new();
+        if kind != NotificationEventKind::QuietSummary {
+            for route in self.routes.iter().filter(|route| route.events.allows(kind)) {
+                for recipient in &route.recipients {
+                    if !recipients.contains(recipient) {
+                        recipients.push(recipient.clone());
+                    }
+                }
+            }
+        }
+
+        if recipients.is_empty() {
+            self.default_recipients.clone()
+        } else {
+            recipients
+        }
+    }
+
+    fn build_message(
+        &self,
+        notification: &Notification,
+        recipients: Vec<Mailbox>,
+    ) -> std::result::Result<Message, lettre::error::Error> {
+        let (title, _) = split_title(&notification.text);
+        let builder = recipients.into_iter().fold(
+            Message::builder().from(self.from.clone()).subject(format!("{MESSAGE_PREFIX} {title}")),
+            |builder, recipient| builder.to(recipient),
+        );
+
+        match &notification.data {
+            NotificationData::Summary {
+                period,
+                date,
+                nodes,
+                ..
+            } => builder.multipart(MultiPart::alternative_plain_html(
+                notification.text.clone(),
+                render_summary_html(title, *period, date, nodes),
+            )),
+            _ => builder.header(ContentType::TEXT_PLAIN).body(notification.text.clone()),
+        }
+    }
+
+    async fn send(
+        &self,
+        notification: &Notification,
+    ) {
+        let recipients = self.recipients(notification.kind);
+        if recipients.is_empty() {
+            return;
+        }
+
+        let message = match self.build_message(notification, recipients) {
+            Ok(message) => message,
+            Err(err) => {
+                warn!("构建通知邮件失败：{}", err);
+                return;
+            },
+        };
+
+        if let Err(err) = self.transport.send(message).await {
+            warn!("发送通知邮件失败：{}", err);
+        }
+    }
+}
+
+impl NotifierBackend for EmailBackend {
+    fn deliver<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(self.send(notification))
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox> {
+    address
+        .parse::<Mailbox>()
+        .map_err(|err| ChaindashError::Other(format!("invalid email address {address}: {err}")))
+}
+
+fn parse_mailboxes(addresses: &[String]) -> Result<Vec<Mailbox>> {
+    addresses.iter().map(|address| parse_mailbox(address)).collect()
+}
+
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Lays a scheduled summary out as one table row per node, mirroring the
+/// fields of the text summary.
+fn render_summary_html(
+    title: &str,
+    period: SummaryPeriod,
+    date: &str,
+    nodes: &[DailyNodeSummaryDetail],
+) -> String {
+    let (period_blocks, period_reward) = match period {
+        SummaryPeriod::Daily => ("当天出块", "当天系统奖励"),
+        SummaryPeriod::Weekly | SummaryPeriod::Monthly => ("周期出块", "周期系统奖励"),
+    };
+    let show_monthly = nodes.iter().any(|node| node.show_monthly_totals);
+
+    let mut headers =
+        vec!["节点", "排名", "累计出块", "累计系统奖励", period_blocks, period_reward];
+    if show_monthly {
+        headers.extend(["上月总出块", "上月总系统奖励"]);
+    }
+    headers.push("状态");
+
+    let mut sorted_nodes = nodes.iter().collect::<Vec<_>>();
+    sorted_nodes.sort_by(|left, right| {
+        left.node_name
+            .cmp(&right.node_name)
+            .then_with(|| left.node_id.cmp(&right.node_id))
+            .then_with(|| left.ranking.cmp(&right.ranking))
+    });
+
+    let rows = sorted_nodes.into_iter().map(|node| {
+        let span_suffix = match period {
+            SummaryPeriod::Daily => daily_span_suffix(node),
+            SummaryPeriod::Weekly | SummaryPeriod::Monthly => {
+                format!("（{} 日）", node.daily_span_days)
+            },
+        };
+        let ranking = if node.ranking > 0 {
+            node.ranking.to_string()
+        } else {
+            "-".to_string()
+        };
+        let mut cells = vec![
+            display_node_name(&node.node_name).to_string(),
+            ranking,
+            node.block_qty.to_string(),
+            format_reward_value(node.reward_value),
+            format!("{}{span_suffix}", format_optional_daily_count(node.daily_block_qty)),
+            format!("{}{span_suffix}", format_optional_reward_value(node.daily_reward_value)),
+        ];
+        if show_monthly {
+            cells.push(format_optional_daily_count(node.monthly_block_qty));
+            cells.push(format_optional_reward_value(node.monthly_reward_value));
+        }
+        cells.push(if node.stale {
+            format!("缓存数据（连续失败 {} 次）", node.consecutive_failures)
+        } else {
+            "正常".to_string()
+        });
+
+        let cells =
+            cells.iter().map(|cell| format!("<td>{}</td>", escape_html(cell))).collect::<String>();
+        format!("<tr>{cells}</tr>")
+    });
+
+    let headers = headers.iter().map(|header| format!("<th>{header}</th>")).collect::<String>();
+    let rows = rows.collect::<Vec<_>>().join("\n");
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<body style=\"font-family: \
+         sans-serif\">\n<h2>{}</h2>\n<p>日期：{}｜节点数：{}</p>\n<table border=\"1\" \
+         cellpadding=\"6\" cellspacing=\"0\" style=\"border-collapse: \
+         collapse\">\n<thead><tr>{headers}</tr></thead>\n<tbody>\n{rows}\n</tbody>\n</table>\n</\
+         body>\n</html>\n",
+        escape_html(title),
+        escape_html(date),
+        nodes.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use tokio::{
+        io::{
+            AsyncBufReadExt,
+            AsyncWriteExt,
+            BufReader,
+        },
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    fn create_test_backend(extra: &[&str]) -> EmailBackend {
+        let mut args = vec![
+            "test",
+            "--smtp-host",
+            "127.0.0.1",
+            "--smtp-security",
+            "none",
+            "--smtp-from",
+            "chaindash <bot@example.com>",
+        ];
+        args.extend_from_slice(extra);
+
+        EmailBackend::from_opts(&Opts::parse_from(args))
+            .expect("config should parse")
+            .expect("email backend should be enabled")
+    }
+
+    fn summary_detail(node_name: &str) -> DailyNodeSummaryDetail {
+        DailyNodeSummaryDetail {
+            node_id: format!("{node_name}-id"),
+            node_name: node_name.to_string(),
+            ranking: 3,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: Some(12),
+            daily_reward_value: Some(5.6),
+            daily_span_days: 1,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }
+    }
+
+    fn summary_notification(nodes: Vec<DailyNodeSummaryDetail>) -> Notification {
+        Notification::new(
+            NotificationEventKind::DailySummary,
+            "2026-04-14",
+            "📊 每日节点快照（2026-04-14）\n🧾 节点数：1".to_string(),
+            NotificationData::Summary {
+                period: SummaryPeriod::Daily,
+                date: "2026-04-14".to_string(),
+                late: false,
+                node_count: nodes.len(),
+                nodes,
+            },
+        )
+    }
+
+    /// Accepts one SMTP session and returns every line the client sent.
+    async fn run_smtp_stand_in(listener: TcpListener) -> Vec<String> {
+        let (stream, _) = listener.accept().await.expect("client should connect");
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut transcript = Vec::new();
+        let mut in_data = false;
+
+        writer.write_all(b"220 localhost ESMTP\r\n").await.expect("greeting should be sent");
+        while let Ok(Some(line)) = lines.next_line().await {
+            transcript.push(line.clone());
+            let command = line.to_ascii_uppercase();
+            let reply: &[u8] = if in_data {
+                if line != "." {
+                    continue;
+                }
+                in_data = false;
+                b"250 OK\r\n"
+            } else if command.starts_with("EHLO") {
+                b"250 localhost\r\n"
+            } else if command.starts_with("DATA") {
+                in_data = true;
+                b"354 End data with <CR><LF>.<CR><LF>\r\n"
+            } else if command.starts_with("QUIT") {
+                b"221 Bye\r\n"
+            } else {
+                b"250 OK\r\n"
+            };
+            writer.write_all(reply).await.expect("reply should be sent");
+            if command.starts_with("QUIT") {
+                break;
+            }
+        }
+
+        transcript
+    }
+
+    #[test]
+    fn test_email_config_requires_sender_and_recipients() {
+        let without_from =
+            Opts::parse_from(["test", "--smtp-host", "127.0.0.1", "--email-to", "ops@example.com"]);
+        let without_recipients = Opts::parse_from([
+            "test",
+            "--smtp-host",
+            "127.0.0.1",
+            "--smtp-from",
+            "bot@example.com",
+        ]);
+        let without_host = Opts::parse_from(["test", "--email-to", "ops@example.com"]);
+        let bad_address = Opts::parse_from([
+            "test",
+            "--smtp-host",
+            "127.0.0.1",
+            "--smtp-from",
+            "bot@example.com",
+            "--email-to",
+            "not-an-address",
+        ]);
+
+        assert!(EmailBackend::from_opts(&without_from).is_err());
+        assert!(EmailBackend::from_opts(&without_recipients).is_err());
+        assert!(EmailBackend::from_opts(&without_host).is_err());
+        assert!(EmailBackend::from_opts(&bad_address).is_err());
+        assert!(EmailBackend::from_opts(&Opts::parse_from(["test"]))
+            .expect("no smtp options is valid")
+            .is_none());
+    }
+
+    #[test]
+    fn test_email_routes_select_recipients_per_event() {
+        let backend = create_test_backend(&[
+            "--email-to",
+            "ops@example.com",
+            "--email-route",
+            "daily=boss@example.com,cto@example.com",
+            "--email-route",
+            "daily-summary=cto@example.com",
+        ]);
+        let addresses = |kind| {
+            backend
+                .recipients(kind)
+                .iter()
+                .map(|mailbox| mailbox.email.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            addresses(NotificationEventKind::DailySummary),
+            vec!["boss@example.com", "cto@example.com"]
+        );
+        assert_eq!(addresses(NotificationEventKind::ConnectionFailed), vec!["ops@example.com"]);
+        assert_eq!(addresses(NotificationEventKind::QuietSummary), vec!["ops@example.com"]);
+    }
+
+    #[test]
+    fn test_summary_html_renders_one_row_per_node() {
+        let mut monthly = summary_detail("<节点B>");
+        monthly.show_monthly_totals = true;
+        monthly.monthly_block_qty = Some(300);
+        monthly.stale = true;
+        monthly.consecutive_failures = 2;
+
+        let html = render_summary_html(
+            "📅 月度节点简报",
+            SummaryPeriod::Daily,
+            "2026-04-01",
+            &[monthly, summary_detail("节点A")],
+        );
+
+        assert!(html.contains("<h2>📅 月度节点简报</h2>"));
+        assert!(html.contains("<th>当天出块</th><th>当天系统奖励</th><th>上月总出块</th>"));
+        assert!(html.contains(
+            "<tr><td>&lt;节点B&gt;</td><td>3</td><td>123</td><td>45.6</td><td>12</td><td>5.6</\
+             td><td>300</td><td>-</td><td>缓存数据（连续失败 2 次）</td></tr>"
+        ));
+        assert!(html.find("&lt;节点B&gt;") < html.find("节点A"));
+    }
+
+    #[tokio::test]
+    async fn test_summary_email_is_delivered_through_smtp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("listener should bind");
+        let port = listener.local_addr().expect("listener has an address").port().to_string();
+        let server = tokio::spawn(run_smtp_stand_in(listener));
+        let backend = create_test_backend(&[
+            "--smtp-port",
+            &port,
+            "--email-to",
+            "ops@example.com",
+            "--email-route",
+            "daily=boss@example.com",
+        ]);
+
+        backend.send(&summary_notification(vec![summary_detail("节点A")])).await;
+        let transcript = server.await.expect("stand-in should finish").join("\n");
+
+        assert!(transcript.contains("MAIL FROM:<bot@example.com>"));
+        assert!(transcript.contains("RCPT TO:<boss@example.com>"));
+        assert!(!transcript.contains("RCPT TO:<ops@example.com>"));
+        assert!(transcript.contains("multipart/alternative"));
+        assert!(transcript.contains("text/html"));
+    }
+}
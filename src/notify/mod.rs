@@ -0,0 +1,4196 @@
+mod commands;
+mod damping;
+mod email;
+mod outbox;
+mod preview;
+mod robot;
+mod state_store;
+mod telegram;
+mod template;
+mod webhook;
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    fmt,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use chrono::{
+    DateTime,
+    Datelike,
+    Local,
+    NaiveDate,
+    Timelike,
+};
+use futures::future::BoxFuture;
+use log::{
+    debug,
+    warn,
+};
+use serde::Serialize;
+
+pub(crate) use self::{
+    commands::{
+        parse_bot_command,
+        render_mute_reply,
+        render_nodes_reply,
+        render_status_reply,
+        render_unmute_reply,
+        BotCommand,
+        BOT_HELP_TEXT,
+    },
+    outbox::{
+        DeliveryRecord,
+        DeliveryStatus,
+    },
+    preview::run as run_template_command,
+    telegram::TelegramCommandBot,
+};
+use self::{
+    damping::{
+        ConnectionDamping,
+        DampingPolicy,
+        FailureDecision,
+    },
+    email::EmailBackend,
+    robot::RobotBackend,
+    state_store::NotificationStateStore,
+    telegram::TelegramBackend,
+    template::{
+        Template,
+        TemplateContext,
+    },
+    webhook::WebhookBackend,
+};
+use crate::{
+    collect::{
+        annualized_yield,
+        AlertSeverity,
+        AlertState,
+        AlertTransition,
+        DailyNodeSummaryDetail,
+        DelegatorChange,
+        DelegatorChangeKind,
+        EpochProduction,
+        NodeDetailChange,
+        NodeDetailChangeKind,
+        ProductionDrop,
+        RankTargetGap,
+        SummaryPeriod,
+        WatchedValidator,
+    },
+    error::{
+        ChaindashError,
+        Result,
+    },
+    opts::{
+        NotifyEvent,
+        Opts,
+        QuietHours,
+        TelegramParseMode,
+    },
+};
+
+const MESSAGE_PREFIX: &str = "[chaindash]";
+const DEFAULT_CONNECTION_FAILED_TEMPLATE: &str =
+    "🚨 节点连接异常\n🔹 节点：{node}\n📝 原因：{reason}";
+const DEFAULT_CONNECTION_RECOVERED_TEMPLATE: &str = "✅ 节点连接已恢复\n🔹 节点：{node}";
+const DEFAULT_CONNECTION_REPEAT_TEMPLATE: &str =
+    "⏰ 节点连接仍未恢复\n🔹 节点：{node}\n⏱️ 已持续：{duration}\n📝 原因：{reason}";
+const DEFAULT_CONNECTION_FLAPPING_TEMPLATE: &str =
+    "🔁 节点连接频繁抖动\n🔹 节点：{node}\n🔄 {window}内断开 {count} 次\n⚠️ 最近原因：{reason}";
+const DEFAULT_CONNECTION_ESCALATED_TEMPLATE: &str = "🚨【严重】节点连接长时间未恢复\n🔹 \
+                                                     节点：{node}\n⏱️ 已持续：{duration}\n📝 \
+                                                     原因：{reason}\n⚠️ 请立即处理";
+const DEFAULT_RANKING_CHANGED_TEMPLATE: &str =
+    "{icon} 节点排名变动\n🔹 节点：{node}\n📍 排名：{previous} → {current}（{delta_text}）";
+const DEFAULT_QUIET_SUMMARY_TEMPLATE: &str = "🌙 静默期摘要\n🧾 共 {count} 条\n{details}";
+/// Written out with the loop and filters rather than `{details}`, which is
+/// kept only for custom templates that still use it.
+const DEFAULT_DAILY_SUMMARY_TEMPLATE: &str = concat!(
+    "{title}（{date}）\n🧾 节点数：{count}\n",
+    "{% for node in nodes %}{% if not loop.first %}\n\n{% endif %}",
+    "🔹 {node.name}\n  🧱 累计出块：{node.blocks}\n  💰 累计系统奖励：{node.rewards}",
+    "{% if period == daily %}",
+    "\n  📅 当天出块：{node.daily_blocks | default(\"-\")}{% if node.span_days > 1 %}",
+    "{% if node.daily_blocks >= 0 %}（近 {node.span_days} 日平均）{% endif %}{% endif %}",
+    "\n  🎁 当天系统奖励：{node.daily_rewards | default(\"-\")}{% if node.span_days > 1 %}",
+    "{% if node.daily_blocks >= 0 %}（近 {node.span_days} 日平均）{% endif %}{% endif %}",
+    "{% else %}",
+    "\n  📅 周期出块（{node.span_days} 日）：{node.daily_blocks | default(\"-\")}",
+    "\n  🎁 周期系统奖励（{node.span_days} 日）：{node.daily_rewards | default(\"-\")}",
+    "{% endif %}",
+    "{% if node.show_monthly %}",
+    "\n  🗓️ 上月总出块：{node.monthly_blocks | default(\"-\")}",
+    "\n  🏆 上月总系统奖励：{node.monthly_rewards | default(\"-\")}",
+    "{% endif %}",
+    "{% if node.estimated_daily_reward >= 0 %}",
+    "\n  📈 预估日收益：{node.estimated_daily_reward}",
+    "（日化 {node.daily_yield | percent(4) | default(\"-\")}）",
+    "\n  💹 预估年化：验证人 {node.validator_apy | percent | default(\"-\")}",
+    "｜委托人 {node.delegator_apy | percent | default(\"-\")}",
+    "{% endif %}",
+    "{% if node.stale %}\n  ⚠️ 数据状态：缓存数据",
+    "{% if node.failures > 0 %}（连续失败 {node.failures} 次）{% endif %}{% endif %}",
+    "{% endfor %}",
+    "{% if not nodes %}📭 暂无可用节点详情{% endif %}",
+    "{% if watchlist %}\n\n👀 关注节点{% for validator in watchlist %}",
+    "\n• {validator.name} #{% if validator.ranking > 0 %}{validator.ranking}{% else %}-{% endif %}",
+    "｜质押 {validator.stake}｜佣金 {validator.commission | round(2)}%",
+    "｜出块率 {validator.block_rate | default(\"-\")}",
+    "{% endfor %}{% endif %}",
+);
+const DEFAULT_PRODUCTION_LOW_TEMPLATE: &str = "⚠️ 节点出块不足\n🔹 节点：{node}\n🧮 \
+                                               Epoch：{epoch}\n🧱 出块：{produced} / \
+                                               {expected}（{ratio}）\n📉 阈值：{threshold}";
+const DEFAULT_PRODUCTION_DROP_TEMPLATE: &str =
+    "📉 节点出块骤降\n🔹 节点：{node}\n🕒 时段：{hour}\n🧱 出块：{blocks}\n📊 近 {trailing_hours} \
+     小时均值：{average}\n📉 阈值：{threshold}";
+const DEFAULT_STAKE_MARGIN_TEMPLATE: &str = "⚠️ 质押安全边际不足\n🔹 节点：{node}\n📍 \
+                                             排名：{ranking}\n🎯 目标排名：{target}\n🛡️ \
+                                             安全边际：{margin} LAT\n📉 阈值：{threshold} LAT";
+const DEFAULT_COMMISSION_CHANGED_TEMPLATE: &str =
+    "💼 节点佣金比例变更\n🔹 节点：{node}\n📝 变更：{previous} → {current}\n🕒 时间：{time}";
+const DEFAULT_BENEFIT_ADDRESS_CHANGED_TEMPLATE: &str =
+    "🚨【严重】节点收益地址变更\n🔹 节点：{node}\n📝 原地址：{previous}\n🆕 新地址：{current}\n🕒 \
+     时间：{time}\n⚠️ 如非本人操作，请立即检查节点密钥安全";
+const DEFAULT_DELEGATOR_CHANGED_TEMPLATE: &str = "💰 大额委托变动\n🔹 节点：{node}\n👤 \
+                                                  委托人：{address}\n📝 {action}：{previous} → \
+                                                  {current} LAT（{delta}）\n🕒 时间：{time}";
+const DEFAULT_ALERT_FIRING_TEMPLATE: &str = "🔔 告警触发：{rule}\n🔹 对象：{target}\n📈 \
+                                             当前值：{value}\n📏 条件：{condition}\n🏷️ \
+                                             级别：{severity}";
+const DEFAULT_ALERT_RESOLVED_TEMPLATE: &str =
+    "✅ 告警已恢复：{rule}\n🔹 对象：{target}\n📈 当前值：{value}\n⏱️ 持续：{duration}";
+const QUIET_SUMMARY_PREVIEW_LIMIT: usize = 3;
+/// How often an unchanged connection state or ranking is written back, so
+/// it is not expired on restart while it is still being observed.
+const STATE_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationEventKind {
+    ConnectionFailed,
+    ConnectionRecovered,
+    /// A connection failure unresolved for `--notify-escalate-after-minutes`.
+    ConnectionEscalated,
+    /// Repeated up/down cycles collapsed into one notification.
+    ConnectionFlapping,
+    RankingChanged,
+    DailySummary,
+    BlockProductionLow,
+    ProductionDrop,
+    StakeMarginLow,
+    CommissionChanged,
+    BenefitAddressChanged,
+    DelegatorChanged,
+    /// An `--alert-rules` series started firing; severity comes from the rule.
+    AlertFiring,
+    AlertResolved,
+    /// Digest of events held back during quiet hours.
+    QuietSummary,
+}
+
+impl NotificationEventKind {
+    const ALL: [Self; 15] = [
+        Self::ConnectionFailed,
+        Self::ConnectionRecovered,
+        Self::ConnectionEscalated,
+        Self::ConnectionFlapping,
+        Self::RankingChanged,
+        Self::DailySummary,
+        Self::BlockProductionLow,
+        Self::ProductionDrop,
+        Self::StakeMarginLow,
+        Self::CommissionChanged,
+        Self::BenefitAddressChanged,
+        Self::DelegatorChanged,
+        Self::AlertFiring,
+        Self::AlertResolved,
+        Self::QuietSummary,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Stable identifier sent to machine-readable backends.
+    fn name(self) -> &'static str {
+        match self {
+            Self::ConnectionFailed => "connection_failed",
+            Self::ConnectionRecovered => "connection_recovered",
+            Self::ConnectionEscalated => "connection_escalated",
+            Self::ConnectionFlapping => "connection_flapping",
+            Self::RankingChanged => "ranking_changed",
+            Self::DailySummary => "daily_summary",
+            Self::BlockProductionLow => "production_low",
+            Self::ProductionDrop => "production_drop",
+            Self::StakeMarginLow => "stake_margin_low",
+            Self::CommissionChanged => "commission_changed",
+            Self::BenefitAddressChanged => "benefit_address_changed",
+            Self::DelegatorChanged => "delegator_changed",
+            Self::AlertFiring => "alert_firing",
+            Self::AlertResolved => "alert_resolved",
+            Self::QuietSummary => "quiet_summary",
+        }
+    }
+
+    fn severity(self) -> NotificationSeverity {
+        match self {
+            Self::BenefitAddressChanged | Self::ConnectionEscalated => {
+                NotificationSeverity::Critical
+            },
+            Self::ConnectionFailed
+            | Self::ConnectionFlapping
+            | Self::BlockProductionLow
+            | Self::ProductionDrop
+            | Self::StakeMarginLow
+            | Self::AlertFiring => NotificationSeverity::Warning,
+            Self::ConnectionRecovered
+            | Self::RankingChanged
+            | Self::DailySummary
+            | Self::CommissionChanged
+            | Self::DelegatorChanged
+            | Self::AlertResolved
+            | Self::QuietSummary => NotificationSeverity::Info,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum NotificationSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NotificationFilter {
+    connection_failed: bool,
+    connection_recovered: bool,
+    connection_flapping: bool,
+    ranking_changed: bool,
+    daily_summary: bool,
+    block_production_low: bool,
+    production_drop: bool,
+    stake_margin_low: bool,
+    commission_changed: bool,
+    benefit_address_changed: bool,
+    delegator_changed: bool,
+    alert_firing: bool,
+    alert_resolved: bool,
+}
+
+impl NotificationFilter {
+    fn all() -> Self {
+        Self {
+            connection_failed: true,
+            connection_recovered: true,
+            connection_flapping: true,
+            ranking_changed: true,
+            daily_summary: true,
+            block_production_low: true,
+            production_drop: true,
+            stake_margin_low: true,
+            commission_changed: true,
+            benefit_address_changed: true,
+            delegator_changed: true,
+            alert_firing: true,
+            alert_resolved: true,
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            connection_failed: false,
+            connection_recovered: false,
+            connection_flapping: false,
+            ranking_changed: false,
+            daily_summary: false,
+            block_production_low: false,
+            production_drop: false,
+            stake_margin_low: false,
+            commission_changed: false,
+            benefit_address_changed: false,
+            delegator_changed: false,
+            alert_firing: false,
+            alert_resolved: false,
+        }
+    }
+
+    fn from_opts(events: &[NotifyEvent]) -> Self {
+        if events.is_empty() {
+            return Self::all();
+        }
+
+        let mut filter = Self::none();
+        for event in events {
+            match event {
+                NotifyEvent::All => filter = Self::all(),
+                NotifyEvent::Connection => {
+                    filter.connection_failed = true;
+                    filter.connection_recovered = true;
+                    filter.connection_flapping = true;
+                },
+                NotifyEvent::ConnectionFailed => filter.connection_failed = true,
+                NotifyEvent::ConnectionRecovered => filter.connection_recovered = true,
+                NotifyEvent::ConnectionFlapping => filter.connection_flapping = true,
+                NotifyEvent::Ranking | NotifyEvent::RankingChanged => {
+                    filter.ranking_changed = true;
+                },
+                NotifyEvent::Daily | NotifyEvent::DailySummary => {
+                    filter.daily_summary = true;
+                },
+                NotifyEvent::Production => {
+                    filter.block_production_low = true;
+                    filter.production_drop = true;
+                },
+                NotifyEvent::ProductionLow => filter.block_production_low = true,
+                NotifyEvent::ProductionDrop => filter.production_drop = true,
+                NotifyEvent::Stake | NotifyEvent::StakeMargin => {
+                    filter.stake_margin_low = true;
+                },
+                NotifyEvent::CommissionChanged => filter.commission_changed = true,
+                NotifyEvent::BenefitAddressChanged => {
+                    filter.benefit_address_changed = true;
+                },
+                NotifyEvent::Delegator | NotifyEvent::DelegatorChanged => {
+                    filter.delegator_changed = true;
+                },
+                NotifyEvent::Alert => {
+                    filter.alert_firing = true;
+                    filter.alert_resolved = true;
+                },
+                NotifyEvent::AlertFiring => filter.alert_firing = true,
+                NotifyEvent::AlertResolved => filter.alert_resolved = true,
+            }
+        }
+
+        filter
+    }
+
+    fn allows(
+        self,
+        event: NotificationEventKind,
+    ) -> bool {
+        match event {
+            // Escalations follow up on failures, so they share the filter.
+            NotificationEventKind::ConnectionFailed
+            | NotificationEventKind::ConnectionEscalated => self.connection_failed,
+            NotificationEventKind::ConnectionRecovered => self.connection_recovered,
+            NotificationEventKind::ConnectionFlapping => self.connection_flapping,
+            NotificationEventKind::RankingChanged => self.ranking_changed,
+            NotificationEventKind::DailySummary => self.daily_summary,
+            NotificationEventKind::BlockProductionLow => self.block_production_low,
+            NotificationEventKind::ProductionDrop => self.production_drop,
+            NotificationEventKind::StakeMarginLow => self.stake_margin_low,
+            NotificationEventKind::CommissionChanged => self.commission_changed,
+            NotificationEventKind::BenefitAddressChanged => self.benefit_address_changed,
+            NotificationEventKind::DelegatorChanged => self.delegator_changed,
+            NotificationEventKind::AlertFiring => self.alert_firing,
+            NotificationEventKind::AlertResolved => self.alert_resolved,
+            NotificationEventKind::QuietSummary => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NotificationTemplates {
+    connection_failed: Template,
+    connection_recovered: Template,
+    connection_repeat: Template,
+    connection_escalated: Template,
+    connection_flapping: Template,
+    ranking_changed: Template,
+    quiet_summary: Template,
+    daily_summary: Template,
+    production_low: Template,
+    production_drop: Template,
+    stake_margin: Template,
+    commission_changed: Template,
+    benefit_address_changed: Template,
+    delegator_changed: Template,
+    alert_firing: Template,
+    alert_resolved: Template,
+}
+
+impl NotificationTemplates {
+    fn from_opts(opts: &Opts) -> Result<Self> {
+        Ok(Self {
+            connection_failed: configured_template(
+                "--telegram-template-connection-failed",
+                opts.telegram_template_connection_failed.as_deref(),
+                DEFAULT_CONNECTION_FAILED_TEMPLATE,
+            )?,
+            connection_recovered: configured_template(
+                "--telegram-template-connection-recovered",
+                opts.telegram_template_connection_recovered.as_deref(),
+                DEFAULT_CONNECTION_RECOVERED_TEMPLATE,
+            )?,
+            connection_repeat: configured_template(
+                "--telegram-template-connection-repeat",
+                opts.telegram_template_connection_repeat.as_deref(),
+                DEFAULT_CONNECTION_REPEAT_TEMPLATE,
+            )?,
+            connection_escalated: configured_template(
+                "--telegram-template-connection-escalated",
+                opts.telegram_template_connection_escalated.as_deref(),
+                DEFAULT_CONNECTION_ESCALATED_TEMPLATE,
+            )?,
+            connection_flapping: configured_template(
+                "--telegram-template-connection-flapping",
+                opts.telegram_template_connection_flapping.as_deref(),
+                DEFAULT_CONNECTION_FLAPPING_TEMPLATE,
+            )?,
+            ranking_changed: configured_template(
+                "--telegram-template-ranking-changed",
+                opts.telegram_template_ranking_changed.as_deref(),
+                DEFAULT_RANKING_CHANGED_TEMPLATE,
+            )?,
+            quiet_summary: configured_template(
+                "--telegram-template-quiet-summary",
+                opts.telegram_template_quiet_summary.as_deref(),
+                DEFAULT_QUIET_SUMMARY_TEMPLATE,
+            )?,
+            daily_summary: configured_template(
+                "--telegram-template-daily-summary",
+                opts.telegram_template_daily_summary.as_deref(),
+                DEFAULT_DAILY_SUMMARY_TEMPLATE,
+            )?,
+            production_low: configured_template(
+                "--telegram-template-production-low",
+                opts.telegram_template_production_low.as_deref(),
+                DEFAULT_PRODUCTION_LOW_TEMPLATE,
+            )?,
+            production_drop: configured_template(
+                "--telegram-template-production-drop",
+                opts.telegram_template_production_drop.as_deref(),
+                DEFAULT_PRODUCTION_DROP_TEMPLATE,
+            )?,
+            stake_margin: configured_template(
+                "--telegram-template-stake-margin",
+                opts.telegram_template_stake_margin.as_deref(),
+                DEFAULT_STAKE_MARGIN_TEMPLATE,
+            )?,
+            commission_changed: configured_template(
+                "--telegram-template-commission-changed",
+                opts.telegram_template_commission_changed.as_deref(),
+                DEFAULT_COMMISSION_CHANGED_TEMPLATE,
+            )?,
+            benefit_address_changed: configured_template(
+                "--telegram-template-benefit-address-changed",
+                opts.telegram_template_benefit_address_changed.as_deref(),
+                DEFAULT_BENEFIT_ADDRESS_CHANGED_TEMPLATE,
+            )?,
+            delegator_changed: configured_template(
+                "--telegram-template-delegator-changed",
+                opts.telegram_template_delegator_changed.as_deref(),
+                DEFAULT_DELEGATOR_CHANGED_TEMPLATE,
+            )?,
+            alert_firing: configured_template(
+                "--telegram-template-alert-firing",
+                opts.telegram_template_alert_firing.as_deref(),
+                DEFAULT_ALERT_FIRING_TEMPLATE,
+            )?,
+            alert_resolved: configured_template(
+                "--telegram-template-alert-resolved",
+                opts.telegram_template_alert_resolved.as_deref(),
+                DEFAULT_ALERT_RESOLVED_TEMPLATE,
+            )?,
+        })
+    }
+}
+
+/// Follow-ups for connection failures that stay unresolved.
+#[derive(Debug, Clone, Default)]
+struct EscalationPolicy {
+    /// Reminder interval while a connection stays down.
+    repeat_interval: Option<Duration>,
+    /// Unresolved time after which the failure is escalated once.
+    escalate_after: Option<Duration>,
+    /// Telegram chats that replace the default ones for escalations.
+    chat_ids: Vec<String>,
+}
+
+impl EscalationPolicy {
+    fn from_opts(opts: &Opts) -> Self {
+        let minutes = |value: u64| (value > 0).then(|| Duration::from_secs(value * 60));
+
+        Self {
+            repeat_interval: minutes(opts.notify_repeat_minutes),
+            escalate_after: minutes(opts.notify_escalate_after_minutes),
+            chat_ids: trimmed_values(&opts.notify_escalation_chat_id),
+        }
+    }
+}
+
+/// Filtering, quiet hours and rate limiting applied once, before a
+/// notification is fanned out to the backends.
+#[derive(Debug, Clone)]
+struct NotificationPolicy {
+    enabled_events: NotificationFilter,
+    quiet_hours: Option<QuietHours>,
+    rate_limit: Duration,
+    escalation: EscalationPolicy,
+    damping: DampingPolicy,
+}
+
+impl NotificationPolicy {
+    fn from_opts(opts: &Opts) -> Self {
+        Self {
+            enabled_events: NotificationFilter::from_opts(&opts.notify_events),
+            quiet_hours: opts.quiet_hours,
+            rate_limit: Duration::from_secs(opts.notify_rate_limit_seconds),
+            escalation: EscalationPolicy::from_opts(opts),
+            damping: DampingPolicy::from_opts(opts),
+        }
+    }
+
+    fn is_quiet_time_now(&self) -> bool {
+        let Some(quiet_hours) = self.quiet_hours else {
+            return false;
+        };
+
+        let now = Local::now();
+        let minutes_since_midnight = (now.hour() * 60 + now.minute()) as u16;
+
+        quiet_hours.contains_minutes_since_midnight(minutes_since_midnight)
+    }
+
+    #[cfg(test)]
+    fn is_quiet_time_at(
+        &self,
+        minutes_since_midnight: u16,
+    ) -> bool {
+        self.quiet_hours.is_some_and(|quiet_hours| {
+            quiet_hours.contains_minutes_since_midnight(minutes_since_midnight)
+        })
+    }
+}
+
+/// Structured counterpart of a rendered message, for backends that forward
+/// machine-readable data.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum NotificationData {
+    Connection {
+        node: String,
+        url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    Flapping {
+        node: String,
+        url: String,
+        reason: String,
+        cycles: usize,
+        window_minutes: u64,
+    },
+    /// A reminder or escalation for a connection that is still down.
+    Incident {
+        node: String,
+        url: String,
+        reason: String,
+        unresolved_minutes: u64,
+        reminder: u32,
+        escalated: bool,
+    },
+    Ranking {
+        node_id: String,
+        node: String,
+        previous: i32,
+        current: i32,
+    },
+    Summary {
+        period: SummaryPeriod,
+        date: String,
+        late: bool,
+        node_count: usize,
+        /// Rows for channels that lay the report out as a table.
+        #[serde(skip)]
+        nodes: Vec<DailyNodeSummaryDetail>,
+    },
+    Production {
+        node_id: String,
+        node: String,
+        epoch: u64,
+        produced: u64,
+        expected: u64,
+        threshold: f64,
+    },
+    ProductionDrop {
+        node_id: String,
+        node: String,
+        hour_start: String,
+        blocks: u64,
+        trailing_average: f64,
+        trailing_hours: usize,
+        threshold: f64,
+    },
+    StakeMargin {
+        node_id: String,
+        node: String,
+        ranking: i64,
+        target_rank: u32,
+        margin: f64,
+        threshold: f64,
+    },
+    NodeDetail {
+        node_id: String,
+        node: String,
+        previous: String,
+        current: String,
+        changed_at: String,
+    },
+    Delegator {
+        node_id: String,
+        node: String,
+        address: String,
+        previous: f64,
+        current: f64,
+        changed_at: String,
+    },
+    Alert {
+        rule: String,
+        metric: &'static str,
+        target: String,
+        state: &'static str,
+        value: String,
+        condition: String,
+        duration_seconds: u64,
+    },
+    QuietSummary {
+        count: usize,
+        details: String,
+    },
+}
+
+impl NotificationData {
+    /// Name and, when known, ID of the node a notification is about, matched
+    /// against `--telegram-route` nodes. Alerts report their series target
+    /// as the name.
+    fn node(&self) -> Option<(&str, Option<&str>)> {
+        match self {
+            Self::Connection { node, .. }
+            | Self::Flapping { node, .. }
+            | Self::Incident { node, .. } => Some((node, None)),
+            Self::Ranking { node_id, node, .. }
+            | Self::Production { node_id, node, .. }
+            | Self::ProductionDrop { node_id, node, .. }
+            | Self::StakeMargin { node_id, node, .. }
+            | Self::NodeDetail { node_id, node, .. }
+            | Self::Delegator { node_id, node, .. } => Some((node, Some(node_id))),
+            Self::Alert { target, .. } => Some((target, None)),
+            Self::Summary { .. } | Self::QuietSummary { .. } => None,
+        }
+    }
+}
+
+/// A notification that passed the shared policy and is ready for delivery.
+#[derive(Debug, Clone)]
+struct Notification {
+    kind: NotificationEventKind,
+    /// Defaults to the severity of `kind`; alert rules set their own.
+    severity: NotificationSeverity,
+    subject: String,
+    text: String,
+    /// The template and values `text` was rendered from; `None` for plain
+    /// text.
+    template: Option<Template>,
+    context: TemplateContext,
+    data: NotificationData,
+    /// Telegram chats that replace the default ones and any routes when not
+    /// empty.
+    chat_ids: Vec<String>,
+}
+
+impl Notification {
+    fn new(
+        kind: NotificationEventKind,
+        subject: &str,
+        message: impl Into<RenderedMessage>,
+        data: NotificationData,
+    ) -> Self {
+        let RenderedMessage {
+            text,
+            template,
+            context,
+        } = message.into();
+        Self {
+            kind,
+            severity: kind.severity(),
+            subject: subject.to_string(),
+            text,
+            template,
+            context,
+            data,
+            chat_ids: Vec::new(),
+        }
+    }
+
+    /// Renders this notification for Telegram in `mode`, with a route's own
+    /// template when it has one. Plain-text notifications keep their text,
+    /// so a route template does not apply to them.
+    fn render_for(
+        &self,
+        route_template: Option<&Template>,
+        mode: TelegramParseMode,
+    ) -> String {
+        let Some(template) = &self.template else {
+            return template::escape(&self.text, mode).into_owned();
+        };
+        match route_template {
+            Some(route_template) => route_template.render(&self.context, mode),
+            None if mode == TelegramParseMode::Plain => self.text.clone(),
+            None => template.render(&self.context, mode),
+        }
+    }
+
+    /// Critical notifications are delivered even during quiet hours.
+    fn is_critical(&self) -> bool {
+        self.severity == NotificationSeverity::Critical
+    }
+}
+
+/// A channel notifications are delivered through. Backends only report
+/// their own failures; filtering, quiet hours and rate limiting happen in
+/// `Notifier`.
+trait NotifierBackend: fmt::Debug + Send + Sync {
+    fn deliver<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, ()>;
+
+    /// Retry queued deliveries that are due. Backends without an outbox
+    /// have nothing to do.
+    fn flush_outbox(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+
+    /// The latest delivery audit rows, newest first.
+    fn recent_deliveries(
+        &self,
+        _limit: usize,
+    ) -> Vec<DeliveryRecord> {
+        Vec::new()
+    }
+}
+
+fn trimmed_option(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+fn trimmed_values(values: &[String]) -> Vec<String> {
+    let mut trimmed = Vec::new();
+
+    for value in values {
+        let value = value.trim();
+        if value.is_empty() || trimmed.iter().any(|existing| existing == value) {
+            continue;
+        }
+
+        trimmed.push(value.to_string());
+    }
+
+    trimmed
+}
+
+/// HTTP channels only accept `http://` and `https://` URLs; `channel` names
+/// the channel in the error.
+fn validate_http_urls(
+    channel: &str,
+    urls: &[String],
+) -> Result<()> {
+    match urls.iter().find(|url| !url.starts_with("http://") && !url.starts_with("https://")) {
+        Some(url) => Err(ChaindashError::Other(format!(
+            "invalid {channel} url: {url}. expected an http:// or https:// url"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Webhook URLs often carry tokens in the query string; keep them out of
+/// the log.
+fn display_url(url: &str) -> &str {
+    url.split_once('?').map_or(url, |(base, _)| base)
+}
+
+/// Splits a rendered message into its first line, used as a title by
+/// channels with a separate title field, and the remaining lines.
+fn split_title(text: &str) -> (&str, &str) {
+    text.split_once('\n').unwrap_or((text, ""))
+}
+
+fn normalize_template(template: &str) -> String {
+    let mut normalized = String::with_capacity(template.len());
+    let mut chars = template.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            normalized.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => normalized.push('\n'),
+            Some('r') => normalized.push('\r'),
+            Some('t') => normalized.push('\t'),
+            Some('\\') => normalized.push('\\'),
+            Some(other) => {
+                normalized.push('\\');
+                normalized.push(other);
+            },
+            None => normalized.push('\\'),
+        }
+    }
+
+    normalized
+}
+
+/// Parses a user template given to `option`. `@PATH` reads the template
+/// from a file as written; inline templates may spell newlines as `\n`.
+fn load_template(
+    option: &str,
+    value: &str,
+) -> Result<Template> {
+    let source = match value.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path.trim()).map_err(|err| {
+            ChaindashError::Other(format!("failed to read template {path} for {option}: {err}"))
+        })?,
+        None => normalize_template(value),
+    };
+
+    Template::parse(&source)
+        .map_err(|err| ChaindashError::Other(format!("invalid template for {option}: {err}")))
+}
+
+fn configured_template(
+    option: &str,
+    value: Option<&str>,
+    default: &'static str,
+) -> Result<Template> {
+    match value {
+        Some(value) => load_template(option, value),
+        None => Ok(Template::builtin(default)),
+    }
+}
+
+fn display_node_name(node_name: &str) -> &str {
+    if node_name.trim().is_empty() {
+        "未命名节点"
+    } else {
+        node_name
+    }
+}
+
+fn format_reward_value(value: f64) -> String {
+    let mut rendered = format!("{value:.4}");
+    while rendered.contains('.') && rendered.ends_with('0') {
+        rendered.pop();
+    }
+    if rendered.ends_with('.') {
+        rendered.pop();
+    }
+    rendered
+}
+
+fn format_optional_daily_count(value: Option<u64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_optional_reward_value(value: Option<f64>) -> String {
+    value.map(format_reward_value).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_ratio_percent(ratio: f64) -> String {
+    format!("{:.2}%", ratio * 100.0)
+}
+
+fn format_optional_ratio_percent(ratio: Option<f64>) -> String {
+    ratio.map(format_ratio_percent).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_optional_daily_yield(ratio: Option<f64>) -> String {
+    ratio.map(|ratio| format!("{:.4}%", ratio * 100.0)).unwrap_or_else(|| "-".to_string())
+}
+
+/// Estimated daily rewards and the stakes that earned them, summed over the
+/// nodes whose reward history already yields a daily estimate.
+#[derive(Debug, Default)]
+struct RewardEstimateTotals {
+    nodes: usize,
+    daily_reward: f64,
+    validator_reward: f64,
+    delegator_reward: f64,
+    total_stake: f64,
+    own_stake: f64,
+    delegated_stake: f64,
+}
+
+impl RewardEstimateTotals {
+    fn from_details<'a>(details: impl IntoIterator<Item = &'a DailyNodeSummaryDetail>) -> Self {
+        let mut totals = Self::default();
+        for detail in details {
+            let Some(daily_reward) = detail.estimated_daily_reward else {
+                continue;
+            };
+            let delegator_share = detail.reward_per / 100.0;
+
+            totals.nodes += 1;
+            totals.daily_reward += daily_reward;
+            totals.validator_reward += daily_reward * (1.0 - delegator_share);
+            totals.delegator_reward += daily_reward * delegator_share;
+            totals.total_stake += detail.total_value;
+            totals.own_stake += (detail.total_value - detail.delegate_value).max(0.0);
+            totals.delegated_stake += detail.delegate_value;
+        }
+        totals
+    }
+
+    fn daily_reward(&self) -> Option<f64> {
+        (self.nodes > 0).then_some(self.daily_reward)
+    }
+
+    fn daily_yield(&self) -> Option<f64> {
+        (self.nodes > 0 && self.total_stake > 0.0).then(|| self.daily_reward / self.total_stake)
+    }
+
+    fn validator_apy(&self) -> Option<f64> {
+        if self.nodes == 0 {
+            return None;
+        }
+        annualized_yield(self.validator_reward, self.own_stake)
+    }
+
+    fn delegator_apy(&self) -> Option<f64> {
+        if self.nodes == 0 {
+            return None;
+        }
+        annualized_yield(self.delegator_reward, self.delegated_stake)
+    }
+}
+
+/// A scheduled node summary and where it goes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SummaryReport<'a> {
+    /// Position of the `--report-schedule` entry, keeping schedules that run
+    /// on the same date apart in the rate limiter.
+    pub schedule_index: usize,
+    pub period: SummaryPeriod,
+    pub date: &'a str,
+    pub node_details: &'a [DailyNodeSummaryDetail],
+    pub watched_validators: &'a [WatchedValidator],
+    /// Sent after startup because the process was down when it was due.
+    pub late: bool,
+    /// Overrides `--telegram-chat-id` when not empty.
+    pub chat_ids: &'a [String],
+}
+
+/// `late` marks a summary sent after startup because the process was down at
+/// midnight.
+fn resolve_daily_summary_title(
+    period: SummaryPeriod,
+    date: &str,
+    node_details: &[DailyNodeSummaryDetail],
+    late: bool,
+) -> &'static str {
+    let monthly = match period {
+        SummaryPeriod::Daily => {
+            node_details.iter().any(|detail| detail.show_monthly_totals)
+                || NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map(|date| date.day() == 1)
+                    .unwrap_or(false)
+        },
+        SummaryPeriod::Weekly => {
+            return if late {
+                "🗓️ 节点周报（补发）"
+            } else {
+                "🗓️ 节点周报"
+            };
+        },
+        SummaryPeriod::Monthly => true,
+    };
+
+    match (monthly, late) {
+        (true, false) => "📅 月度节点简报",
+        (true, true) => "📅 月度节点简报（补发）",
+        (false, false) => "📊 每日节点快照",
+        (false, true) => "📊 每日节点快照（补发）",
+    }
+}
+
+fn render_watchlist_section(watched_validators: &[WatchedValidator]) -> String {
+    let lines = watched_validators.iter().map(|validator| {
+        let ranking = if validator.ranking > 0 {
+            format!("#{}", validator.ranking)
+        } else {
+            "#-".to_string()
+        };
+        let block_rate = if validator.block_rate.is_empty() {
+            "-"
+        } else {
+            validator.block_rate.as_str()
+        };
+
+        format!(
+            "• {} {}｜质押 {}｜佣金 {:.2}%｜出块率 {}",
+            display_node_name(validator.display_name()),
+            ranking,
+            format_reward_value(validator.total_value),
+            validator.reward_per,
+            block_rate
+        )
+    });
+
+    std::iter::once("👀 关注节点".to_string()).chain(lines).collect::<Vec<_>>().join("\n")
+}
+
+/// Daily values averaged over missing snapshots are labelled as such.
+fn daily_span_suffix(detail: &DailyNodeSummaryDetail) -> String {
+    if detail.daily_span_days > 1 && detail.daily_block_qty.is_some() {
+        format!("（近 {} 日平均）", detail.daily_span_days)
+    } else {
+        String::new()
+    }
+}
+
+fn render_daily_summary_detail(
+    period: SummaryPeriod,
+    detail: &DailyNodeSummaryDetail,
+) -> String {
+    let mut lines = vec![
+        format!("🔹 {}", display_node_name(&detail.node_name)),
+        format!("  🧱 累计出块：{}", detail.block_qty),
+        format!("  💰 累计系统奖励：{}", format_reward_value(detail.reward_value)),
+    ];
+    if period == SummaryPeriod::Daily {
+        lines.push(format!(
+            "  📅 当天出块：{}{}",
+            format_optional_daily_count(detail.daily_block_qty),
+            daily_span_suffix(detail)
+        ));
+        lines.push(format!(
+            "  🎁 当天系统奖励：{}{}",
+            format_optional_reward_value(detail.daily_reward_value),
+            daily_span_suffix(detail)
+        ));
+    } else {
+        lines.push(format!(
+            "  📅 周期出块（{} 日）：{}",
+            detail.daily_span_days,
+            format_optional_daily_count(detail.daily_block_qty)
+        ));
+        lines.push(format!(
+            "  🎁 周期系统奖励（{} 日）：{}",
+            detail.daily_span_days,
+            format_optional_reward_value(detail.daily_reward_value)
+        ));
+    }
+
+    if detail.show_monthly_totals {
+        lines.push(format!(
+            "  🗓️ 上月总出块：{}",
+            format_optional_daily_count(detail.monthly_block_qty)
+        ));
+        lines.push(format!(
+            "  🏆 上月总系统奖励：{}",
+            format_optional_reward_value(detail.monthly_reward_value)
+        ));
+    }
+
+    let estimates = RewardEstimateTotals::from_details([detail]);
+    if let Some(daily_reward) = estimates.daily_reward() {
+        lines.push(format!(
+            "  📈 预估日收益：{}（日化 {}）",
+            format_reward_value(daily_reward),
+            format_optional_daily_yield(estimates.daily_yield())
+        ));
+        lines.push(format!(
+            "  💹 预估年化：验证人 {}｜委托人 {}",
+            format_optional_ratio_percent(estimates.validator_apy()),
+            format_optional_ratio_percent(estimates.delegator_apy())
+        ));
+    }
+
+    if detail.stale {
+        if detail.consecutive_failures > 0 {
+            lines.push(format!(
+                "  ⚠️ 数据状态：缓存数据（连续失败 {} 次）",
+                detail.consecutive_failures
+            ));
+        } else {
+            lines.push("  ⚠️ 数据状态：缓存数据".to_string());
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// The fields a daily summary template sees for each entry of `nodes`.
+fn daily_summary_node_context(
+    period: SummaryPeriod,
+    detail: &DailyNodeSummaryDetail,
+) -> TemplateContext {
+    let estimates = RewardEstimateTotals::from_details([detail]);
+
+    TemplateContext::default()
+        .with("name", display_node_name(&detail.node_name))
+        .with("node_id", detail.node_id.as_str())
+        .with("ranking", detail.ranking)
+        .with("blocks", detail.block_qty)
+        .with("rewards", detail.reward_value)
+        .with_optional("daily_blocks", detail.daily_block_qty)
+        .with_optional("daily_rewards", detail.daily_reward_value)
+        .with("span_days", detail.daily_span_days)
+        .with("show_monthly", detail.show_monthly_totals)
+        .with_optional("monthly_blocks", detail.monthly_block_qty)
+        .with_optional("monthly_rewards", detail.monthly_reward_value)
+        .with_optional("estimated_daily_reward", estimates.daily_reward())
+        .with_optional("daily_yield", estimates.daily_yield())
+        .with_optional("validator_apy", estimates.validator_apy())
+        .with_optional("delegator_apy", estimates.delegator_apy())
+        .with("stale", detail.stale)
+        .with("failures", detail.consecutive_failures)
+        .with("detail", render_daily_summary_detail(period, detail))
+}
+
+/// The fields a daily summary template sees for each entry of `watchlist`.
+fn watched_validator_context(validator: &WatchedValidator) -> TemplateContext {
+    TemplateContext::default()
+        .with("name", display_node_name(validator.display_name()))
+        .with("node_id", validator.node_id.as_str())
+        .with("ranking", validator.ranking)
+        .with("stake", validator.total_value)
+        .with("commission", validator.reward_per)
+        .with("block_rate", validator.block_rate.as_str())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionState {
+    Healthy,
+    Unhealthy,
+}
+
+/// An unhealthy connection and the follow-ups already sent for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConnectionIncident {
+    node_name: String,
+    node_url: String,
+    reason: String,
+    since: Instant,
+    reminders_sent: u32,
+    escalated: bool,
+}
+
+/// A reminder or escalation due for an unresolved connection failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IncidentFollowUp {
+    connection_key: String,
+    node_name: String,
+    node_url: String,
+    reason: String,
+    unresolved: Duration,
+    reminder: u32,
+    escalate: bool,
+}
+
+#[derive(Debug, Default)]
+struct QuietSummaryBucket {
+    count: usize,
+    names: Vec<String>,
+}
+
+impl QuietSummaryBucket {
+    fn record(
+        &mut self,
+        name: &str,
+    ) {
+        self.count += 1;
+
+        if self.names.len() >= QUIET_SUMMARY_PREVIEW_LIMIT
+            || self.names.iter().any(|existing| existing == name)
+        {
+            return;
+        }
+
+        self.names.push(name.to_string());
+    }
+
+    fn render_line(
+        &self,
+        label: &str,
+    ) -> Option<String> {
+        if self.count == 0 {
+            return None;
+        }
+
+        if self.names.is_empty() {
+            return Some(format!("{label} {} 次", self.count));
+        }
+
+        Some(format!("{label} {} 次：{}", self.count, self.names.join(", ")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QuietSummarySnapshot {
+    total_count: usize,
+    details: String,
+}
+
+#[derive(Debug, Default)]
+struct QuietSummaryBuffer {
+    total_count: usize,
+    connection_failed: QuietSummaryBucket,
+    connection_recovered: QuietSummaryBucket,
+    connection_flapping: QuietSummaryBucket,
+    ranking_changed: QuietSummaryBucket,
+    block_production_low: QuietSummaryBucket,
+    production_drop: QuietSummaryBucket,
+    stake_margin_low: QuietSummaryBucket,
+    commission_changed: QuietSummaryBucket,
+    delegator_changed: QuietSummaryBucket,
+    alert_firing: QuietSummaryBucket,
+    alert_resolved: QuietSummaryBucket,
+}
+
+impl QuietSummaryBuffer {
+    fn record(
+        &mut self,
+        event: NotificationEventKind,
+        subject: &str,
+    ) {
+        self.total_count += 1;
+
+        match event {
+            NotificationEventKind::ConnectionFailed => self.connection_failed.record(subject),
+            NotificationEventKind::ConnectionRecovered => self.connection_recovered.record(subject),
+            NotificationEventKind::ConnectionFlapping => self.connection_flapping.record(subject),
+            NotificationEventKind::RankingChanged => self.ranking_changed.record(subject),
+            NotificationEventKind::BlockProductionLow => self.block_production_low.record(subject),
+            NotificationEventKind::ProductionDrop => self.production_drop.record(subject),
+            NotificationEventKind::StakeMarginLow => self.stake_margin_low.record(subject),
+            NotificationEventKind::CommissionChanged => self.commission_changed.record(subject),
+            NotificationEventKind::DelegatorChanged => self.delegator_changed.record(subject),
+            NotificationEventKind::AlertFiring => self.alert_firing.record(subject),
+            NotificationEventKind::AlertResolved => self.alert_resolved.record(subject),
+            NotificationEventKind::DailySummary
+            | NotificationEventKind::ConnectionEscalated
+            | NotificationEventKind::BenefitAddressChanged
+            | NotificationEventKind::QuietSummary => {},
+        }
+    }
+
+    fn take_snapshot(&mut self) -> Option<QuietSummarySnapshot> {
+        let snapshot = std::mem::take(self);
+        snapshot.render_snapshot()
+    }
+
+    fn render_snapshot(self) -> Option<QuietSummarySnapshot> {
+        if self.total_count == 0 {
+            return None;
+        }
+
+        let mut lines = Vec::new();
+
+        if let Some(line) = self.connection_failed.render_line("连接失败") {
+            lines.push(line);
+        }
+        if let Some(line) = self.connection_recovered.render_line("连接恢复") {
+            lines.push(line);
+        }
+        if let Some(line) = self.connection_flapping.render_line("连接抖动") {
+            lines.push(line);
+        }
+        if let Some(line) = self.ranking_changed.render_line("排名变化") {
+            lines.push(line);
+        }
+        if let Some(line) = self.block_production_low.render_line("出块不足") {
+            lines.push(line);
+        }
+        if let Some(line) = self.production_drop.render_line("出块骤降") {
+            lines.push(line);
+        }
+        if let Some(line) = self.stake_margin_low.render_line("质押边际") {
+            lines.push(line);
+        }
+        if let Some(line) = self.commission_changed.render_line("佣金变更") {
+            lines.push(line);
+        }
+        if let Some(line) = self.delegator_changed.render_line("委托变动") {
+            lines.push(line);
+        }
+        if let Some(line) = self.alert_firing.render_line("告警触发") {
+            lines.push(line);
+        }
+        if let Some(line) = self.alert_resolved.render_line("告警恢复") {
+            lines.push(line);
+        }
+
+        Some(QuietSummarySnapshot {
+            total_count: self.total_count,
+            details: lines.join("\n"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RankingChange {
+    previous: i32,
+    current: i32,
+}
+
+#[derive(Debug, Default)]
+struct NotificationState {
+    connection_states: HashMap<String, ConnectionState>,
+    /// Open incidents of unhealthy connections, for reminders and escalation.
+    connection_incidents: HashMap<String, ConnectionIncident>,
+    /// Failure streaks not reported yet and recent up/down cycles.
+    connection_damping: ConnectionDamping,
+    last_rankings: HashMap<String, i32>,
+    production_alert_epochs: HashMap<String, u64>,
+    stake_margin_alerts: HashSet<String>,
+    production_drop_alerts: HashSet<String>,
+    last_sent_at: HashMap<String, Instant>,
+    /// When each persisted connection state, ranking or alert was last written.
+    refreshed_at: HashMap<String, Instant>,
+    quiet_summary: QuietSummaryBuffer,
+    /// Set by the `/mute` bot command; buffered like quiet hours until then.
+    muted_until: Option<Instant>,
+    /// Persists connection states, rankings, sent alerts, rate limits and the
+    /// quiet-hours buffer; `None` keeps them in memory only.
+    store: Option<NotificationStateStore>,
+}
+
+impl NotificationState {
+    /// Restore the state persisted in `--db-path`, falling back to an empty
+    /// in-memory state when the database can't be used.
+    fn restore(opts: &Opts) -> Self {
+        let store = match NotificationStateStore::open(&opts.db_path) {
+            Ok(store) => store,
+            Err(err) => {
+                warn!("Failed to open notification state at {}: {}", opts.db_path, err);
+                return Self::default();
+            },
+        };
+        let max_age = Duration::from_secs(opts.notify_state_max_age_hours * 60 * 60);
+        let mut state = store.load(max_age).unwrap_or_else(|err| {
+            warn!("Failed to restore notification state: {}", err);
+            Self::default()
+        });
+        state.store = Some(store);
+        state
+    }
+
+    fn persist(
+        &self,
+        write: impl FnOnce(&NotificationStateStore) -> Result<()>,
+    ) {
+        if let Some(Err(err)) = self.store.as_ref().map(write) {
+            warn!("Failed to persist notification state: {}", err);
+        }
+    }
+
+    /// Whether the persisted row for `key` should be written again although
+    /// its value did not change. Stored state expires by when it was last
+    /// seen, so a state that holds for days is kept.
+    fn refresh_due(
+        &mut self,
+        key: String,
+    ) -> bool {
+        if self.store.is_none() {
+            return false;
+        }
+
+        let now = Instant::now();
+        match self.refreshed_at.get(&key) {
+            Some(refreshed_at)
+                if now.saturating_duration_since(*refreshed_at) < STATE_REFRESH_INTERVAL =>
+            {
+                false
+            },
+            _ => {
+                self.refreshed_at.insert(key, now);
+                true
+            },
+        }
+    }
+
+    fn mark_connection_failed(
+        &mut self,
+        key: &str,
+    ) -> bool {
+        let changed = !matches!(
+            self.connection_states.insert(key.to_string(), ConnectionState::Unhealthy),
+            Some(ConnectionState::Unhealthy)
+        );
+        if self.refresh_due(format!("connection:{key}")) || changed {
+            self.persist(|store| store.save_connection_state(key, ConnectionState::Unhealthy));
+        }
+        changed
+    }
+
+    fn mark_connection_recovered(
+        &mut self,
+        key: &str,
+    ) -> bool {
+        let previous = self.connection_states.insert(key.to_string(), ConnectionState::Healthy);
+        if self.refresh_due(format!("connection:{key}"))
+            || previous != Some(ConnectionState::Healthy)
+        {
+            self.persist(|store| store.save_connection_state(key, ConnectionState::Healthy));
+        }
+        if self.connection_incidents.remove(key).is_some() {
+            self.persist(|store| store.delete_incident(key));
+        }
+        previous == Some(ConnectionState::Unhealthy)
+    }
+
+    /// Start tracking a newly failed connection. An open incident keeps its
+    /// start time and follow-ups.
+    fn open_incident(
+        &mut self,
+        node_name: &str,
+        node_url: &str,
+        reason: &str,
+        now: Instant,
+    ) {
+        let key = &Notifier::connection_key(node_name, node_url);
+        if self.connection_incidents.contains_key(key) {
+            return;
+        }
+
+        let incident = ConnectionIncident {
+            node_name: node_name.to_string(),
+            node_url: node_url.to_string(),
+            reason: reason.to_string(),
+            since: now,
+            reminders_sent: 0,
+            escalated: false,
+        };
+        self.persist(|store| store.save_incident(key, &incident));
+        self.connection_incidents.insert(key.to_string(), incident);
+    }
+
+    /// Follow-ups due at `now`, marked as sent. Reminders missed while the
+    /// process was down are not replayed; only the latest is sent.
+    fn plan_incident_follow_ups(
+        &mut self,
+        policy: &EscalationPolicy,
+        now: Instant,
+    ) -> Vec<IncidentFollowUp> {
+        let mut follow_ups = Vec::new();
+
+        for (key, incident) in &mut self.connection_incidents {
+            let unresolved = now.saturating_duration_since(incident.since);
+            let escalate = !incident.escalated
+                && policy.escalate_after.is_some_and(|escalate_after| unresolved >= escalate_after);
+            let reminders_due = policy.repeat_interval.map_or(0, |repeat_interval| {
+                (unresolved.as_secs() / repeat_interval.as_secs().max(1)) as u32
+            });
+            let remind = reminders_due > incident.reminders_sent;
+
+            if !escalate && !remind {
+                continue;
+            }
+
+            incident.escalated |= escalate;
+            incident.reminders_sent = incident.reminders_sent.max(reminders_due);
+            if let Some(Err(err)) =
+                self.store.as_ref().map(|store| store.save_incident(key, incident))
+            {
+                warn!("Failed to persist notification state: {}", err);
+            }
+            follow_ups.push(IncidentFollowUp {
+                connection_key: key.clone(),
+                node_name: incident.node_name.clone(),
+                node_url: incident.node_url.clone(),
+                reason: incident.reason.clone(),
+                unresolved,
+                reminder: incident.reminders_sent,
+                escalate,
+            });
+        }
+
+        follow_ups.sort_by(|left, right| left.connection_key.cmp(&right.connection_key));
+        follow_ups
+    }
+
+    fn plan_ranking_change(
+        &mut self,
+        node_id: &str,
+        ranking: i32,
+    ) -> Option<RankingChange> {
+        if ranking <= 0 {
+            return None;
+        }
+
+        let previous = self.last_rankings.insert(node_id.to_string(), ranking);
+        if self.refresh_due(format!("ranking:{node_id}")) || previous != Some(ranking) {
+            self.persist(|store| store.save_ranking(node_id, ranking));
+        }
+        let previous = previous.filter(|previous| *previous > 0)?;
+
+        if previous == ranking {
+            return None;
+        }
+
+        Some(RankingChange {
+            previous,
+            current: ranking,
+        })
+    }
+
+    /// Low production is reported at most once per node per epoch.
+    fn plan_block_production_alert(
+        &mut self,
+        node_id: &str,
+        epoch: u64,
+    ) -> bool {
+        let first = self.production_alert_epochs.insert(node_id.to_string(), epoch) != Some(epoch);
+        if self.refresh_due(format!("production-alert-epoch:{node_id}")) || first {
+            self.persist(|store| store.save_production_alert_epoch(node_id, epoch));
+        }
+        first
+    }
+
+    /// A thin stake margin is reported once when it first drops below the
+    /// threshold and re-armed after it recovers.
+    fn plan_stake_margin_alert(
+        &mut self,
+        key: &str,
+        low: bool,
+    ) -> bool {
+        if low {
+            let first = self.stake_margin_alerts.insert(key.to_string());
+            if self.refresh_due(format!("stake-margin-alert:{key}")) || first {
+                self.persist(|store| store.save_stake_margin_alert(key));
+            }
+            first
+        } else {
+            if self.stake_margin_alerts.remove(key) {
+                self.persist(|store| store.delete_stake_margin_alert(key));
+            }
+            false
+        }
+    }
+
+    /// Alert once when a node's hourly production drops and again only after
+    /// an hour back above the threshold.
+    fn plan_production_drop_alert(
+        &mut self,
+        node_id: &str,
+        dropped: bool,
+    ) -> bool {
+        if dropped {
+            let first = self.production_drop_alerts.insert(node_id.to_string());
+            if self.refresh_due(format!("production-drop-alert:{node_id}")) || first {
+                self.persist(|store| store.save_production_drop_alert(node_id));
+            }
+            first
+        } else {
+            if self.production_drop_alerts.remove(node_id) {
+                self.persist(|store| store.delete_production_drop_alert(node_id));
+            }
+            false
+        }
+    }
+
+    fn allow_delivery(
+        &mut self,
+        rate_limit_key: &str,
+        now: Instant,
+        rate_limit: Duration,
+    ) -> bool {
+        if rate_limit.is_zero() {
+            return true;
+        }
+
+        if self
+            .last_sent_at
+            .get(rate_limit_key)
+            .is_some_and(|last_sent_at| now.saturating_duration_since(*last_sent_at) < rate_limit)
+        {
+            return false;
+        }
+
+        self.last_sent_at.insert(rate_limit_key.to_string(), now);
+        self.persist(|store| store.save_sent_at(rate_limit_key, now));
+        true
+    }
+
+    fn record_quiet_summary(
+        &mut self,
+        event: NotificationEventKind,
+        subject: &str,
+    ) {
+        self.quiet_summary.record(event, subject);
+        self.persist(|store| store.record_quiet_event(event, subject));
+    }
+
+    fn take_quiet_summary_snapshot(&mut self) -> Option<QuietSummarySnapshot> {
+        let snapshot = self.quiet_summary.take_snapshot();
+        if snapshot.is_some() {
+            self.persist(NotificationStateStore::clear_quiet_events);
+        }
+        snapshot
+    }
+
+    fn is_muted_at(
+        &self,
+        now: Instant,
+    ) -> bool {
+        self.muted_until.is_some_and(|muted_until| now < muted_until)
+    }
+}
+
+/// Applies the shared notification policy and fans accepted notifications
+/// out to every configured backend.
+#[derive(Debug)]
+pub(crate) struct Notifier {
+    policy: NotificationPolicy,
+    templates: NotificationTemplates,
+    /// The `template=` of each `--report-schedule` entry, by schedule index,
+    /// overriding `--telegram-template-daily-summary`.
+    report_templates: Vec<Option<Template>>,
+    backends: Vec<Box<dyn NotifierBackend>>,
+    state: Mutex<NotificationState>,
+}
+
+#[derive(Debug)]
+enum SendDecision {
+    SuppressedByRateLimit,
+    SuppressedByQuietHours,
+    Send { quiet_summary: Option<QuietSummarySnapshot> },
+}
+
+impl Notifier {
+    /// `None` when no backend is configured.
+    pub(crate) fn from_opts(opts: &Opts) -> Result<Option<Arc<Self>>> {
+        let mut backends: Vec<Box<dyn NotifierBackend>> = Vec::new();
+        if let Some(telegram) = TelegramBackend::from_opts(opts)? {
+            telegram.warn_about_unrouted_events();
+            backends.push(Box::new(telegram.with_outbox(opts)));
+        }
+        if let Some(webhook) = WebhookBackend::from_opts(opts)? {
+            backends.push(Box::new(webhook));
+        }
+        for robot in RobotBackend::from_opts(opts)? {
+            backends.push(Box::new(robot));
+        }
+        if let Some(email) = EmailBackend::from_opts(opts)? {
+            backends.push(Box::new(email));
+        }
+
+        if backends.is_empty() {
+            return Ok(None);
+        }
+        let templates = NotificationTemplates::from_opts(opts)?;
+        let report_templates = opts
+            .report_schedules
+            .iter()
+            .map(|schedule| {
+                schedule
+                    .template
+                    .as_deref()
+                    .map(|template| load_template("--report-schedule", template))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(Arc::new(Self {
+            policy: NotificationPolicy::from_opts(opts),
+            templates,
+            report_templates,
+            backends,
+            state: Mutex::new(NotificationState::restore(opts)),
+        })))
+    }
+
+    /// Hold back non-critical notifications for `duration`, the same way
+    /// quiet hours do. Returns the local time the mute ends.
+    pub(crate) fn mute_for(
+        &self,
+        duration: Duration,
+    ) -> DateTime<Local> {
+        let mut state = self.state.lock().expect("notifier mutex poisoned");
+        state.muted_until = Some(Instant::now() + duration);
+
+        Local::now() + chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::zero())
+    }
+
+    /// Retry every backend's due outbox messages.
+    pub(crate) async fn flush_outboxes(&self) {
+        futures::future::join_all(self.backends.iter().map(|backend| backend.flush_outbox())).await;
+    }
+
+    /// The latest `limit` delivery audit rows across backends, newest first.
+    pub(crate) fn recent_deliveries(
+        &self,
+        limit: usize,
+    ) -> Vec<DeliveryRecord> {
+        let mut records = self
+            .backends
+            .iter()
+            .flat_map(|backend| backend.recent_deliveries(limit))
+            .collect::<Vec<_>>();
+        records.sort_by_key(|record| std::cmp::Reverse(record.recorded_at));
+        records.truncate(limit);
+        records
+    }
+
+    /// Lift a `/mute`. Returns false when notifications were not muted.
+    pub(crate) fn unmute(&self) -> bool {
+        let mut state = self.state.lock().expect("notifier mutex poisoned");
+        let muted = state.is_muted_at(Instant::now());
+        state.muted_until = None;
+        muted
+    }
+
+    /// Renders the daily summary for `date` without sending it, for the
+    /// `/daily` bot command.
+    pub(crate) fn render_on_demand_summary(
+        &self,
+        date: &str,
+        node_details: &[DailyNodeSummaryDetail],
+        watched_validators: &[WatchedValidator],
+    ) -> String {
+        self.render_summary_report_message(&SummaryReport {
+            schedule_index: 0,
+            period: SummaryPeriod::Daily,
+            date,
+            node_details,
+            watched_validators,
+            late: false,
+            chat_ids: &[],
+        })
+        .text
+    }
+
+    pub(crate) async fn notify_node_connection_failed(
+        &self,
+        node_name: &str,
+        node_url: &str,
+        reason: &str,
+    ) {
+        let key = Self::connection_key(node_name, node_url);
+        let decision = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            let decision =
+                state.connection_damping.record_failure(&key, &self.policy.damping, Instant::now());
+            match decision {
+                FailureDecision::Confirmed { since } => {
+                    state.open_incident(node_name, node_url, reason, since);
+                    if !state.mark_connection_failed(&key) {
+                        return;
+                    }
+                },
+                // A flapping connection counts as down until it settles.
+                FailureDecision::FlapStarted { since, .. } => {
+                    state.open_incident(node_name, node_url, reason, since);
+                    state.mark_connection_failed(&key);
+                },
+                FailureDecision::Pending | FailureDecision::Flapping => {},
+            }
+            decision
+        };
+
+        let cycles = match decision {
+            FailureDecision::Pending => {
+                debug!("connection failure held back by damping: {key}");
+                return;
+            },
+            FailureDecision::Flapping => {
+                debug!("connection failure suppressed while flapping: {key}");
+                return;
+            },
+            FailureDecision::Confirmed { .. } => None,
+            FailureDecision::FlapStarted { cycles, .. } => Some(cycles),
+        };
+
+        if let Some(cycles) = cycles {
+            let window_minutes = self.policy.damping.flap_window().as_secs() / 60;
+            let data = NotificationData::Flapping {
+                node: node_name.to_string(),
+                url: node_url.to_string(),
+                reason: reason.to_string(),
+                cycles,
+                window_minutes,
+            };
+            self.send_if_enabled(
+                &format!("connection-flapping:{key}"),
+                Notification::new(
+                    NotificationEventKind::ConnectionFlapping,
+                    display_node_name(node_name),
+                    self.render_connection_flapping_message(
+                        node_name,
+                        reason,
+                        cycles,
+                        window_minutes,
+                    ),
+                    data,
+                ),
+            )
+            .await;
+            return;
+        }
+
+        let data = NotificationData::Connection {
+            node: node_name.to_string(),
+            url: node_url.to_string(),
+            reason: Some(reason.to_string()),
+        };
+        self.send_if_enabled(
+            &format!("connection-failed:{key}"),
+            Notification::new(
+                NotificationEventKind::ConnectionFailed,
+                display_node_name(node_name),
+                self.render_connection_failed_message(node_name, reason),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_node_connection_recovered(
+        &self,
+        node_name: &str,
+        node_url: &str,
+    ) {
+        let key = Self::connection_key(node_name, node_url);
+        let should_notify = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            // A flapping connection is reported recovered once it settles.
+            let flapping =
+                state.connection_damping.record_success(&key, &self.policy.damping, Instant::now());
+            !flapping && state.mark_connection_recovered(&key)
+        };
+
+        if !should_notify {
+            return;
+        }
+
+        let data = NotificationData::Connection {
+            node: node_name.to_string(),
+            url: node_url.to_string(),
+            reason: None,
+        };
+        self.send_if_enabled(
+            &format!("connection-recovered:{key}"),
+            Notification::new(
+                NotificationEventKind::ConnectionRecovered,
+                display_node_name(node_name),
+                self.render_connection_recovered_message(node_name),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    /// Remind about connections that are still down and escalate the ones
+    /// unresolved for too long, as configured by the escalation policy.
+    pub(crate) async fn follow_up_unresolved_incidents(&self) {
+        let escalation = &self.policy.escalation;
+        if escalation.repeat_interval.is_none() && escalation.escalate_after.is_none() {
+            return;
+        }
+
+        let follow_ups = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            state.plan_incident_follow_ups(escalation, Instant::now())
+        };
+
+        for follow_up in follow_ups {
+            let node_name = follow_up.node_name.as_str();
+            let unresolved_minutes = follow_up.unresolved.as_secs() / 60;
+            let data = NotificationData::Incident {
+                node: node_name.to_string(),
+                url: follow_up.node_url.clone(),
+                reason: follow_up.reason.clone(),
+                unresolved_minutes,
+                reminder: follow_up.reminder,
+                escalated: follow_up.escalate,
+            };
+            let text = self.render_connection_follow_up_message(
+                node_name,
+                &follow_up.reason,
+                unresolved_minutes,
+                follow_up.escalate,
+            );
+
+            if follow_up.escalate {
+                let mut notification = Notification::new(
+                    NotificationEventKind::ConnectionEscalated,
+                    display_node_name(node_name),
+                    text,
+                    data,
+                );
+                notification.chat_ids = escalation.chat_ids.clone();
+                self.send_unthrottled(notification).await;
+            } else {
+                self.send_unthrottled(Notification::new(
+                    NotificationEventKind::ConnectionFailed,
+                    display_node_name(node_name),
+                    text,
+                    data,
+                ))
+                .await;
+            }
+        }
+    }
+
+    pub(crate) async fn notify_node_ranking_change(
+        &self,
+        node_id: &str,
+        node_name: &str,
+        ranking: i32,
+    ) {
+        let change = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            state.plan_ranking_change(node_id, ranking)
+        };
+        let Some(change) = change else {
+            return;
+        };
+
+        let data = NotificationData::Ranking {
+            node_id: node_id.to_string(),
+            node: node_name.to_string(),
+            previous: change.previous,
+            current: change.current,
+        };
+        self.send_if_enabled(
+            &format!("ranking-changed:{node_id}"),
+            Notification::new(
+                NotificationEventKind::RankingChanged,
+                display_node_name(node_name),
+                self.render_ranking_changed_message(node_name, change),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_summary_report(
+        &self,
+        report: &SummaryReport<'_>,
+    ) {
+        let data = NotificationData::Summary {
+            period: report.period,
+            date: report.date.to_string(),
+            late: report.late,
+            node_count: report.node_details.len(),
+            nodes: report.node_details.to_vec(),
+        };
+        let notification = Notification {
+            chat_ids: report.chat_ids.to_vec(),
+            ..Notification::new(
+                NotificationEventKind::DailySummary,
+                report.date,
+                self.render_summary_report_message(report),
+                data,
+            )
+        };
+        self.send_if_enabled(
+            &format!("summary-report:{}:{}", report.schedule_index, report.date),
+            notification,
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_block_production_low(
+        &self,
+        node_id: &str,
+        node_name: &str,
+        production: EpochProduction,
+        threshold: f64,
+    ) {
+        let should_notify = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            state.plan_block_production_alert(node_id, production.epoch)
+        };
+
+        if !should_notify {
+            return;
+        }
+
+        let data = NotificationData::Production {
+            node_id: node_id.to_string(),
+            node: node_name.to_string(),
+            epoch: production.epoch,
+            produced: production.produced,
+            expected: production.expected,
+            threshold,
+        };
+        self.send_if_enabled(
+            &format!("production-low:{node_id}"),
+            Notification::new(
+                NotificationEventKind::BlockProductionLow,
+                display_node_name(node_name),
+                self.render_production_low_message(node_name, production, threshold),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    /// `drop` is `None` when the node's latest hour was checked and found
+    /// normal, which re-arms the alert.
+    pub(crate) async fn notify_production_drop(
+        &self,
+        node_id: &str,
+        drop: Option<&ProductionDrop>,
+    ) {
+        let should_notify = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            state.plan_production_drop_alert(node_id, drop.is_some())
+        };
+        let Some(drop) = drop.filter(|_| should_notify) else {
+            return;
+        };
+
+        let data = NotificationData::ProductionDrop {
+            node_id: drop.node_id.clone(),
+            node: drop.node_name.clone(),
+            hour_start: drop.hour_start.to_rfc3339(),
+            blocks: drop.blocks,
+            trailing_average: drop.trailing_average,
+            trailing_hours: drop.trailing_hours,
+            threshold: drop.ratio_threshold,
+        };
+        self.send_if_enabled(
+            &format!("production-drop:{node_id}"),
+            Notification::new(
+                NotificationEventKind::ProductionDrop,
+                display_node_name(&drop.node_name),
+                self.render_production_drop_message(drop),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_stake_margin_low(
+        &self,
+        node_id: &str,
+        node_name: &str,
+        ranking: i64,
+        gap: RankTargetGap,
+        threshold: f64,
+    ) {
+        let key = format!("stake-margin:{node_id}:{}", gap.target_rank);
+        let should_notify = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            state.plan_stake_margin_alert(&key, gap.margin < threshold)
+        };
+
+        if !should_notify {
+            return;
+        }
+
+        let data = NotificationData::StakeMargin {
+            node_id: node_id.to_string(),
+            node: node_name.to_string(),
+            ranking,
+            target_rank: gap.target_rank,
+            margin: gap.margin,
+            threshold,
+        };
+        self.send_if_enabled(
+            &key,
+            Notification::new(
+                NotificationEventKind::StakeMarginLow,
+                display_node_name(node_name),
+                self.render_stake_margin_message(node_name, ranking, gap, threshold),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_node_detail_change(
+        &self,
+        change: &NodeDetailChange,
+    ) {
+        let (event, key_prefix) = match change.kind {
+            NodeDetailChangeKind::Commission => {
+                (NotificationEventKind::CommissionChanged, "commission-changed")
+            },
+            NodeDetailChangeKind::BenefitAddress => {
+                (NotificationEventKind::BenefitAddressChanged, "benefit-address-changed")
+            },
+        };
+
+        let data = NotificationData::NodeDetail {
+            node_id: change.node_id.clone(),
+            node: change.node_name.clone(),
+            previous: change.previous.clone(),
+            current: change.current.clone(),
+            changed_at: change.changed_at.to_rfc3339(),
+        };
+        self.send_if_enabled(
+            &format!("{key_prefix}:{}", change.node_id),
+            Notification::new(
+                event,
+                display_node_name(&change.node_name),
+                self.render_node_detail_change_message(change),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_delegator_change(
+        &self,
+        change: &DelegatorChange,
+    ) {
+        let data = NotificationData::Delegator {
+            node_id: change.node_id.clone(),
+            node: change.node_name.clone(),
+            address: change.address.clone(),
+            previous: change.previous,
+            current: change.current,
+            changed_at: change.changed_at.to_rfc3339(),
+        };
+        self.send_if_enabled(
+            &format!("delegator-changed:{}:{}", change.node_id, change.address),
+            Notification::new(
+                NotificationEventKind::DelegatorChanged,
+                display_node_name(&change.node_name),
+                self.render_delegator_change_message(change),
+                data,
+            ),
+        )
+        .await;
+    }
+
+    /// Notify that an alert rule series started firing or resolved.
+    pub(crate) async fn notify_alert_transition(
+        &self,
+        transition: &AlertTransition,
+    ) {
+        let kind = match transition.state {
+            AlertState::Firing => NotificationEventKind::AlertFiring,
+            AlertState::Resolved => NotificationEventKind::AlertResolved,
+            AlertState::Pending => return,
+        };
+        let data = NotificationData::Alert {
+            rule: transition.rule.clone(),
+            metric: transition.metric.name(),
+            target: transition.target.clone(),
+            state: transition.state.name(),
+            value: transition.value.clone(),
+            condition: transition.condition.clone(),
+            duration_seconds: transition.duration.as_secs(),
+        };
+        let mut notification = Notification::new(
+            kind,
+            &format!("{} · {}", transition.rule, transition.target),
+            self.render_alert_message(transition),
+            data,
+        );
+        if kind == NotificationEventKind::AlertFiring {
+            notification.severity = match transition.severity {
+                AlertSeverity::Info => NotificationSeverity::Info,
+                AlertSeverity::Warning => NotificationSeverity::Warning,
+                AlertSeverity::Critical => NotificationSeverity::Critical,
+            };
+        }
+        self.send_if_enabled(
+            &format!("{}:{}:{}", kind.name(), transition.rule, transition.target),
+            notification,
+        )
+        .await;
+    }
+
+    fn connection_key(
+        node_name: &str,
+        node_url: &str,
+    ) -> String {
+        format!("{node_name}@{node_url}")
+    }
+
+    fn render_connection_failed_message(
+        &self,
+        node_name: &str,
+        reason: &str,
+    ) -> RenderedMessage {
+        RenderedMessage::render(
+            &self.templates.connection_failed,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(node_name)),
+                ("reason", reason),
+            ],
+        )
+    }
+
+    fn render_connection_follow_up_message(
+        &self,
+        node_name: &str,
+        reason: &str,
+        unresolved_minutes: u64,
+        escalated: bool,
+    ) -> RenderedMessage {
+        let template = if escalated {
+            &self.templates.connection_escalated
+        } else {
+            &self.templates.connection_repeat
+        };
+
+        RenderedMessage::render(
+            template,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(node_name)),
+                ("reason", reason),
+                ("duration", &format_unresolved_duration(unresolved_minutes)),
+            ],
+        )
+    }
+
+    fn render_connection_flapping_message(
+        &self,
+        node_name: &str,
+        reason: &str,
+        cycles: usize,
+        window_minutes: u64,
+    ) -> RenderedMessage {
+        RenderedMessage::render(
+            &self.templates.connection_flapping,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(node_name)),
+                ("reason", reason),
+                ("count", &cycles.to_string()),
+                ("window", &format_unresolved_duration(window_minutes)),
+            ],
+        )
+    }
+
+    fn render_connection_recovered_message(
+        &self,
+        node_name: &str,
+    ) -> RenderedMessage {
+        RenderedMessage::render(
+            &self.templates.connection_recovered,
+            &[("prefix", MESSAGE_PREFIX), ("node", display_node_name(node_name))],
+        )
+    }
+
+    fn render_ranking_changed_message(
+        &self,
+        node_name: &str,
+        change: RankingChange,
+    ) -> RenderedMessage {
+        let delta = change.previous.abs_diff(change.current).to_string();
+        let (icon, direction, delta_text) = if change.current < change.previous {
+            ("📈", "up", format!("+{}", change.previous.abs_diff(change.current)))
+        } else {
+            ("📉", "down", format!("-{}", change.previous.abs_diff(change.current)))
+        };
+        let previous = change.previous.to_string();
+        let current = change.current.to_string();
+
+        RenderedMessage::render(
+            &self.templates.ranking_changed,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("icon", icon),
+                ("node", display_node_name(node_name)),
+                ("previous", previous.as_str()),
+                ("current", current.as_str()),
+                ("delta", delta.as_str()),
+                ("delta_text", delta_text.as_str()),
+                ("direction", direction),
+            ],
+        )
+    }
+
+    fn render_alert_message(
+        &self,
+        transition: &AlertTransition,
+    ) -> RenderedMessage {
+        let template = if transition.state == AlertState::Resolved {
+            &self.templates.alert_resolved
+        } else {
+            &self.templates.alert_firing
+        };
+        let severity = match transition.severity {
+            AlertSeverity::Info => "提示",
+            AlertSeverity::Warning => "警告",
+            AlertSeverity::Critical => "严重",
+        };
+
+        RenderedMessage::render(
+            template,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("rule", &transition.rule),
+                ("target", &transition.target),
+                ("value", &transition.value),
+                ("condition", &transition.condition),
+                ("severity", severity),
+                ("duration", &format_alert_duration(transition.duration)),
+            ],
+        )
+    }
+
+    fn render_quiet_summary_message(
+        &self,
+        summary: &QuietSummarySnapshot,
+    ) -> RenderedMessage {
+        let count = summary.total_count.to_string();
+        RenderedMessage::render(
+            &self.templates.quiet_summary,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("count", count.as_str()),
+                ("details", summary.details.as_str()),
+            ],
+        )
+    }
+
+    fn render_summary_report_message(
+        &self,
+        report: &SummaryReport<'_>,
+    ) -> RenderedMessage {
+        let SummaryReport {
+            period,
+            date,
+            node_details,
+            watched_validators,
+            late,
+            ..
+        } = *report;
+        let count = node_details.len().to_string();
+        let title = resolve_daily_summary_title(period, date, node_details, late);
+        let estimates = RewardEstimateTotals::from_details(node_details);
+        let daily_reward = format_optional_reward_value(estimates.daily_reward());
+        let daily_yield = format_optional_daily_yield(estimates.daily_yield());
+        let validator_apy = format_optional_ratio_percent(estimates.validator_apy());
+        let delegator_apy = format_optional_ratio_percent(estimates.delegator_apy());
+        let mut sorted_details = node_details.iter().collect::<Vec<_>>();
+        sorted_details.sort_by(|left, right| {
+            left.node_name
+                .cmp(&right.node_name)
+                .then_with(|| left.node_id.cmp(&right.node_id))
+                .then_with(|| left.ranking.cmp(&right.ranking))
+        });
+        let mut details = if sorted_details.is_empty() {
+            "📭 暂无可用节点详情".to_string()
+        } else {
+            sorted_details
+                .iter()
+                .map(|detail| render_daily_summary_detail(period, detail))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        };
+        if !watched_validators.is_empty() {
+            details.push_str("\n\n");
+            details.push_str(&render_watchlist_section(watched_validators));
+        }
+
+        let template = self
+            .report_templates
+            .get(report.schedule_index)
+            .and_then(Option::as_ref)
+            .unwrap_or(&self.templates.daily_summary);
+        let period_name = match period {
+            SummaryPeriod::Daily => "daily",
+            SummaryPeriod::Weekly => "weekly",
+            SummaryPeriod::Monthly => "monthly",
+        };
+        let context = TemplateContext::from_pairs(&[
+            ("prefix", MESSAGE_PREFIX),
+            ("title", title),
+            ("date", date),
+            ("count", count.as_str()),
+            ("details", details.as_str()),
+            ("daily_reward", daily_reward.as_str()),
+            ("daily_yield", daily_yield.as_str()),
+            ("validator_apy", validator_apy.as_str()),
+            ("delegator_apy", delegator_apy.as_str()),
+            ("period", period_name),
+        ])
+        .with("late", late)
+        .with(
+            "nodes",
+            sorted_details
+                .into_iter()
+                .map(|detail| daily_summary_node_context(period, detail))
+                .collect::<Vec<_>>(),
+        )
+        .with(
+            "watchlist",
+            watched_validators.iter().map(watched_validator_context).collect::<Vec<_>>(),
+        );
+
+        RenderedMessage::render_context(template, context)
+    }
+
+    fn render_node_detail_change_message(
+        &self,
+        change: &NodeDetailChange,
+    ) -> RenderedMessage {
+        let template = match change.kind {
+            NodeDetailChangeKind::Commission => &self.templates.commission_changed,
+            NodeDetailChangeKind::BenefitAddress => &self.templates.benefit_address_changed,
+        };
+        let time = change.changed_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        RenderedMessage::render(
+            template,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(&change.node_name)),
+                ("node_id", change.node_id.as_str()),
+                ("previous", change.previous.as_str()),
+                ("current", change.current.as_str()),
+                ("time", time.as_str()),
+            ],
+        )
+    }
+
+    fn render_delegator_change_message(
+        &self,
+        change: &DelegatorChange,
+    ) -> RenderedMessage {
+        let action = match change.kind() {
+            DelegatorChangeKind::Joined => "新增委托",
+            DelegatorChangeKind::Added => "追加委托",
+            DelegatorChangeKind::Reduced => "减少委托",
+            DelegatorChangeKind::Left => "全部撤出",
+        };
+        let previous = format_reward_value(change.previous);
+        let current = format_reward_value(change.current);
+        let delta = change.delta();
+        let delta = if delta >= 0.0 {
+            format!("+{}", format_reward_value(delta))
+        } else {
+            format!("-{}", format_reward_value(delta.abs()))
+        };
+        let time = change.changed_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        RenderedMessage::render(
+            &self.templates.delegator_changed,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(&change.node_name)),
+                ("node_id", change.node_id.as_str()),
+                ("address", change.address.as_str()),
+                ("action", action),
+                ("previous", previous.as_str()),
+                ("current", current.as_str()),
+                ("delta", delta.as_str()),
+                ("time", time.as_str()),
+            ],
+        )
+    }
+
+    fn render_stake_margin_message(
+        &self,
+        node_name: &str,
+        ranking: i64,
+        gap: RankTargetGap,
+        threshold: f64,
+    ) -> RenderedMessage {
+        let ranking = ranking.to_string();
+        let target = gap.target_rank.to_string();
+        let margin = format_reward_value(gap.margin);
+        let threshold = format_reward_value(threshold);
+
+        RenderedMessage::render(
+            &self.templates.stake_margin,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(node_name)),
+                ("ranking", ranking.as_str()),
+                ("target", target.as_str()),
+                ("margin", margin.as_str()),
+                ("threshold", threshold.as_str()),
+            ],
+        )
+    }
+
+    fn render_production_low_message(
+        &self,
+        node_name: &str,
+        production: EpochProduction,
+        threshold: f64,
+    ) -> RenderedMessage {
+        let epoch = production.epoch.to_string();
+        let produced = production.produced.to_string();
+        let expected = production.expected.to_string();
+        let missed = production.missed().to_string();
+        let ratio = production
+            .production_ratio()
+            .map(format_ratio_percent)
+            .unwrap_or_else(|| "-".to_string());
+        let threshold = format_ratio_percent(threshold);
+
+        RenderedMessage::render(
+            &self.templates.production_low,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(node_name)),
+                ("epoch", epoch.as_str()),
+                ("produced", produced.as_str()),
+                ("expected", expected.as_str()),
+                ("missed", missed.as_str()),
+                ("ratio", ratio.as_str()),
+                ("threshold", threshold.as_str()),
+            ],
+        )
+    }
+
+    fn render_production_drop_message(
+        &self,
+        drop: &ProductionDrop,
+    ) -> RenderedMessage {
+        let hour = format!(
+            "{}-{}",
+            drop.hour_start.format("%Y-%m-%d %H:%M"),
+            (drop.hour_start + chrono::Duration::hours(1)).format("%H:%M")
+        );
+        let blocks = drop.blocks.to_string();
+        let trailing_hours = drop.trailing_hours.to_string();
+        let average = format!("{:.1}", drop.trailing_average);
+        let threshold = format_ratio_percent(drop.ratio_threshold);
+
+        RenderedMessage::render(
+            &self.templates.production_drop,
+            &[
+                ("prefix", MESSAGE_PREFIX),
+                ("node", display_node_name(&drop.node_name)),
+                ("node_id", drop.node_id.as_str()),
+                ("hour", hour.as_str()),
+                ("blocks", blocks.as_str()),
+                ("trailing_hours", trailing_hours.as_str()),
+                ("average", average.as_str()),
+                ("threshold", threshold.as_str()),
+            ],
+        )
+    }
+
+    fn render_quiet_summary_notification(
+        &self,
+        summary: &QuietSummarySnapshot,
+    ) -> Notification {
+        let data = NotificationData::QuietSummary {
+            count: summary.total_count,
+            details: summary.details.clone(),
+        };
+
+        Notification::new(
+            NotificationEventKind::QuietSummary,
+            "静默期摘要",
+            self.render_quiet_summary_message(summary),
+            data,
+        )
+    }
+
+    async fn send_if_enabled(
+        &self,
+        rate_limit_key: &str,
+        notification: Notification,
+    ) {
+        self.send(Some(rate_limit_key), notification).await;
+    }
+
+    /// Sends a notification that is already paced by its own schedule, such
+    /// as incident reminders, bypassing the rate limit.
+    async fn send_unthrottled(
+        &self,
+        notification: Notification,
+    ) {
+        self.send(None, notification).await;
+    }
+
+    async fn send(
+        &self,
+        rate_limit_key: Option<&str>,
+        notification: Notification,
+    ) {
+        let event = notification.kind;
+        if !self.policy.enabled_events.allows(event) {
+            return;
+        }
+
+        // Critical events skip the rate limit as well as quiet hours, so a
+        // burst of routine alerts can't crowd them out.
+        let rate_limit_key = rate_limit_key.filter(|_| !notification.is_critical());
+        let quiet_time_now = self.policy.is_quiet_time_now();
+        let quiet_hours_enabled =
+            !matches!(event, NotificationEventKind::DailySummary) && !notification.is_critical();
+        let decision = {
+            let mut state = self.state.lock().expect("notifier mutex poisoned");
+            let now = Instant::now();
+            if rate_limit_key
+                .is_some_and(|key| !state.allow_delivery(key, now, self.policy.rate_limit))
+            {
+                SendDecision::SuppressedByRateLimit
+            } else if quiet_hours_enabled && (quiet_time_now || state.is_muted_at(now)) {
+                state.record_quiet_summary(event, &notification.subject);
+                SendDecision::SuppressedByQuietHours
+            } else {
+                SendDecision::Send {
+                    quiet_summary: state.take_quiet_summary_snapshot(),
+                }
+            }
+        };
+
+        match decision {
+            SendDecision::SuppressedByRateLimit => {
+                debug!(
+                    "notification suppressed by rate limit: {}",
+                    rate_limit_key.unwrap_or_default()
+                );
+            },
+            SendDecision::SuppressedByQuietHours => {
+                debug!(
+                    "notification buffered by quiet hours: {}",
+                    rate_limit_key.unwrap_or(&notification.subject)
+                );
+            },
+            SendDecision::Send { quiet_summary } => {
+                if let Some(quiet_summary) = quiet_summary {
+                    self.dispatch(&self.render_quiet_summary_notification(&quiet_summary)).await;
+                }
+                self.dispatch(&notification).await;
+            },
+        }
+    }
+
+    /// Delivers `notification` through every backend concurrently, so a
+    /// slow channel does not hold up the others.
+    async fn dispatch(
+        &self,
+        notification: &Notification,
+    ) {
+        futures::future::join_all(
+            self.backends.iter().map(|backend| backend.deliver(notification)),
+        )
+        .await;
+    }
+}
+
+fn format_unresolved_duration(minutes: u64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes} 分钟"),
+        (hours, 0) => format!("{hours} 小时"),
+        (hours, minutes) => format!("{hours} 小时 {minutes} 分钟"),
+    }
+}
+
+fn format_alert_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds < 60 {
+        format!("{seconds} 秒")
+    } else {
+        format_unresolved_duration(seconds / 60)
+    }
+}
+
+/// A rendered message together with the template and values it was
+/// rendered from, so Telegram can render the same event with a route's own
+/// template or in another parse mode.
+#[derive(Debug, Clone, Default)]
+struct RenderedMessage {
+    text: String,
+    template: Option<Template>,
+    context: TemplateContext,
+}
+
+impl RenderedMessage {
+    fn render(
+        template: &Template,
+        replacements: &[(&'static str, &str)],
+    ) -> Self {
+        Self::render_context(template, TemplateContext::from_pairs(replacements))
+    }
+
+    fn render_context(
+        template: &Template,
+        context: TemplateContext,
+    ) -> Self {
+        Self {
+            text: template.render(&context, TelegramParseMode::Plain),
+            template: Some(template.clone()),
+            context,
+        }
+    }
+}
+
+impl From<String> for RenderedMessage {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            template: None,
+            context: TemplateContext::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use clap::Parser;
+
+    use super::*;
+    use crate::collect::AlertMetric;
+
+    fn create_test_notifier_with_templates(templates: NotificationTemplates) -> Notifier {
+        Notifier {
+            policy: NotificationPolicy {
+                enabled_events: NotificationFilter::all(),
+                quiet_hours: None,
+                rate_limit: Duration::from_secs(0),
+                escalation: EscalationPolicy::default(),
+                damping: DampingPolicy::default(),
+            },
+            templates,
+            report_templates: Vec::new(),
+            backends: Vec::new(),
+            state: Mutex::new(NotificationState::default()),
+        }
+    }
+
+    fn render_daily_summary(
+        notifier: &Notifier,
+        date: &str,
+        node_details: &[DailyNodeSummaryDetail],
+        watched_validators: &[WatchedValidator],
+        late: bool,
+    ) -> String {
+        notifier
+            .render_summary_report_message(&SummaryReport {
+                schedule_index: 0,
+                period: SummaryPeriod::Daily,
+                date,
+                node_details,
+                watched_validators,
+                late,
+                chat_ids: &[],
+            })
+            .text
+    }
+
+    fn create_test_notifier() -> Notifier {
+        create_test_notifier_with_templates(NotificationTemplates {
+            connection_failed: Template::builtin(DEFAULT_CONNECTION_FAILED_TEMPLATE),
+            connection_recovered: Template::builtin(DEFAULT_CONNECTION_RECOVERED_TEMPLATE),
+            connection_repeat: Template::builtin(DEFAULT_CONNECTION_REPEAT_TEMPLATE),
+            connection_escalated: Template::builtin(DEFAULT_CONNECTION_ESCALATED_TEMPLATE),
+            connection_flapping: Template::builtin(DEFAULT_CONNECTION_FLAPPING_TEMPLATE),
+            ranking_changed: Template::builtin(DEFAULT_RANKING_CHANGED_TEMPLATE),
+            quiet_summary: Template::builtin(DEFAULT_QUIET_SUMMARY_TEMPLATE),
+            daily_summary: Template::builtin(DEFAULT_DAILY_SUMMARY_TEMPLATE),
+            production_low: Template::builtin(DEFAULT_PRODUCTION_LOW_TEMPLATE),
+            production_drop: Template::builtin(DEFAULT_PRODUCTION_DROP_TEMPLATE),
+            stake_margin: Template::builtin(DEFAULT_STAKE_MARGIN_TEMPLATE),
+            commission_changed: Template::builtin(DEFAULT_COMMISSION_CHANGED_TEMPLATE),
+            benefit_address_changed: Template::builtin(DEFAULT_BENEFIT_ADDRESS_CHANGED_TEMPLATE),
+            delegator_changed: Template::builtin(DEFAULT_DELEGATOR_CHANGED_TEMPLATE),
+            alert_firing: Template::builtin(DEFAULT_ALERT_FIRING_TEMPLATE),
+            alert_resolved: Template::builtin(DEFAULT_ALERT_RESOLVED_TEMPLATE),
+        })
+    }
+
+    #[test]
+    fn test_notification_filter_defaults_to_all_events() {
+        let filter = NotificationFilter::from_opts(&[]);
+
+        assert!(filter.allows(NotificationEventKind::ConnectionFailed));
+        assert!(filter.allows(NotificationEventKind::ConnectionRecovered));
+        assert!(filter.allows(NotificationEventKind::RankingChanged));
+        assert!(filter.allows(NotificationEventKind::DailySummary));
+        assert!(filter.allows(NotificationEventKind::BlockProductionLow));
+    }
+
+    #[test]
+    fn test_render_template_replaces_known_placeholders() {
+        let template =
+            Template::parse("{prefix} {node} {current} {unknown}").expect("valid template");
+        let rendered = RenderedMessage::render(
+            &template,
+            &[("prefix", "[chaindash]"), ("node", "验证节点A"), ("current", "5")],
+        )
+        .text;
+
+        assert_eq!(rendered, "[chaindash] 验证节点A 5 {unknown}");
+    }
+
+    #[test]
+    fn test_normalize_template_unescapes_newline_sequences() {
+        assert_eq!(normalize_template("a\\nb"), "a\nb");
+    }
+
+    #[test]
+    fn test_quiet_summary_buffer_formats_suppressed_notifications() {
+        let mut buffer = QuietSummaryBuffer::default();
+        buffer.record(NotificationEventKind::ConnectionFailed, "main");
+        buffer.record(NotificationEventKind::ConnectionFailed, "backup");
+        buffer.record(NotificationEventKind::RankingChanged, "验证节点A");
+
+        let summary = buffer.take_snapshot().expect("summary should exist");
+
+        assert_eq!(
+            summary,
+            QuietSummarySnapshot {
+                total_count: 3,
+                details: "连接失败 2 次：main, backup\n排名变化 1 次：验证节点A".to_string(),
+            }
+        );
+        assert_eq!(buffer.take_snapshot(), None);
+    }
+
+    #[test]
+    fn test_quiet_summary_bucket_limits_preview_names() {
+        let mut bucket = QuietSummaryBucket::default();
+        bucket.record("node-a");
+        bucket.record("node-b");
+        bucket.record("node-c");
+        bucket.record("node-d");
+
+        assert_eq!(bucket.count, 4);
+        assert_eq!(bucket.names, vec!["node-a", "node-b", "node-c"]);
+    }
+
+    #[test]
+    fn test_notification_filter_supports_event_groups() {
+        let filter = NotificationFilter::from_opts(&[
+            NotifyEvent::Connection,
+            NotifyEvent::RankingChanged,
+            NotifyEvent::DailySummary,
+        ]);
+
+        assert!(filter.allows(NotificationEventKind::ConnectionFailed));
+        assert!(filter.allows(NotificationEventKind::ConnectionRecovered));
+        assert!(filter.allows(NotificationEventKind::RankingChanged));
+        assert!(filter.allows(NotificationEventKind::DailySummary));
+    }
+
+    #[test]
+    fn test_notification_filter_supports_single_event_selection() {
+        let filter = NotificationFilter::from_opts(&[NotifyEvent::ConnectionRecovered]);
+
+        assert!(!filter.allows(NotificationEventKind::ConnectionFailed));
+        assert!(filter.allows(NotificationEventKind::ConnectionRecovered));
+        assert!(!filter.allows(NotificationEventKind::RankingChanged));
+        assert!(!filter.allows(NotificationEventKind::DailySummary));
+    }
+
+    #[test]
+    fn test_notification_policy_filters_events_for_all_backends() {
+        let opts = Opts::parse_from(["test", "--notify-events", "connection,ranking"]);
+
+        let policy = NotificationPolicy::from_opts(&opts);
+
+        assert!(policy.enabled_events.allows(NotificationEventKind::ConnectionFailed));
+        assert!(policy.enabled_events.allows(NotificationEventKind::ConnectionRecovered));
+        assert!(policy.enabled_events.allows(NotificationEventKind::RankingChanged));
+        assert!(!policy.enabled_events.allows(NotificationEventKind::DailySummary));
+        assert!(policy.enabled_events.allows(NotificationEventKind::QuietSummary));
+    }
+
+    #[test]
+    fn test_display_url_hides_query_string() {
+        assert_eq!(
+            display_url("https://oapi.dingtalk.com/robot/send?access_token=abc"),
+            "https://oapi.dingtalk.com/robot/send"
+        );
+    }
+
+    #[test]
+    fn test_split_title_separates_first_line() {
+        assert_eq!(
+            split_title("🚨 节点连接异常\n🔹 节点：main"),
+            ("🚨 节点连接异常", "🔹 节点：main")
+        );
+        assert_eq!(split_title("单行"), ("单行", ""));
+    }
+
+    #[test]
+    fn test_notifier_is_disabled_without_backends() {
+        let opts = Opts::parse_from(["test", "--notify-events", "connection"]);
+
+        assert!(Notifier::from_opts(&opts).expect("opts should be valid").is_none());
+    }
+
+    #[test]
+    fn test_notifier_fans_out_to_every_configured_backend() {
+        let opts = Opts::parse_from([
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+            "--webhook-url",
+            "https://hooks.example.com/chaindash",
+            "--db-path",
+            ":memory:",
+        ]);
+
+        let notifier = Notifier::from_opts(&opts)
+            .expect("opts should be valid")
+            .expect("notifier should be enabled");
+
+        assert_eq!(notifier.backends.len(), 2);
+    }
+
+    #[test]
+    fn test_telegram_templates_accept_custom_templates() {
+        let opts = Opts::parse_from([
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+            "--telegram-template-connection-failed",
+            "{prefix} FAIL {node}: {reason}",
+            "--telegram-template-connection-recovered",
+            "{prefix} OK {node}",
+            "--telegram-template-ranking-changed",
+            "{prefix} {node} {previous}->{current} {delta_text}",
+            "--telegram-template-quiet-summary",
+            "{prefix} summary {count}\\n{details}",
+            "--telegram-template-daily-summary",
+            "{prefix} daily {date}\\n{details}",
+        ]);
+
+        let templates = NotificationTemplates::from_opts(&opts).expect("templates should parse");
+        // Without values every placeholder is printed as written.
+        let source = |template: &Template| {
+            template.render(&TemplateContext::default(), TelegramParseMode::Plain)
+        };
+
+        assert_eq!(source(&templates.connection_failed), "{prefix} FAIL {node}: {reason}");
+        assert_eq!(source(&templates.connection_recovered), "{prefix} OK {node}");
+        assert_eq!(
+            source(&templates.ranking_changed),
+            "{prefix} {node} {previous}->{current} {delta_text}"
+        );
+        assert_eq!(source(&templates.quiet_summary), "{prefix} summary {count}\n{details}");
+        assert_eq!(source(&templates.daily_summary), "{prefix} daily {date}\n{details}");
+    }
+
+    #[test]
+    fn test_default_connection_failed_template_is_rendered() {
+        let notifier = create_test_notifier();
+
+        let message = notifier.render_connection_failed_message("main", "rpc timeout").text;
+
+        assert_eq!(message, "🚨 节点连接异常\n🔹 节点：main\n📝 原因：rpc timeout");
+    }
+
+    #[test]
+    fn test_connection_follow_up_templates_include_unresolved_duration() {
+        let notifier = create_test_notifier();
+
+        assert_eq!(
+            notifier.render_connection_follow_up_message("main", "rpc timeout", 90, false).text,
+            "⏰ 节点连接仍未恢复\n🔹 节点：main\n⏱️ 已持续：1 小时 30 分钟\n📝 原因：rpc timeout"
+        );
+        assert!(notifier
+            .render_connection_follow_up_message("main", "rpc timeout", 120, true)
+            .text
+            .starts_with("🚨【严重】节点连接长时间未恢复\n🔹 节点：main\n⏱️ 已持续：2 小时\n"));
+    }
+
+    #[test]
+    fn test_default_connection_recovered_template_is_rendered() {
+        let notifier = create_test_notifier();
+
+        let message = notifier.render_connection_recovered_message("main").text;
+
+        assert_eq!(message, "✅ 节点连接已恢复\n🔹 节点：main");
+    }
+
+    #[test]
+    fn test_default_quiet_summary_template_is_rendered() {
+        let notifier = create_test_notifier();
+        let summary = QuietSummarySnapshot {
+            total_count: 2,
+            details: "连接失败 1 次：main\n连接恢复 1 次：backup".to_string(),
+        };
+
+        let message = notifier.render_quiet_summary_message(&summary).text;
+
+        assert_eq!(
+            message,
+            "🌙 静默期摘要\n🧾 共 2 条\n连接失败 1 次：main\n连接恢复 1 次：backup"
+        );
+    }
+
+    #[test]
+    fn test_custom_connection_failed_template_is_rendered() {
+        let notifier = create_test_notifier_with_templates(NotificationTemplates {
+            connection_failed: Template::parse("{prefix} FAIL {node}: {reason}")
+                .expect("valid template"),
+            connection_recovered: Template::builtin(DEFAULT_CONNECTION_RECOVERED_TEMPLATE),
+            connection_repeat: Template::builtin(DEFAULT_CONNECTION_REPEAT_TEMPLATE),
+            connection_escalated: Template::builtin(DEFAULT_CONNECTION_ESCALATED_TEMPLATE),
+            connection_flapping: Template::builtin(DEFAULT_CONNECTION_FLAPPING_TEMPLATE),
+            ranking_changed: Template::builtin(DEFAULT_RANKING_CHANGED_TEMPLATE),
+            quiet_summary: Template::builtin(DEFAULT_QUIET_SUMMARY_TEMPLATE),
+            daily_summary: Template::builtin(DEFAULT_DAILY_SUMMARY_TEMPLATE),
+            production_low: Template::builtin(DEFAULT_PRODUCTION_LOW_TEMPLATE),
+            production_drop: Template::builtin(DEFAULT_PRODUCTION_DROP_TEMPLATE),
+            stake_margin: Template::builtin(DEFAULT_STAKE_MARGIN_TEMPLATE),
+            commission_changed: Template::builtin(DEFAULT_COMMISSION_CHANGED_TEMPLATE),
+            benefit_address_changed: Template::builtin(DEFAULT_BENEFIT_ADDRESS_CHANGED_TEMPLATE),
+            delegator_changed: Template::builtin(DEFAULT_DELEGATOR_CHANGED_TEMPLATE),
+            alert_firing: Template::builtin(DEFAULT_ALERT_FIRING_TEMPLATE),
+            alert_resolved: Template::builtin(DEFAULT_ALERT_RESOLVED_TEMPLATE),
+        });
+
+        let message = notifier.render_connection_failed_message("main", "rpc timeout").text;
+
+        assert_eq!(message, "[chaindash] FAIL main: rpc timeout");
+    }
+
+    #[test]
+    fn test_notification_policy_accepts_legacy_quiet_hours_and_rate_limit_flags() {
+        let opts = Opts::parse_from([
+            "test",
+            "--telegram-quiet-hours",
+            "23:00-08:00",
+            "--telegram-rate-limit-seconds",
+            "120",
+        ]);
+
+        let policy = NotificationPolicy::from_opts(&opts);
+
+        assert_eq!(policy.rate_limit, Duration::from_secs(120));
+        assert!(policy.is_quiet_time_at(23 * 60));
+        assert!(policy.is_quiet_time_at(7 * 60 + 59));
+        assert!(!policy.is_quiet_time_at(8 * 60));
+    }
+
+    #[test]
+    fn test_connection_failure_is_only_reported_once_until_recovery() {
+        let mut state = NotificationState::default();
+
+        assert!(state.mark_connection_failed("main@ws://node"));
+        assert!(!state.mark_connection_failed("main@ws://node"));
+    }
+
+    #[test]
+    fn test_connection_recovery_requires_previous_failure() {
+        let mut state = NotificationState::default();
+
+        assert!(!state.mark_connection_recovered("main@ws://node"));
+
+        state.mark_connection_failed("main@ws://node");
+
+        assert!(state.mark_connection_recovered("main@ws://node"));
+    }
+
+    #[test]
+    fn test_initial_ranking_observation_does_not_send_notification() {
+        let mut state = NotificationState::default();
+
+        let change = state.plan_ranking_change("node-a", 7);
+
+        assert_eq!(change, None);
+    }
+
+    #[test]
+    fn test_ranking_improvement_uses_upward_message() {
+        let notifier = create_test_notifier();
+
+        let message = notifier
+            .render_ranking_changed_message(
+                "验证节点A",
+                RankingChange {
+                    previous: 7,
+                    current: 5,
+                },
+            )
+            .text;
+
+        assert_eq!(message, "📈 节点排名变动\n🔹 节点：验证节点A\n📍 排名：7 → 5（+2）");
+        assert!(!message.contains("Node ID"));
+        assert!(!message.contains("节点:"));
+    }
+
+    #[test]
+    fn test_ranking_decline_uses_downward_message() {
+        let notifier = create_test_notifier();
+
+        let message = notifier
+            .render_ranking_changed_message(
+                "验证节点A",
+                RankingChange {
+                    previous: 5,
+                    current: 8,
+                },
+            )
+            .text;
+
+        assert_eq!(message, "📉 节点排名变动\n🔹 节点：验证节点A\n📍 排名：5 → 8（-3）");
+        assert!(!message.contains("Node ID"));
+        assert!(!message.contains("节点:"));
+    }
+
+    #[test]
+    fn test_unknown_ranking_does_not_clear_last_known_value() {
+        let mut state = NotificationState::default();
+        state.plan_ranking_change("node-a", 7);
+
+        assert_eq!(state.plan_ranking_change("node-a", 0), None);
+
+        let change = state
+            .plan_ranking_change("node-a", 5)
+            .expect("ranking change should still compare against last known rank");
+
+        assert_eq!(
+            change,
+            RankingChange {
+                previous: 7,
+                current: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ranking_change_uses_fallback_name_without_node_id() {
+        let notifier = create_test_notifier();
+
+        let message = notifier
+            .render_ranking_changed_message(
+                "",
+                RankingChange {
+                    previous: 7,
+                    current: 5,
+                },
+            )
+            .text;
+
+        assert_eq!(message, "📈 节点排名变动\n🔹 节点：未命名节点\n📍 排名：7 → 5（+2）");
+        assert!(!message.contains("node-a-id"));
+    }
+
+    #[test]
+    fn test_daily_summary_renders_total_and_daily_metrics() {
+        let notifier = create_test_notifier();
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: Some(12),
+            daily_reward_value: Some(5.6),
+            daily_span_days: 1,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], false);
+
+        assert_eq!(
+            message,
+            "📊 每日节点快照（2026-04-14）\n🧾 节点数：1\n🔹 验证节点A\n  🧱 累计出块：123\n  💰 \
+             累计系统奖励：45.6\n  📅 当天出块：12\n  🎁 当天系统奖励：5.6"
+        );
+    }
+
+    #[test]
+    fn test_weekly_report_renders_period_totals_with_schedule_template() {
+        let mut notifier = create_test_notifier();
+        notifier.report_templates = vec![
+            None,
+            Some(
+                load_template("--report-schedule", "{title} {date}\\n{details}")
+                    .expect("template should parse"),
+            ),
+        ];
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: Some(84),
+            daily_reward_value: Some(39.2),
+            daily_span_days: 7,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = notifier
+            .render_summary_report_message(&SummaryReport {
+                schedule_index: 1,
+                period: SummaryPeriod::Weekly,
+                date: "2026-04-20",
+                node_details: &node_details,
+                watched_validators: &[],
+                late: false,
+                chat_ids: &[],
+            })
+            .text;
+
+        assert_eq!(
+            message,
+            "🗓️ 节点周报 2026-04-20\n🔹 验证节点A\n  🧱 累计出块：123\n  💰 累计系统奖励：45.6\n  \
+             📅 周期出块（7 日）：84\n  🎁 周期系统奖励（7 日）：39.2"
+        );
+    }
+
+    #[test]
+    fn test_schedule_template_file_loops_over_nodes_and_watchlist() {
+        let mut notifier = create_test_notifier();
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 1_234_567,
+            reward_value: 45.678,
+            daily_block_qty: Some(84),
+            daily_reward_value: None,
+            daily_span_days: 7,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: true,
+            consecutive_failures: 2,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+        let watched = vec![WatchedValidator {
+            node_id: "rival-a-id".to_string(),
+            node_name: "rival-a".to_string(),
+            ranking: 7,
+            ..Default::default()
+        }];
+        let path =
+            std::env::temp_dir().join(format!("chaindash-test-{}-weekly.tmpl", std::process::id()));
+        std::fs::write(
+            &path,
+            "{% if period == \"weekly\" %}周报{% else %}日报{% endif %} {date}\n{% for node in \
+             nodes %}\n{loop.index}. {node.name} #{node.ranking}: {node.blocks | thousands} / \
+             {node.rewards | round(1)} / {node.daily_rewards | default(\"-\")}\n{% if node.stale \
+             %}  ⚠️ 连续失败 {node.failures} 次\n{% endif %}\n{% endfor %}\n{% for validator in \
+             watchlist %}👀 {validator.name} #{validator.ranking}{% endfor %}",
+        )
+        .expect("template file should be written");
+        notifier.report_templates = vec![
+            None,
+            Some(
+                load_template("--report-schedule", &format!("@{}", path.display()))
+                    .expect("template file should parse"),
+            ),
+        ];
+        // The file is read once, when the notifier is built.
+        std::fs::remove_file(&path).expect("template file should be removed");
+
+        let message = notifier
+            .render_summary_report_message(&SummaryReport {
+                schedule_index: 1,
+                period: SummaryPeriod::Weekly,
+                date: "2026-04-20",
+                node_details: &node_details,
+                watched_validators: &watched,
+                late: false,
+                chat_ids: &[],
+            })
+            .text;
+
+        assert_eq!(
+            message,
+            "周报 2026-04-20\n1. 验证节点A #5: 1,234,567 / 45.7 / -\n  ⚠️ 连续失败 2 次\n👀 \
+             rival-a #7"
+        );
+    }
+
+    #[test]
+    fn test_late_daily_summary_marks_title_and_averaged_deltas() {
+        let notifier = create_test_notifier();
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: Some(12),
+            daily_reward_value: Some(5.6),
+            daily_span_days: 3,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], true);
+
+        assert_eq!(
+            message,
+            "📊 每日节点快照（补发）（2026-04-14）\n🧾 节点数：1\n🔹 验证节点A\n  🧱 \
+             累计出块：123\n  💰 累计系统奖励：45.6\n  📅 当天出块：12（近 3 日平均）\n  🎁 \
+             当天系统奖励：5.6（近 3 日平均）"
+        );
+    }
+
+    #[test]
+    fn test_daily_summary_renders_previous_month_totals_on_month_boundary() {
+        let notifier = create_test_notifier();
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: Some(12),
+            daily_reward_value: Some(5.6),
+            daily_span_days: 1,
+            show_monthly_totals: true,
+            monthly_block_qty: Some(300),
+            monthly_reward_value: Some(30.0),
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-05-01", &node_details, &[], false);
+
+        assert_eq!(
+            message,
+            "📅 月度节点简报（2026-05-01）\n🧾 节点数：1\n🔹 验证节点A\n  🧱 累计出块：123\n  💰 \
+             累计系统奖励：45.6\n  📅 当天出块：12\n  🎁 当天系统奖励：5.6\n  🗓️ \
+             上月总出块：300\n  🏆 上月总系统奖励：30"
+        );
+    }
+
+    #[test]
+    fn test_daily_summary_uses_placeholder_when_previous_snapshot_is_unavailable() {
+        let notifier = create_test_notifier();
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: None,
+            daily_reward_value: None,
+            daily_span_days: 1,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], false);
+
+        assert_eq!(
+            message,
+            "📊 每日节点快照（2026-04-14）\n🧾 节点数：1\n🔹 验证节点A\n  🧱 累计出块：123\n  💰 \
+             累计系统奖励：45.6\n  📅 当天出块：-\n  🎁 当天系统奖励：-"
+        );
+    }
+
+    #[test]
+    fn test_daily_summary_renders_reward_estimates_and_placeholders() {
+        let notifier = create_test_notifier_with_templates(NotificationTemplates {
+            connection_failed: Template::builtin(DEFAULT_CONNECTION_FAILED_TEMPLATE),
+            connection_recovered: Template::builtin(DEFAULT_CONNECTION_RECOVERED_TEMPLATE),
+            connection_repeat: Template::builtin(DEFAULT_CONNECTION_REPEAT_TEMPLATE),
+            connection_escalated: Template::builtin(DEFAULT_CONNECTION_ESCALATED_TEMPLATE),
+            connection_flapping: Template::builtin(DEFAULT_CONNECTION_FLAPPING_TEMPLATE),
+            ranking_changed: Template::builtin(DEFAULT_RANKING_CHANGED_TEMPLATE),
+            quiet_summary: Template::builtin(DEFAULT_QUIET_SUMMARY_TEMPLATE),
+            daily_summary: Template::parse(
+                "{daily_reward}|{daily_yield}|{validator_apy}|{delegator_apy}",
+            )
+            .expect("valid template"),
+            production_low: Template::builtin(DEFAULT_PRODUCTION_LOW_TEMPLATE),
+            production_drop: Template::builtin(DEFAULT_PRODUCTION_DROP_TEMPLATE),
+            stake_margin: Template::builtin(DEFAULT_STAKE_MARGIN_TEMPLATE),
+            commission_changed: Template::builtin(DEFAULT_COMMISSION_CHANGED_TEMPLATE),
+            benefit_address_changed: Template::builtin(DEFAULT_BENEFIT_ADDRESS_CHANGED_TEMPLATE),
+            delegator_changed: Template::builtin(DEFAULT_DELEGATOR_CHANGED_TEMPLATE),
+            alert_firing: Template::builtin(DEFAULT_ALERT_FIRING_TEMPLATE),
+            alert_resolved: Template::builtin(DEFAULT_ALERT_RESOLVED_TEMPLATE),
+        });
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: None,
+            daily_reward_value: None,
+            daily_span_days: 1,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: Some(2_000.0),
+            reward_per: 50.0,
+            total_value: 2_000_000.0,
+            delegate_value: 1_000_000.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], false);
+
+        assert_eq!(message, "2000|0.1000%|36.50%|36.50%");
+
+        let message =
+            render_daily_summary(&create_test_notifier(), "2026-04-14", &node_details, &[], false);
+        assert!(message.ends_with(
+            "  📈 预估日收益：2000（日化 0.1000%）\n  💹 预估年化：验证人 36.50%｜委托人 36.50%"
+        ));
+    }
+
+    #[test]
+    fn test_daily_summary_marks_stale_node_details() {
+        let notifier = create_test_notifier();
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: None,
+            daily_reward_value: None,
+            daily_span_days: 1,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: true,
+            consecutive_failures: 4,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], false);
+
+        assert!(message.ends_with("  ⚠️ 数据状态：缓存数据（连续失败 4 次）"));
+    }
+
+    #[test]
+    fn test_custom_daily_summary_template_is_rendered() {
+        let notifier = create_test_notifier_with_templates(NotificationTemplates {
+            connection_failed: Template::builtin(DEFAULT_CONNECTION_FAILED_TEMPLATE),
+            connection_recovered: Template::builtin(DEFAULT_CONNECTION_RECOVERED_TEMPLATE),
+            connection_repeat: Template::builtin(DEFAULT_CONNECTION_REPEAT_TEMPLATE),
+            connection_escalated: Template::builtin(DEFAULT_CONNECTION_ESCALATED_TEMPLATE),
+            connection_flapping: Template::builtin(DEFAULT_CONNECTION_FLAPPING_TEMPLATE),
+            ranking_changed: Template::builtin(DEFAULT_RANKING_CHANGED_TEMPLATE),
+            quiet_summary: Template::builtin(DEFAULT_QUIET_SUMMARY_TEMPLATE),
+            daily_summary: Template::parse("{prefix} daily {date} count={count}\n{details}")
+                .expect("valid template"),
+            production_low: Template::builtin(DEFAULT_PRODUCTION_LOW_TEMPLATE),
+            production_drop: Template::builtin(DEFAULT_PRODUCTION_DROP_TEMPLATE),
+            stake_margin: Template::builtin(DEFAULT_STAKE_MARGIN_TEMPLATE),
+            commission_changed: Template::builtin(DEFAULT_COMMISSION_CHANGED_TEMPLATE),
+            benefit_address_changed: Template::builtin(DEFAULT_BENEFIT_ADDRESS_CHANGED_TEMPLATE),
+            delegator_changed: Template::builtin(DEFAULT_DELEGATOR_CHANGED_TEMPLATE),
+            alert_firing: Template::builtin(DEFAULT_ALERT_FIRING_TEMPLATE),
+            alert_resolved: Template::builtin(DEFAULT_ALERT_RESOLVED_TEMPLATE),
+        });
+        let node_details = vec![DailyNodeSummaryDetail {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            ranking: 5,
+            block_qty: 123,
+            reward_value: 45.6,
+            daily_block_qty: Some(12),
+            daily_reward_value: Some(5.6),
+            daily_span_days: 1,
+            show_monthly_totals: false,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: None,
+            reward_per: 0.0,
+            total_value: 0.0,
+            delegate_value: 0.0,
+        }];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], false);
+
+        assert_eq!(
+            message,
+            "[chaindash] daily 2026-04-14 count=1\n🔹 验证节点A\n  🧱 累计出块：123\n  💰 \
+             累计系统奖励：45.6\n  📅 当天出块：12\n  🎁 当天系统奖励：5.6"
+        );
+    }
+
+    #[test]
+    fn test_daily_summary_appends_watchlist_section() {
+        let notifier = create_test_notifier();
+        let watched = vec![
+            WatchedValidator {
+                node_id: "rival-a-id".to_string(),
+                node_name: "rival-a".to_string(),
+                ranking: 5,
+                total_value: 1_000.5,
+                reward_per: 20.0,
+                block_rate: "98.00%".to_string(),
+                last_updated_at: None,
+            },
+            WatchedValidator {
+                node_id: "rival-b-id".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &[], &watched, false);
+
+        let section = message.split_once("👀 关注节点\n").map(|(_, section)| section);
+        assert_eq!(
+            section,
+            Some(
+                "• rival-a #5｜质押 1000.5｜佣金 20.00%｜出块率 98.00%\n• rival-b-id #-｜质押 \
+                 0｜佣金 0.00%｜出块率 -"
+            )
+        );
+    }
+
+    #[test]
+    fn test_daily_summary_sorts_node_details_by_node_name() {
+        let notifier = create_test_notifier();
+        let node_details = vec![
+            DailyNodeSummaryDetail {
+                node_id: "node-c-id".to_string(),
+                node_name: "node-c".to_string(),
+                ranking: 1,
+                block_qty: 300,
+                reward_value: 30.0,
+                daily_block_qty: Some(30),
+                daily_reward_value: Some(3.0),
+                daily_span_days: 1,
+                show_monthly_totals: false,
+                monthly_block_qty: None,
+                monthly_reward_value: None,
+                stale: false,
+                consecutive_failures: 0,
+                estimated_daily_reward: None,
+                reward_per: 0.0,
+                total_value: 0.0,
+                delegate_value: 0.0,
+            },
+            DailyNodeSummaryDetail {
+                node_id: "node-a-id".to_string(),
+                node_name: "node-a".to_string(),
+                ranking: 9,
+                block_qty: 100,
+                reward_value: 10.0,
+                daily_block_qty: Some(10),
+                daily_reward_value: Some(1.0),
+                daily_span_days: 1,
+                show_monthly_totals: false,
+                monthly_block_qty: None,
+                monthly_reward_value: None,
+                stale: false,
+                consecutive_failures: 0,
+                estimated_daily_reward: None,
+                reward_per: 0.0,
+                total_value: 0.0,
+                delegate_value: 0.0,
+            },
+            DailyNodeSummaryDetail {
+                node_id: "node-b-id".to_string(),
+                node_name: "node-b".to_string(),
+                ranking: 3,
+                block_qty: 200,
+                reward_value: 20.0,
+                daily_block_qty: Some(20),
+                daily_reward_value: Some(2.0),
+                daily_span_days: 1,
+                show_monthly_totals: false,
+                monthly_block_qty: None,
+                monthly_reward_value: None,
+                stale: false,
+                consecutive_failures: 0,
+                estimated_daily_reward: None,
+                reward_per: 0.0,
+                total_value: 0.0,
+                delegate_value: 0.0,
+            },
+        ];
+
+        let message = render_daily_summary(&notifier, "2026-04-14", &node_details, &[], false);
+
+        assert_eq!(
+            message,
+            "📊 每日节点快照（2026-04-14）\n🧾 节点数：3\n🔹 node-a\n  🧱 累计出块：100\n  💰 \
+             累计系统奖励：10\n  📅 当天出块：10\n  🎁 当天系统奖励：1\n\n🔹 node-b\n  🧱 \
+             累计出块：200\n  💰 累计系统奖励：20\n  📅 当天出块：20\n  🎁 当天系统奖励：2\n\n🔹 \
+             node-c\n  🧱 累计出块：300\n  💰 累计系统奖励：30\n  📅 当天出块：30\n  🎁 \
+             当天系统奖励：3"
+        );
+    }
+
+    #[test]
+    fn test_default_production_low_template_is_rendered() {
+        let notifier = create_test_notifier();
+
+        let message = notifier
+            .render_production_low_message(
+                "验证节点A",
+                EpochProduction {
+                    epoch: 12,
+                    produced: 30,
+                    expected: 40,
+                },
+                0.8,
+            )
+            .text;
+
+        assert_eq!(
+            message,
+            "⚠️ 节点出块不足\n🔹 节点：验证节点A\n🧮 Epoch：12\n🧱 出块：30 / 40（75.00%）\n📉 \
+             阈值：80.00%"
+        );
+    }
+
+    #[test]
+    fn test_default_production_drop_template_is_rendered() {
+        let notifier = create_test_notifier();
+        let hour_start = Local
+            .with_ymd_and_hms(2026, 4, 14, 9, 0, 0)
+            .single()
+            .expect("timestamp should be valid");
+
+        let message = notifier
+            .render_production_drop_message(&ProductionDrop {
+                node_id: "node-a-id".to_string(),
+                node_name: "验证节点A".to_string(),
+                hour_start,
+                blocks: 2,
+                trailing_average: 10.5,
+                trailing_hours: 6,
+                ratio_threshold: 0.5,
+            })
+            .text;
+
+        assert_eq!(
+            message,
+            "📉 节点出块骤降\n🔹 节点：验证节点A\n🕒 时段：2026-04-14 09:00-10:00\n🧱 出块：2\n📊 \
+             近 6 小时均值：10.5\n📉 阈值：50.00%"
+        );
+
+        let filter = NotificationFilter::from_opts(&[NotifyEvent::Production]);
+        assert!(filter.allows(NotificationEventKind::ProductionDrop));
+        let filter = NotificationFilter::from_opts(&[NotifyEvent::ProductionLow]);
+        assert!(!filter.allows(NotificationEventKind::ProductionDrop));
+    }
+
+    #[test]
+    fn test_production_drop_alert_rearms_after_recovery() {
+        let mut state = NotificationState::default();
+
+        assert!(state.plan_production_drop_alert("node-a", true));
+        assert!(!state.plan_production_drop_alert("node-a", true));
+        assert!(!state.plan_production_drop_alert("node-a", false));
+        assert!(state.plan_production_drop_alert("node-a", true));
+    }
+
+    #[test]
+    fn test_block_production_alert_is_reported_once_per_epoch() {
+        let mut state = NotificationState::default();
+
+        assert!(state.plan_block_production_alert("node-a", 12));
+        assert!(!state.plan_block_production_alert("node-a", 12));
+        assert!(state.plan_block_production_alert("node-b", 12));
+        assert!(state.plan_block_production_alert("node-a", 13));
+    }
+
+    #[test]
+    fn test_default_stake_margin_template_is_rendered() {
+        let notifier = create_test_notifier();
+
+        let message = notifier
+            .render_stake_margin_message(
+                "验证节点A",
+                198,
+                RankTargetGap {
+                    target_rank: 201,
+                    within: true,
+                    margin: 1_250.5,
+                },
+                5_000.0,
+            )
+            .text;
+
+        assert_eq!(
+            message,
+            "⚠️ 质押安全边际不足\n🔹 节点：验证节点A\n📍 排名：198\n🎯 目标排名：201\n🛡️ \
+             安全边际：1250.5 LAT\n📉 阈值：5000 LAT"
+        );
+    }
+
+    #[test]
+    fn test_default_benefit_address_changed_template_is_rendered() {
+        let notifier = create_test_notifier();
+        let changed_at = Local
+            .with_ymd_and_hms(2026, 3, 1, 9, 30, 0)
+            .single()
+            .expect("timestamp should be valid");
+
+        let message = notifier
+            .render_node_detail_change_message(&NodeDetailChange {
+                node_id: "node-a-id".to_string(),
+                node_name: "验证节点A".to_string(),
+                kind: NodeDetailChangeKind::BenefitAddress,
+                previous: "lat1old".to_string(),
+                current: "lat1new".to_string(),
+                changed_at,
+            })
+            .text;
+
+        assert_eq!(
+            message,
+            "🚨【严重】节点收益地址变更\n🔹 节点：验证节点A\n📝 原地址：lat1old\n🆕 \
+             新地址：lat1new\n🕒 时间：2026-03-01 09:30:00\n⚠️ \
+             如非本人操作，请立即检查节点密钥安全"
+        );
+    }
+
+    #[test]
+    fn test_benefit_address_change_is_critical_but_commission_change_is_not() {
+        assert_eq!(
+            NotificationEventKind::BenefitAddressChanged.severity(),
+            NotificationSeverity::Critical
+        );
+        assert_ne!(
+            NotificationEventKind::CommissionChanged.severity(),
+            NotificationSeverity::Critical
+        );
+
+        let filter = NotificationFilter::from_opts(&[NotifyEvent::CommissionChanged]);
+        assert!(filter.allows(NotificationEventKind::CommissionChanged));
+        assert!(!filter.allows(NotificationEventKind::BenefitAddressChanged));
+    }
+
+    #[test]
+    fn test_only_escalations_and_benefit_address_changes_are_critical() {
+        for kind in NotificationEventKind::ALL {
+            let expected = matches!(
+                kind,
+                NotificationEventKind::BenefitAddressChanged
+                    | NotificationEventKind::ConnectionEscalated
+            );
+            assert_eq!(kind.severity() == NotificationSeverity::Critical, expected, "{kind:?}");
+        }
+        // Alert rules carry their own severity instead.
+        assert_eq!(NotificationEventKind::AlertFiring.severity(), NotificationSeverity::Warning);
+    }
+
+    #[test]
+    fn test_default_delegator_changed_template_is_rendered() {
+        let notifier = create_test_notifier();
+        let changed_at = Local
+            .with_ymd_and_hms(2026, 3, 1, 9, 30, 0)
+            .single()
+            .expect("timestamp should be valid");
+
+        let message = notifier
+            .render_delegator_change_message(&DelegatorChange {
+                node_id: "node-a-id".to_string(),
+                node_name: "验证节点A".to_string(),
+                address: "lat1whale".to_string(),
+                previous: 120_000.0,
+                current: 20_000.0,
+                changed_at,
+            })
+            .text;
+
+        assert_eq!(
+            message,
+            "💰 大额委托变动\n🔹 节点：验证节点A\n👤 委托人：lat1whale\n📝 减少委托：120000 → \
+             20000 LAT（-100000）\n🕒 时间：2026-03-01 09:30:00"
+        );
+
+        let filter = NotificationFilter::from_opts(&[NotifyEvent::Delegator]);
+        assert!(filter.allows(NotificationEventKind::DelegatorChanged));
+        assert!(!filter.allows(NotificationEventKind::CommissionChanged));
+    }
+
+    #[test]
+    fn test_stake_margin_alert_rearms_after_recovery() {
+        let mut state = NotificationState::default();
+
+        assert!(state.plan_stake_margin_alert("node-a:201", true));
+        assert!(!state.plan_stake_margin_alert("node-a:201", true));
+        assert!(!state.plan_stake_margin_alert("node-a:201", false));
+        assert!(state.plan_stake_margin_alert("node-a:201", true));
+    }
+
+    #[test]
+    fn test_incident_follow_ups_repeat_and_escalate_once() {
+        let policy = EscalationPolicy {
+            repeat_interval: Some(Duration::from_secs(10 * 60)),
+            escalate_after: Some(Duration::from_secs(30 * 60)),
+            chat_ids: Vec::new(),
+        };
+        let mut state = NotificationState::default();
+        let start = Instant::now();
+        let minutes = |minutes: u64| start + Duration::from_secs(minutes * 60);
+        state.open_incident("main", "ws://a", "rpc timeout", start);
+        assert!(state.mark_connection_failed("main@ws://a"));
+
+        assert!(state.plan_incident_follow_ups(&policy, minutes(9)).is_empty());
+        let reminder = state.plan_incident_follow_ups(&policy, minutes(10));
+        assert_eq!(
+            reminder,
+            vec![IncidentFollowUp {
+                connection_key: "main@ws://a".to_string(),
+                node_name: "main".to_string(),
+                node_url: "ws://a".to_string(),
+                reason: "rpc timeout".to_string(),
+                unresolved: Duration::from_secs(10 * 60),
+                reminder: 1,
+                escalate: false,
+            }]
+        );
+        assert!(state.plan_incident_follow_ups(&policy, minutes(15)).is_empty());
+
+        // A missed reminder is not replayed, and escalation happens once.
+        let escalation = state.plan_incident_follow_ups(&policy, minutes(31));
+        assert_eq!(escalation.len(), 1);
+        assert!(escalation[0].escalate);
+        assert_eq!(escalation[0].reminder, 3);
+        assert!(!state.plan_incident_follow_ups(&policy, minutes(40))[0].escalate);
+
+        // Recovery closes the incident; the next failure starts a new one.
+        assert!(state.mark_connection_recovered("main@ws://a"));
+        assert!(state.plan_incident_follow_ups(&policy, minutes(60)).is_empty());
+        state.open_incident("main", "ws://a", "eof", minutes(60));
+        assert!(state.plan_incident_follow_ups(&policy, minutes(65)).is_empty());
+    }
+
+    type DeliveredEvents = Arc<Mutex<Vec<(NotificationEventKind, Vec<String>)>>>;
+
+    #[derive(Debug, Default)]
+    struct RecordingBackend {
+        delivered: DeliveredEvents,
+    }
+
+    impl NotifierBackend for RecordingBackend {
+        fn deliver<'a>(
+            &'a self,
+            notification: &'a Notification,
+        ) -> BoxFuture<'a, ()> {
+            self.delivered
+                .lock()
+                .expect("mutex poisoned")
+                .push((notification.kind, notification.chat_ids.clone()));
+            Box::pin(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_escalation_bypasses_mute_and_goes_to_escalation_chats() {
+        let backend = RecordingBackend::default();
+        let delivered = Arc::clone(&backend.delivered);
+        let mut notifier = create_test_notifier();
+        notifier.backends.push(Box::new(backend));
+        notifier.policy.escalation = EscalationPolicy {
+            repeat_interval: Some(Duration::from_secs(60)),
+            escalate_after: Some(Duration::ZERO),
+            chat_ids: vec!["-100200".to_string()],
+        };
+
+        notifier.notify_node_connection_failed("main", "ws://127.0.0.1:6789", "rpc timeout").await;
+        notifier.mute_for(Duration::from_secs(60 * 60));
+        notifier.follow_up_unresolved_incidents().await;
+        notifier.follow_up_unresolved_incidents().await;
+
+        assert_eq!(
+            *delivered.lock().expect("mutex poisoned"),
+            vec![
+                (NotificationEventKind::ConnectionFailed, Vec::new()),
+                (NotificationEventKind::ConnectionEscalated, vec!["-100200".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incident_reminders_are_not_rate_limited() {
+        let backend = RecordingBackend::default();
+        let delivered = Arc::clone(&backend.delivered);
+        let mut notifier = create_test_notifier();
+        notifier.backends.push(Box::new(backend));
+        notifier.policy.rate_limit = Duration::from_secs(60 * 60);
+        notifier.policy.escalation = EscalationPolicy {
+            repeat_interval: Some(Duration::from_secs(60)),
+            escalate_after: None,
+            chat_ids: Vec::new(),
+        };
+
+        notifier.notify_node_connection_failed("ops@main", "ws://127.0.0.1:6789", "eof").await;
+        for _ in 0..2 {
+            for incident in
+                notifier.state.lock().expect("mutex poisoned").connection_incidents.values_mut()
+            {
+                incident.since =
+                    incident.since.checked_sub(Duration::from_secs(60)).expect("instant");
+            }
+            notifier.follow_up_unresolved_incidents().await;
+        }
+
+        assert_eq!(
+            *delivered.lock().expect("mutex poisoned"),
+            vec![(NotificationEventKind::ConnectionFailed, Vec::new()); 3]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_critical_notifications_are_not_rate_limited() {
+        let backend = RecordingBackend::default();
+        let delivered = Arc::clone(&backend.delivered);
+        let mut notifier = create_test_notifier();
+        notifier.backends.push(Box::new(backend));
+        notifier.policy.rate_limit = Duration::from_secs(60 * 60);
+        let change = |kind, current: &str| NodeDetailChange {
+            node_id: "node-a-id".to_string(),
+            node_name: "验证节点A".to_string(),
+            kind,
+            previous: "old".to_string(),
+            current: current.to_string(),
+            changed_at: Local::now(),
+        };
+
+        for current in ["1", "2"] {
+            notifier
+                .notify_node_detail_change(&change(NodeDetailChangeKind::Commission, current))
+                .await;
+        }
+        for current in ["lat1a", "lat1b"] {
+            notifier
+                .notify_node_detail_change(&change(NodeDetailChangeKind::BenefitAddress, current))
+                .await;
+        }
+
+        assert_eq!(
+            *delivered.lock().expect("mutex poisoned"),
+            vec![
+                (NotificationEventKind::CommissionChanged, Vec::new()),
+                (NotificationEventKind::BenefitAddressChanged, Vec::new()),
+                (NotificationEventKind::BenefitAddressChanged, Vec::new()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_damping_drops_blips_and_collapses_flapping_connections() {
+        let backend = RecordingBackend::default();
+        let delivered = Arc::clone(&backend.delivered);
+        let mut notifier = create_test_notifier();
+        notifier.backends.push(Box::new(backend));
+        notifier.policy.damping = DampingPolicy::from_opts(&Opts::parse_from([
+            "test",
+            "--notify-failure-threshold",
+            "2",
+            "--notify-flap-threshold",
+            "3",
+        ]));
+        let url = "ws://127.0.0.1:6789";
+
+        // A single failed poll is a blip.
+        notifier.notify_node_connection_failed("main", url, "rpc timeout").await;
+        notifier.notify_node_connection_recovered("main", url).await;
+        // Two in a row are reported, and so is the recovery.
+        notifier.notify_node_connection_failed("main", url, "rpc timeout").await;
+        notifier.notify_node_connection_failed("main", url, "rpc timeout").await;
+        notifier.notify_node_connection_recovered("main", url).await;
+        // The third failure streak within the window starts flapping, which
+        // holds back further failures and recoveries.
+        notifier.notify_node_connection_failed("main", url, "rpc timeout").await;
+        notifier.notify_node_connection_recovered("main", url).await;
+        notifier.notify_node_connection_failed("main", url, "rpc timeout").await;
+        notifier.notify_node_connection_failed("main", url, "rpc timeout").await;
+        notifier.notify_node_connection_recovered("main", url).await;
+
+        assert_eq!(
+            *delivered.lock().expect("mutex poisoned"),
+            vec![
+                (NotificationEventKind::ConnectionFailed, Vec::new()),
+                (NotificationEventKind::ConnectionRecovered, Vec::new()),
+                (NotificationEventKind::ConnectionFlapping, Vec::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_connection_flapping_template_is_rendered() {
+        let notifier = create_test_notifier();
+
+        assert_eq!(
+            notifier.render_connection_flapping_message("main", "rpc timeout", 4, 10).text,
+            "🔁 节点连接频繁抖动\n🔹 节点：main\n🔄 10 分钟内断开 4 次\n⚠️ 最近原因：rpc timeout"
+        );
+    }
+
+    fn alert_transition(
+        state: AlertState,
+        severity: AlertSeverity,
+    ) -> AlertTransition {
+        AlertTransition {
+            rule: "disk-full".to_string(),
+            metric: AlertMetric::Disk,
+            target: "/opt".to_string(),
+            severity,
+            state,
+            value: "93.5%".to_string(),
+            condition: "disk > 90.0%".to_string(),
+            duration: Duration::from_secs(150),
+        }
+    }
+
+    #[test]
+    fn test_default_alert_templates_are_rendered() {
+        let notifier = create_test_notifier();
+
+        assert_eq!(
+            notifier
+                .render_alert_message(&alert_transition(
+                    AlertState::Firing,
+                    AlertSeverity::Critical
+                ))
+                .text,
+            "🔔 告警触发：disk-full\n🔹 对象：/opt\n📈 当前值：93.5%\n📏 条件：disk > 90.0%\n🏷️ \
+             级别：严重"
+        );
+        assert_eq!(
+            notifier
+                .render_alert_message(&alert_transition(
+                    AlertState::Resolved,
+                    AlertSeverity::Critical
+                ))
+                .text,
+            "✅ 告警已恢复：disk-full\n🔹 对象：/opt\n📈 当前值：93.5%\n⏱️ 持续：2 分钟"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_critical_alert_rules_bypass_mute() {
+        let backend = RecordingBackend::default();
+        let delivered = Arc::clone(&backend.delivered);
+        let mut notifier = create_test_notifier();
+        notifier.backends.push(Box::new(backend));
+
+        notifier.mute_for(Duration::from_secs(60 * 60));
+        notifier
+            .notify_alert_transition(&alert_transition(AlertState::Firing, AlertSeverity::Critical))
+            .await;
+        notifier
+            .notify_alert_transition(&alert_transition(AlertState::Firing, AlertSeverity::Warning))
+            .await;
+        notifier
+            .notify_alert_transition(&alert_transition(
+                AlertState::Resolved,
+                AlertSeverity::Critical,
+            ))
+            .await;
+
+        assert_eq!(
+            *delivered.lock().expect("mutex poisoned"),
+            vec![(NotificationEventKind::AlertFiring, Vec::new())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mute_buffers_notifications_until_unmuted() {
+        let notifier = create_test_notifier();
+        let connection_failed = || {
+            Notification::new(
+                NotificationEventKind::ConnectionFailed,
+                "main",
+                "🚨 节点连接异常".to_string(),
+                NotificationData::Connection {
+                    node: "main".to_string(),
+                    url: "ws://127.0.0.1:6789".to_string(),
+                    reason: None,
+                },
+            )
+        };
+
+        notifier.mute_for(Duration::from_secs(60));
+        notifier.send_if_enabled("connection:main", connection_failed()).await;
+        {
+            let mut state = notifier.state.lock().expect("notifier mutex poisoned");
+            assert!(!state.is_muted_at(Instant::now() + Duration::from_secs(61)));
+            assert_eq!(
+                state.take_quiet_summary_snapshot().map(|snapshot| snapshot.total_count),
+                Some(1)
+            );
+        }
+
+        assert!(notifier.unmute());
+        assert!(!notifier.unmute());
+        notifier.send_if_enabled("connection:main", connection_failed()).await;
+        assert!(notifier
+            .state
+            .lock()
+            .expect("notifier mutex poisoned")
+            .take_quiet_summary_snapshot()
+            .is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_suppresses_repeated_delivery_within_window() {
+        let mut state = NotificationState::default();
+        let now = Instant::now();
+        let rate_limit = Duration::from_secs(60);
+
+        assert!(state.allow_delivery("ranking-changed:node-a", now, rate_limit));
+        assert!(!state.allow_delivery(
+            "ranking-changed:node-a",
+            now + Duration::from_secs(59),
+            rate_limit,
+        ));
+        assert!(state.allow_delivery(
+            "ranking-changed:node-a",
+            now + Duration::from_secs(60),
+            rate_limit,
+        ));
+    }
+}
@@ -0,0 +1,467 @@
+use std::{
+    sync::Mutex,
+    time::Duration,
+};
+
+use chrono::{
+    DateTime,
+    Local,
+    TimeZone,
+    Utc,
+};
+use rusqlite::{
+    params,
+    Connection,
+};
+
+use super::NotificationEventKind;
+use crate::{
+    db::{
+        open_connection,
+        run_migrations,
+    },
+    error::Result,
+    sync::lock_or_panic,
+};
+
+/// Delay before the second attempt; each later attempt doubles it.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(10);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+/// Audit rows, and dead-lettered messages, kept; older ones are pruned as
+/// new ones are written.
+const AUDIT_LOG_RETENTION: i64 = 1000;
+/// Characters of the first message line kept as the audit title.
+const AUDIT_TITLE_MAX_CHARS: usize = 120;
+
+/// Outcome of one delivery attempt, as shown in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DeliveryStatus {
+    Delivered,
+    Retrying,
+    DeadLetter,
+}
+
+impl DeliveryStatus {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Retrying => "retrying",
+            Self::DeadLetter => "dead_letter",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::Delivered, Self::Retrying, Self::DeadLetter]
+            .into_iter()
+            .find(|status| status.name() == name)
+    }
+}
+
+/// One audit log row.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DeliveryRecord {
+    pub channel: String,
+    pub recipient: String,
+    pub event: String,
+    pub title: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub recorded_at: DateTime<Local>,
+}
+
+/// Why an attempt failed and when the channel allows the next one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DeliveryFailure {
+    pub error: String,
+    /// Wait requested by the server, e.g. Telegram's `retry_after`.
+    pub retry_after: Option<Duration>,
+    /// False for errors a retry cannot fix, such as an unknown chat.
+    pub retryable: bool,
+}
+
+/// A message waiting in the outbox for one recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct OutboxEntry {
+    id: i64,
+    pub recipient: String,
+    event: String,
+    pub text: String,
+    attempts: u32,
+}
+
+/// Delay before the next attempt after `attempts` failed ones: exponential,
+/// unless the server asked for a specific wait.
+fn retry_delay(
+    attempts: u32,
+    retry_after: Option<Duration>,
+) -> Duration {
+    retry_after.unwrap_or_else(|| {
+        let factor = 1u32.checked_shl(attempts.saturating_sub(1)).unwrap_or(u32::MAX);
+        RETRY_BASE_DELAY.saturating_mul(factor).min(RETRY_MAX_DELAY)
+    })
+}
+
+fn audit_title(text: &str) -> String {
+    text.lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .take(AUDIT_TITLE_MAX_CHARS)
+        .collect()
+}
+
+/// Durable per-recipient queue for one channel, kept in the `--db-path`
+/// database. A message stays queued until it is delivered or has failed
+/// `max_attempts` times, and then moves to the dead-letter queue. Every
+/// attempt outcome is written to the audit log.
+#[derive(Debug)]
+pub(super) struct NotificationOutbox {
+    conn: Mutex<Connection>,
+    channel: &'static str,
+    max_attempts: u32,
+}
+
+impl NotificationOutbox {
+    pub(super) fn open(
+        db_path: &str,
+        channel: &'static str,
+        max_attempts: u32,
+    ) -> Result<Self> {
+        let mut conn = open_connection(db_path)?;
+        run_migrations(&mut conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            channel,
+            max_attempts: max_attempts.max(1),
+        })
+    }
+
+    pub(super) fn enqueue(
+        &self,
+        recipient: &str,
+        event: NotificationEventKind,
+        text: &str,
+    ) -> Result<()> {
+        let now = Utc::now().timestamp();
+        lock_or_panic(&self.conn).execute(
+            "INSERT INTO notification_outbox
+                 (channel, recipient, event, text, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![self.channel, recipient, event.name(), text, now],
+        )?;
+        Ok(())
+    }
+
+    /// Queued messages whose next attempt is due, oldest first. A message
+    /// waits while an older one to the same recipient is backing off, so
+    /// each recipient gets its messages in order.
+    pub(super) fn due_entries(&self) -> Result<Vec<OutboxEntry>> {
+        let conn = lock_or_panic(&self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT id, recipient, event, text, attempts FROM notification_outbox AS entry
+             WHERE channel = ?1 AND dead_at IS NULL AND next_attempt_at <= ?2
+               AND NOT EXISTS (
+                   SELECT 1 FROM notification_outbox AS older
+                   WHERE older.channel = entry.channel AND older.recipient = entry.recipient
+                     AND older.dead_at IS NULL AND older.id < entry.id
+                     AND older.next_attempt_at > ?2
+               )
+             ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![self.channel, Utc::now().timestamp()], |row| {
+            Ok(OutboxEntry {
+                id: row.get(0)?,
+                recipient: row.get(1)?,
+                event: row.get(2)?,
+                text: row.get(3)?,
+                attempts: row.get(4)?,
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    pub(super) fn mark_delivered(
+        &self,
+        entry: &OutboxEntry,
+    ) -> Result<()> {
+        let mut conn = lock_or_panic(&self.conn);
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM notification_outbox WHERE id = ?1", params![entry.id])?;
+        self.write_audit(&tx, entry, DeliveryStatus::Delivered, entry.attempts + 1, None)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Schedules the next attempt, or dead-letters the message when it is out
+    /// of attempts or the failure is permanent.
+    pub(super) fn mark_failed(
+        &self,
+        entry: &OutboxEntry,
+        failure: &DeliveryFailure,
+    ) -> Result<DeliveryStatus> {
+        let attempts = entry.attempts + 1;
+        let now = Utc::now().timestamp();
+        let status = if failure.retryable && attempts < self.max_attempts {
+            DeliveryStatus::Retrying
+        } else {
+            DeliveryStatus::DeadLetter
+        };
+        let next_attempt_at =
+            now.saturating_add(retry_delay(attempts, failure.retry_after).as_secs() as i64);
+        let dead_at = (status == DeliveryStatus::DeadLetter).then_some(now);
+
+        let mut conn = lock_or_panic(&self.conn);
+        let tx = conn.transaction()?;
+        tx.execute(
+            "UPDATE notification_outbox
+             SET attempts = ?2, next_attempt_at = ?3, last_error = ?4, dead_at = ?5
+             WHERE id = ?1",
+            params![entry.id, attempts, next_attempt_at, failure.error, dead_at],
+        )?;
+        if status == DeliveryStatus::DeadLetter {
+            tx.execute(
+                "DELETE FROM notification_outbox WHERE dead_at IS NOT NULL AND id NOT IN
+                     (SELECT id FROM notification_outbox WHERE dead_at IS NOT NULL
+                      ORDER BY id DESC LIMIT ?1)",
+                params![AUDIT_LOG_RETENTION],
+            )?;
+        }
+        self.write_audit(&tx, entry, status, attempts, Some(&failure.error))?;
+        tx.commit()?;
+        Ok(status)
+    }
+
+    fn write_audit(
+        &self,
+        conn: &Connection,
+        entry: &OutboxEntry,
+        status: DeliveryStatus,
+        attempts: u32,
+        error: Option<&str>,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO notification_audit_log
+                 (channel, recipient, event, title, status, attempts, error, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                self.channel,
+                entry.recipient,
+                entry.event,
+                audit_title(&entry.text),
+                status.name(),
+                attempts,
+                error,
+                Utc::now().timestamp()
+            ],
+        )?;
+        conn.execute(
+            "DELETE FROM notification_audit_log WHERE id <= ?1",
+            params![conn.last_insert_rowid() - AUDIT_LOG_RETENTION],
+        )?;
+        Ok(())
+    }
+
+    /// The latest `limit` audit rows of every channel, newest first.
+    pub(super) fn recent_deliveries(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<DeliveryRecord>> {
+        let conn = lock_or_panic(&self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT channel, recipient, event, title, status, attempts, error, recorded_at
+             FROM notification_audit_log ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            let status: String = row.get(4)?;
+            let recorded_at: i64 = row.get(7)?;
+            Ok(DeliveryRecord {
+                channel: row.get(0)?,
+                recipient: row.get(1)?,
+                event: row.get(2)?,
+                title: row.get(3)?,
+                status: DeliveryStatus::from_name(&status).unwrap_or(DeliveryStatus::DeadLetter),
+                attempts: row.get(5)?,
+                error: row.get(6)?,
+                recorded_at: Local
+                    .timestamp_opt(recorded_at, 0)
+                    .single()
+                    .unwrap_or_else(Local::now),
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    #[cfg(test)]
+    fn dead_letter_count(&self) -> i64 {
+        lock_or_panic(&self.conn)
+            .query_row(
+                "SELECT COUNT(*) FROM notification_outbox WHERE dead_at IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .expect("count should work")
+    }
+
+    #[cfg(test)]
+    fn next_attempt_in(
+        &self,
+        entry: &OutboxEntry,
+    ) -> Option<i64> {
+        use rusqlite::OptionalExtension;
+
+        lock_or_panic(&self.conn)
+            .query_row(
+                "SELECT next_attempt_at FROM notification_outbox WHERE id = ?1",
+                params![entry.id],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()
+            .expect("query should work")
+            .map(|next_attempt_at| next_attempt_at - Utc::now().timestamp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outbox(max_attempts: u32) -> NotificationOutbox {
+        NotificationOutbox::open(":memory:", "telegram", max_attempts).expect("outbox opens")
+    }
+
+    fn failure(retry_after: Option<Duration>) -> DeliveryFailure {
+        DeliveryFailure {
+            error: "HTTP 429 Too Many Requests".to_string(),
+            retry_after,
+            retryable: true,
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_and_honours_retry_after() {
+        assert_eq!(retry_delay(1, None), Duration::from_secs(10));
+        assert_eq!(retry_delay(3, None), Duration::from_secs(40));
+        assert_eq!(retry_delay(40, None), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(3, Some(Duration::from_secs(7))), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn test_delivered_message_leaves_outbox_and_is_audited() {
+        let outbox = outbox(3);
+        outbox
+            .enqueue("10001", NotificationEventKind::ConnectionFailed, "\n🚨 连接失败\ndetails")
+            .expect("enqueue should work");
+
+        let entries = outbox.due_entries().expect("due entries should load");
+        assert_eq!(entries.len(), 1);
+        outbox.mark_delivered(&entries[0]).expect("delivered should be recorded");
+
+        assert!(outbox.due_entries().expect("due entries should load").is_empty());
+        let records = outbox.recent_deliveries(10).expect("audit should load");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].title, "🚨 连接失败");
+        assert_eq!(records[0].event, "connection_failed");
+        assert_eq!(records[0].status, DeliveryStatus::Delivered);
+        assert_eq!(records[0].attempts, 1);
+    }
+
+    #[test]
+    fn test_failed_message_waits_for_retry_after_then_dead_letters() {
+        let outbox = outbox(2);
+        outbox
+            .enqueue("10001", NotificationEventKind::RankingChanged, "排名变化")
+            .expect("enqueue should work");
+        let entry = outbox.due_entries().expect("due entries should load").remove(0);
+
+        let status = outbox
+            .mark_failed(&entry, &failure(Some(Duration::from_secs(30))))
+            .expect("failure should be recorded");
+
+        assert_eq!(status, DeliveryStatus::Retrying);
+        assert!(outbox.due_entries().expect("due entries should load").is_empty());
+        assert!(matches!(outbox.next_attempt_in(&entry), Some(29..=30)));
+
+        let retried = OutboxEntry {
+            attempts: 1,
+            ..entry
+        };
+        let status = outbox.mark_failed(&retried, &failure(None)).expect("failure is recorded");
+
+        assert_eq!(status, DeliveryStatus::DeadLetter);
+        assert_eq!(outbox.dead_letter_count(), 1);
+        let records = outbox.recent_deliveries(10).expect("audit should load");
+        assert_eq!(
+            records.iter().map(|record| record.status).collect::<Vec<_>>(),
+            vec![DeliveryStatus::DeadLetter, DeliveryStatus::Retrying]
+        );
+        assert_eq!(records[0].error.as_deref(), Some("HTTP 429 Too Many Requests"));
+    }
+
+    #[test]
+    fn test_newer_message_waits_behind_backing_off_one() {
+        let outbox = outbox(3);
+        outbox
+            .enqueue("10001", NotificationEventKind::RankingChanged, "first")
+            .expect("enqueue should work");
+        let first = outbox.due_entries().expect("due entries should load").remove(0);
+        outbox.mark_failed(&first, &failure(None)).expect("failure should be recorded");
+        outbox
+            .enqueue("10001", NotificationEventKind::RankingChanged, "second")
+            .expect("enqueue should work");
+        outbox
+            .enqueue("20002", NotificationEventKind::RankingChanged, "other chat")
+            .expect("enqueue should work");
+
+        let due = outbox.due_entries().expect("due entries should load");
+
+        assert_eq!(due.iter().map(|entry| entry.text.as_str()).collect::<Vec<_>>(), ["other chat"]);
+    }
+
+    #[test]
+    fn test_permanent_failure_dead_letters_immediately() {
+        let outbox = outbox(5);
+        outbox
+            .enqueue("10001", NotificationEventKind::DailySummary, "日报")
+            .expect("enqueue should work");
+        let entry = outbox.due_entries().expect("due entries should load").remove(0);
+
+        let status = outbox
+            .mark_failed(
+                &entry,
+                &DeliveryFailure {
+                    error: "chat not found".to_string(),
+                    retry_after: None,
+                    retryable: false,
+                },
+            )
+            .expect("failure should be recorded");
+
+        assert_eq!(status, DeliveryStatus::DeadLetter);
+        assert_eq!(outbox.dead_letter_count(), 1);
+    }
+
+    #[test]
+    fn test_dead_letters_are_pruned_like_the_audit_log() {
+        let outbox = outbox(1);
+        for index in 0..=AUDIT_LOG_RETENTION {
+            outbox
+                .enqueue("10001", NotificationEventKind::DailySummary, &format!("日报 {index}"))
+                .expect("enqueue should work");
+            let entry = outbox.due_entries().expect("due entries should load").remove(0);
+            outbox.mark_failed(&entry, &failure(None)).expect("failure should be recorded");
+        }
+
+        assert_eq!(outbox.dead_letter_count(), AUDIT_LOG_RETENTION);
+        let oldest: String = lock_or_panic(&outbox.conn)
+            .query_row("SELECT text FROM notification_outbox ORDER BY id LIMIT 1", [], |row| {
+                row.get(0)
+            })
+            .expect("a dead letter should remain");
+        assert_eq!(oldest, "日报 1");
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    sync::Mutex,
+    time::Duration,
+};
+
+use chrono::{
+    Local,
+    TimeZone,
+};
+
+use super::{
+    load_template,
+    NotificationPolicy,
+    NotificationState,
+    NotificationTemplates,
+    Notifier,
+    QuietSummarySnapshot,
+    RankingChange,
+    RenderedMessage,
+    SummaryReport,
+    Template,
+};
+use crate::{
+    collect::{
+        AlertMetric,
+        AlertSeverity,
+        AlertState,
+        AlertTransition,
+        DailyNodeSummaryDetail,
+        DelegatorChange,
+        EpochProduction,
+        NodeDetailChange,
+        NodeDetailChangeKind,
+        ProductionDrop,
+        RankTargetGap,
+        SummaryPeriod,
+        WatchedValidator,
+    },
+    error::Result,
+    opts::{
+        Opts,
+        TelegramParseMode,
+        TemplateArgs,
+        TemplateCommand,
+        TemplateName,
+    },
+};
+
+const SAMPLE_NODE: &str = "platon-main";
+const SAMPLE_NODE_ID: &str = "0x3b53564afbc3aef1f6e0678171811f65a7caa27a927ddd036a46f817d075ef0a";
+const SAMPLE_DATE: &str = "2026-03-01";
+
+/// Runs `chaindash template ...`.
+pub(crate) fn run(
+    opts: &Opts,
+    args: &TemplateArgs,
+) -> Result<()> {
+    match &args.command {
+        TemplateCommand::Preview { name, template } => {
+            println!("{}", preview(opts, *name, template.as_deref())?);
+            Ok(())
+        },
+    }
+}
+
+/// Renders the `name` template with sample data, the way Telegram would
+/// receive it in `--telegram-parse-mode`.
+fn preview(
+    opts: &Opts,
+    name: TemplateName,
+    template: Option<&str>,
+) -> Result<String> {
+    let mut templates = NotificationTemplates::from_opts(opts)?;
+    if let Some(template) = template {
+        *templates.get_mut(name) = load_template("--template", template)?;
+    }
+    let notifier = Notifier {
+        policy: NotificationPolicy::from_opts(opts),
+        templates,
+        report_templates: Vec::new(),
+        backends: Vec::new(),
+        state: Mutex::new(NotificationState::default()),
+    };
+
+    let message = sample_message(&notifier, name);
+    Ok(render(&message, opts.telegram_parse_mode))
+}
+
+fn render(
+    message: &RenderedMessage,
+    mode: TelegramParseMode,
+) -> String {
+    match &message.template {
+        Some(template) => template.render(&message.context, mode),
+        None => super::template::escape(&message.text, mode).into_owned(),
+    }
+}
+
+impl NotificationTemplates {
+    fn get_mut(
+        &mut self,
+        name: TemplateName,
+    ) -> &mut Template {
+        match name {
+            TemplateName::ConnectionFailed => &mut self.connection_failed,
+            TemplateName::ConnectionRecovered => &mut self.connection_recovered,
+            TemplateName::ConnectionRepeat => &mut self.connection_repeat,
+            TemplateName::ConnectionEscalated => &mut self.connection_escalated,
+            TemplateName::ConnectionFlapping => &mut self.connection_flapping,
+            TemplateName::RankingChanged => &mut self.ranking_changed,
+            TemplateName::QuietSummary => &mut self.quiet_summary,
+            TemplateName::DailySummary => &mut self.daily_summary,
+            TemplateName::ProductionLow => &mut self.production_low,
+            TemplateName::ProductionDrop => &mut self.production_drop,
+            TemplateName::StakeMargin => &mut self.stake_margin,
+            TemplateName::CommissionChanged => &mut self.commission_changed,
+            TemplateName::BenefitAddressChanged => &mut self.benefit_address_changed,
+            TemplateName::DelegatorChanged => &mut self.delegator_changed,
+            TemplateName::AlertFiring => &mut self.alert_firing,
+            TemplateName::AlertResolved => &mut self.alert_resolved,
+        }
+    }
+}
+
+fn sample_message(
+    notifier: &Notifier,
+    name: TemplateName,
+) -> RenderedMessage {
+    let changed_at =
+        Local.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).single().unwrap_or_else(Local::now);
+    let reason = "error sending request: connection refused";
+
+    match name {
+        TemplateName::ConnectionFailed => {
+            notifier.render_connection_failed_message(SAMPLE_NODE, reason)
+        },
+        TemplateName::ConnectionRecovered => {
+            notifier.render_connection_recovered_message(SAMPLE_NODE)
+        },
+        TemplateName::ConnectionRepeat => {
+            notifier.render_connection_follow_up_message(SAMPLE_NODE, reason, 45, false)
+        },
+        TemplateName::ConnectionEscalated => {
+            notifier.render_connection_follow_up_message(SAMPLE_NODE, reason, 150, true)
+        },
+        TemplateName::ConnectionFlapping => {
+            notifier.render_connection_flapping_message(SAMPLE_NODE, reason, 4, 10)
+        },
+        TemplateName::RankingChanged => notifier.render_ranking_changed_message(
+            SAMPLE_NODE,
+            RankingChange {
+                previous: 27,
+                current: 24,
+            },
+        ),
+        TemplateName::QuietSummary => {
+            notifier.render_quiet_summary_message(&QuietSummarySnapshot {
+                total_count: 3,
+                details: "• 连接异常 2 条：platon-main、platon-backup\n• 排名变动 1 \
+                          条：platon-main"
+                    .to_string(),
+            })
+        },
+        TemplateName::DailySummary => {
+            let node_details = sample_node_details();
+            let watched_validators = [WatchedValidator {
+                node_id: "0x1f0e2d3c".to_string(),
+                node_name: "friendly-validator".to_string(),
+                ranking: 31,
+                total_value: 1_250_000.0,
+                reward_per: 20.0,
+                block_rate: "98.50%".to_string(),
+                last_updated_at: None,
+            }];
+            notifier.render_summary_report_message(&SummaryReport {
+                schedule_index: 0,
+                period: SummaryPeriod::Daily,
+                date: SAMPLE_DATE,
+                node_details: &node_details,
+                watched_validators: &watched_validators,
+                late: false,
+                chat_ids: &[],
+            })
+        },
+        TemplateName::ProductionLow => notifier.render_production_low_message(
+            SAMPLE_NODE,
+            EpochProduction {
+                epoch: 1024,
+                produced: 7,
+                expected: 10,
+            },
+            0.8,
+        ),
+        TemplateName::ProductionDrop => notifier.render_production_drop_message(&ProductionDrop {
+            node_id: SAMPLE_NODE_ID.to_string(),
+            node_name: SAMPLE_NODE.to_string(),
+            hour_start: changed_at,
+            blocks: 3,
+            trailing_average: 12.5,
+            trailing_hours: 24,
+            ratio_threshold: 0.5,
+        }),
+        TemplateName::StakeMargin => notifier.render_stake_margin_message(
+            SAMPLE_NODE,
+            24,
+            RankTargetGap {
+                target_rank: 25,
+                within: true,
+                margin: 8_500.0,
+            },
+            10_000.0,
+        ),
+        TemplateName::CommissionChanged | TemplateName::BenefitAddressChanged => {
+            let (kind, previous, current) = if name == TemplateName::CommissionChanged {
+                (NodeDetailChangeKind::Commission, "10.00%", "20.00%")
+            } else {
+                (
+                    NodeDetailChangeKind::BenefitAddress,
+                    "lat1zqf3fy0ur3w5rwwqkvp2lrfn8wsl9lks0g4dgv",
+                    "lat1x8f6x2xwc7mzhk3kqk8kfcqd9v7uz4m0z6j7ka",
+                )
+            };
+            notifier.render_node_detail_change_message(&NodeDetailChange {
+                node_id: SAMPLE_NODE_ID.to_string(),
+                node_name: SAMPLE_NODE.to_string(),
+                kind,
+                previous: previous.to_string(),
+                current: current.to_string(),
+                changed_at,
+            })
+        },
+        TemplateName::DelegatorChanged => {
+            notifier.render_delegator_change_message(&DelegatorChange {
+                node_id: SAMPLE_NODE_ID.to_string(),
+                node_name: SAMPLE_NODE.to_string(),
+                address: "lat1m4mz0rr9k5l3d7qwq2v3s8t6lq0u2y7xkj5d2f".to_string(),
+                previous: 50_000.0,
+                current: 150_000.0,
+                changed_at,
+            })
+        },
+        TemplateName::AlertFiring | TemplateName::AlertResolved => {
+            let (state, duration) = if name == TemplateName::AlertFiring {
+                (AlertState::Firing, Duration::from_secs(300))
+            } else {
+                (AlertState::Resolved, Duration::from_secs(1_800))
+            };
+            notifier.render_alert_message(&AlertTransition {
+                rule: "disk-usage".to_string(),
+                metric: AlertMetric::Disk,
+                target: SAMPLE_NODE.to_string(),
+                severity: AlertSeverity::Warning,
+                state,
+                value: "91.2%".to_string(),
+                condition: "disk > 90% for 5m".to_string(),
+                duration,
+            })
+        },
+    }
+}
+
+fn sample_node_details() -> Vec<DailyNodeSummaryDetail> {
+    vec![
+        DailyNodeSummaryDetail {
+            node_id: SAMPLE_NODE_ID.to_string(),
+            node_name: SAMPLE_NODE.to_string(),
+            ranking: 24,
+            block_qty: 1_284_310,
+            reward_value: 2_563_418.5,
+            daily_block_qty: Some(320),
+            daily_reward_value: Some(1_640.25),
+            daily_span_days: 1,
+            show_monthly_totals: true,
+            monthly_block_qty: Some(9_650),
+            monthly_reward_value: Some(49_210.75),
+            stale: false,
+            consecutive_failures: 0,
+            estimated_daily_reward: Some(1_655.0),
+            reward_per: 20.0,
+            total_value: 3_200_000.0,
+            delegate_value: 2_100_000.0,
+        },
+        DailyNodeSummaryDetail {
+            node_id: "0x8c5a0d2e".to_string(),
+            node_name: "platon-backup".to_string(),
+            ranking: 112,
+            block_qty: 48_020,
+            reward_value: 95_600.0,
+            daily_block_qty: None,
+            daily_reward_value: None,
+            daily_span_days: 1,
+            show_monthly_totals: true,
+            monthly_block_qty: None,
+            monthly_reward_value: None,
+            stale: true,
+            consecutive_failures: 3,
+            estimated_daily_reward: None,
+            reward_per: 50.0,
+            total_value: 150_000.0,
+            delegate_value: 0.0,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_preview_renders_every_template_with_sample_data() {
+        let opts = Opts::parse_from(["test"]);
+
+        for name in <TemplateName as clap::ValueEnum>::value_variants() {
+            let rendered = preview(&opts, *name, None).expect("built-in templates render");
+            assert!(!rendered.contains('{'), "{name:?} left a placeholder: {rendered}");
+        }
+    }
+
+    #[test]
+    fn test_preview_renders_an_override_in_the_parse_mode() {
+        let opts = Opts::parse_from(["test", "--telegram-parse-mode", "html"]);
+
+        let rendered = preview(
+            &opts,
+            TemplateName::DailySummary,
+            Some(
+                "<b>{date}</b>\\n{% for node in nodes %}{node.name}: {node.daily_blocks | \
+                 default(\"-\")}{% if not loop.last %}, {% endif %}{% endfor %}",
+            ),
+        )
+        .expect("template should render");
+        assert_eq!(rendered, "<b>2026-03-01</b>\nplaton-backup: -, platon-main: 320");
+
+        assert!(preview(&opts, TemplateName::DailySummary, Some("{% if x %}")).is_err());
+    }
+}
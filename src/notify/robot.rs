@@ -0,0 +1,405 @@
+use std::time::Duration;
+
+use base64::{
+    engine::general_purpose::STANDARD as BASE64,
+    Engine,
+};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use hmac::{
+    Hmac,
+    Mac,
+};
+use log::warn;
+use serde::Deserialize;
+use serde_json::{
+    json,
+    Value,
+};
+use sha2::Sha256;
+
+use super::{
+    display_url,
+    split_title,
+    trimmed_option,
+    trimmed_values,
+    validate_http_urls,
+    Notification,
+    NotificationSeverity,
+    NotifierBackend,
+};
+use crate::{
+    error::{
+        ChaindashError,
+        Result,
+    },
+    opts::Opts,
+};
+
+/// Chat platforms whose group robots accept notifications through an
+/// incoming webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RobotPlatform {
+    DingTalk,
+    Feishu,
+    WeCom,
+}
+
+impl RobotPlatform {
+    fn option_name(self) -> &'static str {
+        match self {
+            Self::DingTalk => "dingtalk",
+            Self::Feishu => "feishu",
+            Self::WeCom => "wecom",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::DingTalk => "钉钉",
+            Self::Feishu => "飞书",
+            Self::WeCom => "企业微信",
+        }
+    }
+}
+
+/// Errors are reported in the body with HTTP 200: `errcode`/`errmsg` on
+/// DingTalk and WeCom, `code`/`msg` (or the legacy `StatusCode`) on Feishu.
+#[derive(Debug, Default, Deserialize)]
+struct RobotResponse {
+    errcode: Option<i64>,
+    errmsg: Option<String>,
+    code: Option<i64>,
+    msg: Option<String>,
+    #[serde(rename = "StatusCode")]
+    status_code: Option<i64>,
+}
+
+impl RobotResponse {
+    fn error(self) -> Option<(i64, String)> {
+        let code = self.errcode.or(self.code).or(self.status_code).unwrap_or(0);
+        if code == 0 {
+            return None;
+        }
+
+        Some((code, self.errmsg.or(self.msg).unwrap_or_default()))
+    }
+}
+
+/// Sends notifications to DingTalk, Feishu/Lark or WeCom group robots,
+/// using each platform's markdown or card format.
+#[derive(Debug)]
+pub(super) struct RobotBackend {
+    platform: RobotPlatform,
+    client: reqwest::Client,
+    urls: Vec<String>,
+    secret: Option<String>,
+}
+
+impl RobotBackend {
+    /// One backend per platform with at least one robot URL configured.
+    pub(super) fn from_opts(opts: &Opts) -> Result<Vec<Self>> {
+        let platforms = [
+            (RobotPlatform::DingTalk, &opts.dingtalk_webhook, opts.dingtalk_secret.as_deref()),
+            (RobotPlatform::Feishu, &opts.feishu_webhook, opts.feishu_secret.as_deref()),
+            (RobotPlatform::WeCom, &opts.wecom_webhook, None),
+        ];
+        let mut backends = Vec::new();
+
+        for (platform, urls, secret) in platforms {
+            let urls = trimmed_values(urls);
+            let secret = trimmed_option(secret).map(str::to_string);
+
+            if urls.is_empty() {
+                if secret.is_some() {
+                    return Err(ChaindashError::Other(format!(
+                        "{name} webhook is required when {name} secret is set",
+                        name = platform.option_name()
+                    )));
+                }
+                continue;
+            }
+
+            validate_http_urls(platform.option_name(), &urls)?;
+
+            backends.push(Self {
+                platform,
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(opts.webhook_timeout_seconds))
+                    .build()?,
+                urls,
+                secret,
+            });
+        }
+
+        Ok(backends)
+    }
+
+    async fn send(
+        &self,
+        notification: &Notification,
+    ) {
+        let body = render_body(self.platform, notification);
+
+        for url in &self.urls {
+            let mut body = body.clone();
+            let mut request = self.client.post(url);
+            if let Some(secret) = &self.secret {
+                match self.platform {
+                    RobotPlatform::DingTalk => {
+                        let timestamp = Utc::now().timestamp_millis();
+                        let sign = sign_dingtalk(secret, timestamp);
+                        request =
+                            request.query(&[("timestamp", timestamp.to_string()), ("sign", sign)]);
+                    },
+                    RobotPlatform::Feishu => {
+                        let timestamp = Utc::now().timestamp();
+                        body["timestamp"] = Value::String(timestamp.to_string());
+                        body["sign"] = Value::String(sign_feishu(secret, timestamp));
+                    },
+                    RobotPlatform::WeCom => {},
+                }
+            }
+
+            self.post(url, request.json(&body)).await;
+        }
+    }
+
+    async fn post(
+        &self,
+        url: &str,
+        request: reqwest::RequestBuilder,
+    ) {
+        let label = self.platform.label();
+
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                let response_text = response.text().await.unwrap_or_default();
+                if !status.is_success() {
+                    warn!(
+                        "{}机器人请求失败，url {}，HTTP {}：{}",
+                        label,
+                        display_url(url),
+                        status,
+                        response_text.trim()
+                    );
+                    return;
+                }
+
+                let body =
+                    serde_json::from_str::<RobotResponse>(&response_text).unwrap_or_default();
+                if let Some((code, message)) = body.error() {
+                    warn!(
+                        "{}机器人返回错误，url {}，错误码 {}：{}",
+                        label,
+                        display_url(url),
+                        code,
+                        message
+                    );
+                }
+            },
+            Err(err) => {
+                warn!("发送{}机器人消息失败，url {}：{}", label, display_url(url), err);
+            },
+        }
+    }
+}
+
+impl NotifierBackend for RobotBackend {
+    fn deliver<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(self.send(notification))
+    }
+}
+
+fn render_body(
+    platform: RobotPlatform,
+    notification: &Notification,
+) -> Value {
+    let (title, details) = split_title(&notification.text);
+    let severity = notification.severity;
+
+    match platform {
+        // DingTalk markdown needs a blank line for each line break.
+        RobotPlatform::DingTalk => json!({
+            "msgtype": "markdown",
+            "markdown": {
+                "title": title,
+                "text": format!(
+                    "#### {title}\n\n{}",
+                    details.lines().collect::<Vec<_>>().join("\n\n")
+                ),
+            },
+        }),
+        RobotPlatform::Feishu => json!({
+            "msg_type": "interactive",
+            "card": {
+                "header": {
+                    "title": { "tag": "plain_text", "content": title },
+                    "template": match severity {
+                        NotificationSeverity::Critical => "red",
+                        NotificationSeverity::Warning => "orange",
+                        NotificationSeverity::Info => "blue",
+                    },
+                },
+                "elements": [
+                    { "tag": "div", "text": { "tag": "lark_md", "content": details } },
+                ],
+            },
+        }),
+        RobotPlatform::WeCom => {
+            let color = match severity {
+                NotificationSeverity::Critical | NotificationSeverity::Warning => "warning",
+                NotificationSeverity::Info => "info",
+            };
+
+            json!({
+                "msgtype": "markdown",
+                "markdown": {
+                    "content": format!("### <font color=\"{color}\">{title}</font>\n{details}"),
+                },
+            })
+        },
+    }
+}
+
+fn hmac_sha256_base64(
+    key: &[u8],
+    message: &[u8],
+) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(message);
+
+    BASE64.encode(mac.finalize().into_bytes())
+}
+
+/// DingTalk signs `"{timestamp}\n{secret}"` with the secret as the key;
+/// `timestamp` is in milliseconds.
+fn sign_dingtalk(
+    secret: &str,
+    timestamp: i64,
+) -> String {
+    hmac_sha256_base64(secret.as_bytes(), format!("{timestamp}\n{secret}").as_bytes())
+}
+
+/// Feishu uses `"{timestamp}\n{secret}"` itself as the key over an empty
+/// message; `timestamp` is in seconds.
+fn sign_feishu(
+    secret: &str,
+    timestamp: i64,
+) -> String {
+    hmac_sha256_base64(format!("{timestamp}\n{secret}").as_bytes(), b"")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::notify::{
+        NotificationData,
+        NotificationEventKind,
+    };
+
+    fn connection_failed() -> Notification {
+        Notification::new(
+            NotificationEventKind::ConnectionFailed,
+            "main",
+            "🚨 节点连接异常\n🔹 节点：main\n📝 原因：rpc timeout".to_string(),
+            NotificationData::Connection {
+                node: "main".to_string(),
+                url: "ws://127.0.0.1:6789".to_string(),
+                reason: Some("rpc timeout".to_string()),
+            },
+        )
+    }
+
+    #[test]
+    fn test_robot_signatures_match_platform_algorithms() {
+        assert_eq!(
+            sign_dingtalk("SECxyz", 1_700_000_000_000),
+            "0PUR1j8g85Xg3vlFV/UrEcxXfF5HpCAGzcjrNfyJoyg="
+        );
+        assert_eq!(
+            sign_feishu("SECxyz", 1_700_000_000),
+            "GnU9dobz1WousiKlH0LX6FYP8yrWNCgoJOvRhpMc8HY="
+        );
+    }
+
+    #[test]
+    fn test_dingtalk_body_uses_markdown_with_blank_line_breaks() {
+        let body = render_body(RobotPlatform::DingTalk, &connection_failed());
+
+        assert_eq!(body["msgtype"], "markdown");
+        assert_eq!(body["markdown"]["title"], "🚨 节点连接异常");
+        assert_eq!(
+            body["markdown"]["text"],
+            "#### 🚨 节点连接异常\n\n🔹 节点：main\n\n📝 原因：rpc timeout"
+        );
+    }
+
+    #[test]
+    fn test_feishu_body_uses_card_colored_by_severity() {
+        let body = render_body(RobotPlatform::Feishu, &connection_failed());
+
+        assert_eq!(body["msg_type"], "interactive");
+        assert_eq!(body["card"]["header"]["title"]["content"], "🚨 节点连接异常");
+        assert_eq!(body["card"]["header"]["template"], "orange");
+        assert_eq!(
+            body["card"]["elements"][0]["text"]["content"],
+            "🔹 节点：main\n📝 原因：rpc timeout"
+        );
+    }
+
+    #[test]
+    fn test_wecom_body_uses_markdown_with_colored_title() {
+        let body = render_body(RobotPlatform::WeCom, &connection_failed());
+
+        assert_eq!(body["msgtype"], "markdown");
+        assert_eq!(
+            body["markdown"]["content"],
+            "### <font color=\"warning\">🚨 节点连接异常</font>\n🔹 节点：main\n📝 原因：rpc \
+             timeout"
+        );
+    }
+
+    #[test]
+    fn test_robot_response_reports_platform_error_codes() {
+        let dingtalk: RobotResponse =
+            serde_json::from_str(r#"{"errcode":310000,"errmsg":"sign not match"}"#)
+                .expect("response should parse");
+        let feishu: RobotResponse = serde_json::from_str(
+            r#"{"StatusCode":0,"StatusMessage":"success","code":0,"msg":"success"}"#,
+        )
+        .expect("response should parse");
+
+        assert_eq!(dingtalk.error(), Some((310000, "sign not match".to_string())));
+        assert_eq!(feishu.error(), None);
+    }
+
+    #[test]
+    fn test_robot_backends_are_created_per_platform() {
+        let opts = Opts::parse_from([
+            "test",
+            "--dingtalk-webhook",
+            "https://oapi.dingtalk.com/robot/send?access_token=a",
+            "--dingtalk-secret",
+            "SECxyz",
+            "--wecom-webhook",
+            "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=b",
+        ]);
+
+        let backends = RobotBackend::from_opts(&opts).expect("config should parse");
+
+        assert_eq!(backends.len(), 2);
+        assert_eq!(backends[0].platform, RobotPlatform::DingTalk);
+        assert_eq!(backends[0].secret.as_deref(), Some("SECxyz"));
+        assert_eq!(backends[1].platform, RobotPlatform::WeCom);
+        assert!(
+            RobotBackend::from_opts(&Opts::parse_from(["test", "--feishu-secret", "s"])).is_err()
+        );
+    }
+}
@@ -0,0 +1,555 @@
+use std::{
+    sync::Mutex,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use chrono::Utc;
+use rusqlite::{
+    params,
+    Connection,
+};
+
+use super::{
+    ConnectionIncident,
+    ConnectionState,
+    NotificationEventKind,
+    NotificationState,
+};
+use crate::{
+    db::{
+        open_connection,
+        run_migrations,
+    },
+    error::Result,
+    sync::lock_or_panic,
+};
+
+/// Notifier state kept in the `--db-path` database, so a restart neither
+/// repeats connection, block-production or stake-margin alerts nor loses the
+/// ranking baseline, rate limits or a pending quiet-hours summary. Every change is written through as it
+/// happens.
+#[derive(Debug)]
+pub(super) struct NotificationStateStore {
+    conn: Mutex<Connection>,
+}
+
+impl NotificationStateStore {
+    pub(super) fn open(db_path: &str) -> Result<Self> {
+        let mut conn = open_connection(db_path)?;
+        run_migrations(&mut conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Drop entries not written within `max_age` and load the rest. Unchanged
+    /// connection states and rankings are written again periodically while
+    /// they are observed, so only entries no longer seen expire.
+    pub(super) fn load(
+        &self,
+        max_age: Duration,
+    ) -> Result<NotificationState> {
+        let now = Instant::now();
+        let now_unix = Utc::now().timestamp();
+        let oldest = now_unix.saturating_sub(max_age.as_secs() as i64);
+        let mut conn = lock_or_panic(&self.conn);
+
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM notification_connection_states WHERE updated_at < ?1",
+            params![oldest],
+        )?;
+        tx.execute("DELETE FROM notification_rankings WHERE updated_at < ?1", params![oldest])?;
+        tx.execute("DELETE FROM notification_rate_limits WHERE sent_at < ?1", params![oldest])?;
+        tx.execute(
+            "DELETE FROM notification_quiet_events WHERE recorded_at < ?1",
+            params![oldest],
+        )?;
+        tx.execute(
+            "DELETE FROM notification_stake_margin_alerts WHERE updated_at < ?1",
+            params![oldest],
+        )?;
+        tx.execute(
+            "DELETE FROM notification_production_drop_alerts WHERE updated_at < ?1",
+            params![oldest],
+        )?;
+        tx.execute(
+            "DELETE FROM notification_production_alert_epochs WHERE updated_at < ?1",
+            params![oldest],
+        )?;
+        // An incident lives as long as its connection is known to be down,
+        // however long ago it opened.
+        tx.execute(
+            "DELETE FROM notification_incidents WHERE connection_key NOT IN
+                 (SELECT connection_key FROM notification_connection_states WHERE healthy = 0)",
+            [],
+        )?;
+        tx.commit()?;
+
+        let mut state = NotificationState::default();
+        {
+            let mut stmt =
+                conn.prepare("SELECT connection_key, healthy FROM notification_connection_states")?;
+            let rows =
+                stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)))?;
+            for row in rows {
+                let (key, healthy) = row?;
+                let connection_state = if healthy {
+                    ConnectionState::Healthy
+                } else {
+                    ConnectionState::Unhealthy
+                };
+                state.connection_states.insert(key, connection_state);
+            }
+        }
+        {
+            let mut stmt = conn.prepare(
+                "SELECT connection_key, node_name, node_url, reason, failed_at, reminders_sent,
+                     escalated
+                 FROM notification_incidents",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, u32>(5)?,
+                    row.get::<_, bool>(6)?,
+                ))
+            })?;
+            for row in rows {
+                let (key, node_name, node_url, reason, failed_at, reminders_sent, escalated) = row?;
+                if state.connection_states.get(&key) != Some(&ConnectionState::Unhealthy) {
+                    continue;
+                }
+                // Incidents stored before the node columns existed only have
+                // the `NAME@URL` key.
+                let (node_name, node_url) = if node_name.is_empty() && node_url.is_empty() {
+                    match key.split_once('@') {
+                        Some((node_name, node_url)) => {
+                            (node_name.to_string(), node_url.to_string())
+                        },
+                        None => continue,
+                    }
+                } else {
+                    (node_name, node_url)
+                };
+                let age = Duration::from_secs(now_unix.saturating_sub(failed_at).max(0) as u64);
+                state.connection_incidents.insert(
+                    key,
+                    ConnectionIncident {
+                        node_name,
+                        node_url,
+                        reason,
+                        since: now.checked_sub(age).unwrap_or(now),
+                        reminders_sent,
+                        escalated,
+                    },
+                );
+            }
+        }
+        {
+            let mut stmt = conn.prepare("SELECT node_id, ranking FROM notification_rankings")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            for row in rows {
+                let (node_id, ranking) = row?;
+                state.last_rankings.insert(node_id, ranking);
+            }
+        }
+        {
+            let mut stmt =
+                conn.prepare("SELECT alert_key FROM notification_stake_margin_alerts")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            for row in rows {
+                state.stake_margin_alerts.insert(row?);
+            }
+        }
+        {
+            let mut stmt =
+                conn.prepare("SELECT node_id FROM notification_production_drop_alerts")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            for row in rows {
+                state.production_drop_alerts.insert(row?);
+            }
+        }
+        {
+            let mut stmt =
+                conn.prepare("SELECT node_id, epoch FROM notification_production_alert_epochs")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)?)))?;
+            for row in rows {
+                let (node_id, epoch) = row?;
+                state.production_alert_epochs.insert(node_id, epoch as u64);
+            }
+        }
+        {
+            let mut stmt =
+                conn.prepare("SELECT rate_limit_key, sent_at FROM notification_rate_limits")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)?)))?;
+            for row in rows {
+                let (key, sent_at) = row?;
+                let age = Duration::from_secs(now_unix.saturating_sub(sent_at).max(0) as u64);
+                state.last_sent_at.insert(key, now.checked_sub(age).unwrap_or(now));
+            }
+        }
+        {
+            let mut stmt =
+                conn.prepare("SELECT event, subject FROM notification_quiet_events ORDER BY id")?;
+            let rows =
+                stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+            for row in rows {
+                let (event, subject) = row?;
+                if let Some(event) = NotificationEventKind::from_name(&event) {
+                    state.quiet_summary.record(event, &subject);
+                }
+            }
+        }
+
+        Ok(state)
+    }
+
+    pub(super) fn save_connection_state(
+        &self,
+        key: &str,
+        connection_state: ConnectionState,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_connection_states
+                 (connection_key, healthy, updated_at)
+             VALUES (?1, ?2, ?3)",
+            params![key, connection_state == ConnectionState::Healthy, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// The failure time is stored as wall-clock time, like `sent_at`.
+    pub(super) fn save_incident(
+        &self,
+        key: &str,
+        incident: &ConnectionIncident,
+    ) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let age = Instant::now().saturating_duration_since(incident.since);
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_incidents
+                 (connection_key, node_name, node_url, reason, failed_at, reminders_sent,
+                  escalated, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                key,
+                incident.node_name,
+                incident.node_url,
+                incident.reason,
+                now - age.as_secs() as i64,
+                incident.reminders_sent,
+                incident.escalated,
+                now
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn delete_incident(
+        &self,
+        key: &str,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "DELETE FROM notification_incidents WHERE connection_key = ?1",
+            params![key],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn save_ranking(
+        &self,
+        node_id: &str,
+        ranking: i32,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_rankings (node_id, ranking, updated_at)
+             VALUES (?1, ?2, ?3)",
+            params![node_id, ranking, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn save_stake_margin_alert(
+        &self,
+        key: &str,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_stake_margin_alerts (alert_key, updated_at)
+             VALUES (?1, ?2)",
+            params![key, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn delete_stake_margin_alert(
+        &self,
+        key: &str,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "DELETE FROM notification_stake_margin_alerts WHERE alert_key = ?1",
+            params![key],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn save_production_drop_alert(
+        &self,
+        node_id: &str,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_production_drop_alerts (node_id, updated_at)
+             VALUES (?1, ?2)",
+            params![node_id, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn delete_production_drop_alert(
+        &self,
+        node_id: &str,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "DELETE FROM notification_production_drop_alerts WHERE node_id = ?1",
+            params![node_id],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn save_production_alert_epoch(
+        &self,
+        node_id: &str,
+        epoch: u64,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_production_alert_epochs
+                 (node_id, epoch, updated_at)
+             VALUES (?1, ?2, ?3)",
+            params![node_id, epoch as i64, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// `sent_at` is stored as wall-clock time, since an `Instant` does not
+    /// survive a restart.
+    pub(super) fn save_sent_at(
+        &self,
+        rate_limit_key: &str,
+        sent_at: Instant,
+    ) -> Result<()> {
+        let age = Instant::now().saturating_duration_since(sent_at);
+        lock_or_panic(&self.conn).execute(
+            "INSERT OR REPLACE INTO notification_rate_limits (rate_limit_key, sent_at)
+             VALUES (?1, ?2)",
+            params![rate_limit_key, Utc::now().timestamp() - age.as_secs() as i64],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn record_quiet_event(
+        &self,
+        event: NotificationEventKind,
+        subject: &str,
+    ) -> Result<()> {
+        lock_or_panic(&self.conn).execute(
+            "INSERT INTO notification_quiet_events (event, subject, recorded_at)
+             VALUES (?1, ?2, ?3)",
+            params![event.name(), subject, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn clear_quiet_events(&self) -> Result<()> {
+        lock_or_panic(&self.conn).execute("DELETE FROM notification_quiet_events", [])?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn backdate(
+        &self,
+        seconds: i64,
+    ) {
+        let conn = lock_or_panic(&self.conn);
+        conn.execute_batch(&format!(
+            "UPDATE notification_connection_states SET updated_at = updated_at - {seconds};
+             UPDATE notification_rankings SET updated_at = updated_at - {seconds};
+             UPDATE notification_rate_limits SET sent_at = sent_at - {seconds};
+             UPDATE notification_quiet_events SET recorded_at = recorded_at - {seconds};
+             UPDATE notification_stake_margin_alerts SET updated_at = updated_at - {seconds};
+             UPDATE notification_production_drop_alerts SET updated_at = updated_at - {seconds};
+             UPDATE notification_production_alert_epochs SET updated_at = updated_at - {seconds};
+             UPDATE notification_incidents SET failed_at = failed_at - {seconds},
+                 updated_at = updated_at - {seconds};"
+        ))
+        .expect("backdating should work");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::STATE_REFRESH_INTERVAL;
+
+    const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn restored(store: NotificationStateStore) -> NotificationState {
+        let mut state = store.load(MAX_AGE).expect("state should load");
+        state.store = Some(store);
+        state
+    }
+
+    #[test]
+    fn test_notification_state_survives_a_restart() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        let now = Instant::now();
+
+        assert!(state.mark_connection_failed("main@ws://a"));
+        state.open_incident("main", "ws://a", "rpc timeout", now);
+        assert!(state.plan_ranking_change("node-a", 5).is_none());
+        assert!(state.allow_delivery("ranking-changed:node-a", now, Duration::from_secs(600)));
+        state.record_quiet_summary(NotificationEventKind::RankingChanged, "node-a");
+
+        // Reload from the same database, as a restarted process would.
+        let store = state.store.take().expect("store is attached");
+        let mut state = restored(store);
+
+        assert!(!state.mark_connection_failed("main@ws://a"));
+        assert_eq!(
+            state.connection_incidents.get("main@ws://a").map(|incident| incident.reason.as_str()),
+            Some("rpc timeout")
+        );
+        assert_eq!(state.plan_ranking_change("node-a", 4).map(|change| change.previous), Some(5));
+        assert!(!state.allow_delivery(
+            "ranking-changed:node-a",
+            Instant::now(),
+            Duration::from_secs(600)
+        ));
+        assert_eq!(
+            state.take_quiet_summary_snapshot().map(|snapshot| snapshot.total_count),
+            Some(1)
+        );
+
+        let store = state.store.take().expect("store is attached");
+        assert!(restored(store).take_quiet_summary_snapshot().is_none());
+    }
+
+    #[test]
+    fn test_long_held_state_is_refreshed_and_survives_a_restart() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        assert!(state.mark_connection_failed("main@ws://a"));
+        assert!(state.plan_ranking_change("node-a", 5).is_none());
+
+        // Both values hold for longer than the max age and keep being
+        // observed after the refresh interval.
+        let store = state.store.take().expect("store is attached");
+        store.backdate(MAX_AGE.as_secs() as i64 + 1);
+        state.store = Some(store);
+        for refreshed_at in state.refreshed_at.values_mut() {
+            *refreshed_at = Instant::now().checked_sub(STATE_REFRESH_INTERVAL).expect("instant");
+        }
+        assert!(!state.mark_connection_failed("main@ws://a"));
+        assert!(state.plan_ranking_change("node-a", 5).is_none());
+
+        let store = state.store.take().expect("store is attached");
+        let mut state = restored(store);
+
+        assert_eq!(state.plan_ranking_change("node-a", 4).map(|change| change.previous), Some(5));
+        assert!(state.mark_connection_recovered("main@ws://a"));
+    }
+
+    #[test]
+    fn test_open_incident_outlives_max_age_until_recovery() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        assert!(state.mark_connection_failed("ops@main@ws://a"));
+        state.open_incident("ops@main", "ws://a", "rpc timeout", Instant::now());
+
+        let store = state.store.take().expect("store is attached");
+        store.backdate(MAX_AGE.as_secs() as i64 + 1);
+        state.store = Some(store);
+        state.refreshed_at.clear();
+        assert!(!state.mark_connection_failed("ops@main@ws://a"));
+
+        let store = state.store.take().expect("store is attached");
+        let mut state = restored(store);
+        let incident =
+            state.connection_incidents.get("ops@main@ws://a").expect("incident is still open");
+        assert_eq!(
+            (incident.node_name.as_str(), incident.node_url.as_str()),
+            ("ops@main", "ws://a")
+        );
+
+        assert!(state.mark_connection_recovered("ops@main@ws://a"));
+        let store = state.store.take().expect("store is attached");
+        assert!(restored(store).connection_incidents.is_empty());
+    }
+
+    #[test]
+    fn test_stake_margin_alert_is_not_repeated_after_a_restart() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        assert!(state.plan_stake_margin_alert("stake-margin:node-a:201", true));
+        assert!(state.plan_stake_margin_alert("stake-margin:node-b:201", true));
+        assert!(!state.plan_stake_margin_alert("stake-margin:node-b:201", false));
+
+        let store = state.store.take().expect("store is attached");
+        let mut state = restored(store);
+
+        assert!(!state.plan_stake_margin_alert("stake-margin:node-a:201", true));
+        assert!(state.plan_stake_margin_alert("stake-margin:node-b:201", true));
+    }
+
+    #[test]
+    fn test_production_drop_alert_is_not_repeated_after_a_restart() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        assert!(state.plan_production_drop_alert("node-a", true));
+        assert!(state.plan_production_drop_alert("node-b", true));
+        assert!(!state.plan_production_drop_alert("node-b", false));
+
+        let store = state.store.take().expect("store is attached");
+        let mut state = restored(store);
+
+        assert!(!state.plan_production_drop_alert("node-a", true));
+        assert!(state.plan_production_drop_alert("node-b", true));
+    }
+
+    #[test]
+    fn test_low_production_alert_is_not_repeated_in_the_same_epoch_after_a_restart() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        assert!(state.plan_block_production_alert("node-a", 12));
+
+        let store = state.store.take().expect("store is attached");
+        let mut state = restored(store);
+
+        assert!(!state.plan_block_production_alert("node-a", 12));
+        assert!(state.plan_block_production_alert("node-a", 13));
+    }
+
+    #[test]
+    fn test_stale_notification_state_expires_on_load() {
+        let mut state = restored(NotificationStateStore::open(":memory:").expect("store opens"));
+        assert!(state.mark_connection_failed("main@ws://a"));
+        state.plan_ranking_change("node-a", 5);
+        state.record_quiet_summary(NotificationEventKind::ConnectionFailed, "main");
+        state.plan_stake_margin_alert("stake-margin:node-a:201", true);
+        state.plan_production_drop_alert("node-a", true);
+        state.plan_block_production_alert("node-a", 12);
+
+        let store = state.store.take().expect("store is attached");
+        store.backdate(MAX_AGE.as_secs() as i64 + 1);
+        let mut state = restored(store);
+
+        assert!(state.connection_states.is_empty());
+        assert!(state.last_rankings.is_empty());
+        assert!(state.stake_margin_alerts.is_empty());
+        assert!(state.production_drop_alerts.is_empty());
+        assert!(state.production_alert_epochs.is_empty());
+        assert!(state.take_quiet_summary_snapshot().is_none());
+    }
+}
@@ -0,0 +1,957 @@
+use std::{
+    collections::HashSet,
+    time::Duration,
+};
+
+use chrono::Utc;
+use futures::future::BoxFuture;
+use log::{
+    debug,
+    warn,
+};
+use reqwest::StatusCode;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    load_template,
+    outbox::{
+        DeliveryFailure,
+        DeliveryRecord,
+        DeliveryStatus,
+        NotificationOutbox,
+    },
+    template::Template,
+    trimmed_option,
+    trimmed_values,
+    Notification,
+    NotificationEventKind,
+    NotificationFilter,
+    NotifierBackend,
+};
+use crate::{
+    collect::node_ids_match,
+    error::{
+        ChaindashError,
+        Result,
+    },
+    opts::{
+        Opts,
+        TelegramParseMode,
+    },
+};
+
+/// Chats that receive the notifications selected by `events`, in place of
+/// the default chats.
+#[derive(Debug, Clone)]
+struct TelegramRoute {
+    events: NotificationFilter,
+    /// Node names or IDs; every node when empty.
+    nodes: Vec<String>,
+    chat_ids: Vec<String>,
+    template: Option<Template>,
+}
+
+impl TelegramRoute {
+    /// Quiet-hours digests cover every event, so no route selects them.
+    fn matches(
+        &self,
+        notification: &Notification,
+    ) -> bool {
+        if notification.kind == NotificationEventKind::QuietSummary
+            || !self.events.allows(notification.kind)
+        {
+            return false;
+        }
+
+        if self.nodes.is_empty() {
+            return true;
+        }
+        let Some((name, node_id)) = notification.data.node() else {
+            return false;
+        };
+        self.nodes.iter().any(|node| {
+            node == name || node_id.is_some_and(|node_id| node_ids_match(node, node_id))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TelegramConfig {
+    bot_token: String,
+    chat_ids: Vec<String>,
+    routes: Vec<TelegramRoute>,
+    parse_mode: TelegramParseMode,
+    api_url: String,
+}
+
+impl TelegramConfig {
+    fn from_opts(opts: &Opts) -> Result<Option<Self>> {
+        let bot_token = trimmed_option(opts.telegram_bot_token.as_deref());
+        let chat_ids = trimmed_values(&opts.telegram_chat_id);
+        let routes = opts
+            .telegram_routes
+            .iter()
+            .map(|route| {
+                Ok(TelegramRoute {
+                    events: NotificationFilter::from_opts(&route.events),
+                    nodes: trimmed_values(&route.nodes),
+                    chat_ids: trimmed_values(&route.chat_ids),
+                    template: route
+                        .template
+                        .as_deref()
+                        .map(|template| load_template("--telegram-route", template))
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match (bot_token, chat_ids.is_empty() && routes.is_empty()) {
+            (None, true) => Ok(None),
+            (Some(_), true) => Err(ChaindashError::Other(
+                "at least one telegram chat id is required when telegram bot token is set"
+                    .to_string(),
+            )),
+            (None, false) => Err(ChaindashError::Other(
+                "telegram bot token is required when telegram chat id is set".to_string(),
+            )),
+            (Some(bot_token), false) => {
+                let api_url = opts.telegram_api_url.trim().trim_end_matches('/').to_string();
+                if api_url.is_empty() {
+                    return Err(ChaindashError::Other(
+                        "telegram api url cannot be empty when telegram notifications are enabled"
+                            .to_string(),
+                    ));
+                }
+
+                Ok(Some(Self {
+                    bot_token: bot_token.to_string(),
+                    chat_ids,
+                    routes,
+                    parse_mode: opts.telegram_parse_mode,
+                    api_url,
+                }))
+            },
+        }
+    }
+
+    fn send_message_url(&self) -> String {
+        format!("{}/bot{}/sendMessage", self.api_url, self.bot_token)
+    }
+
+    fn get_updates_url(&self) -> String {
+        format!("{}/bot{}/getUpdates", self.api_url, self.bot_token)
+    }
+}
+
+/// Seconds Telegram holds a getUpdates request open while waiting for
+/// messages.
+const LONG_POLL_TIMEOUT_SECONDS: u64 = 30;
+/// Telegram rejects messages longer than this many characters.
+const MESSAGE_LENGTH_LIMIT: usize = 4096;
+/// Channel name in the outbox and audit log.
+const CHANNEL_NAME: &str = "telegram";
+
+#[derive(Debug, Serialize)]
+struct TelegramSendMessageRequest<'a> {
+    chat_id: &'a str,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_mode: Option<&'static str>,
+}
+
+/// The Bot API `parse_mode` value; plain text sends none.
+fn api_parse_mode(mode: TelegramParseMode) -> Option<&'static str> {
+    match mode {
+        TelegramParseMode::Plain => None,
+        TelegramParseMode::Html => Some("HTML"),
+        TelegramParseMode::MarkdownV2 => Some("MarkdownV2"),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramSendMessageResponse {
+    ok: bool,
+    description: Option<String>,
+    parameters: Option<TelegramResponseParameters>,
+}
+
+impl TelegramSendMessageResponse {
+    /// The wait Telegram asks for after `429 Too Many Requests`.
+    fn retry_after(&self) -> Option<Duration> {
+        self.parameters
+            .as_ref()
+            .and_then(|parameters| parameters.retry_after)
+            .map(Duration::from_secs)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramResponseParameters {
+    retry_after: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramGetUpdatesResponse {
+    ok: bool,
+    #[serde(default)]
+    result: Vec<TelegramUpdate>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramUpdate {
+    update_id: i64,
+    message: Option<TelegramMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramMessage {
+    /// Unix time the message was sent.
+    date: i64,
+    chat: TelegramChat,
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramChat {
+    id: i64,
+}
+
+/// A text message received from one of the configured chats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TelegramCommand {
+    pub chat_id: String,
+    pub text: String,
+}
+
+/// Sends the rendered text of each notification through the Telegram Bot
+/// API. With an outbox attached, each chat's copy is queued first and
+/// retried until delivered or dead-lettered.
+#[derive(Debug)]
+pub(super) struct TelegramBackend {
+    client: reqwest::Client,
+    config: TelegramConfig,
+    outbox: Option<NotificationOutbox>,
+    /// Keeps concurrent deliveries from sending the same due message twice.
+    flush_lock: tokio::sync::Mutex<()>,
+}
+
+impl TelegramBackend {
+    pub(super) fn from_opts(opts: &Opts) -> Result<Option<Self>> {
+        Ok(TelegramConfig::from_opts(opts)?.map(|config| Self {
+            client: reqwest::Client::new(),
+            config,
+            outbox: None,
+            flush_lock: tokio::sync::Mutex::new(()),
+        }))
+    }
+
+    /// Without `--telegram-chat-id`, notifications no route matches, quiet
+    /// summaries included, have no chat and are dropped.
+    pub(super) fn warn_about_unrouted_events(&self) {
+        if self.config.chat_ids.is_empty() {
+            warn!(
+                "未配置 --telegram-chat-id，未命中任何 --telegram-route \
+                 的通知（包括静默期摘要）不会发送到 Telegram"
+            );
+        }
+    }
+
+    /// Queue notifications in the `--db-path` outbox. Without it each chat
+    /// gets a single attempt.
+    pub(super) fn with_outbox(
+        mut self,
+        opts: &Opts,
+    ) -> Self {
+        match NotificationOutbox::open(&opts.db_path, CHANNEL_NAME, opts.telegram_max_attempts) {
+            Ok(outbox) => self.outbox = Some(outbox),
+            Err(err) => {
+                warn!("Failed to open telegram outbox at {}: {}", opts.db_path, err);
+            },
+        }
+        self
+    }
+
+    /// Chats a notification goes to and the text each one gets, rendered in
+    /// the configured parse mode: its own override, the chats of every
+    /// matching route with that route's template, or the configured default
+    /// chats. A chat selected by several routes gets the first route's text
+    /// once.
+    fn deliveries<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> Vec<(&'a str, String)> {
+        let parse_mode = self.config.parse_mode;
+        if !notification.chat_ids.is_empty() {
+            let text = notification.render_for(None, parse_mode);
+            return notification
+                .chat_ids
+                .iter()
+                .map(|chat_id| (chat_id.as_str(), text.clone()))
+                .collect();
+        }
+
+        let mut deliveries: Vec<(&str, String)> = Vec::new();
+        for route in self.config.routes.iter().filter(|route| route.matches(notification)) {
+            let text = notification.render_for(route.template.as_ref(), parse_mode);
+            for chat_id in &route.chat_ids {
+                if !deliveries.iter().any(|(existing, _)| existing == chat_id) {
+                    deliveries.push((chat_id, text.clone()));
+                }
+            }
+        }
+
+        if deliveries.is_empty() {
+            let text = notification.render_for(None, parse_mode);
+            deliveries = self
+                .config
+                .chat_ids
+                .iter()
+                .map(|chat_id| (chat_id.as_str(), text.clone()))
+                .collect();
+        }
+        deliveries
+    }
+
+    async fn send(
+        &self,
+        notification: &Notification,
+    ) {
+        let Some(outbox) = &self.outbox else {
+            for (chat_id, text) in self.deliveries(notification) {
+                if let Err(failure) = self.send_message_to_chat(chat_id, &text).await {
+                    warn!("Telegram sendMessage 请求失败，chat_id {}：{}", chat_id, failure.error);
+                }
+            }
+            return;
+        };
+
+        for (chat_id, text) in self.deliveries(notification) {
+            if let Err(err) = outbox.enqueue(chat_id, notification.kind, &text) {
+                warn!("写入 Telegram 通知发件箱失败，chat_id {}，改为直接发送：{}", chat_id, err);
+                if let Err(failure) = self.send_message_to_chat(chat_id, &text).await {
+                    warn!("Telegram sendMessage 请求失败，chat_id {}：{}", chat_id, failure.error);
+                }
+            }
+        }
+        self.flush_outbox().await;
+    }
+
+    /// Sends every due outbox message in order. A chat that fails is skipped
+    /// for the rest of the pass, so its later messages are not delivered
+    /// ahead of the failed one.
+    async fn flush_outbox(&self) {
+        let Some(outbox) = &self.outbox else {
+            return;
+        };
+        let _flushing = self.flush_lock.lock().await;
+
+        let entries = match outbox.due_entries() {
+            Ok(entries) => entries,
+            Err(err) => {
+                warn!("读取 Telegram 通知发件箱失败：{}", err);
+                return;
+            },
+        };
+
+        let mut failed_chats = HashSet::new();
+        for entry in entries {
+            if failed_chats.contains(&entry.recipient) {
+                continue;
+            }
+
+            let recorded = match self.send_message_to_chat(&entry.recipient, &entry.text).await {
+                Ok(()) => outbox.mark_delivered(&entry),
+                Err(failure) => {
+                    failed_chats.insert(entry.recipient.clone());
+                    outbox.mark_failed(&entry, &failure).map(|status| match status {
+                        DeliveryStatus::DeadLetter => warn!(
+                            "Telegram 通知投递失败，已移入死信队列，chat_id {}：{}",
+                            entry.recipient, failure.error
+                        ),
+                        _ => warn!(
+                            "Telegram 通知投递失败，稍后重试，chat_id {}：{}",
+                            entry.recipient, failure.error
+                        ),
+                    })
+                },
+            };
+            if let Err(err) = recorded {
+                warn!("更新 Telegram 通知发件箱失败，chat_id {}：{}", entry.recipient, err);
+            }
+        }
+    }
+
+    /// Sends a notification text in the configured parse mode.
+    async fn send_message_to_chat(
+        &self,
+        chat_id: &str,
+        text: &str,
+    ) -> std::result::Result<(), DeliveryFailure> {
+        self.send_message(chat_id, text, api_parse_mode(self.config.parse_mode)).await
+    }
+
+    async fn send_message(
+        &self,
+        chat_id: &str,
+        text: &str,
+        parse_mode: Option<&'static str>,
+    ) -> std::result::Result<(), DeliveryFailure> {
+        let request = TelegramSendMessageRequest {
+            chat_id,
+            text,
+            parse_mode,
+        };
+
+        let response =
+            match self.client.post(self.config.send_message_url()).json(&request).send().await {
+                Ok(response) => response,
+                Err(err) => {
+                    return Err(DeliveryFailure {
+                        error: format!("发送 Telegram 消息失败：{err}"),
+                        retry_after: None,
+                        retryable: true,
+                    });
+                },
+            };
+
+        let status = response.status();
+        if !status.is_success() {
+            let response_text = response.text().await.unwrap_or_default();
+            let body = serde_json::from_str::<TelegramSendMessageResponse>(&response_text).ok();
+            let description = body
+                .as_ref()
+                .and_then(|body| body.description.as_deref())
+                .unwrap_or(response_text.as_str());
+            let error = if description.trim().is_empty() {
+                format!("HTTP {status}")
+            } else {
+                format!("HTTP {}：{}", status, describe_telegram_error(description))
+            };
+
+            return Err(DeliveryFailure {
+                error,
+                retry_after: body.and_then(|body| body.retry_after()),
+                retryable: status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+            });
+        }
+
+        match response.json::<TelegramSendMessageResponse>().await {
+            Ok(body) if body.ok => Ok(()),
+            Ok(body) => Err(DeliveryFailure {
+                error: format!(
+                    "API 返回 ok=false：{}",
+                    describe_telegram_error(body.description.as_deref().unwrap_or("unknown error"))
+                ),
+                retry_after: body.retry_after(),
+                retryable: true,
+            }),
+            Err(err) => {
+                // The request was accepted; retrying could send the message twice.
+                warn!("解析 Telegram sendMessage 响应失败，chat_id {}：{}", chat_id, err);
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Receives messages through getUpdates long polling, so no inbound port is
+/// needed, and replies in the chat they came from. Only the
+/// `--telegram-chat-id` chats are answered. Messages sent before the bot
+/// started are skipped, so a backlog left from downtime is not replayed.
+#[derive(Debug)]
+pub(crate) struct TelegramCommandBot {
+    backend: TelegramBackend,
+    next_update_id: Option<i64>,
+    /// Unix time the bot was created.
+    started_at: i64,
+}
+
+impl TelegramCommandBot {
+    /// `None` unless `--telegram-commands` is set.
+    pub(crate) fn from_opts(opts: &Opts) -> Result<Option<Self>> {
+        if !opts.telegram_commands {
+            return Ok(None);
+        }
+
+        let backend = TelegramBackend::from_opts(opts)?.ok_or_else(|| {
+            ChaindashError::Other(
+                "telegram bot token and chat id are required when telegram commands are enabled"
+                    .to_string(),
+            )
+        })?;
+
+        Ok(Some(Self {
+            backend,
+            next_update_id: None,
+            started_at: Utc::now().timestamp(),
+        }))
+    }
+
+    /// Waits up to the long-poll timeout for new messages and returns the
+    /// ones from authorised chats. Messages from other chats are dropped.
+    pub(crate) async fn poll(&mut self) -> Result<Vec<TelegramCommand>> {
+        let mut query = vec![
+            ("timeout", LONG_POLL_TIMEOUT_SECONDS.to_string()),
+            ("allowed_updates", r#"["message"]"#.to_string()),
+        ];
+        if let Some(next_update_id) = self.next_update_id {
+            query.push(("offset", next_update_id.to_string()));
+        }
+
+        let response = self
+            .backend
+            .client
+            .get(self.backend.config.get_updates_url())
+            .query(&query)
+            .timeout(Duration::from_secs(LONG_POLL_TIMEOUT_SECONDS + 10))
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.json::<TelegramGetUpdatesResponse>().await?;
+        if !status.is_success() || !body.ok {
+            return Err(ChaindashError::Other(format!(
+                "telegram getUpdates failed, HTTP {}: {}",
+                status,
+                describe_telegram_error(body.description.as_deref().unwrap_or("unknown error"))
+            )));
+        }
+
+        Ok(self.accept_updates(body.result))
+    }
+
+    fn accept_updates(
+        &mut self,
+        updates: Vec<TelegramUpdate>,
+    ) -> Vec<TelegramCommand> {
+        let mut commands = Vec::new();
+
+        for update in updates {
+            self.next_update_id = self.next_update_id.max(Some(update.update_id + 1));
+            let Some(TelegramMessage {
+                date,
+                chat,
+                text: Some(text),
+            }) = update.message
+            else {
+                continue;
+            };
+            if date < self.started_at {
+                debug!("ignoring telegram message {} sent before startup", update.update_id);
+                continue;
+            }
+
+            let chat_id = chat.id.to_string();
+            if !self.backend.config.chat_ids.contains(&chat_id) {
+                debug!("ignoring telegram message from unauthorised chat {chat_id}");
+                continue;
+            }
+
+            commands.push(TelegramCommand { chat_id, text });
+        }
+
+        commands
+    }
+
+    /// Replies in `chat_id` as plain text, split into several messages when
+    /// too long.
+    pub(crate) async fn reply(
+        &self,
+        chat_id: &str,
+        text: &str,
+    ) {
+        for chunk in split_message(text, MESSAGE_LENGTH_LIMIT) {
+            if let Err(failure) = self.backend.send_message(chat_id, &chunk, None).await {
+                warn!("回复 Telegram 命令失败，chat_id {}：{}", chat_id, failure.error);
+            }
+        }
+    }
+}
+
+/// Splits `text` at line breaks into chunks of at most `limit` characters;
+/// a single longer line is cut mid-line.
+fn split_message(
+    text: &str,
+    limit: usize,
+) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for line in text.split('\n') {
+        let mut line = line.chars().collect::<Vec<_>>();
+        let separator = usize::from(!current.is_empty());
+        if current_len + separator + line.len() <= limit {
+            if separator == 1 {
+                current.push('\n');
+            }
+            current.extend(&line);
+            current_len += separator + line.len();
+            continue;
+        }
+
+        if !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        while line.len() > limit {
+            chunks.push(line.drain(..limit).collect());
+        }
+        current_len = line.len();
+        current = line.into_iter().collect();
+    }
+    if !current.is_empty() || chunks.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+impl NotifierBackend for TelegramBackend {
+    fn deliver<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(self.send(notification))
+    }
+
+    fn flush_outbox(&self) -> BoxFuture<'_, ()> {
+        Box::pin(TelegramBackend::flush_outbox(self))
+    }
+
+    fn recent_deliveries(
+        &self,
+        limit: usize,
+    ) -> Vec<DeliveryRecord> {
+        let Some(outbox) = &self.outbox else {
+            return Vec::new();
+        };
+
+        outbox.recent_deliveries(limit).unwrap_or_else(|err| {
+            warn!("读取 Telegram 通知审计日志失败：{}", err);
+            Vec::new()
+        })
+    }
+}
+
+fn describe_telegram_error(description: &str) -> String {
+    let trimmed = description.trim();
+    let normalized = trimmed.to_ascii_lowercase();
+
+    let translated = if normalized.contains("chat not found") {
+        "chat_id 无效，或目标会话不存在 / bot 不在该会话中"
+    } else if normalized.contains("bot can't initiate conversation with a user") {
+        "Bot 不能主动向用户发起会话，请先给 bot 发送 /start"
+    } else if normalized.contains("bot was blocked by the user") {
+        "Bot 已被目标用户拉黑"
+    } else if normalized.contains("user is deactivated") {
+        "目标用户已停用"
+    } else if normalized.contains("chat is deactivated") {
+        "目标会话已停用"
+    } else if normalized.contains("group chat was upgraded to a supergroup chat") {
+        "群组已升级为超级群，请更新为新的 chat_id"
+    } else if normalized.contains("not enough rights to send text messages to the chat") {
+        "Bot 在目标会话中没有发送文本消息的权限"
+    } else if normalized.contains("have no rights to send a message") {
+        "Bot 在目标会话中没有发送消息的权限"
+    } else {
+        return trimmed.to_string();
+    };
+
+    format!("{}（原始错误: {}）", translated, trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::notify::{
+        NotificationData,
+        RenderedMessage,
+    };
+
+    fn create_test_backend(opts: &[&str]) -> TelegramBackend {
+        let opts = Opts::parse_from(opts);
+
+        TelegramBackend::from_opts(&opts)
+            .expect("config should parse")
+            .expect("telegram backend should be enabled")
+    }
+
+    #[test]
+    fn test_telegram_config_accepts_multiple_chat_ids() {
+        let backend = create_test_backend(&[
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001,10002,10001",
+        ]);
+
+        assert_eq!(backend.config.chat_ids, vec!["10001".to_string(), "10002".to_string()]);
+    }
+
+    #[test]
+    fn test_telegram_config_requires_bot_token_and_chat_id_together() {
+        let without_chat = Opts::parse_from(["test", "--telegram-bot-token", "bot-token"]);
+        let without_token = Opts::parse_from(["test", "--telegram-chat-id", "10001"]);
+
+        assert!(TelegramBackend::from_opts(&without_chat).is_err());
+        assert!(TelegramBackend::from_opts(&without_token).is_err());
+        assert!(TelegramBackend::from_opts(&Opts::parse_from(["test"]))
+            .expect("no telegram options is valid")
+            .is_none());
+    }
+
+    #[test]
+    fn test_notification_chat_override_replaces_default_chats() {
+        let backend = create_test_backend(&[
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+        ]);
+        let mut notification = Notification::new(
+            NotificationEventKind::DailySummary,
+            "2026-04-14",
+            "summary".to_string(),
+            NotificationData::QuietSummary {
+                count: 0,
+                details: String::new(),
+            },
+        );
+
+        assert_eq!(backend.deliveries(&notification), [("10001", "summary".to_string())]);
+
+        notification.chat_ids = vec!["20002".to_string()];
+
+        assert_eq!(backend.deliveries(&notification), [("20002", "summary".to_string())]);
+    }
+
+    #[test]
+    fn test_routes_select_chats_by_event_and_node_with_their_own_template() {
+        let backend = create_test_backend(&[
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+            "--telegram-route",
+            "ranking;chat=20002",
+            "--telegram-route",
+            "connection@backup;chat=30003;template={node} 掉线：{reason}",
+            "--telegram-route",
+            "ranking@0xABC;chat=30003,40004",
+        ]);
+        let connection_failed = |node: &str| {
+            Notification::new(
+                NotificationEventKind::ConnectionFailed,
+                node,
+                RenderedMessage::render(
+                    &Template::builtin("🚨 节点连接异常\n🔹 节点：{node}"),
+                    &[("node", node), ("reason", "rpc timeout")],
+                ),
+                NotificationData::Connection {
+                    node: node.to_string(),
+                    url: "http://127.0.0.1:6789".to_string(),
+                    reason: Some("rpc timeout".to_string()),
+                },
+            )
+        };
+
+        assert_eq!(
+            backend.deliveries(&connection_failed("backup")),
+            [("30003", "backup 掉线：rpc timeout".to_string())]
+        );
+        assert_eq!(
+            backend.deliveries(&connection_failed("main")),
+            [("10001", "🚨 节点连接异常\n🔹 节点：main".to_string())]
+        );
+
+        let ranking = Notification::new(
+            NotificationEventKind::RankingChanged,
+            "main",
+            "ranking".to_string(),
+            NotificationData::Ranking {
+                node_id: "0xabc".to_string(),
+                node: "main".to_string(),
+                previous: 3,
+                current: 2,
+            },
+        );
+        assert_eq!(
+            backend.deliveries(&ranking),
+            [
+                ("20002", "ranking".to_string()),
+                ("30003", "ranking".to_string()),
+                ("40004", "ranking".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_mode_escapes_values_and_built_in_text() {
+        let backend = create_test_backend(&[
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+            "--telegram-parse-mode",
+            "html",
+            "--telegram-route",
+            "connection;chat=20002;template=<b>{node}</b>：{reason}",
+        ]);
+        let notification = |kind| {
+            Notification::new(
+                kind,
+                "a<b>",
+                RenderedMessage::render(
+                    &Template::builtin("节点 <{node}> & {reason}"),
+                    &[("node", "a<b>"), ("reason", "x & y")],
+                ),
+                NotificationData::Connection {
+                    node: "a<b>".to_string(),
+                    url: "http://127.0.0.1:6789".to_string(),
+                    reason: Some("x & y".to_string()),
+                },
+            )
+        };
+
+        assert_eq!(
+            backend.deliveries(&notification(NotificationEventKind::ConnectionFailed)),
+            [("20002", "<b>a&lt;b&gt;</b>：x &amp; y".to_string())]
+        );
+        assert_eq!(
+            backend.deliveries(&notification(NotificationEventKind::RankingChanged)),
+            [("10001", "节点 &lt;a&lt;b&gt;&gt; &amp; x &amp; y".to_string())]
+        );
+        assert_eq!(
+            serde_json::to_value(TelegramSendMessageRequest {
+                chat_id: "10001",
+                text: "<b>ok</b>",
+                parse_mode: api_parse_mode(backend.config.parse_mode),
+            })
+            .expect("request should serialize"),
+            serde_json::json!({"chat_id": "10001", "text": "<b>ok</b>", "parse_mode": "HTML"})
+        );
+    }
+
+    #[test]
+    fn test_command_bot_only_accepts_messages_from_configured_chats() {
+        let mut bot = TelegramCommandBot::from_opts(&Opts::parse_from([
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+            "--telegram-commands",
+        ]))
+        .expect("config should parse")
+        .expect("command bot should be enabled");
+        bot.started_at = 1_700_000_000;
+        let response: TelegramGetUpdatesResponse = serde_json::from_str(
+            r#"{"ok":true,"result":[
+                {"update_id":7,"message":{"date":1700000000,"chat":{"id":10001},"text":"/status"}},
+                {"update_id":8,"message":{"date":1700000000,"chat":{"id":20002},"text":"/mute 1d"}},
+                {"update_id":9,"message":{"date":1700000000,"chat":{"id":10001}}}
+            ]}"#,
+        )
+        .expect("response should parse");
+
+        let commands = bot.accept_updates(response.result);
+
+        assert_eq!(
+            commands,
+            vec![TelegramCommand {
+                chat_id: "10001".to_string(),
+                text: "/status".to_string(),
+            }]
+        );
+        assert_eq!(bot.next_update_id, Some(10));
+    }
+
+    #[test]
+    fn test_command_bot_skips_messages_sent_before_startup() {
+        let mut bot = TelegramCommandBot::from_opts(&Opts::parse_from([
+            "test",
+            "--telegram-bot-token",
+            "bot-token",
+            "--telegram-chat-id",
+            "10001",
+            "--telegram-commands",
+        ]))
+        .expect("config should parse")
+        .expect("command bot should be enabled");
+        bot.started_at = 1_700_000_000;
+        let response: TelegramGetUpdatesResponse = serde_json::from_str(
+            r#"{"ok":true,"result":[
+                {"update_id":3,"message":{"date":1699990000,"chat":{"id":10001},"text":"/mute 7d"}},
+                {"update_id":4,"message":{"date":1700000060,"chat":{"id":10001},"text":"/status"}}
+            ]}"#,
+        )
+        .expect("response should parse");
+
+        let commands = bot.accept_updates(response.result);
+
+        assert_eq!(
+            commands,
+            vec![TelegramCommand {
+                chat_id: "10001".to_string(),
+                text: "/status".to_string(),
+            }]
+        );
+        // The backlog is still acknowledged so it is not fetched again.
+        assert_eq!(bot.next_update_id, Some(5));
+    }
+
+    #[test]
+    fn test_command_bot_requires_telegram_config() {
+        assert!(TelegramCommandBot::from_opts(&Opts::parse_from(["test"]))
+            .expect("commands are off by default")
+            .is_none());
+        assert!(TelegramCommandBot::from_opts(&Opts::parse_from(["test", "--telegram-commands"]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_split_message_breaks_at_lines_within_limit() {
+        assert_eq!(split_message("ab\ncd\nef", 5), vec!["ab\ncd", "ef"]);
+        assert_eq!(split_message("abcdefg", 3), vec!["abc", "def", "g"]);
+        assert_eq!(split_message("", 3), vec![""]);
+    }
+
+    #[test]
+    fn test_send_message_response_reads_retry_after() {
+        let limited: TelegramSendMessageResponse = serde_json::from_str(
+            r#"{"ok":false,"error_code":429,"description":"Too Many Requests: retry after 17",
+                "parameters":{"retry_after":17}}"#,
+        )
+        .expect("response should parse");
+        let rejected: TelegramSendMessageResponse =
+            serde_json::from_str(r#"{"ok":false,"description":"Bad Request: chat not found"}"#)
+                .expect("response should parse");
+
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(17)));
+        assert_eq!(rejected.retry_after(), None);
+    }
+
+    #[test]
+    fn test_describe_telegram_error_translates_common_chat_not_found_error() {
+        let message = describe_telegram_error("Bad Request: chat not found");
+
+        assert!(message.contains("chat_id 无效"));
+        assert!(message.contains("chat not found"));
+    }
+
+    #[test]
+    fn test_describe_telegram_error_preserves_unknown_errors() {
+        let message = describe_telegram_error("some custom telegram error");
+
+        assert_eq!(message, "some custom telegram error");
+    }
+}
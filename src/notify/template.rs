@@ -0,0 +1,870 @@
+use std::{
+    borrow::Cow,
+    sync::Arc,
+};
+
+use crate::opts::TelegramParseMode;
+
+/// A value a template can print, test or loop over.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TemplateValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<TemplateContext>),
+}
+
+impl TemplateValue {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Self::Text(text) => !text.is_empty(),
+            Self::Number(number) => *number != 0.0,
+            Self::Bool(value) => *value,
+            Self::List(items) => !items.is_empty(),
+        }
+    }
+
+    /// Text values are read as numbers when they parse as one, so the
+    /// pre-formatted values of the flat templates still take number filters.
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => Some(*number),
+            Self::Text(text) => text.trim().parse().ok().filter(|number: &f64| number.is_finite()),
+            Self::Bool(_) | Self::List(_) => None,
+        }
+    }
+
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Number(number) => format_number(*number),
+            Self::Bool(value) => value.to_string(),
+            Self::List(items) => items.len().to_string(),
+        }
+    }
+}
+
+impl From<&str> for TemplateValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for TemplateValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<f64> for TemplateValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<u64> for TemplateValue {
+    fn from(value: u64) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<i32> for TemplateValue {
+    fn from(value: i32) -> Self {
+        Self::Number(f64::from(value))
+    }
+}
+
+impl From<u32> for TemplateValue {
+    fn from(value: u32) -> Self {
+        Self::Number(f64::from(value))
+    }
+}
+
+impl From<bool> for TemplateValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Vec<TemplateContext>> for TemplateValue {
+    fn from(items: Vec<TemplateContext>) -> Self {
+        Self::List(items)
+    }
+}
+
+/// Named values a template is rendered with.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct TemplateContext {
+    values: Vec<(&'static str, TemplateValue)>,
+}
+
+impl TemplateContext {
+    pub(super) fn from_pairs(pairs: &[(&'static str, &str)]) -> Self {
+        Self {
+            values: pairs.iter().map(|(key, value)| (*key, TemplateValue::from(*value))).collect(),
+        }
+    }
+
+    /// Adds `key`, or replaces its value.
+    pub(super) fn with(
+        mut self,
+        key: &'static str,
+        value: impl Into<TemplateValue>,
+    ) -> Self {
+        let value = value.into();
+        match self.values.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((key, value)),
+        }
+        self
+    }
+
+    /// Adds `key` when `value` is known; a missing key renders as written
+    /// unless a `default` filter fills it in.
+    pub(super) fn with_optional(
+        self,
+        key: &'static str,
+        value: Option<impl Into<TemplateValue>>,
+    ) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self,
+        }
+    }
+
+    fn get(
+        &self,
+        key: &str,
+    ) -> Option<&TemplateValue> {
+        self.values.iter().find(|(existing, _)| *existing == key).map(|(_, value)| value)
+    }
+}
+
+/// Escapes `text` so Telegram shows it literally in `mode`.
+pub(super) fn escape(
+    text: &str,
+    mode: TelegramParseMode,
+) -> Cow<'_, str> {
+    match mode {
+        TelegramParseMode::Plain => Cow::Borrowed(text),
+        TelegramParseMode::Html => {
+            if !text.contains(['&', '<', '>']) {
+                return Cow::Borrowed(text);
+            }
+            Cow::Owned(text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"))
+        },
+        TelegramParseMode::MarkdownV2 => {
+            const SPECIAL: &str = "_*[]()~`>#+-=|{}.!\\";
+            if !text.contains(|ch| SPECIAL.contains(ch)) {
+                return Cow::Borrowed(text);
+            }
+            let mut escaped = String::with_capacity(text.len() + 8);
+            for ch in text.chars() {
+                if SPECIAL.contains(ch) {
+                    escaped.push('\\');
+                }
+                escaped.push(ch);
+            }
+            Cow::Owned(escaped)
+        },
+    }
+}
+
+/// Whole numbers print without decimals and fractions with up to four.
+fn format_number(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        return format!("{number:.0}");
+    }
+
+    let formatted = format!("{number:.4}");
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Inserts thousands separators into the integer part of `number`.
+fn group_thousands(number: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let mut grouped = String::with_capacity(number.len() + integer.len() / 3);
+    grouped.push_str(sign);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        grouped.push('.');
+        grouped.push_str(fraction);
+    }
+    grouped
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Round(usize),
+    Percent(usize),
+    Thousands,
+    Default(String),
+    Upper,
+    Lower,
+    Truncate(usize),
+}
+
+impl Filter {
+    fn parse(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        let (name, args) = match source.split_once('(') {
+            Some((name, args)) => {
+                let args = args
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("unclosed arguments in filter: {source}"))?;
+                (name.trim(), Some(args.trim()))
+            },
+            None => (source, None),
+        };
+        let count = |default: usize| match args {
+            None | Some("") => Ok(default),
+            Some(args) => args
+                .parse::<usize>()
+                .map_err(|_| format!("filter {name} expects a whole number, got {args}")),
+        };
+
+        match name {
+            "round" => count(0).map(Self::Round),
+            "percent" => count(2).map(Self::Percent),
+            "thousands" => Ok(Self::Thousands),
+            "upper" => Ok(Self::Upper),
+            "lower" => Ok(Self::Lower),
+            "truncate" => match args {
+                Some(_) => count(0).map(Self::Truncate),
+                None => Err("filter truncate expects a length, e.g. truncate(20)".to_string()),
+            },
+            "default" => {
+                let fallback = args.map(parse_literal).transpose()?.unwrap_or_default();
+                Ok(Self::Default(fallback))
+            },
+            _ => Err(format!(
+                "unknown filter: {name}. expected round, percent, thousands, default, upper, \
+                 lower or truncate"
+            )),
+        }
+    }
+
+    fn apply(
+        &self,
+        value: Option<TemplateValue>,
+    ) -> Option<TemplateValue> {
+        if let Self::Default(fallback) = self {
+            return match value {
+                Some(value) if value != TemplateValue::Text(String::new()) => Some(value),
+                _ => Some(TemplateValue::Text(fallback.clone())),
+            };
+        }
+
+        let value = value?;
+        let text = match self {
+            Self::Round(digits) => match value.as_number() {
+                Some(number) => format!("{number:.digits$}"),
+                None => return Some(value),
+            },
+            Self::Percent(digits) => match value.as_number() {
+                Some(number) => format!("{:.digits$}%", number * 100.0),
+                None => return Some(value),
+            },
+            Self::Thousands => match value.as_number() {
+                Some(_) => group_thousands(value.to_text().trim()),
+                None => return Some(value),
+            },
+            Self::Upper => value.to_text().to_uppercase(),
+            Self::Lower => value.to_text().to_lowercase(),
+            Self::Truncate(length) => {
+                let text = value.to_text();
+                if text.chars().count() <= *length {
+                    text
+                } else {
+                    text.chars().take(*length).chain(std::iter::once('…')).collect()
+                }
+            },
+            Self::Default(_) => unreachable!("handled above"),
+        };
+        Some(TemplateValue::Text(text))
+    }
+}
+
+/// `"text"`, `'text'` or a bare word, used for filter arguments and
+/// comparisons.
+fn parse_literal(source: &str) -> Result<String, String> {
+    let source = source.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = source.strip_prefix(quote) {
+            return inner
+                .strip_suffix(quote)
+                .map(str::to_string)
+                .ok_or_else(|| format!("unterminated string: {source}"));
+        }
+    }
+    Ok(source.to_string())
+}
+
+fn is_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// A value reference such as `node.blocks | thousands`.
+#[derive(Debug, Clone, PartialEq)]
+struct Expression {
+    path: Vec<String>,
+    filters: Vec<Filter>,
+}
+
+impl Expression {
+    /// `Ok(None)` when `source` does not look like an expression at all, so
+    /// braces in plain text stay as they are.
+    fn parse(source: &str) -> Result<Option<Self>, String> {
+        let mut parts = source.split('|');
+        let path = parts.next().unwrap_or_default().trim();
+        let path: Vec<String> = path.split('.').map(str::to_string).collect();
+        if !path.iter().all(|segment| is_identifier(segment)) {
+            return Ok(None);
+        }
+        let filters = parts.map(Filter::parse).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(Self { path, filters }))
+    }
+
+    fn evaluate(
+        &self,
+        scope: &Scope<'_, '_>,
+    ) -> Option<TemplateValue> {
+        let value = scope.lookup(&self.path);
+        self.filters.iter().fold(value, |value, filter| filter.apply(value))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    const ALL: [(&'static str, Self); 6] = [
+        ("==", Self::Equal),
+        ("!=", Self::NotEqual),
+        (">=", Self::GreaterOrEqual),
+        ("<=", Self::LessOrEqual),
+        (">", Self::Greater),
+        ("<", Self::Less),
+    ];
+
+    fn compare(
+        self,
+        value: &TemplateValue,
+        expected: &str,
+    ) -> bool {
+        let ordering = match (value.as_number(), expected.parse::<f64>()) {
+            (Some(value), Ok(expected)) => value.partial_cmp(&expected),
+            _ => Some(value.to_text().as_str().cmp(expected)),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match self {
+            Self::Equal => ordering.is_eq(),
+            Self::NotEqual => ordering.is_ne(),
+            Self::Greater => ordering.is_gt(),
+            Self::GreaterOrEqual => ordering.is_ge(),
+            Self::Less => ordering.is_lt(),
+            Self::LessOrEqual => ordering.is_le(),
+        }
+    }
+}
+
+/// `[not] EXPRESSION [OP LITERAL]`; a bare expression tests truthiness.
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    negated: bool,
+    expression: Expression,
+    comparison: Option<(Comparison, String)>,
+}
+
+impl Condition {
+    fn parse(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        let (negated, source) = match source.strip_prefix("not ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, source),
+        };
+        let split = Comparison::ALL.iter().find_map(|(symbol, comparison)| {
+            source.split_once(symbol).map(|(left, right)| (left, *comparison, right))
+        });
+        let (left, comparison) = match split {
+            Some((left, comparison, right)) => (left, Some((comparison, parse_literal(right)?))),
+            None => (source, None),
+        };
+        let expression = Expression::parse(left)?
+            .ok_or_else(|| format!("invalid condition: {}", source.trim()))?;
+
+        Ok(Self {
+            negated,
+            expression,
+            comparison,
+        })
+    }
+
+    fn holds(
+        &self,
+        scope: &Scope<'_, '_>,
+    ) -> bool {
+        let value = self.expression.evaluate(scope);
+        let holds = match (&value, &self.comparison) {
+            (None, _) => false,
+            (Some(value), None) => value.is_truthy(),
+            (Some(value), Some((comparison, expected))) => comparison.compare(value, expected),
+        };
+        holds != self.negated
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tag {
+    If(Condition),
+    Elif(Condition),
+    Else,
+    EndIf,
+    For { name: String, list: Vec<String> },
+    EndFor,
+}
+
+impl Tag {
+    fn parse(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        let (keyword, rest) = source.split_once(char::is_whitespace).unwrap_or((source, ""));
+        match keyword {
+            "if" => Condition::parse(rest).map(Self::If),
+            "elif" => Condition::parse(rest).map(Self::Elif),
+            "else" if rest.is_empty() => Ok(Self::Else),
+            "endif" if rest.is_empty() => Ok(Self::EndIf),
+            "endfor" if rest.is_empty() => Ok(Self::EndFor),
+            "for" => {
+                let parts: Vec<_> = rest.split_whitespace().collect();
+                match parts.as_slice() {
+                    [name, "in", list] if is_identifier(name) => {
+                        let list: Vec<String> = list.split('.').map(str::to_string).collect();
+                        if list.iter().all(|segment| is_identifier(segment)) {
+                            return Ok(Self::For {
+                                name: name.to_string(),
+                                list,
+                            });
+                        }
+                        Err(format!("invalid tag: {{% {source} %}}"))
+                    },
+                    _ => Err(format!("invalid tag: {{% {source} %}}. expected for NAME in LIST")),
+                }
+            },
+            _ => Err(format!("invalid tag: {{% {source} %}}")),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::If(_) => "if",
+            Self::Elif(_) => "elif",
+            Self::Else => "else",
+            Self::EndIf => "endif",
+            Self::For { .. } => "for",
+            Self::EndFor => "endfor",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Text(String),
+    /// The expression and its source, printed as written when the value is
+    /// missing.
+    Output(Expression, String),
+    Tag(Tag),
+}
+
+/// Whether `text` holds only spaces and tabs.
+fn is_blank(text: &str) -> bool {
+    text.chars().all(|ch| ch == ' ' || ch == '\t')
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = source;
+    // Whether `text` starts at the beginning of a line.
+    let mut at_line_start = true;
+
+    while let Some(position) = rest.find('{') {
+        text.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        if let Some(after) = rest.strip_prefix("{%") {
+            let end = after.find("%}").ok_or("unclosed {% tag")?;
+            let tag = Tag::parse(&after[..end])?;
+            let mut next = &after[end + 2..];
+
+            // A tag alone on its line leaves no blank line behind.
+            let line_start = match text.rfind('\n') {
+                Some(index) => Some(index + 1),
+                None => at_line_start.then_some(0),
+            };
+            let alone = line_start.is_some_and(|start| is_blank(&text[start..]))
+                && (next.is_empty() || next.starts_with('\n') || next.starts_with("\r\n"));
+            if alone {
+                text.truncate(line_start.unwrap_or_default());
+                next =
+                    next.strip_prefix("\r\n").or_else(|| next.strip_prefix('\n')).unwrap_or(next);
+            }
+
+            if !text.is_empty() {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+            }
+            tokens.push(Token::Tag(tag));
+            at_line_start = alone;
+            rest = next;
+            continue;
+        }
+
+        let output = match rest[1..].find(['{', '}', '\n']) {
+            Some(end) if rest.as_bytes()[end + 1] == b'}' => {
+                let inner = &rest[1..=end];
+                Expression::parse(inner)?.map(|expression| (expression, end + 2))
+            },
+            _ => None,
+        };
+        match output {
+            Some((expression, length)) => {
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                tokens.push(Token::Output(expression, rest[..length].to_string()));
+                at_line_start = false;
+                rest = &rest[length..];
+            },
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            },
+        }
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Output(Expression, String),
+    If { branches: Vec<(Condition, Vec<Node>)>, otherwise: Vec<Node> },
+    For { name: String, list: Vec<String>, body: Vec<Node> },
+}
+
+/// Parses nodes until one of `closing` tags, which is returned. At the top
+/// level `closing` is empty and the end of input is expected instead.
+fn parse_nodes(
+    tokens: &mut std::vec::IntoIter<Token>,
+    closing: &[&str],
+) -> Result<(Vec<Node>, Option<Tag>), String> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Output(expression, source) => nodes.push(Node::Output(expression, source)),
+            Token::Tag(tag) if closing.contains(&tag.name()) => return Ok((nodes, Some(tag))),
+            Token::Tag(Tag::If(condition)) => {
+                let mut branches = Vec::new();
+                let mut condition = condition;
+                let otherwise = loop {
+                    let (body, end) = parse_nodes(tokens, &["elif", "else", "endif"])?;
+                    branches.push((condition, body));
+                    match end {
+                        Some(Tag::Elif(next)) => condition = next,
+                        Some(Tag::Else) => {
+                            let (body, end) = parse_nodes(tokens, &["endif"])?;
+                            if end.is_none() {
+                                return Err("unclosed {% if %}, expected {% endif %}".to_string());
+                            }
+                            break body;
+                        },
+                        Some(_) => break Vec::new(),
+                        None => return Err("unclosed {% if %}, expected {% endif %}".to_string()),
+                    }
+                };
+                nodes.push(Node::If {
+                    branches,
+                    otherwise,
+                });
+            },
+            Token::Tag(Tag::For { name, list }) => {
+                let (body, end) = parse_nodes(tokens, &["endfor"])?;
+                if end.is_none() {
+                    return Err("unclosed {% for %}, expected {% endfor %}".to_string());
+                }
+                nodes.push(Node::For { name, list, body });
+            },
+            Token::Tag(tag) => return Err(format!("unexpected {{% {} %}}", tag.name())),
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+/// The item bound by an enclosing `{% for %}`.
+struct LoopFrame<'t, 'c> {
+    name: &'t str,
+    item: &'c TemplateContext,
+    index: usize,
+    length: usize,
+}
+
+struct Scope<'t, 'c> {
+    context: &'c TemplateContext,
+    loops: Vec<LoopFrame<'t, 'c>>,
+}
+
+impl<'c> Scope<'_, 'c> {
+    fn lookup_ref(
+        &self,
+        path: &[String],
+    ) -> Option<&'c TemplateValue> {
+        let (first, rest) = path.split_first()?;
+        if let Some(frame) = self.loops.iter().rev().find(|frame| frame.name == first) {
+            let [field] = rest else {
+                return None;
+            };
+            return frame.item.get(field);
+        }
+
+        match rest {
+            [] => self.context.get(first),
+            _ => None,
+        }
+    }
+
+    /// `loop.index`, `loop.first`, `loop.last` and `loop.length` describe
+    /// the innermost loop.
+    fn lookup(
+        &self,
+        path: &[String],
+    ) -> Option<TemplateValue> {
+        if let (Some(frame), [first, field]) = (self.loops.last(), path) {
+            if first == "loop" && frame.name != "loop" {
+                return match field.as_str() {
+                    "index" => Some(TemplateValue::from(frame.index as u64 + 1)),
+                    "first" => Some(TemplateValue::Bool(frame.index == 0)),
+                    "last" => Some(TemplateValue::Bool(frame.index + 1 == frame.length)),
+                    "length" => Some(TemplateValue::from(frame.length as u64)),
+                    _ => None,
+                };
+            }
+        }
+
+        self.lookup_ref(path).cloned()
+    }
+}
+
+/// A parsed notification template.
+///
+/// Besides `{name}` placeholders, templates support filters
+/// (`{value | round(2)}`), conditionals (`{% if %}`, `{% elif %}`,
+/// `{% else %}`, `{% endif %}`) and loops (`{% for node in nodes %}`).
+/// Placeholders without a value are printed as written.
+#[derive(Debug, Clone)]
+pub(super) struct Template {
+    nodes: Arc<[Node]>,
+    /// Built-in templates are plain text, so their literal text is escaped
+    /// for the Telegram parse mode too; user templates are written in it.
+    builtin: bool,
+}
+
+impl Template {
+    pub(super) fn parse(source: &str) -> Result<Self, String> {
+        let mut tokens = tokenize(source)?.into_iter();
+        let (nodes, _) = parse_nodes(&mut tokens, &[])?;
+
+        Ok(Self {
+            nodes: nodes.into(),
+            builtin: false,
+        })
+    }
+
+    pub(super) fn builtin(source: &'static str) -> Self {
+        Self {
+            builtin: true,
+            ..Self::parse(source).expect("built-in templates are valid")
+        }
+    }
+
+    pub(super) fn render(
+        &self,
+        context: &TemplateContext,
+        mode: TelegramParseMode,
+    ) -> String {
+        let mut scope = Scope {
+            context,
+            loops: Vec::new(),
+        };
+        let mut rendered = String::new();
+        self.render_nodes(&self.nodes, &mut scope, mode, &mut rendered);
+        rendered
+    }
+
+    fn render_nodes<'t, 'c>(
+        &self,
+        nodes: &'t [Node],
+        scope: &mut Scope<'t, 'c>,
+        mode: TelegramParseMode,
+        rendered: &mut String,
+    ) {
+        let literal_mode = if self.builtin {
+            mode
+        } else {
+            TelegramParseMode::Plain
+        };
+
+        for node in nodes {
+            match node {
+                Node::Text(text) => rendered.push_str(&escape(text, literal_mode)),
+                Node::Output(expression, source) => match expression.evaluate(scope) {
+                    Some(value) => rendered.push_str(&escape(&value.to_text(), mode)),
+                    None => rendered.push_str(&escape(source, literal_mode)),
+                },
+                Node::If {
+                    branches,
+                    otherwise,
+                } => {
+                    let body = branches
+                        .iter()
+                        .find(|(condition, _)| condition.holds(scope))
+                        .map_or(otherwise.as_slice(), |(_, body)| body.as_slice());
+                    self.render_nodes(body, scope, mode, rendered);
+                },
+                Node::For { name, list, body } => {
+                    let Some(TemplateValue::List(items)) = scope.lookup_ref(list) else {
+                        continue;
+                    };
+                    for (index, item) in items.iter().enumerate() {
+                        scope.loops.push(LoopFrame {
+                            name,
+                            item,
+                            index,
+                            length: items.len(),
+                        });
+                        self.render_nodes(body, scope, mode, rendered);
+                        scope.loops.pop();
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(
+        source: &str,
+        context: &TemplateContext,
+    ) -> String {
+        Template::parse(source).expect("valid template").render(context, TelegramParseMode::Plain)
+    }
+
+    fn nodes_context() -> TemplateContext {
+        TemplateContext::default().with("title", "日报").with(
+            "nodes",
+            vec![
+                TemplateContext::default()
+                    .with("name", "node-a")
+                    .with("blocks", 1234567_u64)
+                    .with("ratio", 0.98765),
+                TemplateContext::default().with("name", "node-b").with("blocks", 0_u64),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_flat_placeholders_keep_unknown_and_non_placeholder_braces() {
+        let context = TemplateContext::from_pairs(&[("node", "main"), ("reason", "timeout")]);
+
+        assert_eq!(
+            render("{node}: {reason} {unknown} { spaced } {json: 1}", &context),
+            "main: timeout {unknown} { spaced } {json: 1}"
+        );
+    }
+
+    #[test]
+    fn test_loops_conditionals_and_filters() {
+        let template = "{title}\n{% for node in nodes %}\n{loop.index}. {node.name | upper}\n{% \
+                        if node.blocks > 0 %}\n  {node.blocks | thousands} / {node.ratio | \
+                        percent(1)}\n{% else %}\n  idle\n{% endif %}\n{% endfor %}\n";
+
+        assert_eq!(
+            render(template, &nodes_context()),
+            "日报\n1. NODE-A\n  1,234,567 / 98.8%\n2. NODE-B\n  idle\n"
+        );
+    }
+
+    #[test]
+    fn test_filters_format_numbers_and_fill_missing_values() {
+        let context = TemplateContext::from_pairs(&[("reward", "1234.5678"), ("empty", "")]);
+
+        assert_eq!(render("{reward | round(2) | thousands}", &context), "1,234.57");
+        assert_eq!(render("{missing | default(\"-\")}|{empty | default(n/a)}", &context), "-|n/a");
+        assert_eq!(render("{reward | truncate(4)}", &context), "1234…");
+        assert_eq!(render("{% if not missing %}none{% endif %}", &context), "none");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_blocks_and_filters() {
+        for source in [
+            "{% if ready %}unclosed",
+            "{% for node in %}{% endfor %}",
+            "{% endif %}",
+            "{% while true %}",
+            "{value | shout}",
+            "{value | round(two)}",
+            "{% if ready",
+        ] {
+            assert!(Template::parse(source).is_err(), "{source} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_values_are_escaped_for_the_parse_mode() {
+        let context = TemplateContext::from_pairs(&[("reason", "a<b> & c.d")]);
+        let custom = Template::parse("<b>{reason}</b>").expect("valid template");
+        let builtin = Template::builtin("原因：{reason} (x)");
+
+        assert_eq!(custom.render(&context, TelegramParseMode::Html), "<b>a&lt;b&gt; &amp; c.d</b>");
+        assert_eq!(
+            builtin.render(&context, TelegramParseMode::MarkdownV2),
+            "原因：a<b\\> & c\\.d \\(x\\)"
+        );
+        assert_eq!(custom.render(&context, TelegramParseMode::Plain), "<b>a<b> & c.d</b>");
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use chrono::{
+    DateTime,
+    SecondsFormat,
+    Utc,
+};
+use futures::future::BoxFuture;
+use hmac::{
+    Hmac,
+    Mac,
+};
+use log::warn;
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use sha2::Sha256;
+
+use super::{
+    display_url,
+    trimmed_option,
+    trimmed_values,
+    validate_http_urls,
+    Notification,
+    NotificationData,
+    NotificationSeverity,
+    NotifierBackend,
+};
+use crate::{
+    error::{
+        ChaindashError,
+        Result,
+    },
+    opts::Opts,
+};
+
+const WEBHOOK_SOURCE: &str = "chaindash";
+const WEBHOOK_EVENT_HEADER: &str = "X-Chaindash-Event";
+/// `sha256=` followed by the hex HMAC-SHA256 of the raw request body.
+const WEBHOOK_SIGNATURE_HEADER: &str = "X-Chaindash-Signature";
+
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    source: &'static str,
+    event: &'static str,
+    severity: NotificationSeverity,
+    subject: &'a str,
+    message: &'a str,
+    timestamp: String,
+    data: &'a NotificationData,
+}
+
+/// Posts each notification as JSON to one or more URLs, signed with
+/// `--webhook-secret` when set.
+#[derive(Debug)]
+pub(super) struct WebhookBackend {
+    client: reqwest::Client,
+    urls: Vec<String>,
+    secret: Option<String>,
+}
+
+impl WebhookBackend {
+    pub(super) fn from_opts(opts: &Opts) -> Result<Option<Self>> {
+        let urls = trimmed_values(&opts.webhook_url);
+        let secret = trimmed_option(opts.webhook_secret.as_deref()).map(str::to_string);
+
+        if urls.is_empty() {
+            if secret.is_some() {
+                return Err(ChaindashError::Other(
+                    "webhook url is required when webhook secret is set".to_string(),
+                ));
+            }
+            return Ok(None);
+        }
+
+        validate_http_urls("webhook", &urls)?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(opts.webhook_timeout_seconds))
+            .build()?;
+
+        Ok(Some(Self {
+            client,
+            urls,
+            secret,
+        }))
+    }
+
+    async fn send(
+        &self,
+        notification: &Notification,
+    ) {
+        let body = match render_body(notification, Utc::now()) {
+            Ok(body) => body,
+            Err(err) => {
+                warn!("序列化 Webhook 通知失败：{}", err);
+                return;
+            },
+        };
+        let signature = self.secret.as_deref().map(|secret| sign_body(secret, &body));
+
+        for url in &self.urls {
+            let mut request = self
+                .client
+                .post(url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(WEBHOOK_EVENT_HEADER, notification.kind.name())
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(WEBHOOK_SIGNATURE_HEADER, signature);
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {},
+                Ok(response) => {
+                    let status = response.status();
+                    let response_text = response.text().await.unwrap_or_default();
+                    if response_text.trim().is_empty() {
+                        warn!("Webhook 请求失败，url {}，HTTP {}", display_url(url), status);
+                    } else {
+                        warn!(
+                            "Webhook 请求失败，url {}，HTTP {}：{}",
+                            display_url(url),
+                            status,
+                            response_text.trim()
+                        );
+                    }
+                },
+                Err(err) => {
+                    warn!("发送 Webhook 通知失败，url {}：{}", display_url(url), err);
+                },
+            }
+        }
+    }
+}
+
+impl NotifierBackend for WebhookBackend {
+    fn deliver<'a>(
+        &'a self,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(self.send(notification))
+    }
+}
+
+fn render_body(
+    notification: &Notification,
+    timestamp: DateTime<Utc>,
+) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&WebhookPayload {
+        source: WEBHOOK_SOURCE,
+        event: notification.kind.name(),
+        severity: notification.severity,
+        subject: &notification.subject,
+        message: &notification.text,
+        timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+        data: &notification.data,
+    })
+}
+
+fn sign_body(
+    secret: &str,
+    body: &[u8],
+) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use clap::Parser;
+
+    use super::*;
+    use crate::notify::NotificationEventKind;
+
+    #[test]
+    fn test_webhook_signature_matches_hmac_sha256_reference() {
+        // RFC 4231, test case 2.
+        assert_eq!(
+            sign_body("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_webhook_body_carries_event_metadata_and_data() {
+        let notification = Notification::new(
+            NotificationEventKind::ConnectionFailed,
+            "main",
+            "🚨 节点连接异常".to_string(),
+            NotificationData::Connection {
+                node: "main".to_string(),
+                url: "ws://127.0.0.1:6789".to_string(),
+                reason: Some("rpc timeout".to_string()),
+            },
+        );
+        let timestamp = Utc.with_ymd_and_hms(2026, 4, 14, 1, 2, 3).single().expect("valid time");
+
+        let body = render_body(&notification, timestamp).expect("body should serialize");
+        let body: serde_json::Value = serde_json::from_slice(&body).expect("body is json");
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "source": "chaindash",
+                "event": "connection_failed",
+                "severity": "warning",
+                "subject": "main",
+                "message": "🚨 节点连接异常",
+                "timestamp": "2026-04-14T01:02:03Z",
+                "data": {
+                    "node": "main",
+                    "url": "ws://127.0.0.1:6789",
+                    "reason": "rpc timeout",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_webhook_config_validates_urls_and_secret() {
+        let without_url = Opts::parse_from(["test", "--webhook-secret", "s3cret"]);
+        let bad_scheme = Opts::parse_from(["test", "--webhook-url", "ftp://hooks.example.com"]);
+        let valid = Opts::parse_from([
+            "test",
+            "--webhook-url",
+            "https://a.example.com/hook,https://b.example.com/hook",
+            "--webhook-secret",
+            "s3cret",
+        ]);
+
+        assert!(WebhookBackend::from_opts(&without_url).is_err());
+        assert!(WebhookBackend::from_opts(&bad_scheme).is_err());
+
+        let backend = WebhookBackend::from_opts(&valid)
+            .expect("config should parse")
+            .expect("webhook backend should be enabled");
+        assert_eq!(backend.urls.len(), 2);
+        assert_eq!(backend.secret.as_deref(), Some("s3cret"));
+    }
+}
@@ -1,4 +1,17 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use chrono::{
+    NaiveDate,
+    NaiveTime,
+    Weekday,
+};
+use chrono_tz::Tz;
+use clap::{
+    Args,
+    Parser,
+    Subcommand,
+    ValueEnum,
+};
 use num_rational::Ratio;
 
 fn parse_positive_interval(value: &str) -> Result<Ratio<u64>, String> {
@@ -19,45 +32,100 @@ fn parse_positive_u64(value: &str) -> Result<u64, String> {
     Ok(parsed)
 }
 
+fn parse_positive_u32(value: &str) -> Result<u32, String> {
+    let parsed = value.parse::<u32>().map_err(|err| err.to_string())?;
+    if parsed == 0 {
+        return Err("value must be greater than 0".to_string());
+    }
+
+    Ok(parsed)
+}
+
+fn parse_positive_f64(value: &str) -> Result<f64, String> {
+    let parsed = value.parse::<f64>().map_err(|err| err.to_string())?;
+    if !(parsed.is_finite() && parsed > 0.0) {
+        return Err("value must be greater than 0".to_string());
+    }
+
+    Ok(parsed)
+}
+
+fn parse_fraction(value: &str) -> Result<f64, String> {
+    let parsed = value.parse::<f64>().map_err(|err| err.to_string())?;
+    if !(parsed > 0.0 && parsed <= 1.0) {
+        return Err("value must be greater than 0 and at most 1".to_string());
+    }
+
+    Ok(parsed)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TelegramNotifyEvent {
+pub enum NotifyEvent {
     All,
     Connection,
     ConnectionFailed,
     ConnectionRecovered,
+    ConnectionFlapping,
     Ranking,
     RankingChanged,
     Daily,
     DailySummary,
+    Production,
+    ProductionLow,
+    ProductionDrop,
+    Stake,
+    StakeMargin,
+    CommissionChanged,
+    BenefitAddressChanged,
+    Delegator,
+    DelegatorChanged,
+    Alert,
+    AlertFiring,
+    AlertResolved,
 }
 
-fn parse_telegram_notify_event(value: &str) -> Result<TelegramNotifyEvent, String> {
+fn parse_notify_event(value: &str) -> Result<NotifyEvent, String> {
     match value.trim().to_ascii_lowercase().as_str() {
-        "all" => Ok(TelegramNotifyEvent::All),
-        "connection" => Ok(TelegramNotifyEvent::Connection),
-        "connection-failed" | "connection_failed" => Ok(TelegramNotifyEvent::ConnectionFailed),
-        "connection-recovered" | "connection_recovered" => {
-            Ok(TelegramNotifyEvent::ConnectionRecovered)
+        "all" => Ok(NotifyEvent::All),
+        "connection" => Ok(NotifyEvent::Connection),
+        "connection-failed" | "connection_failed" => Ok(NotifyEvent::ConnectionFailed),
+        "connection-recovered" | "connection_recovered" => Ok(NotifyEvent::ConnectionRecovered),
+        "connection-flapping" | "connection_flapping" => Ok(NotifyEvent::ConnectionFlapping),
+        "ranking" => Ok(NotifyEvent::Ranking),
+        "ranking-changed" | "ranking_changed" => Ok(NotifyEvent::RankingChanged),
+        "daily" => Ok(NotifyEvent::Daily),
+        "daily-summary" | "daily_summary" => Ok(NotifyEvent::DailySummary),
+        "production" => Ok(NotifyEvent::Production),
+        "production-low" | "production_low" => Ok(NotifyEvent::ProductionLow),
+        "production-drop" | "production_drop" => Ok(NotifyEvent::ProductionDrop),
+        "stake" => Ok(NotifyEvent::Stake),
+        "stake-margin" | "stake_margin" => Ok(NotifyEvent::StakeMargin),
+        "commission-changed" | "commission_changed" => Ok(NotifyEvent::CommissionChanged),
+        "benefit-address-changed" | "benefit_address_changed" => {
+            Ok(NotifyEvent::BenefitAddressChanged)
         },
-        "ranking" => Ok(TelegramNotifyEvent::Ranking),
-        "ranking-changed" | "ranking_changed" => Ok(TelegramNotifyEvent::RankingChanged),
-        "daily" => Ok(TelegramNotifyEvent::Daily),
-        "daily-summary" | "daily_summary" => Ok(TelegramNotifyEvent::DailySummary),
+        "delegator" => Ok(NotifyEvent::Delegator),
+        "delegator-changed" | "delegator_changed" => Ok(NotifyEvent::DelegatorChanged),
+        "alert" => Ok(NotifyEvent::Alert),
+        "alert-firing" | "alert_firing" => Ok(NotifyEvent::AlertFiring),
+        "alert-resolved" | "alert_resolved" => Ok(NotifyEvent::AlertResolved),
         _ => Err(format!(
-            "invalid telegram notify event: {value}. valid values: all, connection, \
-             connection-failed, connection-recovered, ranking, ranking-changed, daily, \
-             daily-summary"
+            "invalid notify event: {value}. valid values: all, connection, connection-failed, \
+             connection-recovered, connection-flapping, ranking, ranking-changed, daily, \
+             daily-summary, production, production-low, production-drop, stake, stake-margin, \
+             commission-changed, benefit-address-changed, delegator, delegator-changed, alert, \
+             alert-firing, alert-resolved"
         )),
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct TelegramQuietHours {
+pub struct QuietHours {
     start_minutes: u16,
     end_minutes: u16,
 }
 
-impl TelegramQuietHours {
+impl QuietHours {
     pub fn contains_minutes_since_midnight(
         self,
         minutes_since_midnight: u16,
@@ -72,7 +140,7 @@ impl TelegramQuietHours {
     }
 }
 
-fn parse_telegram_time_of_day(value: &str) -> Result<u16, String> {
+fn parse_time_of_day(value: &str) -> Result<u16, String> {
     let Some((hour, minute)) = value.trim().split_once(':') else {
         return Err(format!("invalid time-of-day: {value}. expected HH:MM format, e.g. 23:00"));
     };
@@ -89,26 +157,327 @@ fn parse_telegram_time_of_day(value: &str) -> Result<u16, String> {
     Ok(hour * 60 + minute)
 }
 
-fn parse_telegram_quiet_hours(value: &str) -> Result<TelegramQuietHours, String> {
+fn parse_quiet_hours(value: &str) -> Result<QuietHours, String> {
     let Some((start, end)) = value.trim().split_once('-') else {
-        return Err(format!("invalid telegram quiet hours: {value}. expected HH:MM-HH:MM format"));
+        return Err(format!("invalid quiet hours: {value}. expected HH:MM-HH:MM format"));
     };
 
-    let start_minutes = parse_telegram_time_of_day(start)?;
-    let end_minutes = parse_telegram_time_of_day(end)?;
+    let start_minutes = parse_time_of_day(start)?;
+    let end_minutes = parse_time_of_day(end)?;
 
     if start_minutes == end_minutes {
-        return Err("telegram quiet hours must have different start and end times".to_string());
+        return Err("quiet hours must have different start and end times".to_string());
     }
 
-    Ok(TelegramQuietHours {
+    Ok(QuietHours {
         start_minutes,
         end_minutes,
     })
 }
 
+/// How often a scheduled report is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportCadence {
+    Daily,
+    Weekly(Weekday),
+    /// Day of the month; shorter months use their last day.
+    Monthly(u32),
+}
+
+/// A `--report-schedule` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportSchedule {
+    pub cadence: ReportCadence,
+    pub time: NaiveTime,
+    /// `None` uses the local timezone.
+    pub timezone: Option<Tz>,
+    /// Overrides `--telegram-chat-id` when not empty.
+    pub chat_ids: Vec<String>,
+    /// Overrides `--telegram-template-daily-summary`.
+    pub template: Option<String>,
+}
+
+impl ReportSchedule {
+    /// The built-in schedule used without `--report-schedule`: every day at
+    /// local midnight.
+    pub fn local_midnight() -> Self {
+        Self {
+            cadence: ReportCadence::Daily,
+            time: NaiveTime::MIN,
+            timezone: None,
+            chat_ids: Vec::new(),
+            template: None,
+        }
+    }
+}
+
+fn parse_report_cadence(value: &str) -> Result<ReportCadence, String> {
+    let value = value.trim().to_ascii_lowercase();
+    let (kind, day) = match value.split_once(':') {
+        Some((kind, day)) => (kind, Some(day)),
+        None => (value.as_str(), None),
+    };
+
+    match (kind, day) {
+        ("daily", None) => Ok(ReportCadence::Daily),
+        ("weekly", Some(day)) => day
+            .parse::<Weekday>()
+            .map(ReportCadence::Weekly)
+            .map_err(|_| format!("invalid weekday: {day}. expected mon, tue, ... sun")),
+        ("monthly", Some(day)) => match day.parse::<u32>() {
+            Ok(day @ 1..=31) => Ok(ReportCadence::Monthly(day)),
+            _ => Err(format!("invalid day of month: {day}. expected 1-31")),
+        },
+        _ => Err(format!(
+            "invalid report cadence: {value}. expected daily, weekly:<weekday> or monthly:<day>"
+        )),
+    }
+}
+
+/// `CADENCE@HH:MM[@TIMEZONE][;chat=ID,ID...][;template=TEXT]`, e.g.
+/// `weekly:mon@09:00@Asia/Shanghai;chat=-1001`. `template` takes the rest of
+/// the value, so it must come last.
+fn parse_report_schedule(value: &str) -> Result<ReportSchedule, String> {
+    let (spec, template) = match value.split_once(";template=") {
+        Some((spec, template)) => (spec, Some(template.to_string())),
+        None => (value, None),
+    };
+    let mut parts = spec.split(';');
+    let timing = parts.next().unwrap_or_default();
+
+    let mut timing_parts = timing.trim().splitn(3, '@');
+    let cadence = parse_report_cadence(timing_parts.next().unwrap_or_default())?;
+    let Some(time) = timing_parts.next() else {
+        return Err(format!(
+            "invalid report schedule: {value}. expected CADENCE@HH:MM[@TIMEZONE], e.g. \
+             daily@09:00@Asia/Shanghai"
+        ));
+    };
+    let minutes = parse_time_of_day(time)?;
+    let time = NaiveTime::from_hms_opt(u32::from(minutes / 60), u32::from(minutes % 60), 0)
+        .ok_or_else(|| format!("invalid time-of-day: {time}"))?;
+    let timezone = timing_parts
+        .next()
+        .map(|timezone| {
+            timezone.trim().parse::<Tz>().map_err(|_| format!("unknown timezone: {timezone}"))
+        })
+        .transpose()?;
+
+    let mut chat_ids = Vec::new();
+    for part in parts.map(str::trim).filter(|part| !part.is_empty()) {
+        match part.split_once('=') {
+            Some(("chat", ids)) => chat_ids.extend(
+                ids.split(',').map(str::trim).filter(|id| !id.is_empty()).map(str::to_string),
+            ),
+            _ => return Err(format!("invalid report schedule option: {part}")),
+        }
+    }
+
+    Ok(ReportSchedule {
+        cadence,
+        time,
+        timezone,
+        chat_ids,
+        template,
+    })
+}
+
+fn parse_report_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|err| format!("invalid date: {value}. expected YYYY-MM-DD format ({err})"))
+}
+
+/// A `--telegram-route` entry: the chats that receive `events`, optionally
+/// limited to some nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelegramRoute {
+    pub events: Vec<NotifyEvent>,
+    /// Node names or IDs; every node when empty.
+    pub nodes: Vec<String>,
+    pub chat_ids: Vec<String>,
+    /// Replaces the event templates for these chats.
+    pub template: Option<String>,
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect()
+}
+
+/// `EVENT[,EVENT...][@NODE[,NODE...]];chat=ID[,ID...][;template=TEXT]`, e.g.
+/// `connection@backup;chat=-1001`. `template` takes the rest of the value,
+/// so it must come last.
+fn parse_telegram_route(value: &str) -> Result<TelegramRoute, String> {
+    let (spec, template) = match value.split_once(";template=") {
+        Some((spec, template)) => (spec, Some(template.to_string())),
+        None => (value, None),
+    };
+    let mut parts = spec.split(';');
+    let selector = parts.next().unwrap_or_default();
+    let (events, nodes) = match selector.split_once('@') {
+        Some((events, nodes)) => (events, split_list(nodes)),
+        None => (selector, Vec::new()),
+    };
+    let events = split_list(events)
+        .iter()
+        .map(|event| parse_notify_event(event))
+        .collect::<Result<Vec<_>, _>>()?;
+    if events.is_empty() {
+        return Err(format!(
+            "invalid telegram route: {value}. expected \
+             EVENT[,EVENT...][@NODE[,NODE...]];chat=ID[,ID...], e.g. connection@backup;chat=-1001"
+        ));
+    }
+
+    let mut chat_ids = Vec::new();
+    for part in parts.map(str::trim).filter(|part| !part.is_empty()) {
+        match part.split_once('=') {
+            Some(("chat", ids)) => chat_ids.extend(split_list(ids)),
+            _ => return Err(format!("invalid telegram route option: {part}")),
+        }
+    }
+    if chat_ids.is_empty() {
+        return Err(format!("invalid telegram route: {value}. at least one chat is required"));
+    }
+
+    Ok(TelegramRoute {
+        events,
+        nodes,
+        chat_ids,
+        template,
+    })
+}
+
+/// How Telegram formats notification text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TelegramParseMode {
+    /// Send text as is
+    Plain,
+    /// Telegram HTML; template values are HTML-escaped
+    Html,
+    /// Telegram MarkdownV2; template values are escaped
+    #[value(name = "markdown-v2", alias = "markdownv2")]
+    MarkdownV2,
+}
+
+/// Transport encryption for the SMTP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS (port 587 by default)
+    Starttls,
+    /// Implicit TLS from the first byte (port 465 by default)
+    Tls,
+    /// No encryption (port 25 by default); only for local relays
+    None,
+}
+
+/// Email recipients for the notifications selected by `event`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailRoute {
+    pub event: NotifyEvent,
+    pub recipients: Vec<String>,
+}
+
+fn parse_email_route(value: &str) -> Result<EmailRoute, String> {
+    let Some((event, recipients)) = value.split_once('=') else {
+        return Err(format!(
+            "invalid email route: {value}. expected EVENT=ADDRESS[,ADDRESS...], e.g. \
+             daily=ops@example.com"
+        ));
+    };
+    let recipients = recipients
+        .split(',')
+        .map(str::trim)
+        .filter(|recipient| !recipient.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if recipients.is_empty() {
+        return Err(format!("invalid email route: {value}. at least one address is required"));
+    }
+
+    Ok(EmailRoute {
+        event: parse_notify_event(event)?,
+        recipients,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+    Markdown,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ReportArgs {
+    /// First day of the report period (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_report_date)]
+    pub from: NaiveDate,
+
+    /// Last day of the report period, inclusive (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_report_date)]
+    pub to: NaiveDate,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: ReportFormat,
+}
+
+/// A notification template `template preview` can render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TemplateName {
+    ConnectionFailed,
+    ConnectionRecovered,
+    ConnectionRepeat,
+    ConnectionEscalated,
+    ConnectionFlapping,
+    RankingChanged,
+    QuietSummary,
+    DailySummary,
+    ProductionLow,
+    ProductionDrop,
+    StakeMargin,
+    CommissionChanged,
+    BenefitAddressChanged,
+    DelegatorChanged,
+    AlertFiring,
+    AlertResolved,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum TemplateCommand {
+    /// Render a notification template with sample data, using the
+    /// --telegram-template-* options and --telegram-parse-mode
+    Preview {
+        /// The notification to render
+        #[arg(value_enum)]
+        name: TemplateName,
+
+        /// Template to render instead of the configured one; @PATH reads it from a file
+        #[arg(long)]
+        template: Option<String>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TemplateArgs {
+    #[command(subcommand)]
+    pub command: TemplateCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Print per-node production and reward totals from the stored daily snapshots
+    Report(ReportArgs),
+    /// Work with notification templates
+    Template(TemplateArgs),
+}
+
 #[derive(Parser, Debug)]
 pub struct Opts {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// The platon connection endpoints, separated by `,`.
     #[arg(long, default_value = "test@ws://127.0.0.1:6789")]
     pub url: String,
@@ -121,8 +490,9 @@ pub struct Opts {
     #[arg(long)]
     pub debug: bool,
 
-    /// Path to the SQLite database used for peer geography persistence
-    #[arg(long, default_value = "./chaindash.db")]
+    /// Path to the SQLite database used for peer geography, delegator and daily node
+    /// snapshots
+    #[arg(long, global = true, default_value = "./chaindash.db")]
     pub db_path: String,
 
     /// Disk mount points to monitor (comma-separated)
@@ -145,10 +515,90 @@ pub struct Opts {
     #[arg(long, value_delimiter = ',')]
     pub node_id: Vec<String>,
 
+    /// Competitor node IDs to watch alongside our own (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub watch_node_id: Vec<String>,
+
     /// PlatON Explorer API base URL
     #[arg(long, default_value = "https://scan.platon.network/browser-server")]
     pub explorer_api_url: String,
 
+    /// Seconds between Explorer node detail refreshes
+    #[arg(long, default_value = "10", value_parser = parse_positive_u64)]
+    pub explorer_detail_interval: u64,
+
+    /// Seconds between Explorer ranking list refreshes
+    #[arg(long, default_value = "30", value_parser = parse_positive_u64)]
+    pub explorer_ranking_interval: u64,
+
+    /// Maximum number of concurrent Explorer requests
+    #[arg(long, default_value = "4", value_parser = parse_positive_u64)]
+    pub explorer_max_concurrency: u64,
+
+    /// Retries for Explorer requests failing with HTTP 429, 5xx or network errors
+    #[arg(long, default_value = "3")]
+    pub explorer_max_retries: u32,
+
+    /// Explorer response cache file (default: chaindash state directory)
+    #[arg(long)]
+    pub explorer_cache_file: Option<PathBuf>,
+
+    /// Alert when a node produces less than this fraction of its expected blocks in the
+    /// current epoch
+    #[arg(long, default_value = "0.8", value_parser = parse_fraction)]
+    pub block_production_alert_ratio: f64,
+
+    /// Alert when a node in the validator set produces less than this fraction of its
+    /// trailing hourly average in an hour
+    #[arg(long, default_value = "0.5", value_parser = parse_fraction)]
+    pub production_drop_alert_ratio: f64,
+
+    /// Hours averaged as the baseline for production-drop alerts
+    #[arg(long, default_value = "6", value_parser = parse_positive_u32)]
+    pub production_drop_trailing_hours: u32,
+
+    /// Ranks counted as the validator set; nodes ranked lower raise no production-drop alerts
+    #[arg(long, default_value = "201", value_parser = parse_positive_u32)]
+    pub validator_set_size: u32,
+
+    /// Days of hourly node snapshots to keep
+    #[arg(long, default_value = "14", value_parser = parse_positive_u64)]
+    pub hourly_snapshot_retention_days: u64,
+
+    /// Consensus-set cutoff ranks to measure our stake margin against (comma-separated)
+    #[arg(long, value_delimiter = ',', default_value = "201", value_parser = parse_positive_u32)]
+    pub stake_target_ranks: Vec<u32>,
+
+    /// Alert when the stake margin to a target rank drops below this many LAT
+    #[arg(long, value_parser = parse_positive_f64)]
+    pub stake_margin_alert_threshold: Option<f64>,
+
+    /// Seconds between Explorer delegator list refreshes
+    #[arg(long, default_value = "600", value_parser = parse_positive_u64)]
+    pub delegation_refresh_interval: u64,
+
+    /// Alert when a delegator holding at least this many LAT adds or withdraws stake
+    #[arg(long, value_parser = parse_positive_f64)]
+    pub delegator_alert_threshold: Option<f64>,
+
+    /// JSON file of alert rules evaluated against collected metrics
+    #[arg(long)]
+    pub alert_rules: Option<PathBuf>,
+
+    /// Days of daily node snapshots to keep (default: keep the full history)
+    #[arg(long, value_parser = parse_positive_u64)]
+    pub daily_snapshot_retention_days: Option<u64>,
+
+    /// When to send node reports: CADENCE@HH:MM[@TIMEZONE][;chat=ID,...][;template=TEXT],
+    /// where CADENCE is daily, weekly:<weekday> or monthly:<day>. Repeatable
+    /// (default: daily at local midnight)
+    #[arg(long = "report-schedule", value_parser = parse_report_schedule)]
+    pub report_schedules: Vec<ReportSchedule>,
+
+    /// Send a late daily summary when the process was not running at midnight
+    #[arg(long)]
+    pub daily_summary_catch_up: bool,
+
     /// Telegram bot token used for notifications
     #[arg(long)]
     pub telegram_bot_token: Option<String>,
@@ -157,17 +607,67 @@ pub struct Opts {
     #[arg(long, value_delimiter = ',')]
     pub telegram_chat_id: Vec<String>,
 
-    /// Telegram notification events (comma-separated)
-    #[arg(long, value_delimiter = ',', value_parser = parse_telegram_notify_event)]
-    pub telegram_notify_events: Vec<TelegramNotifyEvent>,
+    /// Telegram chats for some events, format
+    /// EVENT[,EVENT...][@NODE[,NODE...]];chat=ID[,ID...][;template=TEXT], where NODE is a
+    /// node name or ID. Repeatable; events without a matching route go to --telegram-chat-id
+    #[arg(long = "telegram-route", value_parser = parse_telegram_route)]
+    pub telegram_routes: Vec<TelegramRoute>,
+
+    /// Notification events sent to every channel (comma-separated)
+    #[arg(
+        long,
+        alias = "telegram-notify-events",
+        value_delimiter = ',',
+        value_parser = parse_notify_event
+    )]
+    pub notify_events: Vec<NotifyEvent>,
+
+    /// Notification quiet hours in local time, format HH:MM-HH:MM
+    #[arg(long, alias = "telegram-quiet-hours", value_parser = parse_quiet_hours)]
+    pub quiet_hours: Option<QuietHours>,
+
+    /// Minimum interval between repeated notifications for the same event key
+    #[arg(long, alias = "telegram-rate-limit-seconds", default_value = "0")]
+    pub notify_rate_limit_seconds: u64,
+
+    /// Hours after which persisted notification state (connection states,
+    /// rankings, rate limits, quiet-hours buffer) is discarded on startup
+    #[arg(long, default_value = "24", value_parser = parse_positive_u64)]
+    pub notify_state_max_age_hours: u64,
+
+    /// Repeat the connection-failed alert every this many minutes while the
+    /// connection stays down (0 disables repeats)
+    #[arg(long, default_value = "0")]
+    pub notify_repeat_minutes: u64,
+
+    /// Escalate a connection failure still unresolved after this many minutes
+    /// as a critical alert (0 disables escalation)
+    #[arg(long, default_value = "0")]
+    pub notify_escalate_after_minutes: u64,
+
+    /// Telegram chat IDs that receive escalations instead of the default
+    /// chats (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub notify_escalation_chat_id: Vec<String>,
 
-    /// Telegram quiet hours in local time, format HH:MM-HH:MM
-    #[arg(long, value_parser = parse_telegram_quiet_hours)]
-    pub telegram_quiet_hours: Option<TelegramQuietHours>,
+    /// Seconds a connection must keep failing before the connection-failed
+    /// alert is sent
+    #[arg(long, default_value = "0")]
+    pub notify_failure_grace_seconds: u64,
 
-    /// Minimum interval between repeated Telegram notifications for the same event key
+    /// Consecutive failed polls before the connection-failed alert is sent
+    #[arg(long, default_value = "1", value_parser = parse_positive_u32)]
+    pub notify_failure_threshold: u32,
+
+    /// Report a connection as flapping once it fails this many times within
+    /// --notify-flap-window-minutes, instead of sending every failure and
+    /// recovery (0 disables flap detection)
     #[arg(long, default_value = "0")]
-    pub telegram_rate_limit_seconds: u64,
+    pub notify_flap_threshold: u32,
+
+    /// Window in which connection failures are counted for flap detection
+    #[arg(long, default_value = "10", value_parser = parse_positive_u64)]
+    pub notify_flap_window_minutes: u64,
 
     /// Template for connection-failed notifications
     #[arg(long)]
@@ -189,9 +689,133 @@ pub struct Opts {
     #[arg(long)]
     pub telegram_template_daily_summary: Option<String>,
 
+    /// Template for production-low notifications
+    #[arg(long)]
+    pub telegram_template_production_low: Option<String>,
+
+    /// Template for production-drop notifications
+    #[arg(long)]
+    pub telegram_template_production_drop: Option<String>,
+
+    /// Template for stake-margin notifications
+    #[arg(long)]
+    pub telegram_template_stake_margin: Option<String>,
+
+    /// Template for commission-changed notifications
+    #[arg(long)]
+    pub telegram_template_commission_changed: Option<String>,
+
+    /// Template for benefit-address-changed notifications
+    #[arg(long)]
+    pub telegram_template_benefit_address_changed: Option<String>,
+
+    /// Template for delegator-changed notifications
+    #[arg(long)]
+    pub telegram_template_delegator_changed: Option<String>,
+
+    /// Template for reminders about connections that are still down
+    #[arg(long)]
+    pub telegram_template_connection_repeat: Option<String>,
+
+    /// Template for escalated connection failures
+    #[arg(long)]
+    pub telegram_template_connection_escalated: Option<String>,
+
+    /// Template for connection-flapping notifications
+    #[arg(long)]
+    pub telegram_template_connection_flapping: Option<String>,
+
+    /// Template for alert-rule firing notifications
+    #[arg(long)]
+    pub telegram_template_alert_firing: Option<String>,
+
+    /// Template for alert-rule resolved notifications
+    #[arg(long)]
+    pub telegram_template_alert_resolved: Option<String>,
+
+    /// How Telegram formats notifications; custom templates are written in
+    /// this mode and their values are escaped for it
+    #[arg(long, value_enum, default_value = "plain")]
+    pub telegram_parse_mode: TelegramParseMode,
+
     /// Telegram Bot API base URL
     #[arg(long, default_value = "https://api.telegram.org")]
     pub telegram_api_url: String,
+
+    /// Delivery attempts per Telegram chat before a notification is moved to
+    /// the dead-letter queue
+    #[arg(long, default_value = "8", value_parser = parse_positive_u32)]
+    pub telegram_max_attempts: u32,
+
+    /// Answer /status, /nodes, /daily, /mute and /unmute from the Telegram
+    /// chat IDs, polling the bot with getUpdates
+    #[arg(long)]
+    pub telegram_commands: bool,
+
+    /// Webhook URLs that receive notifications as JSON (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub webhook_url: Vec<String>,
+
+    /// Secret used to sign webhook bodies with HMAC-SHA256
+    #[arg(long)]
+    pub webhook_secret: Option<String>,
+
+    /// Seconds before a single webhook or robot request times out
+    #[arg(long, default_value = "10", value_parser = parse_positive_u64)]
+    pub webhook_timeout_seconds: u64,
+
+    /// DingTalk robot webhook URLs (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub dingtalk_webhook: Vec<String>,
+
+    /// DingTalk robot signing secret
+    #[arg(long)]
+    pub dingtalk_secret: Option<String>,
+
+    /// Feishu/Lark robot webhook URLs (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub feishu_webhook: Vec<String>,
+
+    /// Feishu/Lark robot signature verification secret
+    #[arg(long)]
+    pub feishu_secret: Option<String>,
+
+    /// WeCom group robot webhook URLs (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub wecom_webhook: Vec<String>,
+
+    /// SMTP server host; enables email notifications
+    #[arg(long)]
+    pub smtp_host: Option<String>,
+
+    /// SMTP server port (default: 587 for starttls, 465 for tls, 25 for none)
+    #[arg(long)]
+    pub smtp_port: Option<u16>,
+
+    /// SMTP connection security
+    #[arg(long, value_enum, default_value = "starttls")]
+    pub smtp_security: SmtpSecurity,
+
+    /// SMTP username; enables authentication together with --smtp-password
+    #[arg(long)]
+    pub smtp_username: Option<String>,
+
+    /// SMTP password
+    #[arg(long)]
+    pub smtp_password: Option<String>,
+
+    /// Sender address of notification emails, e.g. "chaindash <bot@example.com>"
+    #[arg(long)]
+    pub smtp_from: Option<String>,
+
+    /// Default email recipients (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub email_to: Vec<String>,
+
+    /// Email recipients for one event type, format EVENT=ADDRESS[,ADDRESS...].
+    /// Repeatable; events without a route go to --email-to
+    #[arg(long = "email-route", value_parser = parse_email_route)]
+    pub email_routes: Vec<EmailRoute>,
 }
 
 #[cfg(test)]
@@ -258,6 +882,15 @@ mod tests {
         assert_eq!(opts.node_id, vec!["node-a".to_string(), "node-b".to_string()]);
     }
 
+    #[test]
+    fn test_watch_node_ids_are_separate_from_node_ids() {
+        let opts =
+            Opts::parse_from(["test", "--node-id", "ours", "--watch-node-id", "rival-a,rival-b"]);
+
+        assert_eq!(opts.node_id, vec!["ours".to_string()]);
+        assert_eq!(opts.watch_node_id, vec!["rival-a".to_string(), "rival-b".to_string()]);
+    }
+
     #[test]
     fn test_telegram_options_are_accepted() {
         let opts = Opts::parse_from([
@@ -286,18 +919,15 @@ mod tests {
 
         assert_eq!(opts.telegram_bot_token.as_deref(), Some("bot-token"));
         assert_eq!(opts.telegram_chat_id, vec!["123456".to_string(), "789012".to_string()]);
+        assert_eq!(opts.notify_events, vec![NotifyEvent::Connection, NotifyEvent::RankingChanged]);
         assert_eq!(
-            opts.telegram_notify_events,
-            vec![TelegramNotifyEvent::Connection, TelegramNotifyEvent::RankingChanged]
-        );
-        assert_eq!(
-            opts.telegram_quiet_hours,
-            Some(TelegramQuietHours {
+            opts.quiet_hours,
+            Some(QuietHours {
                 start_minutes: 23 * 60,
                 end_minutes: 8 * 60,
             })
         );
-        assert_eq!(opts.telegram_rate_limit_seconds, 120);
+        assert_eq!(opts.notify_rate_limit_seconds, 120);
         assert_eq!(
             opts.telegram_template_connection_failed.as_deref(),
             Some("{prefix} {node} failed: {reason}")
@@ -322,18 +952,403 @@ mod tests {
     }
 
     #[test]
-    fn test_invalid_telegram_notify_event_is_rejected() {
+    fn test_shared_notification_and_webhook_options_are_accepted() {
+        let opts = Opts::parse_from([
+            "test",
+            "--notify-events",
+            "daily",
+            "--quiet-hours",
+            "22:00-07:00",
+            "--notify-rate-limit-seconds",
+            "30",
+            "--webhook-url",
+            "https://a.example.com/hook,https://b.example.com/hook",
+            "--webhook-secret",
+            "s3cret",
+        ]);
+
+        assert_eq!(opts.notify_events, vec![NotifyEvent::Daily]);
+        assert!(opts.quiet_hours.is_some());
+        assert_eq!(opts.notify_rate_limit_seconds, 30);
+        assert_eq!(
+            opts.webhook_url,
+            vec![
+                "https://a.example.com/hook".to_string(),
+                "https://b.example.com/hook".to_string()
+            ]
+        );
+        assert_eq!(opts.webhook_secret.as_deref(), Some("s3cret"));
+        assert_eq!(opts.webhook_timeout_seconds, 10);
+        assert!(Opts::try_parse_from(["test", "--webhook-timeout-seconds", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_email_options_are_accepted() {
+        let opts = Opts::parse_from([
+            "test",
+            "--smtp-host",
+            "smtp.example.com",
+            "--smtp-security",
+            "tls",
+            "--smtp-from",
+            "chaindash <bot@example.com>",
+            "--email-to",
+            "ops@example.com,oncall@example.com",
+            "--email-route",
+            "daily=boss@example.com, cto@example.com",
+            "--email-route",
+            "connection=oncall@example.com",
+        ]);
+
+        assert_eq!(opts.smtp_host.as_deref(), Some("smtp.example.com"));
+        assert_eq!(opts.smtp_security, SmtpSecurity::Tls);
+        assert_eq!(opts.smtp_port, None);
+        assert_eq!(opts.email_to, vec!["ops@example.com", "oncall@example.com"]);
+        assert_eq!(
+            opts.email_routes,
+            vec![
+                EmailRoute {
+                    event: NotifyEvent::Daily,
+                    recipients: vec!["boss@example.com".to_string(), "cto@example.com".to_string()],
+                },
+                EmailRoute {
+                    event: NotifyEvent::Connection,
+                    recipients: vec!["oncall@example.com".to_string()],
+                },
+            ]
+        );
+        assert_eq!(Opts::parse_from(["test"]).smtp_security, SmtpSecurity::Starttls);
+        assert!(Opts::try_parse_from(["test", "--email-route", "daily"]).is_err());
+        assert!(Opts::try_parse_from(["test", "--email-route", "weekly=a@example.com"]).is_err());
+    }
+
+    #[test]
+    fn test_telegram_route_parses_events_nodes_chats_and_template() {
+        let opts = Opts::parse_from([
+            "test",
+            "--telegram-route",
+            "ranking;chat=-1001",
+            "--telegram-route",
+            "connection@backup, 0xABC;chat=-1002,-1003;template={node} 掉线：{reason}",
+        ]);
+
+        assert_eq!(
+            opts.telegram_routes,
+            vec![
+                TelegramRoute {
+                    events: vec![NotifyEvent::Ranking],
+                    nodes: Vec::new(),
+                    chat_ids: vec!["-1001".to_string()],
+                    template: None,
+                },
+                TelegramRoute {
+                    events: vec![NotifyEvent::Connection],
+                    nodes: vec!["backup".to_string(), "0xABC".to_string()],
+                    chat_ids: vec!["-1002".to_string(), "-1003".to_string()],
+                    template: Some("{node} 掉线：{reason}".to_string()),
+                },
+            ]
+        );
+        for value in ["ranking", ";chat=-1001", "weekly;chat=-1001", "ranking;to=-1001"] {
+            assert!(
+                Opts::try_parse_from(["test", "--telegram-route", value]).is_err(),
+                "{value} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_connection_damping_options() {
+        let defaults = Opts::parse_from(["test"]);
+        assert_eq!(defaults.notify_failure_grace_seconds, 0);
+        assert_eq!(defaults.notify_failure_threshold, 1);
+        assert_eq!(defaults.notify_flap_threshold, 0);
+        assert_eq!(defaults.notify_flap_window_minutes, 10);
+
+        let opts = Opts::parse_from([
+            "test",
+            "--notify-failure-grace-seconds",
+            "30",
+            "--notify-failure-threshold",
+            "3",
+            "--notify-flap-threshold",
+            "4",
+            "--notify-flap-window-minutes",
+            "15",
+            "--notify-events",
+            "connection-flapping",
+        ]);
+        assert_eq!(opts.notify_failure_grace_seconds, 30);
+        assert_eq!(opts.notify_failure_threshold, 3);
+        assert_eq!(opts.notify_flap_threshold, 4);
+        assert_eq!(opts.notify_flap_window_minutes, 15);
+        assert_eq!(opts.notify_events, vec![NotifyEvent::ConnectionFlapping]);
+
+        assert!(Opts::try_parse_from(["test", "--notify-failure-threshold", "0"]).is_err());
+        assert!(Opts::try_parse_from(["test", "--notify-flap-window-minutes", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_notify_event_is_rejected() {
         let result =
             Opts::try_parse_from(["test", "--telegram-notify-events", "not-a-valid-event"]);
 
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("invalid telegram notify event"));
+        assert!(result.unwrap_err().to_string().contains("invalid notify event"));
+    }
+
+    #[test]
+    fn test_block_production_alert_ratio_defaults_to_eighty_percent() {
+        let opts = Opts::parse_from(["test"]);
+
+        assert_eq!(opts.block_production_alert_ratio, 0.8);
+    }
+
+    #[test]
+    fn test_explorer_client_options_have_defaults() {
+        let opts = Opts::parse_from(["test"]);
+
+        assert_eq!(opts.explorer_detail_interval, 10);
+        assert_eq!(opts.explorer_ranking_interval, 30);
+        assert_eq!(opts.explorer_max_concurrency, 4);
+        assert_eq!(opts.explorer_max_retries, 3);
+        assert_eq!(opts.explorer_cache_file, None);
+    }
+
+    #[test]
+    fn test_zero_explorer_concurrency_is_rejected() {
+        let result = Opts::try_parse_from(["test", "--explorer-max-concurrency", "0"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_block_production_alert_ratio_out_of_range_is_rejected() {
+        for value in ["0", "1.5", "-0.2"] {
+            let result = Opts::try_parse_from(["test", "--block-production-alert-ratio", value]);
+
+            assert!(result.is_err(), "{value} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_production_notify_events_are_accepted() {
+        let opts =
+            Opts::parse_from(["test", "--telegram-notify-events", "production,production-low"]);
+
+        assert_eq!(opts.notify_events, vec![NotifyEvent::Production, NotifyEvent::ProductionLow]);
+    }
+
+    #[test]
+    fn test_hourly_production_options() {
+        let defaults = Opts::parse_from(["test"]);
+        assert_eq!(defaults.production_drop_alert_ratio, 0.5);
+        assert_eq!(defaults.production_drop_trailing_hours, 6);
+        assert_eq!(defaults.validator_set_size, 201);
+        assert_eq!(defaults.hourly_snapshot_retention_days, 14);
+
+        let opts = Opts::parse_from([
+            "test",
+            "--production-drop-alert-ratio",
+            "0.3",
+            "--production-drop-trailing-hours",
+            "12",
+            "--telegram-notify-events",
+            "production-drop",
+        ]);
+        assert_eq!(opts.production_drop_alert_ratio, 0.3);
+        assert_eq!(opts.production_drop_trailing_hours, 12);
+        assert_eq!(opts.notify_events, vec![NotifyEvent::ProductionDrop]);
+
+        assert!(Opts::try_parse_from(["test", "--production-drop-trailing-hours", "0"]).is_err());
+        assert!(Opts::try_parse_from(["test", "--production-drop-alert-ratio", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_stake_margin_options() {
+        let defaults = Opts::parse_from(["test"]);
+        assert_eq!(defaults.stake_target_ranks, vec![201]);
+        assert_eq!(defaults.stake_margin_alert_threshold, None);
+
+        let opts = Opts::parse_from([
+            "test",
+            "--stake-target-ranks",
+            "25,201",
+            "--stake-margin-alert-threshold",
+            "50000",
+            "--telegram-notify-events",
+            "stake-margin",
+        ]);
+        assert_eq!(opts.stake_target_ranks, vec![25, 201]);
+        assert_eq!(opts.stake_margin_alert_threshold, Some(50_000.0));
+        assert_eq!(opts.notify_events, vec![NotifyEvent::StakeMargin]);
+
+        assert!(Opts::try_parse_from(["test", "--stake-target-ranks", "0"]).is_err());
+        assert!(Opts::try_parse_from(["test", "--stake-margin-alert-threshold", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_report_subcommand_parses_period_and_format() {
+        assert!(Opts::parse_from(["test"]).command.is_none());
+
+        let opts = Opts::parse_from([
+            "test",
+            "report",
+            "--from",
+            "2026-01-01",
+            "--to",
+            "2026-03-31",
+            "--format",
+            "markdown",
+            "--db-path",
+            "/tmp/report.db",
+        ]);
+
+        let Some(Command::Report(args)) = opts.command else {
+            panic!("report subcommand should parse");
+        };
+        assert_eq!(args.from, NaiveDate::from_ymd_opt(2026, 1, 1).expect("date should be valid"));
+        assert_eq!(args.to, NaiveDate::from_ymd_opt(2026, 3, 31).expect("date should be valid"));
+        assert_eq!(args.format, ReportFormat::Markdown);
+        assert_eq!(opts.db_path, "/tmp/report.db");
+    }
+
+    #[test]
+    fn test_report_subcommand_rejects_invalid_input() {
+        assert!(Opts::try_parse_from(["test", "report", "--from", "2026-01-01"]).is_err());
+        assert!(Opts::try_parse_from([
+            "test",
+            "report",
+            "--from",
+            "2026-13-01",
+            "--to",
+            "2026-12-31"
+        ])
+        .is_err());
+        assert!(Opts::try_parse_from([
+            "test",
+            "report",
+            "--from",
+            "2026-01-01",
+            "--to",
+            "2026-01-31",
+            "--format",
+            "xml"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_template_preview_subcommand_and_parse_mode() {
+        let opts = Opts::parse_from([
+            "test",
+            "--telegram-parse-mode",
+            "markdownv2",
+            "template",
+            "preview",
+            "daily-summary",
+            "--template",
+            "@daily.tmpl",
+        ]);
+
+        assert_eq!(opts.telegram_parse_mode, TelegramParseMode::MarkdownV2);
+        let Some(Command::Template(TemplateArgs {
+            command: TemplateCommand::Preview { name, template },
+        })) = opts.command
+        else {
+            panic!("template preview subcommand should parse");
+        };
+        assert_eq!(name, TemplateName::DailySummary);
+        assert_eq!(template.as_deref(), Some("@daily.tmpl"));
+
+        assert_eq!(Opts::parse_from(["test"]).telegram_parse_mode, TelegramParseMode::Plain);
+        assert!(Opts::try_parse_from(["test", "--telegram-parse-mode", "markdown"]).is_err());
+        assert!(Opts::try_parse_from(["test", "template", "preview", "unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_report_schedule_parses_cadence_time_and_timezone() {
+        assert!(Opts::parse_from(["test"]).report_schedules.is_empty());
+
+        let opts = Opts::parse_from([
+            "test",
+            "--report-schedule",
+            "daily@09:30",
+            "--report-schedule",
+            "weekly:mon@08:00@Asia/Shanghai;chat=-1001, -1002",
+            "--report-schedule",
+            "monthly:31@07:15@Europe/Berlin;template={title}\\n{details}",
+        ]);
+
+        let [daily, weekly, monthly] = opts.report_schedules.as_slice() else {
+            panic!("three schedules should parse");
+        };
+        assert_eq!(daily.cadence, ReportCadence::Daily);
+        assert_eq!(daily.time, NaiveTime::from_hms_opt(9, 30, 0).expect("time should be valid"));
+        assert_eq!(daily.timezone, None);
+        assert_eq!(weekly.cadence, ReportCadence::Weekly(Weekday::Mon));
+        assert_eq!(weekly.timezone, Some(chrono_tz::Asia::Shanghai));
+        assert_eq!(weekly.chat_ids, vec!["-1001".to_string(), "-1002".to_string()]);
+        assert_eq!(monthly.cadence, ReportCadence::Monthly(31));
+        assert_eq!(monthly.template.as_deref(), Some("{title}\\n{details}"));
+    }
+
+    #[test]
+    fn test_report_schedule_rejects_invalid_values() {
+        for value in [
+            "hourly@09:00",
+            "daily",
+            "daily@25:00",
+            "weekly@09:00",
+            "weekly:someday@09:00",
+            "monthly:0@09:00",
+            "daily@09:00@Mars/Base",
+            "daily@09:00;to=123",
+        ] {
+            assert!(
+                Opts::try_parse_from(["test", "--report-schedule", value]).is_err(),
+                "{value} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_daily_snapshot_retention_days() {
+        assert_eq!(Opts::parse_from(["test"]).daily_snapshot_retention_days, None);
+
+        let opts = Opts::parse_from(["test", "--daily-snapshot-retention-days", "400"]);
+        assert_eq!(opts.daily_snapshot_retention_days, Some(400));
+
+        assert!(Opts::try_parse_from(["test", "--daily-snapshot-retention-days", "0"]).is_err());
+        assert!(!Opts::parse_from(["test"]).daily_summary_catch_up);
+        assert!(Opts::parse_from(["test", "--daily-summary-catch-up"]).daily_summary_catch_up);
+    }
+
+    #[test]
+    fn test_delegation_options() {
+        let defaults = Opts::parse_from(["test"]);
+        assert_eq!(defaults.delegation_refresh_interval, 600);
+        assert_eq!(defaults.delegator_alert_threshold, None);
+
+        let opts = Opts::parse_from([
+            "test",
+            "--delegation-refresh-interval",
+            "120",
+            "--delegator-alert-threshold",
+            "100000",
+            "--telegram-notify-events",
+            "delegator-changed",
+        ]);
+        assert_eq!(opts.delegation_refresh_interval, 120);
+        assert_eq!(opts.delegator_alert_threshold, Some(100_000.0));
+        assert_eq!(opts.notify_events, vec![NotifyEvent::DelegatorChanged]);
+
+        assert!(Opts::try_parse_from(["test", "--delegation-refresh-interval", "0"]).is_err());
     }
 
     #[test]
-    fn test_telegram_quiet_hours_contains_overnight_range() {
-        let quiet_hours =
-            parse_telegram_quiet_hours("23:00-08:00").expect("quiet hours should parse");
+    fn test_quiet_hours_contains_overnight_range() {
+        let quiet_hours = parse_quiet_hours("23:00-08:00").expect("quiet hours should parse");
 
         assert!(quiet_hours.contains_minutes_since_midnight(23 * 60));
         assert!(quiet_hours.contains_minutes_since_midnight(7 * 60 + 59));
@@ -342,10 +1357,10 @@ mod tests {
     }
 
     #[test]
-    fn test_invalid_telegram_quiet_hours_are_rejected() {
+    fn test_invalid_quiet_hours_are_rejected() {
         let result = Opts::try_parse_from(["test", "--telegram-quiet-hours", "23:00/08:00"]);
 
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("invalid telegram quiet hours"));
+        assert!(result.unwrap_err().to_string().contains("invalid quiet hours"));
     }
 }
@@ -0,0 +1,423 @@
+use std::{
+    collections::BTreeMap,
+    io::{
+        self,
+        Write,
+    },
+};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    collect::{
+        DailyNodeSnapshotStore,
+        DailySnapshotRow,
+    },
+    error::{
+        ChaindashError,
+        Result,
+    },
+    opts::{
+        Opts,
+        ReportArgs,
+        ReportFormat,
+    },
+};
+
+const REPORT_COLUMNS: [&str; 7] =
+    ["Node", "Node ID", "Blocks", "Rewards (LAT)", "Avg rank", "Snapshots", "Missing days"];
+
+/// Production and reward totals for one node over the report period.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct NodeReport {
+    node_name: String,
+    node_id: String,
+    /// `None` when fewer than two snapshots fall inside the period.
+    blocks_produced: Option<u64>,
+    system_reward: Option<f64>,
+    average_ranking: Option<f64>,
+    snapshot_days: usize,
+    missing_days: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Report {
+    #[serde(serialize_with = "serialize_date")]
+    from: NaiveDate,
+    #[serde(serialize_with = "serialize_date")]
+    to: NaiveDate,
+    days: usize,
+    nodes: Vec<NodeReport>,
+}
+
+fn serialize_date<S: serde::Serializer>(
+    date: &NaiveDate,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(date)
+}
+
+/// Entry point of `chaindash report`.
+pub(crate) fn run(
+    opts: &Opts,
+    args: &ReportArgs,
+) -> Result<()> {
+    if args.from > args.to {
+        return Err(ChaindashError::Other(format!(
+            "report period is empty: --from {} is after --to {}",
+            args.from, args.to
+        )));
+    }
+
+    // Reports only read, so the database is neither migrated nor pruned and
+    // a legacy JSON store is left for the monitor to import.
+    let store = DailyNodeSnapshotStore::open_read_only(&opts.db_path)?;
+    // The snapshot dated the day after `to` closes the last day of the period.
+    let until = args.to.succ_opt().unwrap_or(args.to);
+    let rows = store.snapshots_between(args.from, until)?;
+    let report = build_report(args.from, args.to, &rows);
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(render_report(&report, args.format)?.as_bytes())?;
+    Ok(())
+}
+
+/// Each snapshot holds the cumulative counters at local midnight, so a
+/// node's production is the sum of the deltas between its consecutive
+/// snapshots from `from` to the day after `to`. Gaps are bridged by the
+/// surrounding snapshots and show up as missing days.
+fn build_report(
+    from: NaiveDate,
+    to: NaiveDate,
+    rows: &[DailySnapshotRow],
+) -> Report {
+    let days = (to - from).num_days() as usize + 1;
+    let mut by_node: BTreeMap<String, Vec<&DailySnapshotRow>> = BTreeMap::new();
+    for row in rows.iter().filter(|row| row.date >= from) {
+        let key = if row.node_id.is_empty() {
+            &row.node_name
+        } else {
+            &row.node_id
+        };
+        by_node.entry(key.to_lowercase()).or_default().push(row);
+    }
+
+    let mut nodes: Vec<NodeReport> = by_node
+        .into_values()
+        .map(|mut rows| {
+            rows.sort_by_key(|row| row.date);
+            node_report(to, days, &rows)
+        })
+        .collect();
+    nodes.sort_by(|left, right| {
+        left.node_name.cmp(&right.node_name).then_with(|| left.node_id.cmp(&right.node_id))
+    });
+
+    Report {
+        from,
+        to,
+        days,
+        nodes,
+    }
+}
+
+fn node_report(
+    to: NaiveDate,
+    days: usize,
+    rows: &[&DailySnapshotRow],
+) -> NodeReport {
+    let in_period: Vec<_> = rows.iter().filter(|row| row.date <= to).collect();
+    let latest = rows.last().expect("node rows are never empty");
+    let (blocks_produced, system_reward) = if rows.len() < 2 {
+        (None, None)
+    } else {
+        let blocks =
+            rows.windows(2).map(|pair| pair[1].block_qty.saturating_sub(pair[0].block_qty)).sum();
+        let reward = rows
+            .windows(2)
+            .map(|pair| (pair[1].reward_value - pair[0].reward_value).max(0.0))
+            .sum();
+        (Some(blocks), Some(reward))
+    };
+    let rankings: Vec<f64> =
+        in_period.iter().filter_map(|row| row.ranking).map(f64::from).collect();
+    let average_ranking =
+        (!rankings.is_empty()).then(|| rankings.iter().sum::<f64>() / rankings.len() as f64);
+
+    NodeReport {
+        node_name: latest.node_name.clone(),
+        node_id: rows
+            .iter()
+            .rev()
+            .map(|row| &row.node_id)
+            .find(|id| !id.is_empty())
+            .cloned()
+            .unwrap_or_default(),
+        blocks_produced,
+        system_reward,
+        average_ranking,
+        snapshot_days: in_period.len(),
+        missing_days: days.saturating_sub(in_period.len()),
+    }
+}
+
+fn render_report(
+    report: &Report,
+    format: ReportFormat,
+) -> Result<String> {
+    Ok(match format {
+        ReportFormat::Table => render_table(report),
+        ReportFormat::Csv => render_csv(report),
+        ReportFormat::Json => {
+            let mut rendered = serde_json::to_string_pretty(report)?;
+            rendered.push('\n');
+            rendered
+        },
+        ReportFormat::Markdown => render_markdown(report),
+    })
+}
+
+fn report_title(report: &Report) -> String {
+    format!("chaindash report {} → {} ({} days)", report.from, report.to, report.days)
+}
+
+fn optional_value<T>(
+    value: Option<T>,
+    render: impl FnOnce(T) -> String,
+) -> String {
+    value.map(render).unwrap_or_else(|| "-".to_string())
+}
+
+fn display_cells(node: &NodeReport) -> [String; 7] {
+    [
+        node.node_name.clone(),
+        node.node_id.clone(),
+        optional_value(node.blocks_produced, |blocks| blocks.to_string()),
+        optional_value(node.system_reward, |reward| format!("{reward:.2}")),
+        optional_value(node.average_ranking, |ranking| format!("{ranking:.1}")),
+        node.snapshot_days.to_string(),
+        node.missing_days.to_string(),
+    ]
+}
+
+fn render_table(report: &Report) -> String {
+    let rows: Vec<_> = report.nodes.iter().map(display_cells).collect();
+    let widths: Vec<usize> = REPORT_COLUMNS
+        .iter()
+        .enumerate()
+        .map(|(index, header)| {
+            rows.iter().map(|row| row[index].width()).chain([header.width()]).max().unwrap_or(0)
+        })
+        .collect();
+    let render_line = |cells: &[String]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(index, (cell, width))| {
+                let padding = " ".repeat(width.saturating_sub(cell.width()));
+                // Names and IDs are left aligned, figures right aligned.
+                if index < 2 {
+                    format!("{cell}{padding}")
+                } else {
+                    format!("{padding}{cell}")
+                }
+            })
+            .collect();
+        line.join("  ").trim_end().to_string()
+    };
+
+    let mut output = format!("{}\n\n", report_title(report));
+    if rows.is_empty() {
+        output.push_str("No daily snapshots in this period.\n");
+        return output;
+    }
+
+    let header: Vec<String> = REPORT_COLUMNS.iter().map(|header| header.to_string()).collect();
+    output.push_str(&render_line(&header));
+    output.push('\n');
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    output.push_str(&separator.join("  "));
+    output.push('\n');
+    for row in &rows {
+        output.push_str(&render_line(row));
+        output.push('\n');
+    }
+    output
+}
+
+fn render_markdown(report: &Report) -> String {
+    let mut output = format!("## {}\n\n", report_title(report));
+    if report.nodes.is_empty() {
+        output.push_str("No daily snapshots in this period.\n");
+        return output;
+    }
+
+    output.push_str(&format!("| {} |\n", REPORT_COLUMNS.join(" | ")));
+    output.push_str("| --- | --- | ---: | ---: | ---: | ---: | ---: |\n");
+    for node in &report.nodes {
+        let cells = display_cells(node).map(|cell| cell.replace('|', "\\|"));
+        output.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    output
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Raw figures without grouping or rounding, for spreadsheets.
+fn render_csv(report: &Report) -> String {
+    let mut output = String::from(
+        "from,to,node_name,node_id,blocks_produced,system_reward,average_ranking,snapshot_days,\
+         missing_days\n",
+    );
+    for node in &report.nodes {
+        let fields = [
+            report.from.to_string(),
+            report.to.to_string(),
+            csv_field(&node.node_name),
+            csv_field(&node.node_id),
+            node.blocks_produced.map(|blocks| blocks.to_string()).unwrap_or_default(),
+            node.system_reward.map(|reward| reward.to_string()).unwrap_or_default(),
+            node.average_ranking.map(|ranking| format!("{ranking:.2}")).unwrap_or_default(),
+            node.snapshot_days.to_string(),
+            node.missing_days.to_string(),
+        ];
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).expect("date should be valid")
+    }
+
+    fn row(
+        day: u32,
+        node_name: &str,
+        ranking: Option<i32>,
+        block_qty: u64,
+        reward_value: f64,
+    ) -> DailySnapshotRow {
+        DailySnapshotRow {
+            date: date(day),
+            node_id: format!("0x{node_name}"),
+            node_name: node_name.to_string(),
+            ranking,
+            block_qty,
+            reward_value,
+        }
+    }
+
+    fn sample_report() -> Report {
+        build_report(
+            date(1),
+            date(3),
+            &[
+                row(1, "node-a", Some(10), 100, 1_000.0),
+                row(2, "node-a", Some(12), 130, 1_300.0),
+                // Day 3 is missing; the snapshot of day 4 closes the period.
+                row(4, "node-a", None, 190, 1_900.5),
+                row(2, "node-b", Some(40), 50, 20.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_build_report_sums_production_across_gaps() {
+        let report = sample_report();
+
+        assert_eq!(report.days, 3);
+        assert_eq!(report.nodes.len(), 2);
+        let node_a = &report.nodes[0];
+        assert_eq!(node_a.blocks_produced, Some(90));
+        assert_eq!(node_a.system_reward, Some(900.5));
+        assert_eq!(node_a.average_ranking, Some(11.0));
+        assert_eq!(node_a.snapshot_days, 2);
+        assert_eq!(node_a.missing_days, 1);
+    }
+
+    #[test]
+    fn test_build_report_needs_two_snapshots_for_totals() {
+        let node_b = &sample_report().nodes[1];
+
+        assert_eq!(node_b.blocks_produced, None);
+        assert_eq!(node_b.system_reward, None);
+        assert_eq!(node_b.average_ranking, Some(40.0));
+        assert_eq!(node_b.missing_days, 2);
+    }
+
+    #[test]
+    fn test_build_report_ignores_counter_resets() {
+        let report = build_report(
+            date(1),
+            date(2),
+            &[
+                row(1, "node-a", None, 100, 50.0),
+                row(2, "node-a", None, 20, 10.0),
+                row(3, "node-a", None, 45, 15.0),
+            ],
+        );
+
+        assert_eq!(report.nodes[0].blocks_produced, Some(25));
+        assert_eq!(report.nodes[0].system_reward, Some(5.0));
+    }
+
+    #[test]
+    fn test_render_table_aligns_columns() {
+        let rendered =
+            render_report(&sample_report(), ReportFormat::Table).expect("table should render");
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "chaindash report 2026-01-01 → 2026-01-03 (3 days)");
+        assert!(lines[2].starts_with("Node    Node ID   Blocks  Rewards (LAT)"));
+        assert!(lines[4].starts_with("node-a  0xnode-a      90         900.50"));
+        assert!(lines[5].contains("  -  "));
+    }
+
+    #[test]
+    fn test_render_csv_and_markdown() {
+        let report = sample_report();
+
+        let csv = render_report(&report, ReportFormat::Csv).expect("csv should render");
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("2026-01-01,2026-01-03,node-a,0xnode-a,90,900.5,11.00,2,1")
+        );
+        assert_eq!(csv.lines().nth(2), Some("2026-01-01,2026-01-03,node-b,0xnode-b,,,40.00,1,2"));
+
+        let markdown =
+            render_report(&report, ReportFormat::Markdown).expect("markdown should render");
+        assert!(markdown.contains("| node-a | 0xnode-a | 90 | 900.50 | 11.0 | 2 | 1 |"));
+    }
+
+    #[test]
+    fn test_render_json_uses_null_for_missing_totals() {
+        let rendered =
+            render_report(&sample_report(), ReportFormat::Json).expect("json should render");
+        let value: serde_json::Value =
+            serde_json::from_str(&rendered).expect("output should be valid json");
+
+        assert_eq!(value["from"], "2026-01-01");
+        assert_eq!(value["nodes"][0]["blocks_produced"], 90);
+        assert!(value["nodes"][1]["blocks_produced"].is_null());
+    }
+
+    #[test]
+    fn test_csv_field_quotes_separators() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -32,6 +32,9 @@ pub fn update_widgets(
         &mut widgets.time,
         &mut widgets.node,
         &mut widgets.node_details,
+        &mut widgets.delegators,
+        &mut widgets.notification_log,
+        &mut widgets.alerts,
         &mut widgets.peer_countries,
     ];
 
@@ -385,6 +388,7 @@ mod tests {
                 node_name: "test-node".to_string(),
                 ranking: 1,
                 block_qty: 100,
+                expect_block_qty: 100,
                 block_rate: "50%".to_string(),
                 daily_block_rate: "10/day".to_string(),
                 reward_per: 10.0,
@@ -392,6 +396,15 @@ mod tests {
                 reward_address: "0x123".to_string(),
                 verifier_time: 3600,
                 last_updated_at: None,
+                production: None,
+                last_error: None,
+                consecutive_failures: 0,
+                endpoint: None,
+                total_value: 0.0,
+                delegate_value: 0.0,
+                reward_summary: None,
+                stake_distance: None,
+                hourly_blocks: Vec::new(),
             }));
         }
 
@@ -0,0 +1,270 @@
+use num_rational::Ratio;
+use ratatui::{
+    buffer::Buffer,
+    layout::{
+        Constraint,
+        Rect,
+    },
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Cell,
+        Paragraph,
+        Row,
+        Table,
+        Widget,
+    },
+};
+
+use crate::{
+    collect::{
+        AlertSeverity,
+        AlertState,
+        AlertStatus,
+        SharedData,
+    },
+    sync::lock_or_panic,
+    update::UpdatableWidget,
+    widgets::block,
+};
+
+/// Pending, firing and recently resolved `--alert-rules` series. Shown in
+/// place of Node Details while toggled with `a`.
+pub struct AlertsWidget {
+    update_interval: Ratio<u64>,
+    collect_data: SharedData,
+    alerts: Vec<AlertStatus>,
+}
+
+impl AlertsWidget {
+    const TABLE_MIN_WIDTH: u16 = 60;
+
+    pub fn new(collect_data: SharedData) -> AlertsWidget {
+        AlertsWidget {
+            update_interval: Ratio::from_integer(5),
+            collect_data,
+            alerts: Vec::new(),
+        }
+    }
+
+    fn count(
+        &self,
+        state: AlertState,
+    ) -> usize {
+        self.alerts.iter().filter(|alert| alert.state == state).count()
+    }
+
+    fn title(&self) -> String {
+        let firing = self.count(AlertState::Firing);
+        let pending = self.count(AlertState::Pending);
+        match (firing, pending) {
+            (0, 0) => " Alerts ".to_string(),
+            (firing, 0) => format!(" Alerts · {firing} firing "),
+            (0, pending) => format!(" Alerts · {pending} pending "),
+            (firing, pending) => format!(" Alerts · {firing} firing · {pending} pending "),
+        }
+    }
+
+    fn state_span(state: AlertState) -> Span<'static> {
+        let color = match state {
+            AlertState::Firing => block::ACCENT_ERROR,
+            AlertState::Pending => block::ACCENT_WARN,
+            AlertState::Resolved => block::METRIC_POSITIVE,
+        };
+        Span::styled(state.name(), block::accent_style(color))
+    }
+
+    fn severity_span(severity: AlertSeverity) -> Span<'static> {
+        match severity {
+            AlertSeverity::Critical => {
+                Span::styled("crit", block::accent_style(block::ACCENT_ERROR))
+            },
+            AlertSeverity::Warning => Span::styled("warn", block::accent_style(block::ACCENT_WARN)),
+            AlertSeverity::Info => Span::styled("info", block::muted_style()),
+        }
+    }
+
+    fn render_empty(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let outer_block = block::new(" Alerts ");
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+        Paragraph::new("No active alerts").style(block::empty_state_style()).render(inner, buf);
+    }
+
+    fn render_compact(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let title = self.title();
+        let outer_block = block::new(&title);
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let lines: Vec<_> = self
+            .alerts
+            .iter()
+            .take(inner.height as usize)
+            .map(|alert| {
+                Line::from(vec![
+                    Self::state_span(alert.state),
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("{} {} {}", alert.rule, alert.target, alert.value),
+                        block::content_style(),
+                    ),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    fn render_table(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        // Borders and the header row.
+        let max_rows = area.height.saturating_sub(3) as usize;
+        let rows = self.alerts.iter().take(max_rows).map(|alert| {
+            Row::new(vec![
+                Cell::from(Self::state_span(alert.state)),
+                Cell::from(Self::severity_span(alert.severity)),
+                Cell::from(alert.rule.clone()).style(block::highlight_style()),
+                Cell::from(alert.target.clone()).style(block::content_style()),
+                Cell::from(alert.value.clone()).style(block::content_style()),
+                Cell::from(alert.condition.clone()).style(block::muted_style()),
+                Cell::from(alert.since.format("%H:%M:%S").to_string()).style(block::muted_style()),
+            ])
+        });
+
+        let title = self.title();
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(4),
+                Constraint::Min(10),
+                Constraint::Min(8),
+                Constraint::Length(8),
+                Constraint::Min(12),
+                Constraint::Length(8),
+            ],
+        )
+        .block(block::new(&title))
+        .header(
+            Row::new(vec!["State", "Sev", "Rule", "Target", "Value", "Condition", "Since"])
+                .style(block::header_style()),
+        )
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
+
+impl UpdatableWidget for AlertsWidget {
+    fn update(&mut self) {
+        let data = lock_or_panic(&self.collect_data);
+        self.alerts = data.alerts();
+    }
+
+    fn get_update_interval(&self) -> Ratio<u64> {
+        self.update_interval
+    }
+}
+
+impl Widget for &AlertsWidget {
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        if area.height < 3 {
+            return;
+        }
+
+        if self.alerts.is_empty() {
+            self.render_empty(area, buf);
+        } else if area.width < AlertsWidget::TABLE_MIN_WIDTH {
+            self.render_compact(area, buf);
+        } else {
+            self.render_table(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{
+        Local,
+        TimeZone,
+    };
+
+    use super::*;
+    use crate::collect::Data;
+
+    fn alert(
+        rule: &str,
+        state: AlertState,
+    ) -> AlertStatus {
+        AlertStatus {
+            rule: rule.to_string(),
+            target: "node-a".to_string(),
+            severity: AlertSeverity::Critical,
+            state,
+            value: "12".to_string(),
+            condition: "node_lag > 5".to_string(),
+            since: Local
+                .with_ymd_and_hms(2026, 5, 4, 9, 30, 15)
+                .single()
+                .expect("valid local time"),
+        }
+    }
+
+    fn buffer_text(buf: &Buffer) -> String {
+        let area = buf.area;
+        (area.y..area.y + area.height)
+            .map(|y| {
+                (area.x..area.x + area.width).map(|x| buf[(x, y)].symbol()).collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_update_loads_alerts_and_counts_states_in_title() {
+        let data = Data::new();
+        let mut widget = AlertsWidget::new(data.clone());
+
+        data.lock().expect("mutex poisoned").replace_alerts(vec![
+            alert("lagging", AlertState::Firing),
+            alert("slow", AlertState::Pending),
+            alert("old", AlertState::Resolved),
+        ]);
+        widget.update();
+
+        assert_eq!(widget.title(), " Alerts · 1 firing · 1 pending ");
+    }
+
+    #[test]
+    fn test_table_shows_rule_state_and_condition() {
+        let mut widget = AlertsWidget::new(Data::new());
+        widget.alerts = vec![alert("lagging", AlertState::Firing)];
+        let area = Rect::new(0, 0, 90, 5);
+        let mut buf = Buffer::empty(area);
+
+        (&widget).render(area, &mut buf);
+        let text = buffer_text(&buf);
+
+        assert!(text.contains("firing"));
+        assert!(text.contains("crit"));
+        assert!(text.contains("lagging"));
+        assert!(text.contains("node_lag > 5"));
+        assert!(text.contains("09:30:15"));
+    }
+}
@@ -0,0 +1,352 @@
+use num_rational::Ratio;
+use ratatui::{
+    buffer::Buffer,
+    layout::{
+        Constraint,
+        Rect,
+    },
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Cell,
+        Paragraph,
+        Row,
+        Table,
+        Widget,
+    },
+};
+
+use crate::{
+    collect::{
+        DelegationSnapshot,
+        Delegator,
+        SharedData,
+    },
+    sync::lock_or_panic,
+    update::UpdatableWidget,
+    widgets::{
+        block,
+        helpers::{
+            format_grouped_u64,
+            prefix_chars,
+            suffix_chars,
+        },
+    },
+};
+
+/// Top delegators of our validators and their share of the delegated stake.
+pub struct DelegatorsWidget {
+    update_interval: Ratio<u64>,
+    collect_data: SharedData,
+    snapshots: Vec<DelegationSnapshot>,
+}
+
+impl DelegatorsWidget {
+    const TABLE_MIN_WIDTH: u16 = 48;
+    const ADDRESS_PREFIX_LEN: usize = 8;
+    const ADDRESS_SUFFIX_LEN: usize = 6;
+
+    pub fn new(collect_data: SharedData) -> DelegatorsWidget {
+        DelegatorsWidget {
+            update_interval: Ratio::from_integer(5),
+            collect_data,
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// The panel is only laid out once a delegator list has been fetched.
+    pub fn has_delegations(&self) -> bool {
+        !self.snapshots.is_empty()
+    }
+
+    fn title(&self) -> String {
+        match self.snapshots.as_slice() {
+            [snapshot] => format!(
+                " Delegators · {} · {} LAT ",
+                snapshot.delegators.len(),
+                format_grouped_u64(snapshot.total.round() as u64)
+            ),
+            _ => " Delegators ".to_string(),
+        }
+    }
+
+    fn shorten_address(address: &str) -> String {
+        if address.chars().count() <= Self::ADDRESS_PREFIX_LEN + Self::ADDRESS_SUFFIX_LEN + 1 {
+            return address.to_string();
+        }
+
+        format!(
+            "{}…{}",
+            prefix_chars(address, Self::ADDRESS_PREFIX_LEN),
+            suffix_chars(address, Self::ADDRESS_SUFFIX_LEN)
+        )
+    }
+
+    fn node_label(snapshot: &DelegationSnapshot) -> String {
+        if snapshot.node_name.is_empty() {
+            Self::shorten_address(&snapshot.node_id)
+        } else {
+            snapshot.node_name.clone()
+        }
+    }
+
+    /// The largest delegators of every node, sharing `max_rows` evenly.
+    fn visible_rows(
+        &self,
+        max_rows: usize,
+    ) -> Vec<(&DelegationSnapshot, usize, &Delegator)> {
+        let per_node = (max_rows / self.snapshots.len().max(1)).max(1);
+
+        self.snapshots
+            .iter()
+            .flat_map(|snapshot| {
+                snapshot
+                    .delegators
+                    .iter()
+                    .take(per_node)
+                    .enumerate()
+                    .map(move |(index, delegator)| (snapshot, index + 1, delegator))
+            })
+            .take(max_rows)
+            .collect()
+    }
+
+    fn row_values(
+        snapshot: &DelegationSnapshot,
+        rank: usize,
+        delegator: &Delegator,
+    ) -> Vec<String> {
+        vec![
+            format!(" {rank}"),
+            Self::shorten_address(&delegator.address),
+            format_grouped_u64(delegator.amount.round() as u64),
+            format!("{:.1}%", snapshot.share(delegator)),
+            Self::node_label(snapshot),
+        ]
+    }
+
+    fn compact_line(
+        snapshot: &DelegationSnapshot,
+        rank: usize,
+        delegator: &Delegator,
+    ) -> Line<'static> {
+        let values = Self::row_values(snapshot, rank, delegator);
+
+        Line::from(vec![
+            Span::styled(format!("{}.", values[0].trim_start()), block::muted_style()),
+            Span::raw(" "),
+            Span::styled(values[1].clone(), block::highlight_style()),
+            Span::raw(" "),
+            Span::styled(values[3].clone(), block::accent_style(block::METRIC_POSITIVE)),
+        ])
+    }
+
+    fn render_empty(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let outer_block = block::new(" Delegators ");
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+        Paragraph::new("No delegators").style(block::empty_state_style()).render(inner, buf);
+    }
+
+    fn render_compact(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let title = self.title();
+        let outer_block = block::new(&title);
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let lines: Vec<_> = self
+            .visible_rows(inner.height as usize)
+            .into_iter()
+            .map(|(snapshot, rank, delegator)| Self::compact_line(snapshot, rank, delegator))
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    fn render_table(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let multiple_nodes = self.snapshots.len() > 1;
+        // Borders and the header row.
+        let max_rows = area.height.saturating_sub(3) as usize;
+        let rows = self.visible_rows(max_rows).into_iter().map(|(snapshot, rank, delegator)| {
+            let values = Self::row_values(snapshot, rank, delegator);
+            let mut cells = vec![
+                Cell::from(values[0].clone()).style(block::muted_style()),
+                Cell::from(values[1].clone()).style(block::highlight_style()),
+                Cell::from(values[2].clone()).style(block::accent_style(block::METRIC_PRIMARY)),
+                Cell::from(values[3].clone()).style(block::accent_style(block::METRIC_POSITIVE)),
+            ];
+            if multiple_nodes {
+                cells.push(Cell::from(values[4].clone()).style(block::content_style()));
+            }
+            Row::new(cells)
+        });
+
+        let mut header = vec![" #", "Address", "Stake", "Share"];
+        let mut widths = vec![
+            Constraint::Length(4),
+            Constraint::Length(15),
+            Constraint::Length(14),
+            Constraint::Length(7),
+        ];
+        if multiple_nodes {
+            header.push("Node");
+            widths.push(Constraint::Min(8));
+        }
+
+        let title = self.title();
+        Table::new(rows, widths)
+            .block(block::new(&title))
+            .header(Row::new(header).style(block::header_style()))
+            .column_spacing(1)
+            .render(area, buf);
+    }
+}
+
+impl UpdatableWidget for DelegatorsWidget {
+    fn update(&mut self) {
+        let data = lock_or_panic(&self.collect_data);
+        self.snapshots = data.delegation_snapshots();
+    }
+
+    fn get_update_interval(&self) -> Ratio<u64> {
+        self.update_interval
+    }
+}
+
+impl Widget for &DelegatorsWidget {
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        if area.height < 3 {
+            return;
+        }
+
+        if self.snapshots.iter().all(|snapshot| snapshot.delegators.is_empty()) {
+            self.render_empty(area, buf);
+        } else if area.width < DelegatorsWidget::TABLE_MIN_WIDTH {
+            self.render_compact(area, buf);
+        } else {
+            self.render_table(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use super::*;
+    use crate::collect::Data;
+
+    fn snapshot(
+        node_name: &str,
+        delegators: &[(&str, f64)],
+    ) -> DelegationSnapshot {
+        let delegators: Vec<_> = delegators
+            .iter()
+            .map(|(address, amount)| Delegator {
+                address: address.to_string(),
+                amount: *amount,
+            })
+            .collect();
+        let total = delegators.iter().map(|delegator| delegator.amount).sum();
+        DelegationSnapshot {
+            node_id: format!("{node_name}-id"),
+            node_name: node_name.to_string(),
+            delegators,
+            total,
+            captured_at: Local::now(),
+        }
+    }
+
+    fn buffer_text(buf: &Buffer) -> String {
+        let area = buf.area;
+        (area.y..area.y + area.height)
+            .map(|y| {
+                (area.x..area.x + area.width).map(|x| buf[(x, y)].symbol()).collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_update_loads_snapshots_from_data() {
+        let data = Data::new();
+        let mut widget = DelegatorsWidget::new(data.clone());
+        assert!(!widget.has_delegations());
+
+        data.lock().expect("mutex poisoned").merge_delegation_snapshot(snapshot(
+            "node-a",
+            &[("lat1whale", 75.0), ("lat1small", 25.0)],
+        ));
+        widget.update();
+
+        assert!(widget.has_delegations());
+        assert_eq!(widget.title(), " Delegators · 2 · 100 LAT ");
+    }
+
+    #[test]
+    fn test_visible_rows_share_space_between_nodes() {
+        let mut widget = DelegatorsWidget::new(Data::new());
+        widget.snapshots = vec![
+            snapshot("node-a", &[("lat1a", 3.0), ("lat1b", 2.0), ("lat1c", 1.0)]),
+            snapshot("node-b", &[("lat1d", 3.0), ("lat1e", 2.0)]),
+        ];
+
+        let rows: Vec<_> = widget
+            .visible_rows(4)
+            .into_iter()
+            .map(|(_, rank, delegator)| (rank, delegator.address.as_str()))
+            .collect();
+
+        assert_eq!(rows, vec![(1, "lat1a"), (2, "lat1b"), (1, "lat1d"), (2, "lat1e")]);
+    }
+
+    #[test]
+    fn test_table_shows_shortened_address_and_share() {
+        let mut widget = DelegatorsWidget::new(Data::new());
+        widget.snapshots = vec![snapshot(
+            "node-a",
+            &[("lat1qqqqqqqqqqqqqqqqqqqqqqwhale", 750_000.0), ("lat1small", 250_000.0)],
+        )];
+        let area = Rect::new(0, 0, 60, 6);
+        let mut buf = Buffer::empty(area);
+
+        (&widget).render(area, &mut buf);
+        let text = buffer_text(&buf);
+
+        assert!(text.contains("Delegators · 2 · 1,000,000 LAT"));
+        assert!(text.contains("lat1qqqq…qwhale"));
+        assert!(text.contains("750,000"));
+        assert!(text.contains("75.0%"));
+    }
+
+    #[test]
+    fn test_narrow_panel_uses_compact_lines() {
+        let mut widget = DelegatorsWidget::new(Data::new());
+        widget.snapshots = vec![snapshot("node-a", &[("lat1whale", 3.0), ("lat1small", 1.0)])];
+        let area = Rect::new(0, 0, 30, 4);
+        let mut buf = Buffer::empty(area);
+
+        (&widget).render(area, &mut buf);
+        let text = buffer_text(&buf);
+
+        assert!(text.contains("1. lat1whale 75.0%"));
+        assert!(text.contains("2. lat1small 25.0%"));
+    }
+}
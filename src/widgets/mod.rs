@@ -1,11 +1,14 @@
+mod alerts;
 pub(crate) mod block;
 mod chart;
 mod countries;
+mod delegators;
 #[cfg(target_family = "unix")]
 mod disk_list;
 pub(crate) mod helpers;
 mod node;
 mod node_detail;
+mod notification_log;
 #[cfg(target_family = "unix")]
 mod system_summary;
 mod time;
@@ -16,9 +19,12 @@ pub use self::disk_list::DiskListWidget;
 #[cfg(target_family = "unix")]
 pub use self::system_summary::SystemSummaryWidget;
 pub use self::{
+    alerts::AlertsWidget,
     countries::PeerCountriesWidget,
+    delegators::DelegatorsWidget,
     node::NodeWidget,
     node_detail::NodeDetailWidget,
+    notification_log::NotificationLogWidget,
     time::TimeWidget,
     txs::TxsWidget,
 };
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use num_rational::Ratio;
 use ratatui::{
     buffer::Buffer,
@@ -28,6 +30,7 @@ use ratatui::{
 use crate::{
     collect::{
         ConsensusState,
+        NodeDetail,
         SharedData,
     },
     sync::lock_or_panic,
@@ -52,6 +55,8 @@ pub struct NodeWidget {
     update_interval: Ratio<u64>,
     collect_data: SharedData,
     nodes: Vec<ConsensusState>,
+    /// Linked validator label keyed by node name, from `admin_nodeInfo` matching.
+    validators: HashMap<String, String>,
 }
 
 impl NodeWidget {
@@ -61,7 +66,7 @@ impl NodeWidget {
     const STACKED_LAYOUT_HEIGHT: u16 = 9;
     const HEADING_LAYOUT_HEIGHT: u16 = 6;
     const INLINE_RIGHT_PADDING: u16 = 3;
-    const TABLE_LAYOUT_MIN_WIDTH: u16 = 129;
+    const TABLE_LAYOUT_MIN_WIDTH: u16 = 147;
 
     pub fn new(collect_data: SharedData) -> NodeWidget {
         NodeWidget {
@@ -69,6 +74,7 @@ impl NodeWidget {
             update_interval: Ratio::from_integer(1),
             collect_data,
             nodes: Vec::new(),
+            validators: HashMap::new(),
         }
     }
 
@@ -238,8 +244,34 @@ impl NodeWidget {
         block::accent_style(color)
     }
 
+    fn validator_label(detail: &NodeDetail) -> String {
+        let name = if detail.node_name.is_empty() {
+            prefix_chars(&detail.node_id, 10).to_string()
+        } else {
+            detail.node_name.clone()
+        };
+
+        if detail.ranking > 0 {
+            format!("{name} #{}", detail.ranking)
+        } else {
+            name
+        }
+    }
+
+    fn validator_line(validator: &str) -> Line<'static> {
+        Self::info_line_with_style("Validator", validator.to_string(), block::content_style())
+    }
+
+    fn validator_for(
+        &self,
+        node: &ConsensusState,
+    ) -> Option<&str> {
+        self.validators.get(&node.name).map(String::as_str)
+    }
+
     fn single_node_column_specs(
         node: &ConsensusState,
+        validator: Option<&str>,
         show_section_headings: bool,
         host_max_len: usize,
     ) -> TriplePriorityLines {
@@ -268,6 +300,9 @@ impl NodeWidget {
                 Span::styled(role_text.to_string(), Self::role_value_style(role_color)),
             ]),
         ));
+        if let Some(validator) = validator {
+            left_lines.push((4, Self::validator_line(validator)));
+        }
 
         let mut middle_lines = Vec::new();
         if show_section_headings {
@@ -338,6 +373,8 @@ impl NodeWidget {
             return;
         }
 
+        let validator = self.validator_for(node);
+
         let outer_block = block::new(&self.title);
         let inner = outer_block.inner(area);
         outer_block.render(area, buf);
@@ -357,6 +394,7 @@ impl NodeWidget {
                 let host_max_len = Self::inline_value_max_len(content.width, "Host: ");
                 let lines = Self::visible_stacked_lines(
                     node,
+                    validator,
                     show_section_headings,
                     host_max_len,
                     content.height,
@@ -390,7 +428,7 @@ impl NodeWidget {
         let middle_area = columns[1];
         let right_area = columns[2];
         let (left_specs, middle_specs, right_specs) =
-            Self::single_node_column_specs(node, show_section_headings, host_max_len);
+            Self::single_node_column_specs(node, validator, show_section_headings, host_max_len);
         let left_lines = Self::select_prioritized_lines(left_specs, left_area.height);
         let middle_lines = Self::select_prioritized_lines(middle_specs, middle_area.height);
         let right_lines = Self::select_prioritized_lines(right_specs, right_area.height);
@@ -402,6 +440,7 @@ impl NodeWidget {
 
     fn stacked_line_specs(
         node: &ConsensusState,
+        validator: Option<&str>,
         show_section_headings: bool,
         host_max_len: usize,
     ) -> PriorityLines {
@@ -426,6 +465,9 @@ impl NodeWidget {
             2,
             Self::info_line_with_style("Role", role_text, Self::role_value_style(role_color)),
         ));
+        if let Some(validator) = validator {
+            lines.push((10, Self::validator_line(validator)));
+        }
 
         if show_section_headings {
             lines.push((30, Self::spacer_line()));
@@ -482,12 +524,13 @@ impl NodeWidget {
 
     fn visible_stacked_lines(
         node: &ConsensusState,
+        validator: Option<&str>,
         show_section_headings: bool,
         host_max_len: usize,
         max_rows: u16,
     ) -> Vec<Line<'static>> {
         Self::select_prioritized_lines(
-            Self::stacked_line_specs(node, show_section_headings, host_max_len),
+            Self::stacked_line_specs(node, validator, show_section_headings, host_max_len),
             max_rows,
         )
     }
@@ -495,21 +538,23 @@ impl NodeWidget {
     #[cfg(test)]
     fn stacked_lines(
         node: &ConsensusState,
+        validator: Option<&str>,
         show_section_headings: bool,
         host_max_len: usize,
     ) -> Vec<Line<'static>> {
-        Self::visible_stacked_lines(node, show_section_headings, host_max_len, u16::MAX)
+        Self::visible_stacked_lines(node, validator, show_section_headings, host_max_len, u16::MAX)
     }
 
     fn compact_line_specs(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
     ) -> PriorityLines {
         let (role_text, role_color) = Self::role_badge(node);
         let node_value_style = Self::node_value_style();
         let metric_value_style = Self::metric_value_style();
 
-        vec![
+        let mut lines = vec![
             (1, Self::info_line_with_style("Name", node.name.clone(), node_value_style)),
             (
                 8,
@@ -561,23 +606,33 @@ impl NodeWidget {
                     metric_value_style,
                 ),
             ),
-        ]
+        ];
+        if let Some(validator) = validator {
+            lines.insert(3, (10, Self::validator_line(validator)));
+        }
+
+        lines
     }
 
     fn visible_compact_lines(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
         max_rows: u16,
     ) -> Vec<Line<'static>> {
-        Self::select_prioritized_lines(Self::compact_line_specs(node, host_max_len), max_rows)
+        Self::select_prioritized_lines(
+            Self::compact_line_specs(node, validator, host_max_len),
+            max_rows,
+        )
     }
 
     #[cfg(test)]
     fn compact_lines(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
     ) -> Vec<String> {
-        Self::visible_compact_lines(node, host_max_len, u16::MAX)
+        Self::visible_compact_lines(node, validator, host_max_len, u16::MAX)
             .into_iter()
             .map(|line| line.spans.into_iter().map(|span| span.content.into_owned()).collect())
             .collect()
@@ -585,13 +640,14 @@ impl NodeWidget {
 
     fn compact_summary_column_specs(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
     ) -> DoublePriorityLines {
         let (role_text, role_color) = Self::role_badge(node);
         let node_value_style = Self::node_value_style();
         let metric_value_style = Self::metric_value_style();
 
-        let left = vec![
+        let mut left = vec![
             (1, Self::info_line_with_style("Name", node.name.clone(), node_value_style)),
             (
                 3,
@@ -603,6 +659,9 @@ impl NodeWidget {
             ),
             (2, Self::info_line_with_style("Role", role_text, Self::role_value_style(role_color))),
         ];
+        if let Some(validator) = validator {
+            left.push((4, Self::validator_line(validator)));
+        }
         let right = vec![
             (
                 1,
@@ -652,11 +711,13 @@ impl NodeWidget {
 
     fn compact_summary_columns(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
         max_left_rows: u16,
         max_right_rows: u16,
     ) -> (Vec<Line<'static>>, Vec<Line<'static>>) {
-        let (left_specs, right_specs) = Self::compact_summary_column_specs(node, host_max_len);
+        let (left_specs, right_specs) =
+            Self::compact_summary_column_specs(node, validator, host_max_len);
 
         (
             Self::select_prioritized_lines(left_specs, max_left_rows),
@@ -678,6 +739,7 @@ impl NodeWidget {
             return;
         }
 
+        let validator = self.validator_for(node);
         if inner.width >= Self::COMPACT_TWO_COLUMN_WIDTH && inner.height >= 4 {
             let columns = Layout::default()
                 .direction(Direction::Horizontal)
@@ -698,6 +760,7 @@ impl NodeWidget {
             let host_max_len = Self::inline_value_max_len(left_area.width, "Host: ");
             let (left_lines, right_lines) = Self::compact_summary_columns(
                 node,
+                validator,
                 host_max_len,
                 left_area.height,
                 right_area.height,
@@ -709,7 +772,7 @@ impl NodeWidget {
         }
 
         let host_max_len = Self::inline_value_max_len(inner.width, "Host: ");
-        let lines = Self::visible_compact_lines(node, host_max_len, inner.height);
+        let lines = Self::visible_compact_lines(node, validator, host_max_len, inner.height);
         Paragraph::new(lines).render(inner, buf);
     }
 
@@ -732,6 +795,7 @@ impl NodeWidget {
 
     fn table_row_values(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
     ) -> Vec<String> {
         let (role_text, _) = Self::role_badge(node);
@@ -739,6 +803,9 @@ impl NodeWidget {
         vec![
             format!(" {}", node.name),
             Self::shorten_host_for_width(&node.host, host_max_len),
+            validator
+                .map(|validator| prefix_chars(validator, 17).to_string())
+                .unwrap_or_else(|| "-".into()),
             Self::format_number(node.current_number),
             Self::format_number(node.epoch),
             Self::format_number(node.view),
@@ -751,21 +818,29 @@ impl NodeWidget {
 
     fn table_row_cells(
         node: &ConsensusState,
+        validator: Option<&str>,
         host_max_len: usize,
     ) -> Vec<Cell<'static>> {
         let (_, role_color) = Self::role_badge(node);
-        let values = Self::table_row_values(node, host_max_len);
+        let values = Self::table_row_values(node, validator, host_max_len);
+        let validator_style = if validator.is_some() {
+            block::content_style()
+        } else {
+            block::muted_style()
+        };
 
         vec![
             Cell::from(values[0].clone()).style(Self::node_value_style()),
             Cell::from(values[1].clone()).style(block::content_style()),
-            Cell::from(values[2].clone()).style(Self::metric_value_style()),
+            Cell::from(values[2].clone()).style(validator_style),
+            Cell::from(values[3].clone()).style(Self::metric_value_style()),
             Cell::from(values[3].clone()).style(Self::metric_value_style()),
             Cell::from(values[4].clone()).style(Self::metric_value_style()),
             Cell::from(values[5].clone()).style(Self::metric_value_style()),
             Cell::from(values[6].clone()).style(Self::metric_value_style()),
             Cell::from(values[7].clone()).style(Self::metric_value_style()),
-            Cell::from(values[8].clone()).style(Self::role_value_style(role_color)),
+            Cell::from(values[8].clone()).style(Self::metric_value_style()),
+            Cell::from(values[9].clone()).style(Self::role_value_style(role_color)),
         ]
     }
 
@@ -774,13 +849,24 @@ impl NodeWidget {
         area: Rect,
         buf: &mut Buffer,
     ) {
-        let header =
-            [" Name", "Host", "Block", "Epoch", "View", "QC", "Locked", "Committed", "Role"];
-        let host_width = Self::flexible_width(area.width, 88, 18);
+        let header = [
+            " Name",
+            "Host",
+            "Validator",
+            "Block",
+            "Epoch",
+            "View",
+            "QC",
+            "Locked",
+            "Committed",
+            "Role",
+        ];
+        let host_width = Self::flexible_width(area.width, 107, 18);
         let host_max_len = host_width.saturating_sub(1) as usize;
 
-        let rows =
-            self.nodes.iter().map(|node| Row::new(Self::table_row_cells(node, host_max_len)));
+        let rows = self.nodes.iter().map(|node| {
+            Row::new(Self::table_row_cells(node, self.validator_for(node), host_max_len))
+        });
 
         let header_row = Row::new(header.iter().copied()).style(block::header_style());
 
@@ -789,6 +875,7 @@ impl NodeWidget {
             &[
                 Constraint::Length(16),
                 Constraint::Length(host_width),
+                Constraint::Length(18),
                 Constraint::Length(14),
                 Constraint::Length(10),
                 Constraint::Length(8),
@@ -827,6 +914,15 @@ impl UpdatableWidget for NodeWidget {
     fn update(&mut self) {
         let collect_data = lock_or_panic(&self.collect_data);
         self.nodes = collect_data.states();
+        self.validators = self
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                collect_data
+                    .linked_node_detail(node)
+                    .map(|detail| (node.name.clone(), Self::validator_label(&detail)))
+            })
+            .collect();
     }
 
     fn get_update_interval(&self) -> Ratio<u64> {
@@ -893,6 +989,9 @@ mod tests {
             locked: 145_333_142,
             qc: 145_333_143,
             validator: false,
+            node_id: String::new(),
+            rpc_latency_ms: 0,
+            peer_count: None,
         }
     }
 
@@ -968,7 +1067,7 @@ mod tests {
 
     #[test]
     fn test_stacked_lines_include_key_fields() {
-        let lines = NodeWidget::stacked_lines(&sample_node(), true, 20);
+        let lines = NodeWidget::stacked_lines(&sample_node(), None, true, 20);
 
         assert_eq!(line_text(&lines[0]), "Node");
         assert_eq!(line_text(&lines[1]), "Name: Satyrs");
@@ -984,7 +1083,7 @@ mod tests {
 
     #[test]
     fn test_compact_summary_columns_include_key_fields() {
-        let (left, right) = NodeWidget::compact_summary_columns(&sample_node(), 20, 10, 10);
+        let (left, right) = NodeWidget::compact_summary_columns(&sample_node(), None, 20, 10, 10);
 
         assert_eq!(line_text(&left[0]), "Name: Satyrs");
         assert_eq!(line_text(&left[2]), "Role: OBSERVER");
@@ -1012,12 +1111,13 @@ mod tests {
 
     #[test]
     fn test_table_row_values_include_expected_columns() {
-        let values = NodeWidget::table_row_values(&sample_node(), 20);
+        let values = NodeWidget::table_row_values(&sample_node(), None, 20);
 
         assert_eq!(values[0], " Satyrs");
         assert_eq!(values[1], "127.0.0.1:6790");
-        assert_eq!(values[2], "145,333,141");
-        assert_eq!(values[8], "OBSERVER");
+        assert_eq!(values[2], "-");
+        assert_eq!(values[3], "145,333,141");
+        assert_eq!(values[9], "OBSERVER");
     }
 
     #[test]
@@ -1043,7 +1143,7 @@ mod tests {
 
     #[test]
     fn test_visible_compact_lines_prioritize_core_fields() {
-        let lines = NodeWidget::visible_compact_lines(&sample_node(), 20, 3);
+        let lines = NodeWidget::visible_compact_lines(&sample_node(), None, 20, 3);
 
         assert_eq!(lines.len(), 3);
         assert_eq!(line_text(&lines[0]), "Name: Satyrs");
@@ -1053,7 +1153,7 @@ mod tests {
 
     #[test]
     fn test_compact_lines_include_key_fields() {
-        let lines = NodeWidget::compact_lines(&sample_node(), 20);
+        let lines = NodeWidget::compact_lines(&sample_node(), None, 20);
 
         assert_eq!(lines[0], "Name: Satyrs");
         assert_eq!(lines[1], "Host: 127.0.0.1:6790");
@@ -1066,6 +1166,51 @@ mod tests {
         assert_eq!(lines[8], "Committed: 145,333,141");
     }
 
+    #[test]
+    fn test_linked_validator_is_shown_alongside_endpoint() {
+        let detail = NodeDetail {
+            node_id: "0xabcdef".to_string(),
+            node_name: "validator-a".to_string(),
+            ranking: 7,
+            ..Default::default()
+        };
+        let label = NodeWidget::validator_label(&detail);
+        let compact = NodeWidget::compact_lines(&sample_node(), Some(&label), 20);
+        let values = NodeWidget::table_row_values(&sample_node(), Some(&label), 20);
+
+        assert_eq!(label, "validator-a #7");
+        assert_eq!(compact[3], "Validator: validator-a #7");
+        assert_eq!(values[2], "validator-a #7");
+    }
+
+    #[test]
+    fn test_update_links_validator_details_by_endpoint_node_id() {
+        let data = create_shared_data();
+        {
+            let mut data = lock_or_panic(&data);
+            data.update_consensus_state(
+                "Satyrs".to_string(),
+                ConsensusState {
+                    node_id: "abcdef".to_string(),
+                    ..sample_node()
+                },
+            );
+            data.merge_node_detail_for(
+                "0xabcdef",
+                Some(NodeDetail {
+                    node_id: "0xabcdef".to_string(),
+                    node_name: "validator-a".to_string(),
+                    ..Default::default()
+                }),
+            );
+        }
+        let mut widget = NodeWidget::new(data);
+
+        widget.update();
+
+        assert_eq!(widget.validator_for(&widget.nodes[0]), Some("validator-a"));
+    }
+
     #[test]
     fn test_compact_list_lines_include_more_indicator() {
         let mut widget = NodeWidget::new(create_shared_data());
@@ -32,7 +32,12 @@ use ratatui::{
 use crate::{
     collect::{
         NodeDetail,
+        ProductionTrend,
         SharedData,
+        StakeDistance,
+        StakeNeighbor,
+        WatchedValidator,
+        NODE_DETAIL_STALE_AFTER,
     },
     sync::lock_or_panic,
     update::UpdatableWidget,
@@ -55,6 +60,7 @@ pub struct NodeDetailWidget {
     update_interval: Ratio<u64>,
     loading: bool,
     node_details: Vec<NodeDetail>,
+    watched_validators: Vec<WatchedValidator>,
 
     collect_data: SharedData,
 }
@@ -66,9 +72,12 @@ impl NodeDetailWidget {
     const STACKED_LAYOUT_HEIGHT: u16 = 9;
     const HEADING_LAYOUT_HEIGHT: u16 = 5;
     const INLINE_RIGHT_PADDING: u16 = 3;
-    const TABLE_LAYOUT_MIN_WIDTH: u16 = 98;
-    const FRESH_DETAIL_MAX_AGE_SECS: u64 = 30;
+    const TABLE_LAYOUT_MIN_WIDTH: u16 = 107;
+    const FRESH_DETAIL_MAX_AGE_SECS: u64 = NODE_DETAIL_STALE_AFTER.as_secs();
     const WARN_DETAIL_MAX_AGE_SECS: u64 = 5 * 60;
+    const WATCHLIST_MIN_HEIGHT: u16 = 4;
+    const WATCHLIST_MIN_MAIN_HEIGHT: u16 = 6;
+    const WATCHLIST_TABLE_MIN_WIDTH: u16 = 64;
 
     pub fn new(collect_data: SharedData) -> NodeDetailWidget {
         NodeDetailWidget {
@@ -76,12 +85,13 @@ impl NodeDetailWidget {
             update_interval: Ratio::from_integer(1),
             loading: true,
             node_details: Vec::new(),
+            watched_validators: Vec::new(),
             collect_data,
         }
     }
 
     pub fn needs_periodic_redraw(&self) -> bool {
-        !self.node_details.is_empty()
+        !self.node_details.is_empty() || !self.watched_validators.is_empty()
     }
 
     fn flexible_width(
@@ -164,11 +174,222 @@ impl NodeDetailWidget {
             .map(|updated_at| Instant::now().saturating_duration_since(updated_at).as_secs())
     }
 
-    fn detail_status(detail: &NodeDetail) -> &'static str {
+    fn detail_status(detail: &NodeDetail) -> String {
         match Self::detail_age_secs(detail) {
-            None => "UNKNOWN",
-            Some(elapsed) if elapsed <= Self::FRESH_DETAIL_MAX_AGE_SECS => "OK",
-            Some(_) => "STALE",
+            None => "UNKNOWN".to_string(),
+            Some(elapsed) if elapsed <= Self::FRESH_DETAIL_MAX_AGE_SECS => "OK".to_string(),
+            Some(_) if detail.consecutive_failures > 0 => {
+                format!("STALE×{}", detail.consecutive_failures)
+            },
+            Some(_) => "STALE".to_string(),
+        }
+    }
+
+    fn format_last_error(detail: &NodeDetail) -> Option<String> {
+        if detail.consecutive_failures == 0 {
+            return None;
+        }
+
+        let reason = detail.last_error.as_deref().unwrap_or("unknown error");
+        Some(format!("{reason} ({}×)", detail.consecutive_failures))
+    }
+
+    fn last_error_line(
+        detail: &NodeDetail,
+        label: &str,
+    ) -> Option<Line<'static>> {
+        Self::format_last_error(detail).map(|value| {
+            Self::detail_line_with_style(label, value, block::accent_style(block::ACCENT_ERROR))
+        })
+    }
+
+    fn format_daily_reward(detail: &NodeDetail) -> String {
+        detail
+            .daily_reward()
+            .map(|reward| format!("{} LAT/d", Self::format_amount(reward)))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    fn format_percent(value: Option<f64>) -> String {
+        value.map(|value| format!("{:.2}%", value * 100.0)).unwrap_or_else(|| "-".to_string())
+    }
+
+    fn format_estimated_apy(detail: &NodeDetail) -> String {
+        format!(
+            "V {} · D {}",
+            Self::format_percent(detail.validator_apy()),
+            Self::format_percent(detail.delegator_apy())
+        )
+    }
+
+    /// Epoch reward, daily reward and estimated yield lines, shown once reward
+    /// history has been sampled. Priorities follow the order of `priorities`.
+    fn reward_estimate_specs(
+        detail: &NodeDetail,
+        priorities: [u8; 3],
+    ) -> PriorityLines {
+        let Some(summary) = detail.reward_summary else {
+            return Vec::new();
+        };
+        let reward_style = Self::reward_value_style();
+
+        vec![
+            (
+                priorities[0],
+                Self::detail_line_with_style(
+                    "Daily Reward",
+                    Self::format_daily_reward(detail),
+                    reward_style,
+                ),
+            ),
+            (
+                priorities[1],
+                Self::detail_line_with_style(
+                    "Est. APY",
+                    Self::format_estimated_apy(detail),
+                    reward_style,
+                ),
+            ),
+            (
+                priorities[2],
+                Self::detail_line_with_style(
+                    "Epoch Reward",
+                    format!("{} LAT", Self::format_amount(summary.current.reward)),
+                    Self::metric_value_style(),
+                ),
+            ),
+        ]
+    }
+
+    fn format_signed_amount(value: f64) -> String {
+        let sign = if value < 0.0 { "-" } else { "+" };
+        format!("{sign}{}", Self::format_amount(value.abs()))
+    }
+
+    fn format_stake_margin(distance: &StakeDistance) -> String {
+        if distance.targets.is_empty() {
+            return "-".to_string();
+        }
+
+        distance
+            .targets
+            .iter()
+            .map(|target| {
+                format!("#{} {}", target.target_rank, Self::format_signed_amount(target.margin))
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    fn format_stake_neighbors(distance: &StakeDistance) -> String {
+        let format_gap = |neighbor: Option<&StakeNeighbor>| {
+            neighbor
+                .map(|neighbor| Self::format_amount(neighbor.gap))
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        format!(
+            "↑ {} · ↓ {} LAT",
+            format_gap(distance.above.as_ref()),
+            format_gap(distance.below.as_ref())
+        )
+    }
+
+    /// Stake margin to the target ranks and gaps to the neighbouring
+    /// validators, shown once the ranking list has been fetched.
+    fn stake_distance_specs(
+        detail: &NodeDetail,
+        priorities: [u8; 2],
+    ) -> PriorityLines {
+        let Some(distance) = detail.stake_distance.as_ref() else {
+            return Vec::new();
+        };
+        let margin_style = if distance.targets.iter().any(|target| !target.within) {
+            block::accent_style(block::ACCENT_ERROR)
+        } else {
+            Self::reward_value_style()
+        };
+
+        vec![
+            (
+                priorities[0],
+                Self::detail_line_with_style(
+                    "Stake Margin",
+                    Self::format_stake_margin(distance),
+                    margin_style,
+                ),
+            ),
+            (
+                priorities[1],
+                Self::detail_line_with_style(
+                    "Neighbors",
+                    Self::format_stake_neighbors(distance),
+                    Self::metric_value_style(),
+                ),
+            ),
+        ]
+    }
+
+    fn endpoint_line(detail: &NodeDetail) -> Option<Line<'static>> {
+        detail.endpoint.as_ref().map(|endpoint| {
+            Self::detail_line_with_style("Endpoint", endpoint.clone(), block::content_style())
+        })
+    }
+
+    /// Text sparkline of blocks per hour, scaled to the busiest hour, followed
+    /// by the latest hour's count.
+    fn format_hourly_blocks(hourly_blocks: &[u64]) -> Option<String> {
+        const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let latest = *hourly_blocks.last()?;
+        let max = hourly_blocks.iter().copied().max().unwrap_or(0).max(1);
+        let sparkline: String = hourly_blocks
+            .iter()
+            .map(|blocks| LEVELS[(*blocks * (LEVELS.len() as u64 - 1) / max) as usize])
+            .collect();
+
+        Some(format!("{sparkline} {latest}/h"))
+    }
+
+    fn hourly_blocks_line(detail: &NodeDetail) -> Option<Line<'static>> {
+        Self::format_hourly_blocks(&detail.hourly_blocks)
+            .map(|value| Self::detail_line_with_style("Hourly", value, Self::metric_value_style()))
+    }
+
+    fn format_epoch_blocks(detail: &NodeDetail) -> String {
+        let Some(production) = detail.production else {
+            return "-".to_string();
+        };
+
+        format!(
+            "{}/{}",
+            Self::format_number(production.current.produced),
+            Self::format_number(production.current.expected)
+        )
+    }
+
+    fn format_missed(detail: &NodeDetail) -> String {
+        let Some(production) = detail.production else {
+            return "-".to_string();
+        };
+        let Some(missed_ratio) = production.current.missed_ratio() else {
+            return "-".to_string();
+        };
+
+        let missed = format!("{:.2}%", missed_ratio * 100.0);
+        match production.trend() {
+            Some(ProductionTrend::Improving) => format!("{missed} ↓"),
+            Some(ProductionTrend::Steady) => format!("{missed} →"),
+            Some(ProductionTrend::Worsening) => format!("{missed} ↑"),
+            None => missed,
+        }
+    }
+
+    fn missed_value_style(detail: &NodeDetail) -> Style {
+        match detail.production.and_then(|production| production.trend()) {
+            Some(ProductionTrend::Improving) => block::accent_style(block::METRIC_POSITIVE),
+            Some(ProductionTrend::Worsening) => block::accent_style(block::ACCENT_ERROR),
+            _ => Self::metric_value_style(),
         }
     }
 
@@ -265,10 +486,7 @@ impl NodeDetailWidget {
         }
 
         spans.push(Span::raw(" "));
-        spans.push(Span::styled(
-            Self::detail_status(detail).to_string(),
-            Self::status_value_style(detail),
-        ));
+        spans.push(Span::styled(Self::detail_status(detail), Self::status_value_style(detail)));
 
         if area_width >= 78 {
             spans.push(Span::raw(" "));
@@ -412,7 +630,26 @@ impl NodeDetailWidget {
                     metric_style,
                 ),
             ),
+            (
+                6,
+                Self::detail_line_with_style(
+                    "Epoch Blocks",
+                    Self::format_epoch_blocks(detail),
+                    metric_style,
+                ),
+            ),
+            (
+                7,
+                Self::detail_line_with_style(
+                    "Missed",
+                    Self::format_missed(detail),
+                    Self::missed_value_style(detail),
+                ),
+            ),
         ]);
+        if let Some(line) = Self::hourly_blocks_line(detail) {
+            left.push((8, line));
+        }
 
         let updated_style = Self::updated_value_style(detail.last_updated_at);
         let mut right = Vec::new();
@@ -469,6 +706,14 @@ impl NodeDetailWidget {
                 ),
             ),
         ]);
+        right.extend(Self::reward_estimate_specs(detail, [10, 11, 12]));
+        right.extend(Self::stake_distance_specs(detail, [13, 14]));
+        if let Some(line) = Self::endpoint_line(detail) {
+            right.push((8, line));
+        }
+        if let Some(line) = Self::last_error_line(detail, "Last Error") {
+            right.push((7, line));
+        }
 
         (left, right)
     }
@@ -512,6 +757,21 @@ impl NodeDetailWidget {
             5,
             Self::detail_line_with_style("24H", detail.daily_block_rate.clone(), metric_style),
         ));
+        lines.push((
+            12,
+            Self::detail_line_with_style("Epoch", Self::format_epoch_blocks(detail), metric_style),
+        ));
+        lines.push((
+            13,
+            Self::detail_line_with_style(
+                "Missed",
+                Self::format_missed(detail),
+                Self::missed_value_style(detail),
+            ),
+        ));
+        if let Some(line) = Self::hourly_blocks_line(detail) {
+            lines.push((21, line));
+        }
 
         if show_section_headings {
             lines.push((30, Self::spacer_line()));
@@ -565,6 +825,14 @@ impl NodeDetailWidget {
                 updated_style,
             ),
         ));
+        lines.extend(Self::reward_estimate_specs(detail, [16, 17, 18]));
+        lines.extend(Self::stake_distance_specs(detail, [19, 20]));
+        if let Some(line) = Self::endpoint_line(detail) {
+            lines.push((15, line));
+        }
+        if let Some(line) = Self::last_error_line(detail, "Error") {
+            lines.push((14, line));
+        }
 
         lines
     }
@@ -672,7 +940,7 @@ impl NodeDetailWidget {
         let address_style = Self::address_value_style();
         let updated_style = Self::updated_value_style(detail.last_updated_at);
 
-        vec![
+        let mut lines = vec![
             (1, Self::detail_line("Name", Self::display_name(detail))),
             (
                 2,
@@ -692,6 +960,22 @@ impl NodeDetailWidget {
             ),
             (4, Self::detail_line_with_style("Rate", detail.block_rate.clone(), reward_style)),
             (5, Self::detail_line_with_style("24H", detail.daily_block_rate.clone(), metric_style)),
+            (
+                12,
+                Self::detail_line_with_style(
+                    "Epoch",
+                    Self::format_epoch_blocks(detail),
+                    metric_style,
+                ),
+            ),
+            (
+                13,
+                Self::detail_line_with_style(
+                    "Missed",
+                    Self::format_missed(detail),
+                    Self::missed_value_style(detail),
+                ),
+            ),
             (
                 6,
                 Self::detail_line_with_style(
@@ -740,7 +1024,20 @@ impl NodeDetailWidget {
                     updated_style,
                 ),
             ),
-        ]
+        ];
+        lines.extend(Self::reward_estimate_specs(detail, [16, 17, 18]));
+        lines.extend(Self::stake_distance_specs(detail, [19, 20]));
+        if let Some(line) = Self::hourly_blocks_line(detail) {
+            lines.push((21, line));
+        }
+        if let Some(line) = Self::endpoint_line(detail) {
+            lines.push((15, line));
+        }
+        if let Some(line) = Self::last_error_line(detail, "Error") {
+            lines.push((14, line));
+        }
+
+        lines
     }
 
     fn visible_compact_lines(
@@ -790,8 +1087,24 @@ impl NodeDetailWidget {
             ),
             (4, Self::detail_line_with_style("Rate", detail.block_rate.clone(), reward_style)),
             (5, Self::detail_line_with_style("24H", detail.daily_block_rate.clone(), metric_style)),
+            (
+                6,
+                Self::detail_line_with_style(
+                    "Epoch",
+                    Self::format_epoch_blocks(detail),
+                    metric_style,
+                ),
+            ),
+            (
+                7,
+                Self::detail_line_with_style(
+                    "Missed",
+                    Self::format_missed(detail),
+                    Self::missed_value_style(detail),
+                ),
+            ),
         ];
-        let right = vec![
+        let mut right = vec![
             (
                 1,
                 Self::detail_line_with_style(
@@ -841,6 +1154,14 @@ impl NodeDetailWidget {
                 ),
             ),
         ];
+        right.extend(Self::reward_estimate_specs(detail, [9, 10, 11]));
+        right.extend(Self::stake_distance_specs(detail, [12, 13]));
+        if let Some(line) = Self::endpoint_line(detail) {
+            right.push((8, line));
+        }
+        if let Some(line) = Self::last_error_line(detail, "Error") {
+            right.push((7, line));
+        }
 
         (left, right)
     }
@@ -941,8 +1262,9 @@ impl NodeDetailWidget {
             Self::format_number(detail.block_qty),
             detail.block_rate.clone(),
             detail.daily_block_rate.clone(),
+            Self::format_missed(detail),
             format!("{:.2}%", detail.reward_per),
-            Self::detail_status(detail).to_string(),
+            Self::detail_status(detail),
             Self::format_updated_at(detail.last_updated_at),
             Self::shorten_address_for_width(&detail.reward_address, address_max_len),
         ]
@@ -960,10 +1282,11 @@ impl NodeDetailWidget {
             Cell::from(values[2].clone()).style(Self::metric_value_style()),
             Cell::from(values[3].clone()).style(Self::reward_value_style()),
             Cell::from(values[4].clone()).style(Self::metric_value_style()),
-            Cell::from(values[5].clone()).style(Self::reward_value_style()),
-            Cell::from(values[6].clone()).style(Self::status_value_style(detail)),
-            Cell::from(values[7].clone()).style(Self::updated_value_style(detail.last_updated_at)),
-            Cell::from(values[8].clone()).style(Self::address_value_style()),
+            Cell::from(values[5].clone()).style(Self::missed_value_style(detail)),
+            Cell::from(values[6].clone()).style(Self::reward_value_style()),
+            Cell::from(values[7].clone()).style(Self::status_value_style(detail)),
+            Cell::from(values[8].clone()).style(Self::updated_value_style(detail.last_updated_at)),
+            Cell::from(values[9].clone()).style(Self::address_value_style()),
         ]
     }
 
@@ -972,10 +1295,12 @@ impl NodeDetailWidget {
         area: Rect,
         buf: &mut Buffer,
     ) {
-        let address_width = Self::flexible_width(area.width, 87, 14);
+        let address_width = Self::flexible_width(area.width, 96, 14);
         let address_max_len = address_width.saturating_sub(1) as usize;
-        let header =
-            [" Name", "Rank", "Blocks", "Rate", "24H", "Ratio", "Status", "Updated", "Address"];
+        let header = [
+            " Name", "Rank", "Blocks", "Rate", "24H", "Missed", "Ratio", "Status", "Updated",
+            "Address",
+        ];
         let rows = self
             .node_details
             .iter()
@@ -993,6 +1318,7 @@ impl NodeDetailWidget {
                 Constraint::Length(8),
                 Constraint::Length(8),
                 Constraint::Length(8),
+                Constraint::Length(8),
                 Constraint::Length(address_width),
             ],
         )
@@ -1001,12 +1327,124 @@ impl NodeDetailWidget {
         .column_spacing(1)
         .render(area, buf);
     }
+
+    /// Carve the competitor watchlist off the bottom of the panel, as long as
+    /// our own nodes keep enough rows.
+    fn split_watchlist_area(
+        &self,
+        area: Rect,
+    ) -> (Rect, Option<Rect>) {
+        if self.watched_validators.is_empty() {
+            return (area, None);
+        }
+
+        let available = area.height.saturating_sub(Self::WATCHLIST_MIN_MAIN_HEIGHT);
+        if available < Self::WATCHLIST_MIN_HEIGHT {
+            return (area, None);
+        }
+
+        // Borders, header row and one row per watched validator.
+        let wanted =
+            u16::try_from(self.watched_validators.len()).unwrap_or(u16::MAX).saturating_add(3);
+        let height = wanted.min(available);
+        let main = Rect::new(area.x, area.y, area.width, area.height - height);
+        let watchlist = Rect::new(area.x, area.y + main.height, area.width, height);
+
+        (main, Some(watchlist))
+    }
+
+    fn format_watched_ranking(validator: &WatchedValidator) -> String {
+        if validator.ranking > 0 {
+            format!("#{}", validator.ranking)
+        } else {
+            "#-".to_string()
+        }
+    }
+
+    fn watchlist_row_values(validator: &WatchedValidator) -> Vec<String> {
+        vec![
+            format!(" {}", validator.display_name()),
+            Self::format_watched_ranking(validator),
+            Self::format_amount(validator.total_value),
+            format!("{:.2}%", validator.reward_per),
+            if validator.block_rate.is_empty() {
+                "-".to_string()
+            } else {
+                validator.block_rate.clone()
+            },
+            Self::format_updated_at(validator.last_updated_at),
+        ]
+    }
+
+    fn watchlist_compact_line(validator: &WatchedValidator) -> Line<'static> {
+        let values = Self::watchlist_row_values(validator);
+
+        Line::from(vec![
+            Span::styled(values[0].trim_start().to_string(), Self::name_value_style()),
+            Span::raw(" "),
+            Span::styled(values[1].clone(), Self::metric_value_style()),
+            Span::raw(" "),
+            Span::styled(values[3].clone(), Self::reward_value_style()),
+            Span::raw(" "),
+            Span::styled(values[4].clone(), Self::metric_value_style()),
+        ])
+    }
+
+    fn render_watchlist(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let title = " Watchlist ";
+
+        if area.width < Self::WATCHLIST_TABLE_MIN_WIDTH {
+            let outer_block = block::new(title);
+            let inner = outer_block.inner(area);
+            outer_block.render(area, buf);
+            let lines: Vec<_> =
+                self.watched_validators.iter().map(Self::watchlist_compact_line).collect();
+            Paragraph::new(lines).render(inner, buf);
+            return;
+        }
+
+        let header = [" Name", "Rank", "Stake", "Ratio", "Rate", "Updated"];
+        let rows = self.watched_validators.iter().map(|validator| {
+            let values = Self::watchlist_row_values(validator);
+            Row::new(vec![
+                Cell::from(values[0].clone()).style(Self::name_value_style()),
+                Cell::from(values[1].clone()).style(Self::metric_value_style()),
+                Cell::from(values[2].clone()).style(Self::metric_value_style()),
+                Cell::from(values[3].clone()).style(Self::reward_value_style()),
+                Cell::from(values[4].clone()).style(Self::metric_value_style()),
+                Cell::from(values[5].clone())
+                    .style(Self::updated_value_style(validator.last_updated_at)),
+            ])
+        });
+        let name_width = Self::flexible_width(area.width, 53, 12);
+
+        Table::new(
+            rows,
+            &[
+                Constraint::Length(name_width),
+                Constraint::Length(6),
+                Constraint::Length(16),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(10),
+            ],
+        )
+        .block(block::new(title))
+        .header(Row::new(header.iter().copied()).style(block::header_style()))
+        .column_spacing(1)
+        .render(area, buf);
+    }
 }
 
 impl UpdatableWidget for NodeDetailWidget {
     fn update(&mut self) {
         let data = lock_or_panic(&self.collect_data);
         self.node_details = data.node_details();
+        self.watched_validators = data.watched_validators();
         self.loading = self.node_details.is_empty() && !data.node_details_loaded();
     }
 
@@ -1025,6 +1463,11 @@ impl Widget for &NodeDetailWidget {
             return;
         }
 
+        let (area, watchlist_area) = self.split_watchlist_area(area);
+        if let Some(watchlist_area) = watchlist_area {
+            self.render_watchlist(watchlist_area, buf);
+        }
+
         if self.node_details.len() > 1 {
             if area.width < NodeDetailWidget::TABLE_LAYOUT_MIN_WIDTH {
                 self.render_compact_detail_list(area, buf);
@@ -1042,7 +1485,13 @@ impl Widget for &NodeDetailWidget {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::collect::Data;
+    use crate::collect::{
+        BlockProductionSummary,
+        Data,
+        EpochProduction,
+        RankTargetGap,
+        RewardSummary,
+    };
 
     fn create_shared_data() -> SharedData {
         Data::new()
@@ -1058,6 +1507,7 @@ mod tests {
             node_name: "node-a".to_string(),
             ranking: 7,
             block_qty: 123_456,
+            expect_block_qty: 123_456,
             block_rate: "12.34%".to_string(),
             daily_block_rate: "3/day".to_string(),
             reward_per: 5.0,
@@ -1065,6 +1515,15 @@ mod tests {
             reward_address: "lat1zytcgvw35sagn722cneh6sz92y8j3dp8gqj5h".to_string(),
             verifier_time: 9,
             last_updated_at: Some(Instant::now()),
+            production: None,
+            last_error: None,
+            consecutive_failures: 0,
+            endpoint: None,
+            total_value: 0.0,
+            delegate_value: 0.0,
+            reward_summary: None,
+            stake_distance: None,
+            hourly_blocks: Vec::new(),
         }
     }
 
@@ -1090,6 +1549,33 @@ mod tests {
         assert!(widget.loading);
     }
 
+    #[test]
+    fn test_watchlist_takes_bottom_rows_only_when_room_remains() {
+        let mut widget = NodeDetailWidget::new(create_shared_data());
+        widget.watched_validators = vec![WatchedValidator {
+            node_id: "rival-id".to_string(),
+            node_name: "rival".to_string(),
+            ranking: 12,
+            total_value: 1_234_567.0,
+            reward_per: 20.0,
+            block_rate: "98.00%".to_string(),
+            last_updated_at: None,
+        }];
+
+        let (main, watchlist) = widget.split_watchlist_area(Rect::new(0, 0, 80, 20));
+        assert_eq!(main.height, 16);
+        assert_eq!(watchlist, Some(Rect::new(0, 16, 80, 4)));
+        assert_eq!(widget.split_watchlist_area(Rect::new(0, 0, 80, 9)).1, None);
+
+        let area = Rect::new(0, 0, 80, 20);
+        let mut buf = Buffer::empty(area);
+        (&widget).render(area, &mut buf);
+        let row: String = (0..80).map(|x| buf[(x, 18)].symbol().to_string()).collect();
+        assert!(row.contains("rival"));
+        assert!(row.contains("#12"));
+        assert!(row.contains("1,234,567.00"));
+    }
+
     #[test]
     fn test_node_detail_widget_update_with_no_detail() {
         let shared_data = create_shared_data();
@@ -1156,8 +1642,9 @@ mod tests {
         let row = NodeDetailWidget::table_row_values(&detail, 18);
 
         assert_eq!(row[0], " node-a");
-        assert_eq!(row[6], "UNKNOWN");
-        assert_eq!(row[7], "-");
+        assert_eq!(row[5], "-");
+        assert_eq!(row[7], "UNKNOWN");
+        assert_eq!(row[8], "-");
     }
 
     #[test]
@@ -1203,6 +1690,103 @@ mod tests {
         detail.last_updated_at = Some(Instant::now() - StdDuration::from_secs(90));
         assert_eq!(NodeDetailWidget::detail_status(&detail), "STALE");
         assert_eq!(NodeDetailWidget::status_value_style(&detail).fg, Some(block::ACCENT_WARN));
+        detail.consecutive_failures = 3;
+        detail.last_error = Some("Request timed out after 30s".to_string());
+        assert_eq!(NodeDetailWidget::detail_status(&detail), "STALE×3");
+        assert_eq!(
+            NodeDetailWidget::format_last_error(&detail).as_deref(),
+            Some("Request timed out after 30s (3×)")
+        );
+    }
+
+    #[test]
+    fn test_last_error_line_only_shown_after_failures() {
+        let mut detail = sample_detail();
+        let (_, right) = NodeDetailWidget::detail_column_specs(&detail, false, 19);
+        assert_eq!(right.len(), 6);
+
+        detail.consecutive_failures = 1;
+        detail.last_error = Some("HTTP 502".to_string());
+        let (_, right) = NodeDetailWidget::detail_column_specs(&detail, false, 19);
+        assert_eq!(line_text(&right[6].1), "Last Error: HTTP 502 (1×)");
+    }
+
+    #[test]
+    fn test_reward_estimate_lines_only_shown_with_reward_history() {
+        let mut detail = sample_detail();
+        let (_, right) = NodeDetailWidget::detail_column_specs(&detail, false, 19);
+        assert_eq!(right.len(), 6);
+
+        detail.reward_per = 50.0;
+        detail.total_value = 2_000_000.0;
+        detail.delegate_value = 1_000_000.0;
+        detail.reward_summary = Some(RewardSummary {
+            daily_reward: Some(2_000.0),
+            ..Default::default()
+        });
+        let (_, right) = NodeDetailWidget::detail_column_specs(&detail, false, 19);
+        assert_eq!(line_text(&right[6].1), "Daily Reward: 2,000.00 LAT/d");
+        assert_eq!(line_text(&right[7].1), "Est. APY: V 36.50% · D 36.50%");
+        assert_eq!(line_text(&right[8].1), "Epoch Reward: 0.00 LAT");
+    }
+
+    #[test]
+    fn test_stake_distance_lines_show_margin_and_neighbors() {
+        let mut detail = sample_detail();
+        detail.stake_distance = Some(StakeDistance {
+            ranking: 30,
+            total_value: 1_000_000.0,
+            above: Some(StakeNeighbor {
+                node_name: "above".to_string(),
+                ranking: 29,
+                gap: 1_500.0,
+            }),
+            below: None,
+            targets: vec![
+                RankTargetGap {
+                    target_rank: 25,
+                    within: false,
+                    margin: -20_000.0,
+                },
+                RankTargetGap {
+                    target_rank: 201,
+                    within: true,
+                    margin: 400_000.0,
+                },
+            ],
+        });
+
+        let (_, right) = NodeDetailWidget::detail_column_specs(&detail, false, 19);
+        assert_eq!(line_text(&right[6].1), "Stake Margin: #25 -20,000.00 · #201 +400,000.00");
+        assert_eq!(line_text(&right[7].1), "Neighbors: ↑ 1,500.00 · ↓ - LAT");
+    }
+
+    #[test]
+    fn test_endpoint_line_only_shown_when_linked() {
+        let mut detail = sample_detail();
+        let lines = NodeDetailWidget::visible_compact_lines(&detail, 24, u16::MAX);
+        assert!(!lines.iter().any(|line| line_text(line).starts_with("Endpoint")));
+
+        detail.endpoint = Some("main".to_string());
+        let lines = NodeDetailWidget::visible_compact_lines(&detail, 24, u16::MAX);
+        assert!(lines.iter().any(|line| line_text(line) == "Endpoint: main"));
+    }
+
+    #[test]
+    fn test_hourly_blocks_sparkline_scales_to_busiest_hour() {
+        assert_eq!(NodeDetailWidget::format_hourly_blocks(&[]), None);
+        assert_eq!(
+            NodeDetailWidget::format_hourly_blocks(&[0, 7, 14, 3]),
+            Some("▁▄█▂ 3/h".to_string())
+        );
+
+        let mut detail = sample_detail();
+        let lines = NodeDetailWidget::visible_compact_lines(&detail, 24, u16::MAX);
+        assert!(!lines.iter().any(|line| line_text(line).starts_with("Hourly")));
+
+        detail.hourly_blocks = vec![10, 0];
+        let lines = NodeDetailWidget::visible_compact_lines(&detail, 24, u16::MAX);
+        assert!(lines.iter().any(|line| line_text(line) == "Hourly: █▁ 0/h"));
     }
 
     #[test]
@@ -1255,7 +1839,7 @@ mod tests {
         let (left, right) = NodeDetailWidget::detail_column_specs(&sample_detail(), false, 19);
 
         assert_eq!(left[0].1.spans[0].content, "Name: ");
-        assert_eq!(left.len(), 5);
+        assert_eq!(left.len(), 7);
         assert_eq!(right[0].1.spans[0].content, "Verifier Time: ");
         assert_eq!(right.len(), 6);
     }
@@ -1268,11 +1852,13 @@ mod tests {
         assert_eq!(line_text(&lines[1]), "Name: node-a");
         assert_eq!(line_text(&lines[2]), "Ranking: 7");
         assert_eq!(line_text(&lines[3]), "Blocks: 123,456");
-        assert_eq!(line_text(&lines[6]), "");
-        assert_eq!(line_text(&lines[7]), "Rewards");
-        assert_eq!(line_text(&lines[8]), "Verifier: 9");
-        assert_eq!(line_text(&lines[12]), "Address: lat1zytcgvw3…8gqj5h");
-        assert!(line_text(&lines[13]).starts_with("Updated: "));
+        assert_eq!(line_text(&lines[6]), "Epoch: -");
+        assert_eq!(line_text(&lines[7]), "Missed: -");
+        assert_eq!(line_text(&lines[8]), "");
+        assert_eq!(line_text(&lines[9]), "Rewards");
+        assert_eq!(line_text(&lines[10]), "Verifier: 9");
+        assert_eq!(line_text(&lines[14]), "Address: lat1zytcgvw3…8gqj5h");
+        assert!(line_text(&lines[15]).starts_with("Updated: "));
     }
 
     #[test]
@@ -1324,9 +1910,32 @@ mod tests {
         assert_eq!(lines[2], "Blocks: 123,456");
         assert_eq!(lines[3], "Rate: 12.34%");
         assert_eq!(lines[4], "24H: 3/day");
-        assert_eq!(lines[8], "Rewards: 11,728.39 LAT");
-        assert_eq!(lines[9], "Address: lat1zytcgvw35sag…p8gqj5h");
-        assert!(lines[10].starts_with("Updated: "));
+        assert_eq!(lines[5], "Epoch: -");
+        assert_eq!(lines[6], "Missed: -");
+        assert_eq!(lines[10], "Rewards: 11,728.39 LAT");
+        assert_eq!(lines[11], "Address: lat1zytcgvw35sag…p8gqj5h");
+        assert!(lines[12].starts_with("Updated: "));
+    }
+
+    #[test]
+    fn test_production_lines_show_epoch_blocks_and_missed_trend() {
+        let mut detail = sample_detail();
+        detail.production = Some(BlockProductionSummary {
+            current: EpochProduction {
+                epoch: 8,
+                produced: 1_900,
+                expected: 2_000,
+            },
+            previous: Some(EpochProduction {
+                epoch: 7,
+                produced: 1_990,
+                expected: 2_000,
+            }),
+        });
+
+        assert_eq!(NodeDetailWidget::format_epoch_blocks(&detail), "1,900/2,000");
+        assert_eq!(NodeDetailWidget::format_missed(&detail), "5.00% ↑");
+        assert_eq!(NodeDetailWidget::missed_value_style(&detail).fg, Some(block::ACCENT_ERROR));
     }
 
     #[test]
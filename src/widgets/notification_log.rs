@@ -0,0 +1,277 @@
+use num_rational::Ratio;
+use ratatui::{
+    buffer::Buffer,
+    layout::{
+        Constraint,
+        Rect,
+    },
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Cell,
+        Paragraph,
+        Row,
+        Table,
+        Widget,
+    },
+};
+
+use crate::{
+    collect::SharedData,
+    notify::{
+        DeliveryRecord,
+        DeliveryStatus,
+    },
+    sync::lock_or_panic,
+    update::UpdatableWidget,
+    widgets::block,
+};
+
+/// Delivery audit log of the notification outbox, newest first. Shown in
+/// place of Node Details while toggled with `n`.
+pub struct NotificationLogWidget {
+    update_interval: Ratio<u64>,
+    collect_data: SharedData,
+    deliveries: Vec<DeliveryRecord>,
+}
+
+impl NotificationLogWidget {
+    const TABLE_MIN_WIDTH: u16 = 60;
+
+    pub fn new(collect_data: SharedData) -> NotificationLogWidget {
+        NotificationLogWidget {
+            update_interval: Ratio::from_integer(5),
+            collect_data,
+            deliveries: Vec::new(),
+        }
+    }
+
+    fn title(&self) -> String {
+        let dead_letters = self
+            .deliveries
+            .iter()
+            .filter(|record| record.status == DeliveryStatus::DeadLetter)
+            .count();
+        if dead_letters > 0 {
+            format!(" Notifications · {dead_letters} dead-lettered ")
+        } else {
+            " Notifications ".to_string()
+        }
+    }
+
+    fn status_span(status: DeliveryStatus) -> Span<'static> {
+        match status {
+            DeliveryStatus::Delivered => {
+                Span::styled("sent", block::accent_style(block::METRIC_POSITIVE))
+            },
+            DeliveryStatus::Retrying => {
+                Span::styled("retry", block::accent_style(block::ACCENT_WARN))
+            },
+            DeliveryStatus::DeadLetter => {
+                Span::styled("dead", block::accent_style(block::ACCENT_ERROR))
+            },
+        }
+    }
+
+    /// The title, or the error for failed attempts.
+    fn detail(record: &DeliveryRecord) -> String {
+        match &record.error {
+            Some(error) if record.status != DeliveryStatus::Delivered => {
+                format!("{} · {}", record.title, error)
+            },
+            _ => record.title.clone(),
+        }
+    }
+
+    fn render_empty(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let outer_block = block::new(" Notifications ");
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+        Paragraph::new("No notifications sent yet")
+            .style(block::empty_state_style())
+            .render(inner, buf);
+    }
+
+    fn render_compact(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let title = self.title();
+        let outer_block = block::new(&title);
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let lines: Vec<_> = self
+            .deliveries
+            .iter()
+            .take(inner.height as usize)
+            .map(|record| {
+                Line::from(vec![
+                    Span::styled(
+                        record.recorded_at.format("%H:%M").to_string(),
+                        block::muted_style(),
+                    ),
+                    Span::raw(" "),
+                    Self::status_span(record.status),
+                    Span::raw(" "),
+                    Span::styled(Self::detail(record), block::content_style()),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    fn render_table(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        // Borders and the header row.
+        let max_rows = area.height.saturating_sub(3) as usize;
+        let rows = self.deliveries.iter().take(max_rows).map(|record| {
+            Row::new(vec![
+                Cell::from(format!(" {}", record.recorded_at.format("%m-%d %H:%M:%S")))
+                    .style(block::muted_style()),
+                Cell::from(Self::status_span(record.status)),
+                Cell::from(record.attempts.to_string()).style(block::content_style()),
+                Cell::from(format!("{}:{}", record.channel, record.recipient))
+                    .style(block::highlight_style()),
+                Cell::from(Self::detail(record)).style(block::content_style()),
+            ])
+        });
+
+        let title = self.title();
+        Table::new(
+            rows,
+            [
+                Constraint::Length(15),
+                Constraint::Length(5),
+                Constraint::Length(3),
+                Constraint::Length(18),
+                Constraint::Min(10),
+            ],
+        )
+        .block(block::new(&title))
+        .header(
+            Row::new(vec![" Time", "State", "Try", "Recipient", "Message"])
+                .style(block::header_style()),
+        )
+        .column_spacing(1)
+        .render(area, buf);
+    }
+}
+
+impl UpdatableWidget for NotificationLogWidget {
+    fn update(&mut self) {
+        let data = lock_or_panic(&self.collect_data);
+        self.deliveries = data.notification_deliveries();
+    }
+
+    fn get_update_interval(&self) -> Ratio<u64> {
+        self.update_interval
+    }
+}
+
+impl Widget for &NotificationLogWidget {
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        if area.height < 3 {
+            return;
+        }
+
+        if self.deliveries.is_empty() {
+            self.render_empty(area, buf);
+        } else if area.width < NotificationLogWidget::TABLE_MIN_WIDTH {
+            self.render_compact(area, buf);
+        } else {
+            self.render_table(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{
+        Local,
+        TimeZone,
+    };
+
+    use super::*;
+    use crate::collect::Data;
+
+    fn record(
+        status: DeliveryStatus,
+        title: &str,
+        error: Option<&str>,
+    ) -> DeliveryRecord {
+        DeliveryRecord {
+            channel: "telegram".to_string(),
+            recipient: "10001".to_string(),
+            event: "connection_failed".to_string(),
+            title: title.to_string(),
+            status,
+            attempts: 2,
+            error: error.map(str::to_string),
+            recorded_at: Local
+                .with_ymd_and_hms(2026, 5, 4, 9, 30, 15)
+                .single()
+                .expect("valid local time"),
+        }
+    }
+
+    fn buffer_text(buf: &Buffer) -> String {
+        let area = buf.area;
+        (area.y..area.y + area.height)
+            .map(|y| {
+                (area.x..area.x + area.width).map(|x| buf[(x, y)].symbol()).collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_update_loads_deliveries_from_data() {
+        let data = Data::new();
+        let mut widget = NotificationLogWidget::new(data.clone());
+
+        data.lock().expect("mutex poisoned").replace_notification_deliveries(vec![record(
+            DeliveryStatus::DeadLetter,
+            "node down",
+            Some("HTTP 400"),
+        )]);
+        widget.update();
+
+        assert_eq!(widget.title(), " Notifications · 1 dead-lettered ");
+    }
+
+    #[test]
+    fn test_table_shows_status_recipient_and_error() {
+        let mut widget = NotificationLogWidget::new(Data::new());
+        widget.deliveries = vec![
+            record(DeliveryStatus::Retrying, "node down", Some("HTTP 429")),
+            record(DeliveryStatus::Delivered, "node up", Some("HTTP 429")),
+        ];
+        let area = Rect::new(0, 0, 90, 6);
+        let mut buf = Buffer::empty(area);
+
+        (&widget).render(area, &mut buf);
+        let text = buffer_text(&buf);
+
+        assert!(text.contains("05-04 09:30:15"));
+        assert!(text.contains("retry"));
+        assert!(text.contains("telegram:10001"));
+        assert!(text.contains("node down · HTTP 429"));
+        assert!(text.contains("node up "));
+        assert!(!text.contains("node up ·"));
+    }
+}